@@ -0,0 +1,24 @@
+-- Migration 015: persistent outbound delivery queue
+
+-- One row per (activity, inbox) pair. Rows are deleted once delivered;
+-- rows that exhaust their retry budget are kept with status = 'dead'.
+CREATE TABLE IF NOT EXISTS delivery_queue (
+    id TEXT PRIMARY KEY,
+    inbox_uri TEXT NOT NULL,
+    activity_id TEXT,
+    activity_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    last_status_code INTEGER,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_queue_status_next_attempt
+    ON delivery_queue(status, next_attempt_at);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_queue_inbox_uri
+    ON delivery_queue(inbox_uri);
@@ -0,0 +1,30 @@
+-- Migration 016: Filters v2 (titles, actions and status filters)
+
+-- v2 filters carry a title and an action instead of a single phrase.
+-- `phrase` and `irreversible` are kept in sync for the v1 API.
+ALTER TABLE filters ADD COLUMN title TEXT NOT NULL DEFAULT '';
+ALTER TABLE filters ADD COLUMN filter_action TEXT NOT NULL DEFAULT 'warn'
+    CHECK (filter_action IN ('warn', 'hide', 'blur'));
+
+UPDATE filters SET title = phrase;
+UPDATE filters SET filter_action = 'hide' WHERE irreversible = 1;
+
+-- Keywords are now the only source of matching text. Each v1 phrase
+-- becomes a keyword sharing the id of its filter, so the v1 API can keep
+-- addressing it.
+INSERT OR IGNORE INTO filter_keywords (id, filter_id, keyword, whole_word, created_at)
+SELECT id, id, phrase, whole_word, created_at
+FROM filters
+WHERE trim(phrase) != '';
+
+-- Individual statuses attached to a filter
+CREATE TABLE IF NOT EXISTS filter_statuses (
+    id TEXT PRIMARY KEY,
+    filter_id TEXT NOT NULL,
+    status_id TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (filter_id) REFERENCES filters(id) ON DELETE CASCADE,
+    UNIQUE (filter_id, status_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_filter_statuses_filter_id ON filter_statuses(filter_id);
@@ -0,0 +1,9 @@
+-- Migration 017: remote accounts mentioned by local statuses
+
+CREATE TABLE IF NOT EXISTS status_mentions (
+    status_id TEXT NOT NULL,
+    account_address TEXT NOT NULL,
+    actor_uri TEXT NOT NULL,
+    PRIMARY KEY (status_id, actor_uri),
+    FOREIGN KEY (status_id) REFERENCES statuses(id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- Migration 018: plain-text source of local statuses
+--
+-- Status content is stored as rendered HTML; the original text is kept so
+-- it can be returned for editing.
+
+CREATE TABLE IF NOT EXISTS status_sources (
+    status_id TEXT PRIMARY KEY NOT NULL,
+    text TEXT NOT NULL,
+    FOREIGN KEY (status_id) REFERENCES statuses(id) ON DELETE CASCADE
+);
@@ -0,0 +1,8 @@
+-- Migration 019: authoring content type of local statuses
+--
+-- Statuses can be written as plain text or Markdown; the type is kept with
+-- the source so it can be returned for editing and federated.
+
+ALTER TABLE status_sources ADD COLUMN content_type TEXT NOT NULL DEFAULT 'text/plain';
+
+ALTER TABLE scheduled_statuses ADD COLUMN content_type TEXT NOT NULL DEFAULT 'text/plain';
@@ -0,0 +1,11 @@
+-- Migration 020: polls of remote Question objects
+--
+-- Remote polls are stored next to local ones so they can be viewed and voted
+-- on through the same API. They are keyed by the Question URI; `status_id`
+-- stays NULL because the status itself is usually only cached.
+
+ALTER TABLE polls ADD COLUMN uri TEXT;
+
+ALTER TABLE polls ADD COLUMN account_address TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_polls_uri ON polls(uri) WHERE uri IS NOT NULL;
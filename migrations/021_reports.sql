@@ -0,0 +1,24 @@
+-- Migration 021: abuse reports
+
+-- Reports filed by the local account and reports forwarded to us as Flag
+-- activities. `uri` is the Flag activity ID and deduplicates redelivery.
+CREATE TABLE IF NOT EXISTS reports (
+    id TEXT PRIMARY KEY,
+    uri TEXT,
+    account_address TEXT NOT NULL,
+    target_account_address TEXT NOT NULL,
+    -- JSON array of reported status URIs
+    status_uris TEXT NOT NULL DEFAULT '[]',
+    comment TEXT NOT NULL DEFAULT '',
+    category TEXT NOT NULL DEFAULT 'other'
+        CHECK (category IN ('spam', 'legal', 'violation', 'other')),
+    forwarded INTEGER NOT NULL DEFAULT 0,
+    assigned INTEGER NOT NULL DEFAULT 0,
+    action_taken INTEGER NOT NULL DEFAULT 0,
+    action_taken_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reports_uri ON reports(uri) WHERE uri IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_reports_action_taken ON reports(action_taken, id);
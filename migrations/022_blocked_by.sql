@@ -0,0 +1,9 @@
+-- Migration 022: remote accounts blocking the local account
+
+-- Written when a remote actor sends Block and removed on Undo(Block).
+-- Nothing is delivered to these accounts directly.
+CREATE TABLE IF NOT EXISTS blocked_by (
+    id TEXT PRIMARY KEY,
+    account_address TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,33 @@
+-- Migration 023: inbound favourites and boosts of local statuses
+
+-- One row per remote Like/Announce of a local status. `activity_uri` lets an
+-- object-id-only Undo find the interaction; `notification_id` is the
+-- notification created for it, removed together with the row.
+CREATE TABLE IF NOT EXISTS status_interactions (
+    id TEXT PRIMARY KEY,
+    activity_uri TEXT,
+    interaction_type TEXT NOT NULL CHECK (interaction_type IN ('favourite', 'reblog')),
+    account_address TEXT NOT NULL,
+    status_uri TEXT NOT NULL,
+    notification_id TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_status_interactions_activity_uri
+    ON status_interactions(activity_uri) WHERE activity_uri IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_status_interactions_account
+    ON status_interactions(status_uri, interaction_type, account_address);
+
+-- `status_interactions` refers to statuses by URI, so a foreign key cannot
+-- cascade. Delete the interactions, and the notifications created for them,
+-- together with the status.
+CREATE TRIGGER IF NOT EXISTS trg_statuses_delete_interactions
+AFTER DELETE ON statuses
+BEGIN
+    DELETE FROM notifications
+    WHERE id IN (
+        SELECT notification_id FROM status_interactions
+        WHERE status_uri = old.uri AND notification_id IS NOT NULL
+    );
+    DELETE FROM status_interactions WHERE status_uri = old.uri;
+END;
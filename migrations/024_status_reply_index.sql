@@ -0,0 +1,4 @@
+-- Migration 024: reply lookups by parent URI
+
+-- Reply counts and thread contexts look statuses up by `in_reply_to_uri`.
+CREATE INDEX IF NOT EXISTS idx_statuses_in_reply_to_uri ON statuses(in_reply_to_uri);
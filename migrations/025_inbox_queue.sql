@@ -0,0 +1,25 @@
+-- Migration 025: persistent inbound activity queue
+
+-- One row per verified inbox delivery. Rows are deleted once processed or
+-- rejected; rows that keep failing transiently are kept with status = 'dead'.
+CREATE TABLE IF NOT EXISTS inbox_queue (
+    id TEXT PRIMARY KEY,
+    actor_uri TEXT NOT NULL,
+    actor_domain TEXT NOT NULL,
+    activity_id TEXT,
+    activity_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_inbox_queue_status_next_attempt
+    ON inbox_queue(status, next_attempt_at);
+
+-- Dead-lettered inbound activities are purged once they are a week old.
+CREATE INDEX IF NOT EXISTS idx_inbox_queue_status_updated_at
+    ON inbox_queue(status, updated_at);
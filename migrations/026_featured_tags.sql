@@ -0,0 +1,17 @@
+-- Migration 026: featured hashtags and remote featured statuses
+
+-- Hashtags featured on the local profile, published as `featuredTags`.
+CREATE TABLE IF NOT EXISTS featured_tags (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Statuses remote authors pinned to their `featured` collection. Kept apart
+-- from `pinned_statuses`, which only holds the local account's own pins.
+CREATE TABLE IF NOT EXISTS featured_statuses (
+    id TEXT PRIMARY KEY,
+    status_id TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (status_id) REFERENCES statuses(id) ON DELETE CASCADE
+);
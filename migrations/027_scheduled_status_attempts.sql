@@ -0,0 +1,10 @@
+-- Migration 027: scheduled status publication attempts
+
+-- Publication failures are retried with backoff. Rows that cannot be
+-- published, or keep failing, stay with status = 'failed' so the owner can
+-- see them and reschedule or cancel.
+ALTER TABLE scheduled_statuses
+    ADD COLUMN status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'failed'));
+ALTER TABLE scheduled_statuses ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE scheduled_statuses ADD COLUMN last_error TEXT;
+ALTER TABLE scheduled_statuses ADD COLUMN next_attempt_at TEXT;
@@ -1,37 +1,158 @@
 //! ActivityPub endpoints
 //!
 //! - Actor profile
+//! - Instance actor
 //! - Inbox (activity receiving)
 //! - Outbox
 //! - Followers/Following collections
+//! - Featured (pinned posts) and featured tags collections
 
 use axum::body::Bytes;
 use axum::{
     Router,
-    extract::{Path, State},
+    extract::{OriginalUri, Path, Query, State},
     response::Json,
     routing::{get, post},
 };
-use http::HeaderMap;
+use http::{HeaderMap, StatusCode};
+use serde::Deserialize;
 use std::sync::Arc;
 
+use super::mastodon::build_note_serializer;
 use crate::AppState;
 use crate::data::Account;
 use crate::error::AppError;
+use crate::federation::StatusNote;
 use crate::metrics::{
-    ACTIVITYPUB_ACTIVITIES_RECEIVED, FEDERATION_REQUEST_DURATION_SECONDS,
-    FEDERATION_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS, HTTP_REQUESTS_TOTAL,
+    ACTIVITYPUB_ACTIVITIES_RECEIVED, ACTIVITYPUB_DUPLICATE_ACTIVITIES_DROPPED,
+    FEDERATION_REQUEST_DURATION_SECONDS, FEDERATION_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS,
+    HTTP_REQUESTS_TOTAL,
 };
+use crate::service::AccountService;
 
-fn extract_signature_key_id(headers: &HeaderMap) -> Result<String, AppError> {
-    let signature = headers
-        .get("signature")
-        .ok_or(AppError::Unauthorized)?
-        .to_str()
-        .map_err(|_| AppError::Unauthorized)?;
+fn signature_max_date_skew(state: &AppState) -> std::time::Duration {
+    std::time::Duration::from_secs(state.config.federation.signature_max_skew_seconds)
+}
+
+/// Fields of the actor document served to unsigned requests in secure mode
+///
+/// Enough to verify our signatures, but without profile data.
+const KEY_FETCH_ACTOR_FIELDS: &[&str] = &[
+    "@context",
+    "type",
+    "id",
+    "preferredUsername",
+    "inbox",
+    "outbox",
+    "followers",
+    "following",
+    "featured",
+    "featuredTags",
+    "url",
+    "publicKey",
+];
+
+/// Fetch the public key for a signature, signing the fetch as the instance
+async fn fetch_signature_key(state: &AppState, key_id: &str) -> Result<String, AppError> {
+    let signer = state.instance_actor.fetch_signer().await?;
+    crate::federation::fetch_public_key(key_id, state.http_client.as_ref(), Some(&signer)).await
+}
+
+/// Require a valid HTTP signature on a GET when authorized fetch is enabled
+///
+/// # Returns
+/// The verified key ID, or None when authorized fetch is disabled
+///
+/// # Errors
+/// - Unauthorized if the request is unsigned or the signature does not verify
+/// - Forbidden if the key belongs to a blocked domain
+async fn authorize_fetch(
+    state: &AppState,
+    uri: &http::Uri,
+    headers: &HeaderMap,
+) -> Result<Option<String>, AppError> {
+    if !state.config.federation.authorized_fetch {
+        return Ok(None);
+    }
+
+    if crate::federation::SignatureScheme::detect(headers).is_none() {
+        return Err(AppError::Unauthorized);
+    }
+    let key_id = crate::federation::request_signature_key_id(headers)?;
+
+    // Reject blocked domains before fetching anything from them
+    if crate::federation::is_actor_domain_blocked(&state.db, &key_id).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    let public_key_pem = fetch_signature_key(state, &key_id).await?;
+    let path_and_query = uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or_else(|| uri.path());
+    crate::federation::verify_request_signature(
+        &crate::federation::MessageRequest {
+            method: "GET",
+            scheme: &state.config.server.protocol,
+            path_and_query,
+            headers,
+        },
+        None,
+        &public_key_pem,
+        signature_max_date_skew(state),
+    )
+    .map_err(|error| {
+        tracing::debug!(%key_id, %error, "Rejected signed fetch");
+        AppError::Unauthorized
+    })?;
+
+    Ok(Some(key_id))
+}
+
+/// Queue a verified inbound activity unless it was already seen
+///
+/// Duplicates are acknowledged without queueing. If queueing fails the
+/// activity is forgotten again so that the sender's retry is handled.
+async fn enqueue_unless_duplicate(
+    state: &AppState,
+    activity: &serde_json::Value,
+    actor_id: &str,
+    body: &[u8],
+) -> Result<(), AppError> {
+    let key = crate::federation::SeenActivities::activity_key(actor_id, activity, body);
+    if !state.seen_activities.insert(&key).await {
+        let activity_type = activity
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("Unknown");
+        ACTIVITYPUB_DUPLICATE_ACTIVITIES_DROPPED
+            .with_label_values(&[activity_type])
+            .inc();
+        tracing::debug!(actor = %actor_id, key = %key, "Dropping duplicate inbound activity");
+        return Ok(());
+    }
+
+    if let Err(error) = state.inbox_queue.enqueue(actor_id, activity).await {
+        state.seen_activities.remove(&key).await;
+        return Err(error);
+    }
+
+    Ok(())
+}
 
-    let parsed = crate::federation::parse_signature_header(signature)?;
-    Ok(parsed.key_id)
+/// Process queued inbound activities that are due
+///
+/// Called by the inbox queue worker.
+pub async fn process_due_inbox_jobs(
+    state: &AppState,
+    limit: usize,
+) -> Result<crate::federation::InboxRunStats, AppError> {
+    let Some(account) = state.db.get_account().await? else {
+        return Ok(crate::federation::InboxRunStats::default());
+    };
+    let processor = Arc::new(build_activity_processor(state, &account));
+
+    state.inbox_queue.process_due(processor, limit).await
 }
 
 fn build_activity_processor(
@@ -39,26 +160,177 @@ fn build_activity_processor(
     account: &Account,
 ) -> crate::federation::ActivityProcessor {
     let local_address = format!("{}@{}", account.username, state.config.server.domain);
-    let delivery = Arc::new(crate::federation::build_local_delivery(
-        state.http_client.clone(),
-        &state.config.server.base_url(),
-        account,
-    ));
+    let delivery = Arc::new(
+        crate::federation::build_local_delivery(
+            state.http_client.clone(),
+            &state.config.server.base_url(),
+            account,
+        )
+        .with_queue(state.delivery_queue.clone()),
+    );
 
     crate::federation::ActivityProcessor::new(
         state.db.clone(),
         state.timeline_cache.clone(),
         state.profile_cache.clone(),
-        state.http_client.clone(),
+        state.federation_fetch_client.clone(),
         local_address,
         state.config.server.protocol.clone(),
     )
     .with_delivery(delivery)
+    .with_streaming(state.streaming.clone())
+    .with_instance_actor(state.instance_actor.clone())
+}
+
+/// Items per page of the outbox, followers and following collections
+const COLLECTION_PAGE_SIZE: usize = 20;
+
+/// Page selection for paginated collections
+///
+/// `max_id` pages go back in time, `min_id` pages forward. Any of the
+/// parameters selects a page; without them the collection is returned.
+#[derive(Debug, Default, Deserialize)]
+struct CollectionPageQuery {
+    page: Option<String>,
+    max_id: Option<String>,
+    min_id: Option<String>,
+}
+
+impl CollectionPageQuery {
+    fn is_page(&self) -> bool {
+        self.page.is_some() || self.max_id.is_some() || self.min_id.is_some()
+    }
+
+    /// URL of the page selected by `max_id` / `min_id` within `collection_url`
+    fn page_url(collection_url: &str, max_id: Option<&str>, min_id: Option<&str>) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(max_id) = max_id {
+            query.append_pair("max_id", max_id);
+        }
+        if let Some(min_id) = min_id {
+            query.append_pair("min_id", min_id);
+        }
+        query.append_pair("page", "true");
+        format!("{}?{}", collection_url, query.finish())
+    }
+}
+
+/// Page query for the followers and following collections
+///
+/// Returns `None` when the collection itself should be served, which is
+/// always the case when the social graph is hidden.
+fn social_graph_page_query(
+    state: &AppState,
+    query: CollectionPageQuery,
+) -> Option<CollectionPageQuery> {
+    if state.config.federation.hide_social_graph || !query.is_page() {
+        return None;
+    }
+    Some(query)
+}
+
+/// Build an `OrderedCollection` that links to its first page
+///
+/// `with_first` is false for collections that only publish their size.
+fn ordered_collection(
+    context: serde_json::Value,
+    collection_url: &str,
+    total_items: i64,
+    with_first: bool,
+) -> serde_json::Value {
+    let mut collection = serde_json::json!({
+        "@context": context,
+        "type": "OrderedCollection",
+        "id": collection_url,
+        "totalItems": total_items,
+    });
+    if with_first {
+        collection["first"] =
+            serde_json::json!(CollectionPageQuery::page_url(collection_url, None, None));
+    }
+    collection
+}
+
+/// Build an `OrderedCollectionPage`
+///
+/// `items` are newest first, each paired with the ID used as its cursor.
+fn ordered_collection_page(
+    context: serde_json::Value,
+    collection_url: &str,
+    query: &CollectionPageQuery,
+    total_items: i64,
+    items: Vec<(String, serde_json::Value)>,
+) -> serde_json::Value {
+    let is_full = items.len() >= COLLECTION_PAGE_SIZE;
+    let newest_id = items.first().map(|(id, _)| id.clone());
+    let oldest_id = items.last().map(|(id, _)| id.clone());
+
+    let mut page = serde_json::json!({
+        "@context": context,
+        "type": "OrderedCollectionPage",
+        "id": CollectionPageQuery::page_url(
+            collection_url,
+            query.max_id.as_deref(),
+            query.min_id.as_deref()
+        ),
+        "partOf": collection_url,
+        "totalItems": total_items,
+        "orderedItems": items.into_iter().map(|(_, item)| item).collect::<Vec<_>>(),
+    });
+
+    // Walking forward with `min_id` leaves the cursor item behind, so
+    // there is always an older page; otherwise a short page is the last.
+    let has_next = if query.min_id.is_some() {
+        oldest_id.is_some()
+    } else {
+        is_full
+    };
+    // The first page has nothing newer; a `max_id` page always does.
+    let has_prev = if query.min_id.is_some() {
+        is_full
+    } else {
+        query.max_id.is_some()
+    };
+
+    if has_next && let Some(oldest_id) = oldest_id {
+        page["next"] = serde_json::json!(CollectionPageQuery::page_url(
+            collection_url,
+            Some(&oldest_id),
+            None
+        ));
+    }
+    if has_prev && let Some(newest_id) = newest_id {
+        page["prev"] = serde_json::json!(CollectionPageQuery::page_url(
+            collection_url,
+            None,
+            Some(&newest_id)
+        ));
+    }
+
+    page
+}
+
+/// Actor URI for a follower or followee address
+///
+/// Uses the cached profile when there is one. Otherwise the URI is
+/// derived from the address using the common `/users/{name}` layout.
+async fn address_actor_uri(state: &AppState, address: &str) -> String {
+    if let Some(profile) = state.profile_cache.get(address).await {
+        return profile.uri.clone();
+    }
+
+    format!(
+        "https://{}/users/{}",
+        address.split('@').nth(1).unwrap_or("unknown.example"),
+        address.split('@').next().unwrap_or("unknown")
+    )
 }
 
 /// Create ActivityPub router
 ///
 /// Routes:
+/// - GET /actor - Instance actor
+/// - GET /actor/outbox - Instance actor outbox (always empty)
 /// - GET /users/:username - Actor profile
 /// - POST /users/:username/inbox - Personal inbox
 /// - POST /inbox - Shared inbox
@@ -66,8 +338,12 @@ fn build_activity_processor(
 /// - GET /users/:username/statuses/:id - Note object
 /// - GET /users/:username/followers - Followers collection
 /// - GET /users/:username/following - Following collection
+/// - GET /users/:username/collections/featured - Pinned posts collection
+/// - GET /users/:username/collections/tags - Featured hashtags collection
 pub fn activitypub_router() -> Router<AppState> {
     Router::new()
+        .route(crate::federation::INSTANCE_ACTOR_PATH, get(instance_actor))
+        .route("/actor/outbox", get(instance_actor_outbox))
         .route("/users/:username", get(actor))
         .route("/users/:username/inbox", post(inbox))
         .route("/inbox", post(shared_inbox))
@@ -75,6 +351,41 @@ pub fn activitypub_router() -> Router<AppState> {
         .route("/users/:username/statuses/:id", get(status_object))
         .route("/users/:username/followers", get(followers))
         .route("/users/:username/following", get(following))
+        .route("/users/:username/collections/featured", get(featured))
+        .route("/users/:username/collections/tags", get(featured_tags))
+}
+
+/// GET /actor
+///
+/// Returns the instance actor document. Served without a signature even
+/// in secure mode so peers can verify fetches signed by the instance.
+async fn instance_actor(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let _timer = HTTP_REQUEST_DURATION_SECONDS
+        .with_label_values(&["GET", "/actor"])
+        .start_timer();
+
+    let document = state.instance_actor.document().await?;
+
+    HTTP_REQUESTS_TOTAL
+        .with_label_values(&["GET", "/actor", "200"])
+        .inc();
+
+    Ok(Json(document))
+}
+
+/// GET /actor/outbox
+///
+/// The instance actor never publishes anything.
+async fn instance_actor_outbox(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "type": "OrderedCollection",
+        "id": format!("{}/outbox", state.instance_actor.actor_uri()),
+        "totalItems": 0,
+        "orderedItems": []
+    }))
 }
 
 /// GET /users/:username
@@ -85,6 +396,8 @@ pub fn activitypub_router() -> Router<AppState> {
 async fn actor(
     State(state): State<AppState>,
     Path(username): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Start timing the request
     let _timer = HTTP_REQUEST_DURATION_SECONDS
@@ -96,14 +409,32 @@ async fn actor(
 
     match account {
         Some(acc) if acc.username == username => {
+            // In secure mode unsigned requests only get what is needed to
+            // verify our signatures.
+            let authorized = match authorize_fetch(&state, &uri, &headers).await {
+                Ok(_) => true,
+                Err(AppError::Unauthorized) => false,
+                Err(error) => return Err(error),
+            };
+
             let base_url = state.config.server.base_url();
             let actor_url = format!("{}/users/{}", base_url, username);
+            let moved_to = AccountService::new(state.db.clone(), state.storage.clone())
+                .get_moved_to()
+                .await?;
 
             // Build Actor document according to ActivityPub spec
-            let response = Json(serde_json::json!({
+            let mut document = serde_json::json!({
                 "@context": [
                     "https://www.w3.org/ns/activitystreams",
-                    "https://w3id.org/security/v1"
+                    "https://w3id.org/security/v1",
+                    {
+                        "toot": "http://joinmastodon.org/ns#",
+                        "alsoKnownAs": { "@id": "as:alsoKnownAs", "@type": "@id" },
+                        "movedTo": { "@id": "as:movedTo", "@type": "@id" },
+                        "featured": { "@id": "toot:featured", "@type": "@id" },
+                        "featuredTags": { "@id": "toot:featuredTags", "@type": "@id" }
+                    }
                 ],
                 "type": "Person",
                 "id": actor_url.clone(),
@@ -114,6 +445,8 @@ async fn actor(
                 "outbox": format!("{}/outbox", actor_url),
                 "followers": format!("{}/followers", actor_url),
                 "following": format!("{}/following", actor_url),
+                "featured": crate::federation::featured_collection_uri(&actor_url),
+                "featuredTags": crate::federation::featured_tags_collection_uri(&actor_url),
                 "url": actor_url.clone(),
                 "publicKey": {
                     "id": format!("{}#main-key", actor_url),
@@ -130,7 +463,17 @@ async fn actor(
                     "mediaType": "image/webp",
                     "url": state.storage.get_public_url(&key)
                 }))
-            }));
+            });
+            if !state.config.admin.also_known_as.is_empty() {
+                document["alsoKnownAs"] = serde_json::json!(state.config.admin.also_known_as);
+            }
+            if let Some(moved_to) = moved_to {
+                document["movedTo"] = serde_json::json!(moved_to);
+            }
+            if !authorized && let Some(fields) = document.as_object_mut() {
+                fields.retain(|key, _| KEY_FETCH_ACTOR_FIELDS.contains(&key.as_str()));
+            }
+            let response = Json(document);
 
             // Record successful request
             HTTP_REQUESTS_TOTAL
@@ -150,13 +493,14 @@ async fn actor(
 /// # Steps
 /// 1. Verify HTTP Signature
 /// 2. Parse activity
-/// 3. Process based on type
+/// 3. Drop already seen activities
+/// 4. Queue for processing and answer `202 Accepted`
 async fn inbox(
     State(state): State<AppState>,
     Path(username): Path<String>,
     headers: HeaderMap,
     body: Bytes,
-) -> Result<(), AppError> {
+) -> Result<StatusCode, AppError> {
     // Start timing the request
     let _timer = HTTP_REQUEST_DURATION_SECONDS
         .with_label_values(&["POST", "/users/:username/inbox"])
@@ -167,13 +511,12 @@ async fn inbox(
 
     // Verify username exists
     let account = state.db.get_account().await?;
-    let account = match account {
-        Some(account) if account.username == username => account,
-        _ => return Err(AppError::NotFound),
-    };
+    if account.is_none_or(|account| account.username != username) {
+        return Err(AppError::NotFound);
+    }
 
-    // Check for Signature header first (reject unsigned requests immediately)
-    if headers.get("signature").is_none() {
+    // Check for a signature first (reject unsigned requests immediately)
+    if crate::federation::SignatureScheme::detect(&headers).is_none() {
         FEDERATION_REQUESTS_TOTAL
             .with_label_values(&["inbound", "unauthorized"])
             .inc();
@@ -190,7 +533,7 @@ async fn inbox(
         .ok_or_else(|| AppError::Validation("Missing actor field".to_string()))?
         .to_string(); // Clone the string to avoid borrow issues;
 
-    let signature_key_id = extract_signature_key_id(&headers)?;
+    let signature_key_id = crate::federation::request_signature_key_id(&headers)?;
     if !crate::federation::key_id_matches_actor(&signature_key_id, &actor_id)? {
         FEDERATION_REQUESTS_TOTAL
             .with_label_values(&["inbound", "unauthorized"])
@@ -199,14 +542,23 @@ async fn inbox(
     }
 
     // Fetch the actor's public key from signature keyId.
-    let public_key_pem =
-        crate::federation::fetch_public_key(&signature_key_id, state.http_client.as_ref()).await?;
+    let public_key_pem = fetch_signature_key(&state, &signature_key_id).await?;
 
     // Get the request path
     let path = format!("/users/{}/inbox", username);
 
-    // Verify the HTTP signature
-    crate::federation::verify_signature("POST", &path, &headers, Some(&body), &public_key_pem)?;
+    // Verify the HTTP signature (RFC 9421 or draft-cavage)
+    crate::federation::verify_request_signature(
+        &crate::federation::MessageRequest {
+            method: "POST",
+            scheme: &state.config.server.protocol,
+            path_and_query: &path,
+            headers: &headers,
+        },
+        Some(&body),
+        &public_key_pem,
+        signature_max_date_skew(&state),
+    )?;
 
     // Apply inbound federation rate limiting only after signature verification
     // to avoid unauthenticated quota poisoning.
@@ -223,20 +575,18 @@ async fn inbox(
             .inc();
     }
 
-    // Process the activity
-    let processor = build_activity_processor(&state, &account);
-
-    processor.process(activity, &actor_id).await?;
+    // Queue the activity unless it is a retried or relayed duplicate
+    enqueue_unless_duplicate(&state, &activity, &actor_id, &body).await?;
 
     // Record successful federation request
     FEDERATION_REQUESTS_TOTAL
         .with_label_values(&["inbound", "success"])
         .inc();
     HTTP_REQUESTS_TOTAL
-        .with_label_values(&["POST", "/users/:username/inbox", "200"])
+        .with_label_values(&["POST", "/users/:username/inbox", "202"])
         .inc();
 
-    Ok(())
+    Ok(StatusCode::ACCEPTED)
 }
 
 /// POST /inbox
@@ -247,14 +597,15 @@ async fn inbox(
 /// # Steps
 /// 1. Verify HTTP Signature
 /// 2. Parse activity
-/// 3. Route to appropriate user(s)
+/// 3. Drop already seen activities
+/// 4. Queue for processing and answer `202 Accepted`
 async fn shared_inbox(
     State(state): State<AppState>,
     headers: HeaderMap,
     body: Bytes,
-) -> Result<(), AppError> {
-    // Check for Signature header first (reject unsigned requests immediately)
-    if headers.get("signature").is_none() {
+) -> Result<StatusCode, AppError> {
+    // Check for a signature first (reject unsigned requests immediately)
+    if crate::federation::SignatureScheme::detect(&headers).is_none() {
         return Err(AppError::Unauthorized);
     }
 
@@ -268,20 +619,29 @@ async fn shared_inbox(
         .ok_or_else(|| AppError::Validation("Missing actor field".to_string()))?
         .to_string(); // Clone the string to avoid borrow issues;
 
-    let signature_key_id = extract_signature_key_id(&headers)?;
+    let signature_key_id = crate::federation::request_signature_key_id(&headers)?;
     if !crate::federation::key_id_matches_actor(&signature_key_id, &actor_id)? {
         return Err(AppError::Unauthorized);
     }
 
     // Fetch the actor's public key from signature keyId.
-    let public_key_pem =
-        crate::federation::fetch_public_key(&signature_key_id, state.http_client.as_ref()).await?;
+    let public_key_pem = fetch_signature_key(&state, &signature_key_id).await?;
 
     // Get the request path
     let path = "/inbox";
 
-    // Verify the HTTP signature
-    crate::federation::verify_signature("POST", path, &headers, Some(&body), &public_key_pem)?;
+    // Verify the HTTP signature (RFC 9421 or draft-cavage)
+    crate::federation::verify_request_signature(
+        &crate::federation::MessageRequest {
+            method: "POST",
+            scheme: &state.config.server.protocol,
+            path_and_query: path,
+            headers: &headers,
+        },
+        Some(&body),
+        &public_key_pem,
+        signature_max_date_skew(&state),
+    )?;
 
     // Apply inbound federation rate limiting only after signature verification
     // to avoid unauthenticated quota poisoning.
@@ -292,88 +652,73 @@ async fn shared_inbox(
         .await?;
 
     // Verify we have at least one account on this instance
-    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
-
-    // Process the activity
-    let processor = build_activity_processor(&state, &account);
+    state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
-    processor.process(activity, &actor_id).await?;
+    // Queue the activity unless it is a retried or relayed duplicate
+    enqueue_unless_duplicate(&state, &activity, &actor_id, &body).await?;
 
-    Ok(())
+    Ok(StatusCode::ACCEPTED)
 }
 
 /// GET /users/:username/outbox
 ///
-/// Returns Outbox collection (paginated).
+/// Returns the Outbox collection, or one of its pages when any of `page`,
+/// `max_id` or `min_id` is given.
 ///
 /// Only public activities are included.
 async fn outbox(
     State(state): State<AppState>,
     Path(username): Path<String>,
+    Query(query): Query<CollectionPageQuery>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Verify username matches local account
     let account = state.db.get_account().await?;
 
     match account {
         Some(acc) if acc.username == username => {
-            // Get outbox-safe statuses from database.
-            // ActivityPub outbox must not expose private/direct posts.
-            let statuses = state.db.get_local_outbox_statuses(20, None).await?;
+            authorize_fetch(&state, &uri, &headers).await?;
 
             let base_url = state.config.server.base_url();
             let outbox_url = format!("{}/users/{}/outbox", base_url, username);
-            let followers_url = format!("{}/users/{}/followers", base_url, username);
-            let public_audience = "https://www.w3.org/ns/activitystreams#Public";
-
-            // Build OrderedCollection
-            let items: Vec<serde_json::Value> = statuses
-                .iter()
-                .map(|status| {
-                    let (to, cc) = match status.visibility.as_str() {
-                        "unlisted" => (
-                            serde_json::json!([followers_url.clone()]),
-                            serde_json::json!([public_audience]),
-                        ),
-                        _ => (
-                            serde_json::json!([public_audience]),
-                            serde_json::json!([followers_url.clone()]),
-                        ),
-                    };
-                    let mut object = serde_json::json!({
-                        "type": "Note",
-                        "id": status.uri.clone(),
-                        "attributedTo": format!("{}/users/{}", base_url, username),
-                        "content": status.content.clone(),
-                        "published": status.created_at.to_rfc3339(),
-                        "to": to,
-                        "cc": cc
-                    });
-                    if let Some(summary) = &status.content_warning {
-                        object["summary"] = serde_json::json!(summary);
-                        object["sensitive"] = serde_json::json!(true);
-                    }
-                    if let Some(in_reply_to) = &status.in_reply_to_uri {
-                        object["inReplyTo"] = serde_json::json!(in_reply_to);
-                    }
-                    serde_json::json!({
-                        "type": "Create",
-                        "id": format!("{}/activity", status.uri),
-                        "actor": format!("{}/users/{}", base_url, username),
-                        "published": status.created_at.to_rfc3339(),
-                        "to": object["to"].clone(),
-                        "cc": object["cc"].clone(),
-                        "object": object
-                    })
-                })
-                .collect();
+            let total_items = state.db.count_local_outbox_statuses().await?;
+
+            if !query.is_page() {
+                return Ok(Json(ordered_collection(
+                    crate::federation::note_context(),
+                    &outbox_url,
+                    total_items,
+                    true,
+                )));
+            }
 
-            Ok(Json(serde_json::json!({
-                "@context": "https://www.w3.org/ns/activitystreams",
-                "type": "OrderedCollection",
-                "id": outbox_url,
-                "totalItems": items.len(),
-                "orderedItems": items
-            })))
+            // Get outbox-safe statuses from database.
+            // ActivityPub outbox must not expose private/direct posts.
+            let statuses = state
+                .db
+                .get_local_outbox_statuses(
+                    COLLECTION_PAGE_SIZE,
+                    query.max_id.as_deref(),
+                    query.min_id.as_deref(),
+                )
+                .await?;
+
+            let serializer = build_note_serializer(&state, &acc);
+            let mut items = Vec::with_capacity(statuses.len());
+            for status in statuses {
+                let id = status.id.clone();
+                let note = StatusNote::load(&state.db, status).await?;
+                items.push((id, serializer.create(&note)));
+            }
+
+            Ok(Json(ordered_collection_page(
+                crate::federation::note_context(),
+                &outbox_url,
+                &query,
+                total_items,
+                items,
+            )))
         }
         _ => Err(AppError::NotFound),
     }
@@ -385,6 +730,8 @@ async fn outbox(
 async fn status_object(
     State(state): State<AppState>,
     Path((username, id)): Path<(String, String)>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let _timer = HTTP_REQUEST_DURATION_SECONDS
         .with_label_values(&["GET", "/users/:username/statuses/:id"])
@@ -393,10 +740,10 @@ async fn status_object(
     let account = state.db.get_account().await?;
     match account {
         Some(acc) if acc.username == username => {
+            authorize_fetch(&state, &uri, &headers).await?;
+
             let base_url = state.config.server.base_url();
-            let actor_url = format!("{}/users/{}", base_url, username);
-            let status_uri = format!("{}/statuses/{}", actor_url, id);
-            let followers_url = format!("{}/followers", actor_url);
+            let status_uri = format!("{}/users/{}/statuses/{}", base_url, username, id);
 
             let status = state
                 .db
@@ -408,37 +755,9 @@ async fn status_object(
                 return Err(AppError::NotFound);
             }
 
-            let public_audience = "https://www.w3.org/ns/activitystreams#Public";
-            let (to_audience, cc_audience) = match status.visibility.as_str() {
-                "unlisted" => (
-                    serde_json::json!([followers_url.clone()]),
-                    serde_json::json!([public_audience]),
-                ),
-                _ => (
-                    serde_json::json!([public_audience]),
-                    serde_json::json!([followers_url.clone()]),
-                ),
-            };
-
-            let mut note = serde_json::json!({
-                "@context": "https://www.w3.org/ns/activitystreams",
-                "type": "Note",
-                "id": status.uri,
-                "attributedTo": actor_url,
-                "content": status.content,
-                "published": status.created_at.to_rfc3339(),
-                "to": to_audience,
-                "cc": cc_audience
-            });
-
-            if let Some(summary) = status.content_warning {
-                note["summary"] = serde_json::json!(summary);
-                note["sensitive"] = serde_json::json!(true);
-            }
-
-            if let Some(in_reply_to) = status.in_reply_to_uri {
-                note["inReplyTo"] = serde_json::json!(in_reply_to);
-            }
+            let note = StatusNote::load(&state.db, status).await?;
+            let mut note = build_note_serializer(&state, &acc).note(&note);
+            note["@context"] = crate::federation::note_context();
 
             HTTP_REQUESTS_TOTAL
                 .with_label_values(&["GET", "/users/:username/statuses/:id", "200"])
@@ -452,43 +771,56 @@ async fn status_object(
 
 /// GET /users/:username/followers
 ///
-/// Returns Followers collection.
+/// Returns the Followers collection, or one of its pages.
 async fn followers(
     State(state): State<AppState>,
     Path(username): Path<String>,
+    Query(query): Query<CollectionPageQuery>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Verify username
     let account = state.db.get_account().await?;
 
     match account {
         Some(acc) if acc.username == username => {
-            // Get follower addresses from database
-            let follower_addresses = state.db.get_all_follower_addresses().await?;
+            authorize_fetch(&state, &uri, &headers).await?;
 
             let base_url = state.config.server.base_url();
             let followers_url = format!("{}/users/{}/followers", base_url, username);
+            let total_items = state.db.count_follower_addresses().await?;
+
+            let Some(page_query) = social_graph_page_query(&state, query) else {
+                return Ok(Json(ordered_collection(
+                    serde_json::json!("https://www.w3.org/ns/activitystreams"),
+                    &followers_url,
+                    total_items,
+                    !state.config.federation.hide_social_graph,
+                )));
+            };
 
-            // Build OrderedCollection of follower URIs
-            // Note: In a real implementation, these should be actor URIs, not addresses
-            // For now, we'll use placeholder URIs
-            let items: Vec<String> = follower_addresses
-                .iter()
-                .map(|addr| {
-                    format!(
-                        "https://{}/users/{}",
-                        addr.split('@').nth(1).unwrap_or("unknown.example"),
-                        addr.split('@').next().unwrap_or("unknown")
-                    )
-                })
-                .collect();
+            let followers = state
+                .db
+                .get_followers_page(
+                    COLLECTION_PAGE_SIZE,
+                    page_query.max_id.as_deref(),
+                    page_query.min_id.as_deref(),
+                )
+                .await?;
+
+            let mut items = Vec::with_capacity(followers.len());
+            for follower in followers {
+                let actor_uri = address_actor_uri(&state, &follower.follower_address).await;
+                items.push((follower.id, serde_json::json!(actor_uri)));
+            }
 
-            Ok(Json(serde_json::json!({
-                "@context": "https://www.w3.org/ns/activitystreams",
-                "type": "OrderedCollection",
-                "id": followers_url,
-                "totalItems": items.len(),
-                "orderedItems": items
-            })))
+            Ok(Json(ordered_collection_page(
+                serde_json::json!("https://www.w3.org/ns/activitystreams"),
+                &followers_url,
+                &page_query,
+                total_items,
+                items,
+            )))
         }
         _ => Err(AppError::NotFound),
     }
@@ -496,40 +828,138 @@ async fn followers(
 
 /// GET /users/:username/following
 ///
-/// Returns Following collection.
+/// Returns the Following collection, or one of its pages.
 async fn following(
     State(state): State<AppState>,
     Path(username): Path<String>,
+    Query(query): Query<CollectionPageQuery>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Verify username
     let account = state.db.get_account().await?;
 
     match account {
         Some(acc) if acc.username == username => {
-            // Get follow addresses from database
-            let follow_addresses = state.db.get_all_follow_addresses().await?;
+            authorize_fetch(&state, &uri, &headers).await?;
 
             let base_url = state.config.server.base_url();
             let following_url = format!("{}/users/{}/following", base_url, username);
+            let total_items = state.db.count_follow_addresses().await?;
+
+            let Some(page_query) = social_graph_page_query(&state, query) else {
+                return Ok(Json(ordered_collection(
+                    serde_json::json!("https://www.w3.org/ns/activitystreams"),
+                    &following_url,
+                    total_items,
+                    !state.config.federation.hide_social_graph,
+                )));
+            };
+
+            let follows = state
+                .db
+                .get_follows_page(
+                    COLLECTION_PAGE_SIZE,
+                    page_query.max_id.as_deref(),
+                    page_query.min_id.as_deref(),
+                )
+                .await?;
+
+            let mut items = Vec::with_capacity(follows.len());
+            for follow in follows {
+                let actor_uri = address_actor_uri(&state, &follow.target_address).await;
+                items.push((follow.id, serde_json::json!(actor_uri)));
+            }
+
+            Ok(Json(ordered_collection_page(
+                serde_json::json!("https://www.w3.org/ns/activitystreams"),
+                &following_url,
+                &page_query,
+                total_items,
+                items,
+            )))
+        }
+        _ => Err(AppError::NotFound),
+    }
+}
+
+/// GET /users/:username/collections/featured
+///
+/// Returns the pinned public and unlisted posts, most recently pinned first.
+async fn featured(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let account = state.db.get_account().await?;
+
+    match account {
+        Some(acc) if acc.username == username => {
+            authorize_fetch(&state, &uri, &headers).await?;
+
+            let actor_url = format!("{}/users/{}", state.config.server.base_url(), username);
+            let statuses = state.db.get_local_pinned_statuses().await?;
+
+            let serializer = build_note_serializer(&state, &acc);
+            let mut items = Vec::with_capacity(statuses.len());
+            for status in statuses {
+                let note = StatusNote::load(&state.db, status).await?;
+                items.push(serializer.note(&note));
+            }
+
+            Ok(Json(serde_json::json!({
+                "@context": crate::federation::note_context(),
+                "type": "OrderedCollection",
+                "id": crate::federation::featured_collection_uri(&actor_url),
+                "totalItems": items.len(),
+                "orderedItems": items,
+            })))
+        }
+        _ => Err(AppError::NotFound),
+    }
+}
+
+/// GET /users/:username/collections/tags
+///
+/// Returns the featured hashtags as `Hashtag` objects.
+async fn featured_tags(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let account = state.db.get_account().await?;
+
+    match account {
+        Some(acc) if acc.username == username => {
+            authorize_fetch(&state, &uri, &headers).await?;
 
-            // Build OrderedCollection of following URIs
-            let items: Vec<String> = follow_addresses
-                .iter()
-                .map(|addr| {
-                    format!(
-                        "https://{}/users/{}",
-                        addr.split('@').nth(1).unwrap_or("unknown.example"),
-                        addr.split('@').next().unwrap_or("unknown")
-                    )
+            let base_url = state.config.server.base_url();
+            let actor_url = format!("{}/users/{}", base_url, username);
+            let items: Vec<serde_json::Value> = state
+                .db
+                .get_featured_tags()
+                .await?
+                .into_iter()
+                .map(|tag| {
+                    serde_json::json!({
+                        "type": "Hashtag",
+                        "href": format!("{}/tags/{}", base_url, tag.name),
+                        "name": format!("#{}", tag.name),
+                    })
                 })
                 .collect();
 
             Ok(Json(serde_json::json!({
-                "@context": "https://www.w3.org/ns/activitystreams",
-                "type": "OrderedCollection",
-                "id": following_url,
+                "@context": [
+                    "https://www.w3.org/ns/activitystreams",
+                    { "Hashtag": "as:Hashtag" }
+                ],
+                "type": "Collection",
+                "id": crate::federation::featured_tags_collection_uri(&actor_url),
                 "totalItems": items.len(),
-                "orderedItems": items
+                "items": items,
             })))
         }
         _ => Err(AppError::NotFound),
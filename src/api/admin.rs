@@ -14,6 +14,7 @@ use chrono::Utc;
 use crate::AppState;
 use crate::auth::CurrentUser;
 use crate::error::AppError;
+use crate::service::AccountService;
 
 /// Create admin router
 ///
@@ -23,6 +24,7 @@ use crate::error::AppError;
 /// - POST /api/admin/domain_blocks - Block domain
 /// - DELETE /api/admin/domain_blocks/:domain - Unblock domain
 /// - GET /api/admin/domain_blocks - List blocked domains
+/// - POST /api/admin/move - Move this account to another actor
 pub fn admin_router() -> Router<AppState> {
     Router::new()
         // Backup
@@ -35,6 +37,8 @@ pub fn admin_router() -> Router<AppState> {
             axum::routing::delete(unblock_domain),
         )
         .route("/domain_blocks", get(list_domain_blocks))
+        // Account migration
+        .route("/move", post(move_account))
 }
 
 // =============================================================================
@@ -148,6 +152,85 @@ async fn list_domain_blocks(
     Ok(Json(domains))
 }
 
+// =============================================================================
+// Account migration
+// =============================================================================
+
+/// Move account request
+#[derive(Debug, serde::Deserialize)]
+struct MoveAccountRequest {
+    /// New account, as `user@domain` or actor URI
+    target: String,
+}
+
+/// Move account response
+#[derive(Debug, serde::Serialize)]
+pub struct MoveAccountResponse {
+    pub moved_to: String,
+    /// Follower inboxes the Move was queued for
+    pub deliveries: usize,
+}
+
+/// POST /api/admin/move
+///
+/// Migrates this account to another actor and broadcasts a Move to all
+/// followers. The target must already list this account in `alsoKnownAs`.
+async fn move_account(
+    State(state): State<AppState>,
+    CurrentUser(_user): CurrentUser,
+    Json(req): Json<MoveAccountRequest>,
+) -> Result<Json<MoveAccountResponse>, AppError> {
+    let account_service = AccountService::new(state.db.clone(), state.storage.clone());
+    let account = account_service.get_account().await?;
+    let actor_uri =
+        crate::federation::local_actor_uri(&state.config.server.base_url(), &account.username);
+
+    let signer = state.instance_actor.fetch_signer().await?;
+    let target_uri = crate::federation::resolve_webfinger(
+        req.target.trim(),
+        &state.federation_fetch_client,
+        Some(&signer),
+    )
+    .await?
+    .actor_uri;
+    let target_document =
+        crate::federation::fetch_actor(&target_uri, &state.federation_fetch_client, Some(&signer))
+            .await?;
+    let target = crate::federation::parse_actor(&target_document)?;
+    if target.id == actor_uri {
+        return Err(AppError::Validation(
+            "cannot move to this account".to_string(),
+        ));
+    }
+    if !target.also_known_as.iter().any(|alias| alias == &actor_uri) {
+        return Err(AppError::Validation(
+            "target account must list this account in alsoKnownAs".to_string(),
+        ));
+    }
+
+    account_service.set_moved_to(&target.id).await?;
+
+    let follower_inboxes = account_service.get_follower_inboxes().await?;
+    let delivery = crate::federation::build_local_delivery(
+        state.http_client.clone(),
+        &state.config.server.base_url(),
+        &account,
+    )
+    .with_queue(state.delivery_queue.clone());
+    let results = delivery.send_move(&target.id, follower_inboxes).await;
+    let deliveries = results.iter().filter(|result| result.success).count();
+    tracing::info!(
+        moved_to = %target.id,
+        deliveries,
+        "Queued Move to followers"
+    );
+
+    Ok(Json(MoveAccountResponse {
+        moved_to: target.id,
+        deliveries,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::normalize_domain;
@@ -2,7 +2,8 @@
 
 use crate::api::dto::*;
 use crate::config::AppConfig;
-use crate::data::{Account, Status};
+use crate::data::{Account, FilterKeyword, FilterStatus, Status};
+use crate::service::{ActiveFilter, FilterMatch};
 
 /// Convert Account to AccountResponse
 pub fn account_to_response(account: &Account, config: &AppConfig) -> AccountResponse {
@@ -54,10 +55,15 @@ pub fn account_to_response(account: &Account, config: &AppConfig) -> AccountResp
 }
 
 fn remote_account_to_response(status: &Status, config: &AppConfig) -> AccountResponse {
+    remote_address_to_account_response(&status.account_address, config)
+}
+
+/// Convert a remote account address (user@domain) to AccountResponse
+pub fn remote_address_to_account_response(address: &str, config: &AppConfig) -> AccountResponse {
     let placeholder_created_at = chrono::DateTime::from_timestamp(0, 0)
         .expect("unix epoch timestamp should always be valid");
     let media_url = &config.storage.media.public_url;
-    let address = status.account_address.trim();
+    let address = address.trim();
     let (username, domain) = address
         .split_once('@')
         .unwrap_or(("unknown", "unknown.invalid"));
@@ -91,6 +97,109 @@ fn remote_account_to_response(status: &Status, config: &AppConfig) -> AccountRes
     }
 }
 
+/// Read a double-quoted attribute value from an HTML start tag
+fn html_attribute(tag: &str, name: &str) -> Option<String> {
+    let marker = format!(" {}=\"", name);
+    let start = tag.find(&marker)? + marker.len();
+    let end = start + tag[start..].find('"')?;
+    Some(html_escape::decode_html_entities(&tag[start..end]).into_owned())
+}
+
+/// Collect the accounts linked as `u-url mention` anchors in status HTML.
+///
+/// Links to this server resolve to the local account; other links use the
+/// `username@host` address as id, like remote account responses.
+fn mentions_from_content(
+    content: &str,
+    account: &Account,
+    base_url: &str,
+) -> Vec<StatusMentionResponse> {
+    let mut mentions: Vec<StatusMentionResponse> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<a ") {
+        rest = &rest[start..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        let Some(close) = rest[tag_end..].find("</a>").map(|offset| tag_end + offset) else {
+            break;
+        };
+        let inner = &rest[tag_end + 1..close];
+        rest = &rest[close..];
+
+        let is_mention = html_attribute(tag, "class").is_some_and(|class| {
+            let classes: Vec<&str> = class.split_whitespace().collect();
+            classes.contains(&"mention") && !classes.contains(&"hashtag")
+        });
+        let Some(href) = html_attribute(tag, "href").filter(|_| is_mention) else {
+            continue;
+        };
+
+        let mut text = String::new();
+        let mut in_tag = false;
+        for ch in inner.chars() {
+            match ch {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(ch),
+                _ => {}
+            }
+        }
+        let text = html_escape::decode_html_entities(&text).into_owned();
+        let username = text
+            .trim()
+            .trim_start_matches('@')
+            .split('@')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if username.is_empty() || mentions.iter().any(|mention| mention.url == href) {
+            continue;
+        }
+
+        let mention = if href.starts_with(&format!("{}/", base_url)) {
+            StatusMentionResponse {
+                id: account.id.clone(),
+                username: account.username.clone(),
+                url: href,
+                acct: account.username.clone(),
+            }
+        } else {
+            let Some(host) = url::Url::parse(&href)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            else {
+                continue;
+            };
+            let acct = format!("{}@{}", username.to_ascii_lowercase(), host);
+            StatusMentionResponse {
+                id: acct.clone(),
+                username,
+                url: href,
+                acct,
+            }
+        };
+        mentions.push(mention);
+    }
+
+    mentions
+}
+
+/// Account that authored a status: the local account or a remote author
+pub fn status_author_to_response(
+    status: &Status,
+    account: &Account,
+    config: &AppConfig,
+) -> AccountResponse {
+    if status.is_local || status.account_address.trim().is_empty() {
+        account_to_response(account, config)
+    } else {
+        remote_account_to_response(status, config)
+    }
+}
+
 /// Convert Status to StatusResponse
 pub fn status_to_response(
     status: &Status,
@@ -103,11 +212,7 @@ pub fn status_to_response(
     pinned: Option<bool>,
 ) -> StatusResponse {
     let base_url = config.server.base_url();
-    let account_response = if status.is_local || status.account_address.trim().is_empty() {
-        account_to_response(account, config)
-    } else {
-        remote_account_to_response(status, config)
-    };
+    let account_response = status_author_to_response(status, account, config);
 
     StatusResponse {
         id: status.id.clone(),
@@ -135,8 +240,14 @@ pub fn status_to_response(
         reblog: None, // TODO: Handle boosts
         account: account_response,
         media_attachments: vec![], // TODO: Load from database
-        mentions: vec![],
-        tags: vec![],
+        mentions: mentions_from_content(&status.content, account, &base_url),
+        tags: crate::data::extract_hashtags_from_content(&status.content)
+            .into_iter()
+            .map(|name| StatusTagResponse {
+                url: format!("{}/tags/{}", base_url, name),
+                name,
+            })
+            .collect(),
         emojis: vec![],
         card: None,
         poll: None,
@@ -145,9 +256,62 @@ pub fn status_to_response(
         muted,
         bookmarked,
         pinned,
+        filtered: vec![],
+    }
+}
+
+/// Convert an active filter to FilterV2Response
+pub fn filter_to_v2_response(filter: &ActiveFilter) -> FilterV2Response {
+    FilterV2Response {
+        id: filter.id.clone(),
+        title: filter.title.clone(),
+        context: filter.context.clone(),
+        expires_at: filter.expires_at.clone(),
+        filter_action: filter.action.as_str().to_string(),
+        keywords: filter
+            .keywords
+            .iter()
+            .map(filter_keyword_to_response)
+            .collect(),
+        statuses: filter
+            .statuses
+            .iter()
+            .map(filter_status_to_response)
+            .collect(),
     }
 }
 
+/// Convert a filter keyword to API response
+pub fn filter_keyword_to_response(keyword: &FilterKeyword) -> FilterKeywordResponse {
+    FilterKeywordResponse {
+        id: keyword.id.clone(),
+        keyword: keyword.keyword.clone(),
+        whole_word: keyword.whole_word,
+    }
+}
+
+/// Convert a filter status to API response
+pub fn filter_status_to_response(status: &FilterStatus) -> FilterStatusResponse {
+    FilterStatusResponse {
+        id: status.id.clone(),
+        status_id: status.status_id.clone(),
+    }
+}
+
+/// Convert filter matches to the `filtered` array of a status
+pub fn filter_matches_to_response(matches: &[FilterMatch]) -> Vec<FilterResultResponse> {
+    matches
+        .iter()
+        .map(|filter_match| FilterResultResponse {
+            filter: filter_to_v2_response(&filter_match.filter),
+            keyword_matches: (!filter_match.keyword_matches.is_empty())
+                .then(|| filter_match.keyword_matches.clone()),
+            status_matches: (!filter_match.status_matches.is_empty())
+                .then(|| filter_match.status_matches.clone()),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,11 +368,13 @@ mod tests {
                 display_name: "Admin".to_string(),
                 email: Some("admin@test.example.com".to_string()),
                 note: Some("Test administrator".to_string()),
+                also_known_as: Vec::new(),
             },
             cache: CacheConfig {
                 timeline_max_items: 2000,
                 profile_ttl: 86400,
             },
+            federation: FederationConfig::default(),
             logging: LoggingConfig {
                 level: "info".to_string(),
                 format: "pretty".to_string(),
@@ -303,6 +469,65 @@ mod tests {
         assert_eq!(response.account.username, "testuser");
     }
 
+    #[test]
+    fn test_status_to_response_lists_formatted_mentions_and_tags() {
+        let config = create_test_config();
+        let account = Account {
+            id: "123".to_string(),
+            username: "testuser".to_string(),
+            display_name: None,
+            note: None,
+            avatar_s3_key: None,
+            header_s3_key: None,
+            private_key_pem: "private".to_string(),
+            public_key_pem: "public".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let mentions = vec![crate::data::StatusMention {
+            status_id: "456".to_string(),
+            account_address: "bob@remote.example".to_string(),
+            actor_uri: "https://remote.example/users/bob".to_string(),
+            inbox_uri: None,
+        }];
+        let content = crate::service::ContentFormatter::new(&config.server.base_url(), "testuser")
+            .with_mentions(&mentions)
+            .format("@testuser @bob@remote.example #Rust #rust https://example.com");
+
+        let status = Status {
+            id: "456".to_string(),
+            uri: "https://test.example.com/users/testuser/statuses/456".to_string(),
+            content,
+            content_warning: None,
+            visibility: "public".to_string(),
+            language: Some("en".to_string()),
+            account_address: String::new(),
+            is_local: true,
+            in_reply_to_uri: None,
+            boost_of_uri: None,
+            persisted_reason: "own".to_string(),
+            created_at: Utc::now(),
+            fetched_at: None,
+        };
+
+        let response = status_to_response(&status, &account, &config, None, None, None, None, None);
+
+        assert_eq!(response.mentions.len(), 2);
+        assert_eq!(response.mentions[0].id, "123");
+        assert_eq!(response.mentions[0].acct, "testuser");
+        assert_eq!(
+            response.mentions[0].url,
+            "https://test.example.com/users/testuser"
+        );
+        assert_eq!(response.mentions[1].id, "bob@remote.example");
+        assert_eq!(response.mentions[1].username, "bob");
+        assert_eq!(response.mentions[1].acct, "bob@remote.example");
+        assert_eq!(response.mentions[1].url, "https://remote.example/users/bob");
+        assert_eq!(response.tags.len(), 1);
+        assert_eq!(response.tags[0].name, "rust");
+        assert_eq!(response.tags[0].url, "https://test.example.com/tags/rust");
+    }
+
     #[test]
     fn test_status_to_response_remote_account_uses_stable_placeholder_created_at() {
         let config = create_test_config();
@@ -52,8 +52,8 @@ pub struct StatusResponse {
     pub reblog: Option<Box<StatusResponse>>,
     pub account: AccountResponse,
     pub media_attachments: Vec<MediaAttachmentResponse>,
-    pub mentions: Vec<serde_json::Value>,
-    pub tags: Vec<serde_json::Value>,
+    pub mentions: Vec<StatusMentionResponse>,
+    pub tags: Vec<StatusTagResponse>,
     pub emojis: Vec<serde_json::Value>,
     pub card: Option<serde_json::Value>,
     pub poll: Option<serde_json::Value>,
@@ -62,6 +62,45 @@ pub struct StatusResponse {
     pub muted: Option<bool>,
     pub bookmarked: Option<bool>,
     pub pinned: Option<bool>,
+    /// Filters matching this status in the requested context
+    #[serde(default)]
+    pub filtered: Vec<FilterResultResponse>,
+}
+
+/// Filter response (v2 API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterV2Response {
+    pub id: String,
+    pub title: String,
+    pub context: Vec<String>,
+    pub expires_at: Option<String>,
+    /// warn, hide or blur
+    pub filter_action: String,
+    pub keywords: Vec<FilterKeywordResponse>,
+    pub statuses: Vec<FilterStatusResponse>,
+}
+
+/// Filter keyword response (v2 API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterKeywordResponse {
+    pub id: String,
+    pub keyword: String,
+    pub whole_word: bool,
+}
+
+/// Filter status response (v2 API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterStatusResponse {
+    pub id: String,
+    pub status_id: String,
+}
+
+/// Filter result attached to a status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterResultResponse {
+    pub filter: FilterV2Response,
+    pub keyword_matches: Option<Vec<String>>,
+    pub status_matches: Option<Vec<String>>,
 }
 
 /// Media attachment response
@@ -79,6 +118,22 @@ pub struct MediaAttachmentResponse {
     pub blurhash: Option<String>,
 }
 
+/// Account mentioned in a status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusMentionResponse {
+    pub id: String,
+    pub username: String,
+    pub url: String,
+    pub acct: String,
+}
+
+/// Hashtag used in a status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTagResponse {
+    pub name: String,
+    pub url: String,
+}
+
 /// Context response (ancestors and descendants)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextResponse {
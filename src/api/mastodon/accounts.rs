@@ -10,6 +10,7 @@ use std::collections::HashSet;
 use super::federation_delivery::{
     build_delivery, resolve_remote_actor_and_inbox, spawn_best_effort_delivery,
 };
+use super::statuses::apply_status_counts;
 use crate::AppState;
 use crate::auth::CurrentUser;
 use crate::error::AppError;
@@ -61,7 +62,7 @@ pub struct SearchParams {
     pub following: Option<bool>,
 }
 
-fn default_port_for_protocol(protocol: &str) -> Option<u16> {
+pub(super) fn default_port_for_protocol(protocol: &str) -> Option<u16> {
     if protocol.eq_ignore_ascii_case("http") {
         Some(80)
     } else if protocol.eq_ignore_ascii_case("https") {
@@ -118,7 +119,11 @@ fn format_authority_host(host: &str) -> String {
     }
 }
 
-fn is_same_local_account(target_address: &str, local_address: &str, local_protocol: &str) -> bool {
+pub(super) fn is_same_local_account(
+    target_address: &str,
+    local_address: &str,
+    local_protocol: &str,
+) -> bool {
     let Some((target_user, target_domain)) = target_address.split_once('@') else {
         return false;
     };
@@ -148,7 +153,7 @@ fn is_same_local_account(target_address: &str, local_address: &str, local_protoc
     target_effective_port == local_effective_port
 }
 
-fn normalize_account_address(raw: &str) -> Result<String, AppError> {
+pub(super) fn normalize_account_address(raw: &str) -> Result<String, AppError> {
     fn normalize_domain(raw: &str) -> Result<String, AppError> {
         let parsed = url::Url::parse(&format!("https://{}", raw))
             .map_err(|_| AppError::Validation("Invalid account ID format".to_string()))?;
@@ -240,7 +245,7 @@ fn account_addresses_match_with_default_port(
     }
 }
 
-async fn resolve_target_address(state: &AppState, id: &str) -> Result<String, AppError> {
+pub(super) async fn resolve_target_address(state: &AppState, id: &str) -> Result<String, AppError> {
     if id.starts_with("http://") || id.starts_with("https://") {
         return Err(AppError::Validation(
             "Account URI is not yet supported".to_string(),
@@ -332,6 +337,7 @@ pub async fn verify_credentials(
 
     response.followers_count = followers_count;
     response.following_count = following_count;
+    response.statuses_count = state.db.count_local_statuses().await? as i32;
 
     // Update metrics
     FOLLOWERS_TOTAL.set(followers_count as i64);
@@ -345,6 +351,18 @@ pub async fn verify_credentials(
     Ok(Json(serde_json::to_value(response).unwrap()))
 }
 
+/// Fill in the local account's follower, following and status counts
+async fn apply_local_account_counts(
+    state: &AppState,
+    response: &mut crate::api::AccountResponse,
+) -> Result<(), AppError> {
+    response.followers_count = state.db.count_follower_addresses().await? as i32;
+    response.following_count = state.db.count_follow_addresses().await? as i32;
+    response.statuses_count = state.db.count_local_statuses().await? as i32;
+
+    Ok(())
+}
+
 /// PATCH /api/v1/accounts/update_credentials
 pub async fn update_credentials(
     State(state): State<AppState>,
@@ -377,12 +395,7 @@ pub async fn update_credentials(
     // Return updated account
     let mut response = crate::api::account_to_response(&account, &state.config);
 
-    // Get counts
-    let followers_count = state.db.count_follower_addresses().await? as i32;
-    let following_count = state.db.count_follow_addresses().await? as i32;
-
-    response.followers_count = followers_count;
-    response.following_count = following_count;
+    apply_local_account_counts(&state, &mut response).await?;
 
     Ok(Json(serde_json::to_value(response).unwrap()))
 }
@@ -403,32 +416,104 @@ pub async fn get_account(
     // Convert to API response
     let mut response = crate::api::account_to_response(&account, &state.config);
 
-    // Get counts
-    let followers_count = state.db.count_follower_addresses().await? as i32;
-    let following_count = state.db.count_follow_addresses().await? as i32;
-
-    response.followers_count = followers_count;
-    response.following_count = following_count;
+    apply_local_account_counts(&state, &mut response).await?;
 
     Ok(Json(serde_json::to_value(response).unwrap()))
 }
 
+/// Pinned statuses of a remote account
+///
+/// Reads the account's `featured` collection and stores the result, so the
+/// last known pins are served when the remote server cannot be reached.
+async fn remote_pinned_statuses(
+    state: &AppState,
+    address: &str,
+) -> Result<Vec<crate::data::Status>, AppError> {
+    if let Err(error) = refresh_remote_pins(state, address).await {
+        tracing::debug!(
+            %error,
+            address,
+            "Serving stored pins because the featured collection could not be read"
+        );
+    }
+
+    state.db.get_remote_pinned_statuses(address).await
+}
+
+async fn refresh_remote_pins(state: &AppState, address: &str) -> Result<(), AppError> {
+    let client = state.federation_fetch_client.as_ref();
+    let signer = state.instance_actor.fetch_signer().await?;
+    let actor_uri = match state.profile_cache.get(address).await {
+        Some(profile) => profile.uri.clone(),
+        None => {
+            crate::federation::resolve_webfinger(address, client, Some(&signer))
+                .await?
+                .actor_uri
+        }
+    };
+    let actor_document = crate::federation::fetch_actor(&actor_uri, client, Some(&signer)).await?;
+    let actor = crate::federation::parse_actor(&actor_document)?;
+
+    let statuses: Vec<_> = match actor.featured {
+        Some(featured_uri) => {
+            crate::federation::fetch_featured_objects(&featured_uri, client, Some(&signer))
+                .await?
+                .iter()
+                .filter_map(|object| {
+                    crate::federation::featured_status_from_object(object, &actor.id, address)
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    state
+        .db
+        .replace_remote_status_pins(address, &statuses)
+        .await
+}
+
 /// GET /api/v1/accounts/:id/statuses
 pub async fn account_statuses(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Query(params): Query<AccountStatusesParams>,
-) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+) -> Result<Json<Vec<crate::api::StatusResponse>>, AppError> {
     // Get the account
     let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
+    let limit = params.limit.unwrap_or(20).min(40);
+    let only_pinned = params.pinned.unwrap_or(false);
+
+    // Only the pinned posts of remote accounts are known.
+    if account.id != id && id.contains('@') && only_pinned {
+        let address = normalize_account_address(&id)?;
+        let responses = remote_pinned_statuses(&state, &address)
+            .await?
+            .iter()
+            .take(limit)
+            .map(|status| {
+                // `pinned` is about the local account's pins, so it is left out
+                crate::api::status_to_response(
+                    status,
+                    &account,
+                    &state.config,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            })
+            .collect();
+        return Ok(Json(responses));
+    }
+
     if account.id != id {
         return Err(AppError::NotFound);
     }
 
     // Get local statuses in pagination window.
-    let limit = params.limit.unwrap_or(20).min(40);
-    let only_pinned = params.pinned.unwrap_or(false);
     let exclude_reblogs = params.exclude_reblogs.unwrap_or(false);
     let exclude_replies = params.exclude_replies.unwrap_or(false);
     let only_media = params.only_media.unwrap_or(false);
@@ -483,7 +568,7 @@ pub async fn account_statuses(
                 None,
                 Some(is_pinned),
             );
-            responses.push(serde_json::to_value(response).unwrap());
+            responses.push(response);
             if responses.len() >= limit {
                 break;
             }
@@ -508,6 +593,8 @@ pub async fn account_statuses(
         page_max_id = Some(next_max_id);
     }
 
+    apply_status_counts(&state, &mut responses).await?;
+
     Ok(Json(responses))
 }
 
@@ -818,13 +905,17 @@ pub async fn get_relationships(
             .db
             .has_follow_request_with_default_port(&target_address, default_port)
             .await?;
+        let blocked_by = state
+            .db
+            .is_blocked_by(&target_address, default_port)
+            .await?;
 
         let relationship = RelationshipResponse {
             id: id.clone(),
             following,
             followed_by,
             blocking,
-            blocked_by: false,
+            blocked_by,
             muting,
             muting_notifications,
             requested,
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::AppState;
 use crate::auth::CurrentUser;
+use crate::config::AppConfig;
+use crate::data::{Account, Report};
 use crate::error::AppError;
 
 #[derive(Debug, Deserialize)]
@@ -52,15 +54,8 @@ pub struct AdminActionRequest {
     pub reason: Option<String>,
 }
 
-/// GET /api/v1/admin/accounts
-pub async fn list_accounts(
-    State(state): State<AppState>,
-    CurrentUser(_session): CurrentUser,
-    Query(_params): Query<AdminAccountParams>,
-) -> Result<Json<Vec<AdminAccount>>, AppError> {
-    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
-
-    let admin_account = AdminAccount {
+fn local_admin_account(account: &Account, config: &AppConfig) -> AdminAccount {
+    AdminAccount {
         id: account.id.clone(),
         username: account.username.clone(),
         domain: None,
@@ -73,11 +68,43 @@ pub async fn list_accounts(
         silenced: false,
         disabled: false,
         approved: true,
-        account: serde_json::to_value(crate::api::account_to_response(&account, &state.config))
-            .unwrap(),
-    };
+        account: serde_json::to_value(crate::api::account_to_response(account, config)).unwrap(),
+    }
+}
+
+fn remote_admin_account(address: &str, config: &AppConfig) -> AdminAccount {
+    let account = crate::api::remote_address_to_account_response(address, config);
+    let domain = account
+        .acct
+        .split_once('@')
+        .map(|(_, domain)| domain.to_string());
+
+    AdminAccount {
+        id: account.id.clone(),
+        username: account.username.clone(),
+        domain,
+        created_at: account.created_at.to_rfc3339(),
+        email: None,
+        ip: None,
+        role: "user".to_string(),
+        confirmed: true,
+        suspended: false,
+        silenced: false,
+        disabled: false,
+        approved: true,
+        account: serde_json::to_value(account).unwrap(),
+    }
+}
+
+/// GET /api/v1/admin/accounts
+pub async fn list_accounts(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Query(_params): Query<AdminAccountParams>,
+) -> Result<Json<Vec<AdminAccount>>, AppError> {
+    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
-    Ok(Json(vec![admin_account]))
+    Ok(Json(vec![local_admin_account(&account, &state.config)]))
 }
 
 /// GET /api/v1/admin/accounts/:id
@@ -92,24 +119,7 @@ pub async fn get_account(
         return Err(AppError::NotFound);
     }
 
-    let admin_account = AdminAccount {
-        id: account.id.clone(),
-        username: account.username.clone(),
-        domain: None,
-        created_at: account.created_at.to_rfc3339(),
-        email: None,
-        ip: None,
-        role: "owner".to_string(),
-        confirmed: true,
-        suspended: false,
-        silenced: false,
-        disabled: false,
-        approved: true,
-        account: serde_json::to_value(crate::api::account_to_response(&account, &state.config))
-            .unwrap(),
-    };
-
-    Ok(Json(admin_account))
+    Ok(Json(local_admin_account(&account, &state.config)))
 }
 
 /// POST /api/v1/admin/accounts/:id/action
@@ -126,26 +136,176 @@ pub async fn account_action(
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AdminReportParams {
+    pub resolved: Option<bool>,
+    pub max_id: Option<String>,
+    pub limit: Option<usize>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AdminReport {
     pub id: String,
     pub action_taken: bool,
+    pub action_taken_at: Option<String>,
+    pub category: String,
     pub comment: String,
+    pub forwarded: bool,
     pub created_at: String,
     pub updated_at: String,
-    pub account: serde_json::Value,
-    pub target_account: serde_json::Value,
-    pub assigned_account: Option<serde_json::Value>,
-    pub action_taken_by_account: Option<serde_json::Value>,
+    pub account: AdminAccount,
+    pub target_account: AdminAccount,
+    pub assigned_account: Option<AdminAccount>,
+    pub action_taken_by_account: Option<AdminAccount>,
     pub statuses: Vec<serde_json::Value>,
+    pub rules: Vec<serde_json::Value>,
+}
+
+/// Render a report for the admin API
+///
+/// Reported statuses that are no longer stored or cached are left out.
+async fn report_to_admin_response(
+    state: &AppState,
+    account: &Account,
+    report: &Report,
+) -> Result<AdminReport, AppError> {
+    let local_address = format!("{}@{}", account.username, state.config.server.domain);
+    let admin_account = |address: &str| {
+        if address.eq_ignore_ascii_case(&local_address) {
+            local_admin_account(account, &state.config)
+        } else {
+            remote_admin_account(address, &state.config)
+        }
+    };
+
+    let mut statuses = Vec::new();
+    for status_uri in report.status_uri_list() {
+        let status = match state.db.get_status_by_uri(&status_uri).await? {
+            Some(status) => Some(status),
+            None => state
+                .timeline_cache
+                .get_by_uri(&status_uri)
+                .await
+                .map(|cached| cached.to_status()),
+        };
+        if let Some(status) = status {
+            let response = crate::api::status_to_response(
+                &status,
+                account,
+                &state.config,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            statuses.push(serde_json::to_value(response).unwrap());
+        }
+    }
+
+    Ok(AdminReport {
+        id: report.id.clone(),
+        action_taken: report.action_taken,
+        action_taken_at: report.action_taken_at.map(|at| at.to_rfc3339()),
+        category: report.category.clone(),
+        comment: report.comment.clone(),
+        forwarded: report.forwarded,
+        created_at: report.created_at.to_rfc3339(),
+        updated_at: report.updated_at.to_rfc3339(),
+        account: admin_account(&report.account_address),
+        target_account: admin_account(&report.target_account_address),
+        assigned_account: report
+            .assigned
+            .then(|| local_admin_account(account, &state.config)),
+        action_taken_by_account: report
+            .action_taken
+            .then(|| local_admin_account(account, &state.config)),
+        statuses,
+        rules: vec![],
+    })
 }
 
 /// GET /api/v1/admin/reports
 pub async fn list_reports(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
+    Query(params): Query<AdminReportParams>,
 ) -> Result<Json<Vec<AdminReport>>, AppError> {
-    Ok(Json(vec![]))
+    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
+    let limit = params.limit.unwrap_or(100).clamp(1, 200);
+    let reports = state
+        .db
+        .get_reports(params.resolved, limit, params.max_id.as_deref())
+        .await?;
+
+    let mut responses = Vec::with_capacity(reports.len());
+    for report in &reports {
+        responses.push(report_to_admin_response(&state, &account, report).await?);
+    }
+
+    Ok(Json(responses))
+}
+
+/// GET /api/v1/admin/reports/:id
+pub async fn get_report(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<AdminReport>, AppError> {
+    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
+    let report = state.db.get_report(&id).await?.ok_or(AppError::NotFound)?;
+
+    Ok(Json(
+        report_to_admin_response(&state, &account, &report).await?,
+    ))
+}
+
+/// POST /api/v1/admin/reports/:id/assign_to_self
+pub async fn assign_report_to_self(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<AdminReport>, AppError> {
+    if !state.db.set_report_assigned(&id, true).await? {
+        return Err(AppError::NotFound);
+    }
+    get_report(State(state), current_user, Path(id)).await
+}
+
+/// POST /api/v1/admin/reports/:id/unassign
+pub async fn unassign_report(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<AdminReport>, AppError> {
+    if !state.db.set_report_assigned(&id, false).await? {
+        return Err(AppError::NotFound);
+    }
+    get_report(State(state), current_user, Path(id)).await
+}
+
+/// POST /api/v1/admin/reports/:id/resolve
+pub async fn resolve_report(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<AdminReport>, AppError> {
+    if !state.db.set_report_action_taken(&id, true).await? {
+        return Err(AppError::NotFound);
+    }
+    get_report(State(state), current_user, Path(id)).await
+}
+
+/// POST /api/v1/admin/reports/:id/reopen
+pub async fn reopen_report(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<AdminReport>, AppError> {
+    if !state.db.set_report_action_taken(&id, false).await? {
+        return Err(AppError::NotFound);
+    }
+    get_report(State(state), current_user, Path(id)).await
 }
 
 #[derive(Debug, Serialize)]
@@ -0,0 +1,104 @@
+//! Featured tags endpoints
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+use crate::auth::CurrentUser;
+use crate::data::FeaturedTag;
+use crate::error::AppError;
+
+/// Most hashtags that can be featured (advertised as `max_featured_tags`)
+pub const MAX_FEATURED_TAGS: usize = 10;
+
+/// Featured tag response
+#[derive(Debug, Serialize)]
+pub struct FeaturedTagResponse {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub statuses_count: i64,
+    pub last_status_at: Option<DateTime<Utc>>,
+}
+
+/// Feature tag request
+#[derive(Debug, Deserialize)]
+pub struct CreateFeaturedTagRequest {
+    pub name: String,
+}
+
+async fn featured_tag_to_response(
+    state: &AppState,
+    tag: FeaturedTag,
+) -> Result<FeaturedTagResponse, AppError> {
+    let (statuses_count, last_status_at) = state.db.get_local_hashtag_usage(&tag.name).await?;
+
+    Ok(FeaturedTagResponse {
+        url: format!("{}/tags/{}", state.config.server.base_url(), tag.name),
+        id: tag.id,
+        name: tag.name,
+        statuses_count,
+        last_status_at,
+    })
+}
+
+/// GET /api/v1/featured_tags
+/// Get the hashtags featured on the profile
+pub async fn get_featured_tags(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+) -> Result<Json<Vec<FeaturedTagResponse>>, AppError> {
+    let mut response = Vec::new();
+    for tag in state.db.get_featured_tags().await? {
+        response.push(featured_tag_to_response(&state, tag).await?);
+    }
+
+    Ok(Json(response))
+}
+
+/// POST /api/v1/featured_tags
+/// Feature a hashtag on the profile
+pub async fn create_featured_tag(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Json(req): Json<CreateFeaturedTagRequest>,
+) -> Result<Json<FeaturedTagResponse>, AppError> {
+    let name = req.name.trim().trim_start_matches('#');
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(AppError::Unprocessable("Invalid hashtag name".to_string()));
+    }
+
+    let featured = state.db.get_featured_tags().await?;
+    let already_featured = featured
+        .iter()
+        .any(|tag| tag.name.eq_ignore_ascii_case(name));
+    if !already_featured && featured.len() >= MAX_FEATURED_TAGS {
+        return Err(AppError::Unprocessable(format!(
+            "Cannot feature more than {} hashtags",
+            MAX_FEATURED_TAGS
+        )));
+    }
+
+    let tag = state.db.insert_featured_tag(name).await?;
+    Ok(Json(featured_tag_to_response(&state, tag).await?))
+}
+
+/// DELETE /api/v1/featured_tags/:id
+/// Stop featuring a hashtag
+pub async fn delete_featured_tag(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let deleted = state.db.delete_featured_tag(&id).await?;
+
+    if !deleted {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({})))
+}
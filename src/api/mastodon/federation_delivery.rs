@@ -4,13 +4,14 @@ use std::net::SocketAddr;
 use std::time::Duration;
 
 use crate::AppState;
-use crate::data::{Account, CachedProfile};
+use crate::data::{Account, CachedProfile, StatusMention};
 use crate::error::AppError;
-use crate::federation::{ActivityDelivery, DeliveryResult};
+use crate::federation::{
+    ActivityDelivery, DeliveryResult, FetchSigner, NoteSerializer, extract_mentions,
+};
 use chrono::Utc;
 
 const OUTBOUND_DELIVERY_TIMEOUT_SECS: u64 = 5;
-const MAX_FETCH_REDIRECTS: usize = 5;
 
 struct DiscoveredRemoteActor {
     actor_uri: String,
@@ -118,55 +119,6 @@ async fn validate_remote_fetch_url(url: &url::Url) -> Result<(), AppError> {
     Ok(())
 }
 
-async fn send_validated_get(
-    http_client: &reqwest::Client,
-    url: &url::Url,
-    accept_header: &str,
-) -> Result<reqwest::Response, AppError> {
-    let mut current = url.clone();
-    for _ in 0..=MAX_FETCH_REDIRECTS {
-        let _ = resolve_allowed_remote_addrs(&current).await?;
-
-        let response = http_client
-            .get(current.clone())
-            .header("Accept", accept_header)
-            .send()
-            .await
-            .map_err(|error| {
-                AppError::Federation(format!("Request failed for {}: {}", current, error))
-            })?;
-
-        if response.status().is_redirection() {
-            let Some(location) = response.headers().get(reqwest::header::LOCATION) else {
-                return Err(AppError::Federation(format!(
-                    "Redirect response from {} missing Location header",
-                    current
-                )));
-            };
-            let location = location.to_str().map_err(|_| {
-                AppError::Federation(format!(
-                    "Redirect response from {} had non-UTF8 Location header",
-                    current
-                ))
-            })?;
-            current = current.join(location).map_err(|error| {
-                AppError::Federation(format!(
-                    "Redirect response from {} had invalid target {} ({})",
-                    current, location, error
-                ))
-            })?;
-            continue;
-        }
-
-        return Ok(response);
-    }
-
-    Err(AppError::Federation(format!(
-        "Request exceeded redirect limit ({}) for {}",
-        MAX_FETCH_REDIRECTS, url
-    )))
-}
-
 async fn validate_actor_and_inbox_urls(actor_uri: &str, inbox_uri: &str) -> Result<(), AppError> {
     let actor_url = url::Url::parse(actor_uri).map_err(|error| {
         AppError::Federation(format!("Invalid actor URI {} ({})", actor_uri, error))
@@ -194,11 +146,35 @@ pub fn build_delivery(state: &AppState, account: &Account) -> ActivityDelivery {
         &state.config.server.base_url(),
         account,
     )
+    .with_queue(state.delivery_queue.clone())
 }
 
+pub(crate) fn build_note_serializer(state: &AppState, account: &Account) -> NoteSerializer {
+    NoteSerializer::new(
+        &state.config.server.base_url(),
+        &account.username,
+        &state.config.storage.media.public_url,
+    )
+}
+
+/// Resolve the actor and inbox of an account we are about to deliver to.
+///
+/// Accounts that block us are refused, so nothing is delivered to them.
 pub async fn resolve_remote_actor_and_inbox(
     state: &AppState,
     address: &str,
+) -> Result<(String, String), AppError> {
+    let default_port = super::accounts::default_port_for_protocol(&state.config.server.protocol);
+    if state.db.is_blocked_by(address.trim(), default_port).await? {
+        return Err(AppError::Forbidden);
+    }
+
+    lookup_remote_actor_and_inbox(state, address).await
+}
+
+async fn lookup_remote_actor_and_inbox(
+    state: &AppState,
+    address: &str,
 ) -> Result<(String, String), AppError> {
     let address = address.trim();
 
@@ -212,8 +188,10 @@ pub async fn resolve_remote_actor_and_inbox(
         }
     }
 
+    let signer = state.instance_actor.fetch_signer().await?;
     let discovered =
-        discover_remote_actor_and_inbox(&state.federation_fetch_client, address).await?;
+        discover_remote_actor_and_inbox(&state.federation_fetch_client, address, Some(&signer))
+            .await?;
 
     if let Some(profile) = build_cached_profile(
         address,
@@ -239,6 +217,63 @@ pub async fn resolve_remote_actor_and_inbox(
     Ok((discovered.actor_uri, discovered.inbox_uri))
 }
 
+/// Resolve the remote accounts mentioned in plain status text.
+///
+/// Mentions of this instance and addresses that cannot be resolved are left
+/// as plain text. The returned mentions carry the inbox found on the way, so
+/// delivering to them afterwards does not repeat the lookups.
+pub async fn resolve_status_mentions(
+    state: &AppState,
+    status_id: &str,
+    text: &str,
+) -> Vec<StatusMention> {
+    let local_domain = state.config.server.domain.to_ascii_lowercase();
+    let addresses: Vec<String> = extract_mentions(text)
+        .into_iter()
+        .filter(|address| {
+            address
+                .rsplit_once('@')
+                .is_some_and(|(_, domain)| domain != local_domain)
+        })
+        .collect();
+
+    let resolved = futures::future::join_all(
+        addresses
+            .iter()
+            .map(|address| lookup_remote_actor_and_inbox(state, address)),
+    )
+    .await;
+
+    let mut mentions: Vec<StatusMention> = Vec::new();
+    for (address, result) in addresses.into_iter().zip(resolved) {
+        match result {
+            Ok((actor_uri, inbox_uri)) => {
+                if mentions
+                    .iter()
+                    .any(|mention| mention.actor_uri == actor_uri)
+                {
+                    continue;
+                }
+                mentions.push(StatusMention {
+                    status_id: status_id.to_string(),
+                    account_address: address,
+                    actor_uri,
+                    inbox_uri: Some(inbox_uri),
+                });
+            }
+            Err(error) => {
+                tracing::warn!(
+                    mention_address = %address,
+                    %error,
+                    "Failed to resolve mentioned account; leaving mention unlinked"
+                );
+            }
+        }
+    }
+
+    mentions
+}
+
 pub fn spawn_best_effort_delivery<F>(action: &'static str, future: F)
 where
     F: Future<Output = Result<(), AppError>> + Send + 'static,
@@ -248,20 +283,20 @@ where
             .await
         {
             Ok(Ok(())) => {
-                tracing::info!(action, "Outbound federation delivery completed");
+                tracing::info!(action, "Outbound federation delivery queued");
             }
             Ok(Err(error)) => {
                 tracing::warn!(
                     action,
                     %error,
-                    "Outbound federation delivery failed (no retry policy configured)"
+                    "Outbound federation delivery could not be queued"
                 );
             }
             Err(_) => {
                 tracing::warn!(
                     action,
                     timeout_seconds = OUTBOUND_DELIVERY_TIMEOUT_SECS,
-                    "Outbound federation delivery timed out (no retry policy configured)"
+                    "Outbound federation delivery timed out before it was queued"
                 );
             }
         }
@@ -277,21 +312,17 @@ where
             .await
         {
             Ok(results) => {
-                let delivered = results.iter().filter(|result| result.success).count();
-                let failed = results.len().saturating_sub(delivered);
+                let queued = results.iter().filter(|result| result.success).count();
+                let failed = results.len().saturating_sub(queued);
 
                 if failed == 0 {
-                    tracing::info!(
-                        action,
-                        delivered,
-                        "Outbound federation batch delivery completed"
-                    );
+                    tracing::info!(action, queued, "Outbound federation batch delivery queued");
                 } else {
                     tracing::warn!(
                         action,
-                        delivered,
+                        queued,
                         failed,
-                        "Outbound federation batch delivery completed with failures (no retry policy configured)"
+                        "Outbound federation batch delivery could not be fully queued"
                     );
                 }
             }
@@ -299,7 +330,7 @@ where
                 tracing::warn!(
                     action,
                     timeout_seconds = OUTBOUND_DELIVERY_TIMEOUT_SECS,
-                    "Outbound federation batch delivery timed out (no retry policy configured)"
+                    "Outbound federation batch delivery timed out before it was queued"
                 );
             }
         }
@@ -309,6 +340,7 @@ where
 async fn discover_remote_actor_and_inbox(
     http_client: &reqwest::Client,
     address: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<DiscoveredRemoteActor, AppError> {
     let actor_uri = if let Some(actor_uri) = parse_actor_uri_address(address) {
         actor_uri
@@ -325,9 +357,9 @@ async fn discover_remote_actor_and_inbox(
             ));
         }
 
-        discover_actor_uri(http_client, username, domain).await?
+        discover_actor_uri(http_client, username, domain, signer).await?
     };
-    let actor = fetch_actor_document(http_client, &actor_uri).await?;
+    let actor = fetch_actor_document(http_client, &actor_uri, signer).await?;
     let canonical_actor_uri = actor
         .get("id")
         .and_then(|value| value.as_str())
@@ -429,16 +461,18 @@ async fn discover_actor_uri(
     http_client: &reqwest::Client,
     username: &str,
     domain: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<String, AppError> {
     let resource = format!("acct:{}@{}", username, domain);
     let webfinger_urls = webfinger_urls_for_domain(domain, &resource)?;
     let mut last_error = None;
 
     for webfinger_url in webfinger_urls {
-        let response = match send_validated_get(
+        let response = match crate::federation::fetch_with_validated_redirects(
             http_client,
             &webfinger_url,
             "application/jrd+json, application/json",
+            signer,
         )
         .await
         {
@@ -576,17 +610,21 @@ fn is_supported_webfinger_link_type(link_type: &str) -> bool {
 async fn fetch_actor_document(
     http_client: &reqwest::Client,
     actor_uri: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<serde_json::Value, AppError> {
     let actor_url = url::Url::parse(actor_uri).map_err(|error| {
         AppError::Federation(format!("Invalid actor URI {} ({})", actor_uri, error))
     })?;
-    let response = send_validated_get(
+    let response = crate::federation::fetch_with_validated_redirects(
         http_client,
         &actor_url,
-        "application/activity+json, application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
+        crate::federation::ACTIVITY_JSON_ACCEPT,
+        signer,
     )
     .await
-    .map_err(|error| AppError::Federation(format!("Actor fetch failed for {}: {}", actor_uri, error)))?;
+    .map_err(|error| {
+        AppError::Federation(format!("Actor fetch failed for {}: {}", actor_uri, error))
+    })?;
 
     if !response.status().is_success() {
         return Err(AppError::Federation(format!(
@@ -4,11 +4,15 @@ use axum::{
     extract::{Path, State},
     response::Json,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::AppState;
+use crate::api::dto::{FilterKeywordResponse, FilterStatusResponse, FilterV2Response};
+use crate::api::{filter_keyword_to_response, filter_status_to_response, filter_to_v2_response};
 use crate::auth::CurrentUser;
+use crate::data::FilterKeyword;
 use crate::error::AppError;
+use crate::service::{FilterAction, FilterContext, FilterService};
 
 /// Filter response (v1 API)
 #[derive(Debug, Serialize)]
@@ -122,14 +126,7 @@ pub async fn create_filter(
     }
 
     // Validate context values
-    for ctx in &req.context {
-        if !["home", "notifications", "public", "thread", "account"].contains(&ctx.as_str()) {
-            return Err(AppError::Validation(format!(
-                "Invalid context '{}'. Must be 'home', 'notifications', 'public', 'thread', or 'account'",
-                ctx
-            )));
-        }
-    }
+    validate_context(&req.context)?;
 
     // Join context array into comma-separated string
     let context_str = req.context.join(",");
@@ -179,14 +176,7 @@ pub async fn update_filter(
     let phrase = req.phrase.unwrap_or(existing.1.clone());
     let context_vec = if let Some(ctx) = req.context {
         // Validate new context
-        for c in &ctx {
-            if !["home", "notifications", "public", "thread", "account"].contains(&c.as_str()) {
-                return Err(AppError::Validation(format!(
-                    "Invalid context '{}'. Must be 'home', 'notifications', 'public', 'thread', or 'account'",
-                    c
-                )));
-            }
-        }
+        validate_context(&ctx)?;
         ctx
     } else {
         // Parse existing context
@@ -254,15 +244,443 @@ pub async fn delete_filter(
     Ok(Json(serde_json::json!({})))
 }
 
+/// Keyword attributes nested in v2 filter requests
+#[derive(Debug, Deserialize)]
+pub struct FilterKeywordAttributes {
+    /// Existing keyword to update or destroy; None creates a keyword
+    pub id: Option<String>,
+    pub keyword: Option<String>,
+    pub whole_word: Option<bool>,
+    #[serde(default, rename = "_destroy")]
+    pub destroy: bool,
+}
+
+/// Create filter request (v2 API)
+#[derive(Debug, Deserialize)]
+pub struct CreateFilterV2Request {
+    pub title: String,
+    pub context: Vec<String>,
+    pub filter_action: Option<String>, // warn (default), hide or blur
+    pub expires_in: Option<i64>,       // Seconds from now
+    #[serde(default)]
+    pub keywords_attributes: Vec<FilterKeywordAttributes>,
+}
+
+/// Update filter request (v2 API)
+#[derive(Debug, Deserialize)]
+pub struct UpdateFilterV2Request {
+    pub title: Option<String>,
+    pub context: Option<Vec<String>>,
+    pub filter_action: Option<String>,
+    /// Omitted = unchanged, null = never expires
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub expires_in: Option<Option<i64>>,
+    #[serde(default)]
+    pub keywords_attributes: Vec<FilterKeywordAttributes>,
+}
+
+/// Create filter keyword request
+#[derive(Debug, Deserialize)]
+pub struct CreateFilterKeywordRequest {
+    pub keyword: String,
+    pub whole_word: Option<bool>,
+}
+
+/// Update filter keyword request
+#[derive(Debug, Deserialize)]
+pub struct UpdateFilterKeywordRequest {
+    pub keyword: Option<String>,
+    pub whole_word: Option<bool>,
+}
+
+/// Create filter status request
+#[derive(Debug, Deserialize)]
+pub struct CreateFilterStatusRequest {
+    pub status_id: String,
+}
+
+/// Distinguish an explicit `null` from an omitted field
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+fn validate_context(context: &[String]) -> Result<(), AppError> {
+    for ctx in context {
+        if FilterContext::parse(ctx).is_none() {
+            return Err(AppError::Validation(format!(
+                "Invalid context '{}'. Must be 'home', 'notifications', 'public', 'thread', or 'account'",
+                ctx
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn parse_filter_action(value: &str) -> Result<FilterAction, AppError> {
+    FilterAction::parse(value).ok_or_else(|| {
+        AppError::Validation(format!(
+            "Invalid filter_action '{}'. Must be 'warn', 'hide', or 'blur'",
+            value
+        ))
+    })
+}
+
+fn validate_title(title: &str) -> Result<(), AppError> {
+    if title.trim().is_empty() {
+        return Err(AppError::Validation("Title cannot be empty".to_string()));
+    }
+    Ok(())
+}
+
+fn validate_keyword(keyword: &str) -> Result<(), AppError> {
+    if keyword.trim().is_empty() {
+        return Err(AppError::Validation("Keyword cannot be empty".to_string()));
+    }
+    Ok(())
+}
+
+fn expires_at_from(expires_in: i64) -> Result<String, AppError> {
+    if expires_in <= 0 {
+        return Err(AppError::Validation(
+            "expires_in must be a positive number of seconds".to_string(),
+        ));
+    }
+    let expires = chrono::Utc::now() + chrono::Duration::seconds(expires_in);
+    Ok(expires.to_rfc3339())
+}
+
 /// GET /api/v2/filters
-/// Get all filters (v2 API)
-///
-/// For now, this returns the same as v1 API
-/// In the future, this should return filters with keywords
+/// Get all filters with their keywords and statuses
 pub async fn get_filters_v2(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+) -> Result<Json<Vec<FilterV2Response>>, AppError> {
+    let filters = FilterService::new(state.db.clone()).list().await?;
+    Ok(Json(filters.iter().map(filter_to_v2_response).collect()))
+}
+
+/// GET /api/v2/filters/:id
+/// Get a specific filter
+pub async fn get_filter_v2(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<FilterV2Response>, AppError> {
+    let filter = FilterService::new(state.db.clone()).get(&id).await?;
+    Ok(Json(filter_to_v2_response(&filter)))
+}
+
+/// POST /api/v2/filters
+/// Create a filter, optionally with keywords
+pub async fn create_filter_v2(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Json(req): Json<CreateFilterV2Request>,
+) -> Result<Json<FilterV2Response>, AppError> {
+    validate_title(&req.title)?;
+    if req.context.is_empty() {
+        return Err(AppError::Validation(
+            "At least one context is required".to_string(),
+        ));
+    }
+    validate_context(&req.context)?;
+
+    let filter_action = parse_filter_action(req.filter_action.as_deref().unwrap_or("warn"))?;
+    let expires_at = req.expires_in.map(expires_at_from).transpose()?;
+
+    let mut keywords = Vec::new();
+    for attributes in req.keywords_attributes {
+        if attributes.destroy {
+            continue;
+        }
+        let keyword = attributes.keyword.unwrap_or_default();
+        validate_keyword(&keyword)?;
+        keywords.push((keyword, attributes.whole_word.unwrap_or(true)));
+    }
+
+    let id = state
+        .db
+        .create_filter_v2(
+            &req.title,
+            &req.context.join(","),
+            expires_at.as_deref(),
+            filter_action.as_str(),
+            &keywords,
+        )
+        .await?;
+
+    get_filter_v2(State(state), CurrentUser(_session), Path(id)).await
+}
+
+/// PUT /api/v2/filters/:id
+/// Update a filter
+///
+/// `keywords_attributes` entries with an `id` update that keyword (or delete
+/// it with `_destroy`); entries without one add a keyword.
+pub async fn update_filter_v2(
     State(state): State<AppState>,
     CurrentUser(session): CurrentUser,
-) -> Result<Json<Vec<FilterResponse>>, AppError> {
-    // For now, just return v1 filters
-    get_filters(State(state), CurrentUser(session)).await
+    Path(id): Path<String>,
+    Json(req): Json<UpdateFilterV2Request>,
+) -> Result<Json<FilterV2Response>, AppError> {
+    let existing = FilterService::new(state.db.clone()).get(&id).await?;
+
+    let title = req.title.unwrap_or(existing.title);
+    validate_title(&title)?;
+
+    let context = match req.context {
+        Some(context) => {
+            if context.is_empty() {
+                return Err(AppError::Validation(
+                    "At least one context is required".to_string(),
+                ));
+            }
+            validate_context(&context)?;
+            context
+        }
+        None => existing.context,
+    };
+
+    let filter_action = match req.filter_action.as_deref() {
+        Some(value) => parse_filter_action(value)?,
+        None => existing.action,
+    };
+
+    let expires_at = match req.expires_in {
+        Some(Some(seconds)) => Some(expires_at_from(seconds)?),
+        Some(None) => None,
+        None => existing.expires_at,
+    };
+
+    let mut keywords_to_add = Vec::new();
+    let mut keywords_to_update = Vec::new();
+    let mut keywords_to_delete = Vec::new();
+    for attributes in req.keywords_attributes {
+        let Some(keyword_id) = attributes.id else {
+            if attributes.destroy {
+                continue;
+            }
+            let keyword = attributes.keyword.unwrap_or_default();
+            validate_keyword(&keyword)?;
+            keywords_to_add.push((keyword, attributes.whole_word.unwrap_or(true)));
+            continue;
+        };
+
+        let current = existing
+            .keywords
+            .iter()
+            .find(|keyword| keyword.id == keyword_id)
+            .ok_or(AppError::NotFound)?;
+        if attributes.destroy {
+            keywords_to_delete.push(keyword_id);
+            continue;
+        }
+
+        let keyword = attributes
+            .keyword
+            .unwrap_or_else(|| current.keyword.clone());
+        validate_keyword(&keyword)?;
+        keywords_to_update.push(FilterKeyword {
+            id: keyword_id,
+            filter_id: id.clone(),
+            keyword,
+            whole_word: attributes.whole_word.unwrap_or(current.whole_word),
+        });
+    }
+
+    let updated = state
+        .db
+        .update_filter_v2(
+            &id,
+            &title,
+            &context.join(","),
+            expires_at.as_deref(),
+            filter_action.as_str(),
+            &keywords_to_add,
+            &keywords_to_update,
+            &keywords_to_delete,
+        )
+        .await?;
+    if !updated {
+        return Err(AppError::NotFound);
+    }
+
+    get_filter_v2(State(state), CurrentUser(session), Path(id)).await
+}
+
+/// GET /api/v2/filters/:id/keywords
+/// Get the keywords of a filter
+pub async fn get_filter_keywords(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(filter_id): Path<String>,
+) -> Result<Json<Vec<FilterKeywordResponse>>, AppError> {
+    let filter = FilterService::new(state.db.clone()).get(&filter_id).await?;
+    Ok(Json(
+        filter
+            .keywords
+            .iter()
+            .map(filter_keyword_to_response)
+            .collect(),
+    ))
+}
+
+/// POST /api/v2/filters/:id/keywords
+/// Add a keyword to a filter
+pub async fn create_filter_keyword(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(filter_id): Path<String>,
+    Json(req): Json<CreateFilterKeywordRequest>,
+) -> Result<Json<FilterKeywordResponse>, AppError> {
+    validate_keyword(&req.keyword)?;
+    state
+        .db
+        .get_filter_v2(&filter_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let keyword = state
+        .db
+        .create_filter_keyword(&filter_id, &req.keyword, req.whole_word.unwrap_or(true))
+        .await?;
+    Ok(Json(filter_keyword_to_response(&keyword)))
+}
+
+/// GET /api/v2/filters/keywords/:id
+/// Get a filter keyword
+pub async fn get_filter_keyword(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<FilterKeywordResponse>, AppError> {
+    let keyword = state
+        .db
+        .get_filter_keyword(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(filter_keyword_to_response(&keyword)))
+}
+
+/// PUT /api/v2/filters/keywords/:id
+/// Update a filter keyword
+pub async fn update_filter_keyword(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateFilterKeywordRequest>,
+) -> Result<Json<FilterKeywordResponse>, AppError> {
+    let mut keyword = state
+        .db
+        .get_filter_keyword(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if let Some(text) = req.keyword {
+        validate_keyword(&text)?;
+        keyword.keyword = text;
+    }
+    if let Some(whole_word) = req.whole_word {
+        keyword.whole_word = whole_word;
+    }
+
+    if !state
+        .db
+        .update_filter_keyword(&id, &keyword.keyword, keyword.whole_word)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(filter_keyword_to_response(&keyword)))
+}
+
+/// DELETE /api/v2/filters/keywords/:id
+/// Remove a keyword from its filter
+pub async fn delete_filter_keyword(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if !state.db.delete_filter_keyword(&id).await? {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(serde_json::json!({})))
+}
+
+/// GET /api/v2/filters/:id/statuses
+/// Get the statuses attached to a filter
+pub async fn get_filter_statuses(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(filter_id): Path<String>,
+) -> Result<Json<Vec<FilterStatusResponse>>, AppError> {
+    let filter = FilterService::new(state.db.clone()).get(&filter_id).await?;
+    Ok(Json(
+        filter
+            .statuses
+            .iter()
+            .map(filter_status_to_response)
+            .collect(),
+    ))
+}
+
+/// POST /api/v2/filters/:id/statuses
+/// Attach a status to a filter
+pub async fn create_filter_status(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(filter_id): Path<String>,
+    Json(req): Json<CreateFilterStatusRequest>,
+) -> Result<Json<FilterStatusResponse>, AppError> {
+    state
+        .db
+        .get_filter_v2(&filter_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    state
+        .db
+        .get_status(&req.status_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let status = state
+        .db
+        .create_filter_status(&filter_id, &req.status_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::Unprocessable("Status is already attached to this filter".to_string())
+        })?;
+    Ok(Json(filter_status_to_response(&status)))
+}
+
+/// GET /api/v2/filters/statuses/:id
+/// Get a filter status
+pub async fn get_filter_status(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<FilterStatusResponse>, AppError> {
+    let status = state
+        .db
+        .get_filter_status(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(filter_status_to_response(&status)))
+}
+
+/// DELETE /api/v2/filters/statuses/:id
+/// Detach a status from its filter
+pub async fn delete_filter_status(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if !state.db.delete_filter_status(&id).await? {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(serde_json::json!({})))
 }
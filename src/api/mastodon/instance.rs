@@ -177,7 +177,7 @@ pub async fn instance_v2(State(state): State<AppState>) -> Json<serde_json::Valu
                 "streaming": format!("wss://{}", state.config.server.domain)
             },
             "accounts": {
-                "max_featured_tags": 10
+                "max_featured_tags": super::featured_tags::MAX_FEATURED_TAGS
             },
             "statuses": {
                 "max_characters": 500,
@@ -15,6 +15,7 @@ pub mod admin;
 pub mod apps;
 pub mod bookmarks;
 pub mod conversations;
+pub mod featured_tags;
 mod federation_delivery;
 pub mod filters;
 pub mod instance;
@@ -22,12 +23,15 @@ pub mod lists;
 pub mod media;
 pub mod notifications;
 pub mod polls;
+pub mod reports;
 pub mod scheduled_statuses;
 pub mod search;
 pub mod statuses;
 pub mod streaming;
 pub mod timelines;
 
+pub(crate) use federation_delivery::build_note_serializer;
+
 /// Create Mastodon API router
 ///
 /// Routes are split into public and authenticated endpoints.
@@ -60,7 +64,10 @@ pub fn mastodon_api_router(state: AppState) -> Router<AppState> {
         .route(
             "/v1/statuses/:id/favourited_by",
             get(statuses::get_favourited_by),
-        );
+        )
+        // WebSocket streaming authenticates in the handler because clients
+        // may send the token as a query parameter or subprotocol.
+        .route("/v1/streaming", get(streaming::stream_websocket));
 
     // Authenticated endpoints (require valid token)
     let authenticated_routes = Router::new()
@@ -187,6 +194,16 @@ pub fn mastodon_api_router(state: AppState) -> Router<AppState> {
             "/v1/lists/:id/accounts",
             delete(lists::delete_list_accounts),
         )
+        // Featured tags
+        .route("/v1/featured_tags", get(featured_tags::get_featured_tags))
+        .route(
+            "/v1/featured_tags",
+            post(featured_tags::create_featured_tag),
+        )
+        .route(
+            "/v1/featured_tags/:id",
+            delete(featured_tags::delete_featured_tag),
+        )
         // Filters
         .route("/v1/filters", get(filters::get_filters))
         .route("/v1/filters/:id", get(filters::get_filter))
@@ -194,6 +211,40 @@ pub fn mastodon_api_router(state: AppState) -> Router<AppState> {
         .route("/v1/filters/:id", put(filters::update_filter))
         .route("/v1/filters/:id", delete(filters::delete_filter))
         .route("/v2/filters", get(filters::get_filters_v2))
+        .route("/v2/filters", post(filters::create_filter_v2))
+        .route("/v2/filters/:id", get(filters::get_filter_v2))
+        .route("/v2/filters/:id", put(filters::update_filter_v2))
+        .route("/v2/filters/:id", delete(filters::delete_filter))
+        .route(
+            "/v2/filters/:id/keywords",
+            get(filters::get_filter_keywords),
+        )
+        .route(
+            "/v2/filters/:id/keywords",
+            post(filters::create_filter_keyword),
+        )
+        .route("/v2/filters/keywords/:id", get(filters::get_filter_keyword))
+        .route(
+            "/v2/filters/keywords/:id",
+            put(filters::update_filter_keyword),
+        )
+        .route(
+            "/v2/filters/keywords/:id",
+            delete(filters::delete_filter_keyword),
+        )
+        .route(
+            "/v2/filters/:id/statuses",
+            get(filters::get_filter_statuses),
+        )
+        .route(
+            "/v2/filters/:id/statuses",
+            post(filters::create_filter_status),
+        )
+        .route("/v2/filters/statuses/:id", get(filters::get_filter_status))
+        .route(
+            "/v2/filters/statuses/:id",
+            delete(filters::delete_filter_status),
+        )
         // Bookmarks / Favourites
         .route("/v1/bookmarks", get(bookmarks::get_bookmarks))
         .route("/v1/favourites", get(bookmarks::get_favourites))
@@ -203,6 +254,8 @@ pub fn mastodon_api_router(state: AppState) -> Router<AppState> {
         // Polls
         .route("/v1/polls/:id", get(polls::get_poll))
         .route("/v1/polls/:id/votes", post(polls::vote_in_poll))
+        // Reports
+        .route("/v1/reports", post(reports::create_report))
         // Scheduled Statuses
         .route(
             "/v1/scheduled_statuses",
@@ -246,6 +299,17 @@ pub fn mastodon_api_router(state: AppState) -> Router<AppState> {
         .route("/v1/admin/accounts/:id", get(admin::get_account))
         .route("/v1/admin/accounts/:id/action", post(admin::account_action))
         .route("/v1/admin/reports", get(admin::list_reports))
+        .route("/v1/admin/reports/:id", get(admin::get_report))
+        .route(
+            "/v1/admin/reports/:id/assign_to_self",
+            post(admin::assign_report_to_self),
+        )
+        .route(
+            "/v1/admin/reports/:id/unassign",
+            post(admin::unassign_report),
+        )
+        .route("/v1/admin/reports/:id/resolve", post(admin::resolve_report))
+        .route("/v1/admin/reports/:id/reopen", post(admin::reopen_report))
         .route("/v1/admin/domain_blocks", get(admin::list_domain_blocks_v1))
         .route(
             "/v1/admin/domain_blocks",
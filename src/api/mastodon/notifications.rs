@@ -7,9 +7,11 @@ use axum::{
 
 use super::accounts::PaginationParams;
 use crate::AppState;
+use crate::api::dto::NotificationResponse;
 use crate::auth::CurrentUser;
-use crate::data::Status;
+use crate::data::{Account, Notification, Status};
 use crate::error::AppError;
+use crate::service::{FilterAction, FilterContext, FilterService, StatusFilter};
 
 async fn get_notification_status(state: &AppState, status_uri: &str) -> Option<Status> {
     if let Ok(status) = state.db.get_status_by_uri(status_uri).await {
@@ -19,79 +21,111 @@ async fn get_notification_status(state: &AppState, status_uri: &str) -> Option<S
     }
 
     let cached = state.timeline_cache.get_by_uri(status_uri).await?;
-    Some(Status {
-        id: cached.id.clone(),
-        uri: cached.uri.clone(),
-        content: cached.content.clone(),
-        content_warning: None,
-        visibility: cached.visibility.clone(),
-        language: None,
-        account_address: cached.account_address.clone(),
-        is_local: false,
-        in_reply_to_uri: cached.reply_to_uri.clone(),
-        boost_of_uri: cached.boost_of_uri.clone(),
-        persisted_reason: "cache_only".to_string(),
-        created_at: cached.created_at,
-        fetched_at: Some(chrono::Utc::now()),
+    Some(cached.to_status())
+}
+
+/// Render a notification with its related status, if still available.
+///
+/// The status carries the `filtered` results of `status_filter`.
+/// Shared by the REST endpoints and the streaming API.
+pub(crate) async fn notification_to_response(
+    state: &AppState,
+    account: &Account,
+    notification: &Notification,
+    status_filter: &StatusFilter,
+) -> NotificationResponse {
+    let status = if let Some(status_uri) = &notification.status_uri {
+        get_notification_status(state, status_uri).await
+    } else {
+        None
+    };
+
+    let status_response = status.map(|status| {
+        let mut response = crate::api::status_to_response(
+            &status,
+            account,
+            &state.config,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        response.filtered = crate::api::filter_matches_to_response(&status_filter.matches(&status));
+        response
+    });
+
+    NotificationResponse {
+        id: notification.id.clone(),
+        notification_type: notification.notification_type.clone(),
+        created_at: notification.created_at,
+        account: crate::api::account_to_response(account, &state.config),
+        status: status_response,
+    }
+}
+
+/// Whether a rendered notification is about a status removed by a `hide` filter
+pub(crate) fn is_hidden_by_filter(response: &NotificationResponse) -> bool {
+    response.status.as_ref().is_some_and(|status| {
+        status
+            .filtered
+            .iter()
+            .any(|result| result.filter.filter_action == FilterAction::Hide.as_str())
     })
 }
 
+/// Load the filters applied to notifications
+pub(crate) async fn notification_filter(state: &AppState) -> Result<StatusFilter, AppError> {
+    FilterService::new(state.db.clone())
+        .status_filter(FilterContext::Notifications)
+        .await
+}
+
 /// GET /api/v1/notifications
 pub async fn get_notifications(
     State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<serde_json::Value>>, AppError> {
-    use crate::api::dto::NotificationResponse;
-
     // Get account
     let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
-    // Get notifications
-    let limit = params.limit.unwrap_or(20).min(40);
-    let notifications = state
-        .db
-        .get_notifications(
-            limit,
-            params.max_id.as_deref(),
-            false, // Get all notifications, not just unread
-        )
-        .await?;
+    let status_filter = notification_filter(&state).await?;
 
-    // Convert to API responses
+    // Keep fetching while `hide` filters drop notifications so the page
+    // stays full.
+    let limit = params.limit.unwrap_or(20).min(40);
+    let mut cursor = params.max_id.clone();
     let mut responses = vec![];
-    for notification in notifications {
-        // Get status if present
-        let status = if let Some(status_uri) = &notification.status_uri {
-            get_notification_status(&state, status_uri).await
-        } else {
-            None
-        };
-
-        let status_response = if let Some(status) = status {
-            Some(crate::api::status_to_response(
-                &status,
-                &account,
-                &state.config,
-                None,
-                None,
-                None,
-                None,
-                None,
-            ))
-        } else {
-            None
-        };
-
-        let response = NotificationResponse {
-            id: notification.id.clone(),
-            notification_type: notification.notification_type.clone(),
-            created_at: notification.created_at,
-            account: crate::api::account_to_response(&account, &state.config),
-            status: status_response,
-        };
-
-        responses.push(serde_json::to_value(response).unwrap());
+    while responses.len() < limit {
+        let notifications = state
+            .db
+            .get_notifications(
+                limit,
+                cursor.as_deref(),
+                false, // Get all notifications, not just unread
+            )
+            .await?;
+        let fetched_count = notifications.len();
+        cursor = notifications
+            .last()
+            .map(|notification| notification.id.clone());
+
+        for notification in notifications {
+            let response =
+                notification_to_response(&state, &account, &notification, &status_filter).await;
+            if is_hidden_by_filter(&response) {
+                continue;
+            }
+            responses.push(serde_json::to_value(response).unwrap());
+            if responses.len() >= limit {
+                break;
+            }
+        }
+
+        if fetched_count < limit || cursor.is_none() || !status_filter.can_hide() {
+            break;
+        }
     }
 
     Ok(Json(responses))
@@ -127,8 +161,6 @@ pub async fn get_notification(
     CurrentUser(_session): CurrentUser,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    use crate::api::dto::NotificationResponse;
-
     // Get account
     let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
@@ -138,35 +170,8 @@ pub async fn get_notification(
         .await?
         .ok_or(AppError::NotFound)?;
 
-    // Get status if present
-    let status = if let Some(status_uri) = &notification.status_uri {
-        get_notification_status(&state, status_uri).await
-    } else {
-        None
-    };
-
-    let status_response = if let Some(status) = status {
-        Some(crate::api::status_to_response(
-            &status,
-            &account,
-            &state.config,
-            None,
-            None,
-            None,
-            None,
-            None,
-        ))
-    } else {
-        None
-    };
-
-    let response = NotificationResponse {
-        id: notification.id.clone(),
-        notification_type: notification.notification_type.clone(),
-        created_at: notification.created_at,
-        account: crate::api::account_to_response(&account, &state.config),
-        status: status_response,
-    };
+    let status_filter = notification_filter(&state).await?;
+    let response = notification_to_response(&state, &account, &notification, &status_filter).await;
 
     Ok(Json(serde_json::to_value(response).unwrap()))
 }
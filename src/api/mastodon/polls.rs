@@ -7,7 +7,21 @@ use axum::{
 use serde::Deserialize;
 use std::collections::HashSet;
 
-use crate::{AppState, auth::CurrentUser, error::AppError};
+use super::federation_delivery::{
+    build_delivery, build_note_serializer, resolve_remote_actor_and_inbox,
+    spawn_best_effort_batch_delivery, spawn_best_effort_delivery,
+};
+use super::statuses::{
+    build_account_service, prefetch_create_delivery_targets, should_federate_to_followers,
+};
+use crate::{
+    AppState,
+    auth::CurrentUser,
+    data::{Account, ClosedPoll, EntityId, Notification, Status},
+    error::AppError,
+    federation::StatusNote,
+    service::StreamEvent,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct VoteParams {
@@ -70,7 +84,8 @@ pub async fn get_poll(
 
 /// POST /api/v1/polls/:id/votes - Vote in a poll
 ///
-/// Vote on a poll attached to a status.
+/// Vote on a poll attached to a status. Votes on remote polls are also sent
+/// to the poll author.
 pub async fn vote_in_poll(
     State(state): State<AppState>,
     CurrentUser(session): CurrentUser,
@@ -125,10 +140,180 @@ pub async fn vote_in_poll(
         .vote_in_poll(&id, &account_address, &option_ids)
         .await?;
 
+    if let Some((question_uri, author_address)) = state.db.get_remote_poll_origin(&id).await? {
+        let choices: Vec<String> = params
+            .choices
+            .iter()
+            .map(|choice_index| options[*choice_index].1.clone())
+            .collect();
+        let state_for_delivery = state.clone();
+        spawn_best_effort_delivery("poll_vote", async move {
+            let (author_actor_uri, author_inbox_uri) =
+                resolve_remote_actor_and_inbox(&state_for_delivery, &author_address).await?;
+            let delivery = build_delivery(&state_for_delivery, &account);
+            delivery
+                .send_poll_votes(
+                    &question_uri,
+                    &author_actor_uri,
+                    &author_inbox_uri,
+                    &choices,
+                )
+                .await
+        });
+    }
+
     // Return updated poll
     get_poll(State(state), CurrentUser(session), Path(id)).await
 }
 
+/// Close polls whose closing time has passed
+///
+/// The local account is notified about its own polls and about remote polls
+/// it voted in. Final results of local polls are sent as `Update(Question)`
+/// to followers and remote voters. Returns the number of polls closed.
+pub async fn close_expired_polls(state: &AppState, limit: usize) -> Result<usize, AppError> {
+    let closed = state.db.close_expired_polls(limit).await?;
+    if closed.is_empty() {
+        return Ok(0);
+    }
+    let Some(account) = state.db.get_account().await? else {
+        return Ok(closed.len());
+    };
+    let account_address = format!("{}@{}", account.username, state.config.server.domain);
+
+    for poll in &closed {
+        if let Err(error) = finish_closed_poll(state, &account, &account_address, poll).await {
+            tracing::error!(poll_id = %poll.id, %error, "Failed to finish closed poll");
+        }
+    }
+
+    Ok(closed.len())
+}
+
+async fn finish_closed_poll(
+    state: &AppState,
+    account: &Account,
+    account_address: &str,
+    poll: &ClosedPoll,
+) -> Result<(), AppError> {
+    if let Some(status_id) = &poll.status_id {
+        let Some(status) = state.db.get_status(status_id).await? else {
+            return Ok(());
+        };
+        notify_poll_ended(state, account_address, &status.uri).await?;
+        spawn_poll_results_delivery(state, account, &status, &poll.id);
+        return Ok(());
+    }
+
+    if let (Some(question_uri), Some(author_address)) = (&poll.uri, &poll.account_address) {
+        let voted = !state
+            .db
+            .get_user_poll_votes(&poll.id, account_address)
+            .await?
+            .is_empty();
+        if voted {
+            notify_poll_ended(state, author_address, question_uri).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn notify_poll_ended(
+    state: &AppState,
+    origin_account_address: &str,
+    status_uri: &str,
+) -> Result<(), AppError> {
+    let notification = Notification {
+        id: EntityId::new().0,
+        notification_type: "poll".to_string(),
+        origin_account_address: origin_account_address.to_string(),
+        status_uri: Some(status_uri.to_string()),
+        read: false,
+        created_at: chrono::Utc::now(),
+    };
+    state.db.insert_notification(&notification).await?;
+    state
+        .streaming
+        .publish(StreamEvent::Notification(notification));
+    Ok(())
+}
+
+/// Deliver the final tally of a local poll in the background.
+///
+/// The closed Question goes to followers (for public and unlisted statuses)
+/// and to every remote voter.
+fn spawn_poll_results_delivery(
+    state: &AppState,
+    account: &Account,
+    status: &Status,
+    poll_id: &str,
+) {
+    let delivery = build_delivery(state, account);
+    let serializer = build_note_serializer(state, account);
+    let state_for_delivery = state.clone();
+    let status_for_delivery = status.clone();
+    let poll_id = poll_id.to_string();
+    let local_domain = state.config.server.domain.to_ascii_lowercase();
+    spawn_best_effort_batch_delivery("poll_results", async move {
+        let mut delivery_targets = if should_federate_to_followers(&status_for_delivery.visibility)
+        {
+            prefetch_create_delivery_targets(&build_account_service(&state_for_delivery)).await
+        } else {
+            Vec::new()
+        };
+
+        let voter_addresses = match state_for_delivery
+            .db
+            .get_poll_voter_addresses(&poll_id)
+            .await
+        {
+            Ok(addresses) => addresses,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to load poll voters for results delivery");
+                Vec::new()
+            }
+        };
+        for voter_address in voter_addresses {
+            let is_local = voter_address
+                .rsplit_once('@')
+                .is_some_and(|(_, domain)| domain.eq_ignore_ascii_case(&local_domain));
+            if is_local {
+                continue;
+            }
+            match resolve_remote_actor_and_inbox(&state_for_delivery, &voter_address).await {
+                Ok((_, voter_inbox_uri)) => {
+                    if !delivery_targets.contains(&voter_inbox_uri) {
+                        delivery_targets.push(voter_inbox_uri);
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        voter_address = %voter_address,
+                        %error,
+                        "Failed to resolve poll voter inbox for results delivery"
+                    );
+                }
+            }
+        }
+
+        if delivery_targets.is_empty() {
+            return Vec::new();
+        }
+
+        let note = match StatusNote::load(&state_for_delivery.db, status_for_delivery).await {
+            Ok(note) => note,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to load status for poll results delivery");
+                return Vec::new();
+            }
+        };
+        delivery
+            .send_update(serializer.note(&note), &[], delivery_targets)
+            .await
+    });
+}
+
 // Helper function to create poll response (for future use)
 #[allow(dead_code)]
 fn poll_to_response(
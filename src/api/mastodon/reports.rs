@@ -0,0 +1,172 @@
+//! Report endpoints
+
+use axum::{extract::State, response::Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::accounts::{is_same_local_account, normalize_account_address, resolve_target_address};
+use super::federation_delivery::{resolve_remote_actor_and_inbox, spawn_best_effort_delivery};
+use crate::AppState;
+use crate::auth::CurrentUser;
+use crate::data::{EntityId, Report};
+use crate::error::AppError;
+
+const REPORT_CATEGORIES: &[&str] = &["spam", "legal", "violation", "other"];
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReportRequest {
+    pub account_id: String,
+    #[serde(default)]
+    pub status_ids: Vec<String>,
+    #[serde(default)]
+    pub comment: String,
+    /// Also send the report to the reported account's server
+    #[serde(default)]
+    pub forward: bool,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportResponse {
+    pub id: String,
+    pub action_taken: bool,
+    pub action_taken_at: Option<String>,
+    pub category: String,
+    pub comment: String,
+    pub forwarded: bool,
+    pub created_at: String,
+    pub status_ids: Vec<String>,
+    pub rule_ids: Vec<String>,
+    pub target_account: crate::api::AccountResponse,
+}
+
+/// Find the URI of a reported status by ID, checking it belongs to the target
+async fn resolve_reported_status_uri(
+    state: &AppState,
+    status_id: &str,
+    target_address: &str,
+) -> Result<String, AppError> {
+    let (uri, account_address) = match state.db.get_status(status_id).await? {
+        Some(status) => (status.uri, status.account_address),
+        None => {
+            let cached = state
+                .timeline_cache
+                .get_by_uri(status_id)
+                .await
+                .ok_or(AppError::NotFound)?;
+            (cached.uri.clone(), cached.account_address.clone())
+        }
+    };
+
+    let author_matches = normalize_account_address(&account_address)
+        .is_ok_and(|author_address| author_address == target_address);
+    if !author_matches {
+        return Err(AppError::Validation(
+            "Reported statuses must belong to the reported account".to_string(),
+        ));
+    }
+
+    Ok(uri)
+}
+
+/// POST /api/v1/reports
+///
+/// File a report against a remote account. With `forward`, the report is
+/// also sent as a `Flag` to the account's server.
+pub async fn create_report(
+    State(state): State<AppState>,
+    CurrentUser(_session): CurrentUser,
+    Json(req): Json<CreateReportRequest>,
+) -> Result<Json<ReportResponse>, AppError> {
+    let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
+    let target_address = resolve_target_address(&state, &req.account_id).await?;
+    let local_address = format!("{}@{}", account.username, state.config.server.domain);
+    if is_same_local_account(
+        &target_address,
+        &local_address,
+        &state.config.server.protocol,
+    ) {
+        return Err(AppError::Validation("cannot report yourself".to_string()));
+    }
+
+    let category = req.category.unwrap_or_else(|| "other".to_string());
+    if !REPORT_CATEGORIES.contains(&category.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Invalid report category: {}",
+            category
+        )));
+    }
+
+    let mut status_uris = Vec::with_capacity(req.status_ids.len());
+    for status_id in &req.status_ids {
+        let uri = resolve_reported_status_uri(&state, status_id, &target_address).await?;
+        if !status_uris.contains(&uri) {
+            status_uris.push(uri);
+        }
+    }
+
+    let now = Utc::now();
+    let id = EntityId::new().0;
+    // Forwarded reports come from the instance actor, so the reported
+    // server does not learn who filed them.
+    let flag_uri = format!("{}/flags/{}", state.instance_actor.actor_uri(), id);
+    let report = Report {
+        id,
+        uri: req.forward.then(|| flag_uri.clone()),
+        account_address: local_address,
+        target_account_address: target_address.clone(),
+        status_uris: serde_json::to_string(&status_uris)
+            .map_err(|error| AppError::Internal(error.into()))?,
+        comment: req.comment,
+        category,
+        forwarded: req.forward,
+        assigned: false,
+        action_taken: false,
+        action_taken_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    state.db.insert_report(&report).await?;
+
+    if report.forwarded {
+        let state_for_delivery = state.clone();
+        let target_address_for_delivery = target_address.clone();
+        let status_uris_for_delivery = status_uris.clone();
+        let comment = report.comment.clone();
+        spawn_best_effort_delivery("report", async move {
+            let (target_actor_uri, target_inbox_uri) =
+                resolve_remote_actor_and_inbox(&state_for_delivery, &target_address_for_delivery)
+                    .await?;
+            let delivery = state_for_delivery
+                .instance_actor
+                .delivery(state_for_delivery.http_client.clone())
+                .await?
+                .with_signature_preferences(state_for_delivery.signature_preferences.clone());
+            delivery
+                .send_flag(
+                    &flag_uri,
+                    &target_actor_uri,
+                    &target_inbox_uri,
+                    &status_uris_for_delivery,
+                    &comment,
+                )
+                .await
+        });
+    }
+
+    Ok(Json(ReportResponse {
+        id: report.id,
+        action_taken: false,
+        action_taken_at: None,
+        category: report.category,
+        comment: report.comment,
+        forwarded: report.forwarded,
+        created_at: report.created_at.to_rfc3339(),
+        status_ids: req.status_ids,
+        rule_ids: vec![],
+        target_account: crate::api::remote_address_to_account_response(
+            &target_address,
+            &state.config,
+        ),
+    }))
+}
@@ -6,7 +6,21 @@ use axum::{
 };
 use serde::Deserialize;
 
-use crate::{AppState, auth::CurrentUser, error::AppError};
+use super::federation_delivery::resolve_status_mentions;
+use super::statuses::{
+    ReplyContext, build_account_service, build_status_service, prefetch_create_delivery_targets,
+    resolve_reply_context, should_federate_create, should_federate_to_followers,
+    spawn_create_delivery,
+};
+use crate::{
+    AppState,
+    auth::CurrentUser,
+    data::{Account, EntityId, ScheduledStatus, Status, StatusSource},
+    error::AppError,
+    federation::retry_delay,
+    metrics::POSTS_TOTAL,
+    service::{AccountService, ContentFormatter, ContentType, StatusService},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct ScheduledStatusesParams {
@@ -18,6 +32,8 @@ pub struct ScheduledStatusesParams {
     since_id: Option<String>,
     /// Return results immediately newer than this ID
     min_id: Option<String>,
+    /// List the statuses that failed to publish instead of pending ones
+    failed: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,7 +52,10 @@ pub async fn get_scheduled_statuses(
 ) -> Result<Json<serde_json::Value>, AppError> {
     let limit = params.limit.unwrap_or(20).min(40);
 
-    let scheduled_statuses = state.db.get_all_scheduled_statuses(limit).await?;
+    let scheduled_statuses = state
+        .db
+        .get_all_scheduled_statuses(limit, params.failed.unwrap_or(false))
+        .await?;
 
     Ok(Json(serde_json::json!(scheduled_statuses)))
 }
@@ -114,6 +133,192 @@ pub async fn delete_scheduled_status(
     Ok(Json(serde_json::json!({})))
 }
 
+/// Publication attempts before a scheduled status is marked failed
+///
+/// With 30 second steps the final attempt happens about an hour after the first.
+pub const MAX_SCHEDULED_STATUS_ATTEMPTS: i64 = 8;
+
+/// Publish every scheduled status whose `scheduled_at` has passed
+///
+/// Each row is turned into a local status and removed in the same
+/// transaction, so overlapping passes or a restart mid-pass never post it
+/// twice. Rows that can no longer be published (reply target or media gone)
+/// are marked failed at once; other failures are retried with backoff until
+/// the attempt budget runs out. Failed rows stay listed for the owner.
+/// Returns the number of statuses published.
+pub async fn publish_due_scheduled_statuses(
+    state: &AppState,
+    limit: usize,
+) -> Result<usize, AppError> {
+    let status_service = build_status_service(state);
+    let due = status_service.get_due_scheduled_statuses(limit).await?;
+    if due.is_empty() {
+        return Ok(0);
+    }
+    let Some(account) = state.db.get_account().await? else {
+        return Ok(0);
+    };
+    let account_service = build_account_service(state);
+
+    let mut published = 0;
+    for scheduled in due {
+        match publish_scheduled_status(
+            state,
+            &status_service,
+            &account_service,
+            &account,
+            &scheduled,
+        )
+        .await
+        {
+            Ok(true) => published += 1,
+            Ok(false) => {
+                tracing::debug!(
+                    scheduled_status_id = %scheduled.id,
+                    "Scheduled status was already published or cancelled"
+                );
+            }
+            Err(AppError::Validation(reason)) => {
+                tracing::warn!(
+                    scheduled_status_id = %scheduled.id,
+                    %reason,
+                    "Scheduled status can no longer be published, marking it failed"
+                );
+                status_service
+                    .fail_scheduled_status(&scheduled.id, &reason)
+                    .await?;
+            }
+            Err(error) => {
+                let attempts_made = scheduled.attempts + 1;
+                match retry_delay(
+                    attempts_made,
+                    chrono::Duration::seconds(30),
+                    chrono::Duration::hours(1),
+                    MAX_SCHEDULED_STATUS_ATTEMPTS,
+                ) {
+                    Some(delay) => {
+                        tracing::error!(
+                            scheduled_status_id = %scheduled.id,
+                            attempts_made,
+                            %error,
+                            "Failed to publish scheduled status, retrying later"
+                        );
+                        status_service
+                            .retry_scheduled_status(
+                                &scheduled.id,
+                                chrono::Utc::now() + delay,
+                                &error.to_string(),
+                            )
+                            .await?;
+                    }
+                    None => {
+                        tracing::error!(
+                            scheduled_status_id = %scheduled.id,
+                            attempts_made,
+                            %error,
+                            "Failed to publish scheduled status, giving up"
+                        );
+                        status_service
+                            .fail_scheduled_status(&scheduled.id, &error.to_string())
+                            .await?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(published)
+}
+
+async fn publish_scheduled_status(
+    state: &AppState,
+    status_service: &StatusService,
+    account_service: &AccountService,
+    account: &Account,
+    scheduled: &ScheduledStatus,
+) -> Result<bool, AppError> {
+    let ReplyContext {
+        in_reply_to_uri,
+        target_account_address: reply_target_account_address,
+        persisted_reason,
+    } = resolve_reply_context(state, status_service, scheduled.in_reply_to_id.as_deref()).await?;
+
+    let content_type = ContentType::parse(&scheduled.content_type).unwrap_or_default();
+    let status_id = EntityId::new().0;
+    let mentions = resolve_status_mentions(state, &status_id, &scheduled.status_text).await;
+    let base_url = state.config.server.base_url();
+    let status = Status {
+        uri: format!(
+            "{}/users/{}/statuses/{}",
+            base_url, account.username, status_id
+        ),
+        id: status_id,
+        content: ContentFormatter::new(&base_url, &account.username)
+            .with_mentions(&mentions)
+            .render(&scheduled.status_text, content_type),
+        content_warning: scheduled.content_warning.clone(),
+        visibility: scheduled.visibility.clone(),
+        language: Some("en".to_string()),
+        account_address: String::new(),
+        is_local: true,
+        in_reply_to_uri,
+        boost_of_uri: None,
+        persisted_reason,
+        created_at: chrono::Utc::now(),
+        fetched_at: None,
+    };
+
+    let should_federate = should_federate_create(&status.visibility, &mentions);
+    let create_delivery_targets = if should_federate_to_followers(&status.visibility) {
+        prefetch_create_delivery_targets(account_service).await
+    } else {
+        Vec::new()
+    };
+
+    let media_ids = scheduled.media_id_list();
+    let poll = scheduled.poll();
+    let published = status_service
+        .publish_scheduled_status(
+            &scheduled.id,
+            &status,
+            &media_ids,
+            poll.as_ref().map(|(options, expires_in, multiple)| {
+                (options.as_slice(), *expires_in, *multiple)
+            }),
+        )
+        .await?;
+    if !published {
+        return Ok(false);
+    }
+    let source = StatusSource {
+        status_id: status.id.clone(),
+        text: scheduled.status_text.clone(),
+        content_type: content_type.as_str().to_string(),
+    };
+    status_service.save_mentions(&status.id, &mentions).await?;
+    status_service.save_source(&source).await?;
+
+    POSTS_TOTAL.inc();
+    tracing::info!(
+        scheduled_status_id = %scheduled.id,
+        status_id = %status.id,
+        "Published scheduled status"
+    );
+
+    if should_federate {
+        spawn_create_delivery(
+            state,
+            account,
+            &status,
+            mentions,
+            create_delivery_targets,
+            reply_target_account_address,
+        );
+    }
+
+    Ok(true)
+}
+
 // Helper function to create scheduled status response (for future use)
 #[allow(dead_code)]
 fn scheduled_status_to_response(
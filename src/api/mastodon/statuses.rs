@@ -11,18 +11,19 @@ use std::collections::{HashSet, VecDeque};
 
 use super::accounts::PaginationParams;
 use super::federation_delivery::{
-    build_delivery, local_actor_uri, resolve_remote_actor_and_inbox,
-    spawn_best_effort_batch_delivery, spawn_best_effort_delivery,
+    build_delivery, build_note_serializer, local_actor_uri, resolve_remote_actor_and_inbox,
+    resolve_status_mentions, spawn_best_effort_batch_delivery, spawn_best_effort_delivery,
 };
 use crate::AppState;
 use crate::auth::CurrentUser;
-use crate::data::PersistedReason;
+use crate::data::{PersistedReason, StatusMention, StatusSource};
 use crate::error::AppError;
+use crate::federation::{ActivityDelivery, DeliveryResult, StatusNote};
 use crate::metrics::{
     DB_QUERIES_TOTAL, DB_QUERY_DURATION_SECONDS, HTTP_REQUEST_DURATION_SECONDS,
     HTTP_REQUESTS_TOTAL, POSTS_TOTAL,
 };
-use crate::service::{AccountService, StatusService};
+use crate::service::{AccountService, ContentFormatter, ContentType, StatusService};
 
 const DEFAULT_VISIBILITY: &str = "public";
 const CREATE_STATUS_IDEMPOTENCY_ENDPOINT: &str = "/api/v1/statuses";
@@ -60,6 +61,8 @@ pub struct CreateStatusRequest {
     pub spoiler_text: Option<String>,
     pub visibility: Option<String>,
     pub language: Option<String>,
+    /// `text/plain` (default) or `text/markdown`
+    pub content_type: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -88,7 +91,7 @@ fn resolve_action_uri<'a>(
     Ok(None)
 }
 
-fn should_federate_to_followers(visibility: &str) -> bool {
+pub(super) fn should_federate_to_followers(visibility: &str) -> bool {
     visibility == "public" || visibility == "unlisted"
 }
 
@@ -114,6 +117,21 @@ fn normalize_visibility_input(raw_visibility: Option<String>) -> Result<String,
     }
 }
 
+fn normalize_content_type_input(
+    raw_content_type: Option<String>,
+) -> Result<Option<ContentType>, AppError> {
+    let Some(raw_content_type) = raw_content_type else {
+        return Ok(None);
+    };
+    ContentType::parse(raw_content_type.trim().to_ascii_lowercase().as_str())
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::Validation(
+                "content_type must be one of: text/plain, text/markdown".to_string(),
+            )
+        })
+}
+
 #[derive(Debug)]
 struct NormalizedCreatePoll {
     options: Vec<String>,
@@ -227,19 +245,338 @@ fn media_type_from_content_type(content_type: &str) -> &'static str {
     }
 }
 
-fn build_status_service(state: &AppState) -> StatusService {
+pub(super) fn build_status_service(state: &AppState) -> StatusService {
     StatusService::new(
         state.db.clone(),
         state.timeline_cache.clone(),
         state.storage.clone(),
         state.config.server.base_url().to_string(),
     )
+    .with_streaming(state.streaming.clone())
 }
 
-fn build_account_service(state: &AppState) -> AccountService {
+pub(super) fn build_account_service(state: &AppState) -> AccountService {
     AccountService::new(state.db.clone(), state.storage.clone())
 }
 
+/// Reply context resolved from a client-supplied `in_reply_to_id`
+pub(super) struct ReplyContext {
+    pub(super) in_reply_to_uri: Option<String>,
+    /// Remote author of the reply target, who also receives the Create
+    pub(super) target_account_address: Option<String>,
+    pub(super) persisted_reason: String,
+}
+
+/// Resolve a reply target by local ID, URI, or cached timeline URI.
+pub(super) async fn resolve_reply_context(
+    state: &AppState,
+    status_service: &StatusService,
+    in_reply_to_id: Option<&str>,
+) -> Result<ReplyContext, AppError> {
+    let mut context = ReplyContext {
+        in_reply_to_uri: None,
+        target_account_address: None,
+        persisted_reason: "own".to_string(),
+    };
+    let Some(in_reply_to_id) = in_reply_to_id else {
+        return Ok(context);
+    };
+
+    let reply_target = match status_service.find(in_reply_to_id).await? {
+        Some(reply_target) => Some(reply_target),
+        None => status_service.find_by_uri(in_reply_to_id).await?,
+    };
+    if let Some(reply_target) = reply_target {
+        context.in_reply_to_uri = Some(reply_target.uri.clone());
+        if reply_target.is_local {
+            context.persisted_reason = "reply_to_own".to_string();
+        } else if !reply_target.account_address.is_empty() {
+            context.target_account_address = Some(reply_target.account_address);
+        }
+    } else if let Some(cached_target) = state.timeline_cache.get_by_uri(in_reply_to_id).await {
+        context.in_reply_to_uri = Some(cached_target.uri.clone());
+        if !cached_target.account_address.is_empty() {
+            context.target_account_address = Some(cached_target.account_address.clone());
+        }
+    } else {
+        return Err(AppError::Validation(
+            "in_reply_to_id does not exist".to_string(),
+        ));
+    }
+
+    Ok(context)
+}
+
+/// Prefetch follower inboxes for a Create fan-out.
+pub(super) async fn prefetch_create_delivery_targets(
+    account_service: &AccountService,
+) -> Vec<String> {
+    match account_service.get_follower_inboxes().await {
+        Ok(follower_inboxes) => follower_inboxes,
+        Err(error) => {
+            tracing::warn!(
+                %error,
+                "Skipping follower fan-out prefetch for Create delivery"
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Whether a new local status has anyone to deliver its Create to.
+pub(super) fn should_federate_create(visibility: &str, mentions: &[StatusMention]) -> bool {
+    should_federate_to_followers(visibility) || !mentions.is_empty()
+}
+
+/// Activity wrapping a local status on delivery
+#[derive(Debug, Clone, Copy)]
+enum NoteActivity {
+    Create,
+    Update,
+}
+
+impl NoteActivity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Update => "Update",
+        }
+    }
+
+    fn action(self) -> &'static str {
+        match self {
+            Self::Create => "create_status",
+            Self::Update => "update_status",
+        }
+    }
+}
+
+/// Resolve the inboxes of the accounts mentioned by a local status.
+///
+/// Inboxes found when the mentions were parsed are reused; the rest are
+/// looked up concurrently. Accounts that cannot be resolved are logged and
+/// skipped.
+async fn resolve_mention_inboxes(
+    state: &AppState,
+    mentions: &[StatusMention],
+    activity_type: &str,
+) -> Vec<String> {
+    let resolved = futures::future::join_all(mentions.iter().map(|mention| async move {
+        match &mention.inbox_uri {
+            Some(inbox_uri) => Ok(inbox_uri.clone()),
+            None => resolve_remote_actor_and_inbox(state, &mention.account_address)
+                .await
+                .map(|(_, inbox_uri)| inbox_uri),
+        }
+    }))
+    .await;
+
+    let mut inboxes = Vec::with_capacity(mentions.len());
+    for (mention, result) in mentions.iter().zip(resolved) {
+        match result {
+            Ok(mention_inbox_uri) => inboxes.push(mention_inbox_uri),
+            Err(error) => {
+                tracing::warn!(
+                    mention_address = %mention.account_address,
+                    %error,
+                    "Failed to resolve mentioned account inbox for {} delivery",
+                    activity_type
+                );
+            }
+        }
+    }
+    inboxes
+}
+
+/// Deliver a Create for a newly published local status in the background.
+///
+/// The inboxes of mentioned accounts and of the remote author of the reply
+/// target (if any) are added to the prefetched follower inboxes. The status
+/// must already be persisted with its source, mentions, media and poll.
+pub(super) fn spawn_create_delivery(
+    state: &AppState,
+    account: &crate::data::Account,
+    status: &crate::data::Status,
+    mentions: Vec<StatusMention>,
+    delivery_targets: Vec<String>,
+    reply_target_account_address: Option<String>,
+) {
+    spawn_note_delivery(
+        NoteActivity::Create,
+        state,
+        account,
+        status,
+        mentions,
+        Vec::new(),
+        delivery_targets,
+        reply_target_account_address,
+    );
+}
+
+/// Deliver an Update for an edited local status in the background.
+///
+/// Reaches the same audience as the original Create, plus the accounts the
+/// edit stopped mentioning. The edit must already be persisted so the
+/// delivered Note carries its `updated` time.
+fn spawn_update_delivery(
+    state: &AppState,
+    account: &crate::data::Account,
+    status: &crate::data::Status,
+    mentions: Vec<StatusMention>,
+    former_mentions: Vec<StatusMention>,
+    delivery_targets: Vec<String>,
+    reply_target_account_address: Option<String>,
+) {
+    spawn_note_delivery(
+        NoteActivity::Update,
+        state,
+        account,
+        status,
+        mentions,
+        former_mentions,
+        delivery_targets,
+        reply_target_account_address,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_note_delivery(
+    activity: NoteActivity,
+    state: &AppState,
+    account: &crate::data::Account,
+    status: &crate::data::Status,
+    mentions: Vec<StatusMention>,
+    former_mentions: Vec<StatusMention>,
+    delivery_targets: Vec<String>,
+    reply_target_account_address: Option<String>,
+) {
+    let delivery = build_delivery(state, account);
+    let serializer = build_note_serializer(state, account);
+    let state_for_delivery = state.clone();
+    let status_for_delivery = status.clone();
+    spawn_best_effort_batch_delivery(activity.action(), async move {
+        let mut delivery_targets = delivery_targets;
+        delivery_targets.extend(
+            resolve_mention_inboxes(&state_for_delivery, &mentions, activity.as_str()).await,
+        );
+        delivery_targets.extend(
+            resolve_mention_inboxes(&state_for_delivery, &former_mentions, activity.as_str()).await,
+        );
+        let former_mentioned_actor_uris: Vec<String> = former_mentions
+            .into_iter()
+            .map(|mention| mention.actor_uri)
+            .collect();
+
+        if let Some(reply_target_account_address) = reply_target_account_address {
+            match resolve_remote_actor_and_inbox(&state_for_delivery, &reply_target_account_address)
+                .await
+            {
+                Ok((_, reply_target_inbox_uri)) => {
+                    delivery_targets.push(reply_target_inbox_uri);
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        reply_target_account_address = %reply_target_account_address,
+                        %error,
+                        "Failed to resolve reply target inbox for {} delivery",
+                        activity.as_str()
+                    );
+                }
+            }
+        }
+
+        if delivery_targets.is_empty() {
+            tracing::debug!(
+                "Skipping outbound {} delivery because no targets were found",
+                activity.as_str()
+            );
+            return Vec::new();
+        }
+
+        let note = match StatusNote::load(&state_for_delivery.db, status_for_delivery).await {
+            Ok(note) => note,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to load status for {} delivery", activity.as_str());
+                return Vec::new();
+            }
+        };
+
+        let note = serializer.note(&note);
+        match activity {
+            NoteActivity::Create => delivery.send_create(note, delivery_targets).await,
+            NoteActivity::Update => {
+                delivery
+                    .send_update(note, &former_mentioned_actor_uris, delivery_targets)
+                    .await
+            }
+        }
+    });
+}
+
+/// Announce a change to the featured (pinned) collection in the background.
+///
+/// Only statuses visible to followers are announced, and only to follower
+/// inboxes. `send` receives the delivery, the status URI and visibility and
+/// the target inboxes, and sends the Add or Remove.
+async fn spawn_featured_delivery<F, Fut>(
+    state: &AppState,
+    account: &crate::data::Account,
+    status: &crate::data::Status,
+    action: &'static str,
+    send: F,
+) where
+    F: FnOnce(ActivityDelivery, String, String, Vec<String>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Vec<DeliveryResult>> + Send + 'static,
+{
+    if !should_federate_to_followers(&status.visibility) {
+        return;
+    }
+
+    match build_account_service(state).get_follower_inboxes().await {
+        Ok(follower_inboxes) if !follower_inboxes.is_empty() => {
+            let delivery = build_delivery(state, account);
+            let status_uri = status.uri.clone();
+            let status_visibility = status.visibility.clone();
+            spawn_best_effort_batch_delivery(
+                action,
+                send(delivery, status_uri, status_visibility, follower_inboxes),
+            );
+        }
+        Ok(_) => {}
+        Err(error) => {
+            tracing::warn!(
+                action,
+                %error,
+                "Skipping outbound featured collection delivery because follower inbox lookup failed"
+            );
+        }
+    }
+}
+
+/// Fill in the reply, boost and favourite counts of status responses
+///
+/// Only statuses stored in the database have counts; cached remote statuses
+/// keep zeros.
+pub(super) async fn apply_status_counts(
+    state: &AppState,
+    responses: &mut [crate::api::StatusResponse],
+) -> Result<(), AppError> {
+    let uris: Vec<String> = responses
+        .iter()
+        .map(|response| response.uri.clone())
+        .collect();
+    let counts = state.db.get_status_counts(&uris).await?;
+    for response in responses {
+        if let Some(counts) = counts.get(&response.uri) {
+            response.replies_count = counts.replies_count as i32;
+            response.reblogs_count = counts.reblogs_count as i32;
+            response.favourites_count = counts.favourites_count as i32;
+        }
+    }
+
+    Ok(())
+}
+
 fn status_response_without_interaction_state(
     state: &AppState,
     account: &crate::data::Account,
@@ -296,6 +633,7 @@ struct StatusSourceResponse {
     id: String,
     text: String,
     spoiler_text: String,
+    content_type: String,
 }
 
 /// POST /api/v1/statuses
@@ -390,9 +728,11 @@ pub async fn create_status(
             spoiler_text,
             visibility,
             language,
+            content_type,
         } = req;
 
         let visibility = normalize_visibility_input(visibility)?;
+        let content_type = normalize_content_type_input(content_type)?.unwrap_or_default();
         let poll = normalize_poll_input(poll)?;
         let scheduled_at = normalize_scheduled_at(scheduled_at)?;
         let media_ids = media_ids.unwrap_or_default();
@@ -421,37 +761,11 @@ pub async fn create_status(
         }
 
         // Resolve reply target if provided.
-        let mut in_reply_to_uri = None;
-        let mut reply_target_account_address = None;
-        let mut persisted_reason = "own".to_string();
-        if let Some(in_reply_to_id) = in_reply_to_id.as_deref() {
-            if let Some(reply_target) = status_service.find(in_reply_to_id).await? {
-                in_reply_to_uri = Some(reply_target.uri.clone());
-                if reply_target.is_local {
-                    persisted_reason = "reply_to_own".to_string();
-                } else if !reply_target.account_address.is_empty() {
-                    reply_target_account_address = Some(reply_target.account_address);
-                }
-            } else if let Some(reply_target) = status_service.find_by_uri(in_reply_to_id).await? {
-                in_reply_to_uri = Some(reply_target.uri.clone());
-                if reply_target.is_local {
-                    persisted_reason = "reply_to_own".to_string();
-                } else if !reply_target.account_address.is_empty() {
-                    reply_target_account_address = Some(reply_target.account_address);
-                }
-            } else if let Some(cached_target) =
-                state.timeline_cache.get_by_uri(in_reply_to_id).await
-            {
-                in_reply_to_uri = Some(cached_target.uri.clone());
-                if !cached_target.account_address.is_empty() {
-                    reply_target_account_address = Some(cached_target.account_address.clone());
-                }
-            } else {
-                return Err(AppError::Validation(
-                    "in_reply_to_id does not exist".to_string(),
-                ));
-            }
-        }
+        let ReplyContext {
+            in_reply_to_uri,
+            target_account_address: reply_target_account_address,
+            persisted_reason,
+        } = resolve_reply_context(&state, &status_service, in_reply_to_id.as_deref()).await?;
 
         if let Some(scheduled_at) = scheduled_at {
             let media_ids_json = if media_ids.is_empty() {
@@ -483,6 +797,7 @@ pub async fn create_status(
                     poll_options_json.as_deref(),
                     poll.as_ref().map(|poll| poll.expires_in),
                     poll.as_ref().is_some_and(|poll| poll.multiple),
+                    content_type.as_str(),
                 )
                 .await?;
             return status_service
@@ -492,17 +807,19 @@ pub async fn create_status(
         }
 
         let status_id = EntityId::new().0;
+        let base_url = state.config.server.base_url();
         let uri = format!(
             "{}/users/{}/statuses/{}",
-            state.config.server.base_url(),
-            account.username,
-            status_id
+            base_url, account.username, status_id
         );
+        let mentions = resolve_status_mentions(&state, &status_id, &content).await;
 
         let status = Status {
             id: status_id.clone(),
             uri: uri.clone(),
-            content: format!("<p>{}</p>", html_escape::encode_text(&content)),
+            content: ContentFormatter::new(&base_url, &account.username)
+                .with_mentions(&mentions)
+                .render(&content, content_type),
             content_warning: spoiler_text.clone(),
             visibility: visibility.clone(),
             language: language.or(Some("en".to_string())),
@@ -515,18 +832,9 @@ pub async fn create_status(
             fetched_at: None,
         };
 
-        let should_federate_create = should_federate_to_followers(&status.visibility);
-        let create_delivery_targets = if should_federate_create {
-            match account_service.get_follower_inboxes().await {
-                Ok(follower_inboxes) => follower_inboxes,
-                Err(error) => {
-                    tracing::warn!(
-                        %error,
-                        "Skipping follower fan-out prefetch for Create delivery"
-                    );
-                    Vec::new()
-                }
-            }
+        let should_federate = should_federate_create(&status.visibility, &mentions);
+        let create_delivery_targets = if should_federate_to_followers(&status.visibility) {
+            prefetch_create_delivery_targets(&account_service).await
         } else {
             Vec::new()
         };
@@ -543,6 +851,13 @@ pub async fn create_status(
                     .map(|poll| (poll.options.as_slice(), poll.expires_in, poll.multiple)),
             )
             .await?;
+        let source = StatusSource {
+            status_id: status.id.clone(),
+            text: content,
+            content_type: content_type.as_str().to_string(),
+        };
+        status_service.save_mentions(&status.id, &mentions).await?;
+        status_service.save_source(&source).await?;
         DB_QUERIES_TOTAL
             .with_label_values(&["INSERT", "statuses"])
             .inc();
@@ -551,51 +866,19 @@ pub async fn create_status(
         // Update posts total metric
         POSTS_TOTAL.inc();
 
-        if should_federate_create {
-            let delivery = build_delivery(&state, &account);
-            let state_for_delivery = state.clone();
-            let status_for_delivery = status.clone();
-            let reply_target_account_address_for_delivery = reply_target_account_address;
-            spawn_best_effort_batch_delivery("create_status", async move {
-                let mut delivery_targets = create_delivery_targets;
-
-                if let Some(reply_target_account_address) =
-                    reply_target_account_address_for_delivery
-                {
-                    match resolve_remote_actor_and_inbox(
-                        &state_for_delivery,
-                        &reply_target_account_address,
-                    )
-                    .await
-                    {
-                        Ok((_, reply_target_inbox_uri)) => {
-                            delivery_targets.push(reply_target_inbox_uri);
-                        }
-                        Err(error) => {
-                            tracing::warn!(
-                                reply_target_account_address = %reply_target_account_address,
-                                %error,
-                                "Failed to resolve reply target inbox for Create delivery"
-                            );
-                        }
-                    }
-                }
-
-                if delivery_targets.is_empty() {
-                    tracing::debug!(
-                        "Skipping outbound Create delivery because no targets were found"
-                    );
-                    return Vec::new();
-                }
-
-                delivery
-                    .send_create(&status_for_delivery, delivery_targets)
-                    .await
-            });
-        } else if !should_federate_create {
+        if should_federate {
+            spawn_create_delivery(
+                &state,
+                &account,
+                &status,
+                mentions,
+                create_delivery_targets,
+                reply_target_account_address,
+            );
+        } else {
             tracing::debug!(
                 visibility = %status.visibility,
-                "Skipping outbound Create delivery for non-public visibility"
+                "Skipping outbound Create delivery for non-public status without remote mentions"
             );
         }
 
@@ -751,7 +1034,8 @@ pub async fn get_status(
     db_timer.observe_duration();
 
     // Convert to API response
-    let response = status_response_without_interaction_state(&state, &account, &status);
+    let mut response = status_response_without_interaction_state(&state, &account, &status);
+    apply_status_counts(&state, std::slice::from_mut(&mut response)).await?;
 
     // Record successful request
     HTTP_REQUESTS_TOTAL
@@ -794,6 +1078,9 @@ pub async fn delete_status(
         .inc();
     db_timer.observe_duration();
 
+    // Mentions cascade away with the status, so load them first
+    let mentions = status_service.get_mentions(&status.id).await?;
+
     // Delete the status
     let db_timer = DB_QUERY_DURATION_SECONDS
         .with_label_values(&["DELETE", "statuses"])
@@ -804,31 +1091,42 @@ pub async fn delete_status(
         .inc();
     db_timer.observe_duration();
 
-    let should_federate_delete = should_federate_to_followers(&status.visibility);
-    if should_federate_delete {
-        match build_account_service(&state).get_follower_inboxes().await {
-            Ok(follower_inboxes) if !follower_inboxes.is_empty() => {
-                let delivery = build_delivery(&state, &account);
-                let status_uri = status.uri.clone();
-                let status_visibility = status.visibility.clone();
-                spawn_best_effort_batch_delivery("delete_status", async move {
-                    delivery
-                        .send_delete(&status_uri, &status_visibility, follower_inboxes)
-                        .await
-                });
-            }
-            Ok(_) => {}
-            Err(error) => {
-                tracing::warn!(
-                    %error,
-                    "Skipping outbound Delete delivery because follower inbox lookup failed"
-                );
+    // The Delete reaches the same audience as the Create did.
+    if should_federate_create(&status.visibility, &mentions) {
+        let follower_inboxes = if should_federate_to_followers(&status.visibility) {
+            prefetch_create_delivery_targets(&build_account_service(&state)).await
+        } else {
+            Vec::new()
+        };
+        let delivery = build_delivery(&state, &account);
+        let state_for_delivery = state.clone();
+        let status_uri = status.uri.clone();
+        let status_visibility = status.visibility.clone();
+        spawn_best_effort_batch_delivery("delete_status", async move {
+            let mut delivery_targets = follower_inboxes;
+            delivery_targets
+                .extend(resolve_mention_inboxes(&state_for_delivery, &mentions, "Delete").await);
+            if delivery_targets.is_empty() {
+                return Vec::new();
             }
-        }
-    } else if !should_federate_delete {
+
+            let mentioned_actor_uris: Vec<String> = mentions
+                .iter()
+                .map(|mention| mention.actor_uri.clone())
+                .collect();
+            delivery
+                .send_delete(
+                    &status_uri,
+                    &status_visibility,
+                    &mentioned_actor_uris,
+                    delivery_targets,
+                )
+                .await
+        });
+    } else {
         tracing::debug!(
             visibility = %status.visibility,
-            "Skipping outbound Delete delivery for non-public visibility"
+            "Skipping outbound Delete delivery for non-public status without remote mentions"
         );
     }
 
@@ -936,6 +1234,9 @@ pub async fn get_status_context(
         ));
     }
 
+    apply_status_counts(&state, &mut ancestor_responses).await?;
+    apply_status_counts(&state, &mut descendant_responses).await?;
+
     let context = ContextResponse {
         ancestors: ancestor_responses,
         descendants: descendant_responses,
@@ -948,7 +1249,7 @@ pub async fn get_status_context(
 pub async fn get_reblogged_by(
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Query(_params): Query<PaginationParams>,
+    Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<serde_json::Value>>, AppError> {
     let status_service = build_status_service(&state);
 
@@ -956,19 +1257,15 @@ pub async fn get_reblogged_by(
     let status = status_service.get(&id).await?;
     ensure_public_visibility_for_public_endpoint(&status.visibility)?;
 
-    // For single-user instance, only the owner can reblog
-    // In a full implementation, we would query the reposts table
-    // and fetch account information for each user who reblogged
-
-    // For now, return empty array as we don't track individual rebloggers
-    Ok(Json(vec![]))
+    let reblogged = state.db.is_reposted(&status.id).await?;
+    interaction_accounts(&state, &status, "reblog", reblogged, params.limit).await
 }
 
 /// GET /api/v1/statuses/:id/favourited_by
 pub async fn get_favourited_by(
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Query(_params): Query<PaginationParams>,
+    Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<serde_json::Value>>, AppError> {
     let status_service = build_status_service(&state);
 
@@ -976,20 +1273,44 @@ pub async fn get_favourited_by(
     let status = status_service.get(&id).await?;
     ensure_public_visibility_for_public_endpoint(&status.visibility)?;
 
-    // For single-user instance, only the owner can favourite
-    // Check if the status is favourited by the owner
-    let is_favourited = status_service.is_favourited(&id).await?;
-
-    if is_favourited {
-        // Return the owner's account
-        let account = build_account_service(&state).get_account().await?;
+    let favourited = status_service.is_favourited(&id).await?;
+    interaction_accounts(&state, &status, "favourite", favourited, params.limit).await
+}
 
+/// Accounts that boosted or favourited a status
+///
+/// The local account comes first when it interacted itself, followed by
+/// remote accounts, newest first.
+async fn interaction_accounts(
+    state: &AppState,
+    status: &crate::data::Status,
+    interaction_type: &str,
+    by_local_account: bool,
+    limit: Option<usize>,
+) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+    let limit = limit.unwrap_or(40).clamp(1, 80);
+    let mut accounts = Vec::new();
+    if by_local_account {
+        let account = build_account_service(state).get_account().await?;
         let account_response = crate::api::account_to_response(&account, &state.config);
-        Ok(Json(vec![serde_json::to_value(account_response).unwrap()]))
-    } else {
-        // Not favourited, return empty array
-        Ok(Json(vec![]))
+        accounts.push(serde_json::to_value(account_response).unwrap());
+    }
+
+    let addresses = state
+        .db
+        .get_status_interaction_addresses(
+            &status.uri,
+            interaction_type,
+            limit.saturating_sub(accounts.len()),
+        )
+        .await?;
+    for address in addresses {
+        let account_response =
+            crate::api::remote_address_to_account_response(&address, &state.config);
+        accounts.push(serde_json::to_value(account_response).unwrap());
     }
+
+    Ok(Json(accounts))
 }
 
 /// GET /api/v1/statuses/:id/source
@@ -1009,10 +1330,17 @@ pub async fn get_status_source(
     }
 
     // Return the source
+    let stored_source = status_service.get_source(&status.id).await?;
     let source = StatusSourceResponse {
         id: status.id.clone(),
-        text: status_content_to_source_text(&status.content),
+        text: match &stored_source {
+            Some(source) => source.text.clone(),
+            None => status_content_to_source_text(&status.content),
+        },
         spoiler_text: status.content_warning.unwrap_or_default(),
+        content_type: stored_source
+            .map(|source| source.content_type)
+            .unwrap_or_else(|| ContentType::PlainText.as_str().to_string()),
     };
 
     Ok(Json(serde_json::to_value(source).unwrap()))
@@ -1386,6 +1714,8 @@ pub struct UpdateStatusRequest {
     pub spoiler_text: Option<String>,
     pub sensitive: Option<bool>,
     pub media_ids: Option<Vec<String>>,
+    /// Format of `status`; defaults to the format the status was written in
+    pub content_type: Option<String>,
 }
 
 /// PUT /api/v1/statuses/:id
@@ -1415,13 +1745,33 @@ pub async fn update_status(
     let mut changed = false;
     let mut media_ids_to_replace: Option<Vec<String>> = None;
 
+    let requested_content_type = normalize_content_type_input(req.content_type)?;
+    let mut next_source: Option<(StatusSource, Vec<StatusMention>)> = None;
     if let Some(content) = req.status {
         if !content.is_empty() {
-            let next_content = format!("<p>{}</p>", html_escape::encode_text(&content));
+            let content_type = match requested_content_type {
+                Some(content_type) => content_type,
+                None => status_service
+                    .get_source(&status.id)
+                    .await?
+                    .and_then(|source| ContentType::parse(&source.content_type))
+                    .unwrap_or_default(),
+            };
+            let mentions = resolve_status_mentions(&state, &status.id, &content).await;
+            let next_content =
+                ContentFormatter::new(&state.config.server.base_url(), &account.username)
+                    .with_mentions(&mentions)
+                    .render(&content, content_type);
             if status.content != next_content {
                 status.content = next_content;
                 changed = true;
             }
+            let source = StatusSource {
+                status_id: status.id.clone(),
+                text: content,
+                content_type: content_type.as_str().to_string(),
+            };
+            next_source = Some((source, mentions));
         }
     }
 
@@ -1469,6 +1819,53 @@ pub async fn update_status(
             )
             .await?;
     }
+    let previous_mentions = status_service.get_mentions(&status.id).await?;
+    let mentions = match next_source {
+        Some((source, mentions)) => {
+            status_service.save_mentions(&status.id, &mentions).await?;
+            status_service.save_source(&source).await?;
+            mentions
+        }
+        None => previous_mentions.clone(),
+    };
+    let former_mentions: Vec<StatusMention> = previous_mentions
+        .into_iter()
+        .filter(|previous| {
+            !mentions
+                .iter()
+                .any(|mention| mention.actor_uri == previous.actor_uri)
+        })
+        .collect();
+
+    // Send the edited Note to everyone who received the original Create.
+    if changed
+        && (should_federate_create(&status.visibility, &mentions) || !former_mentions.is_empty())
+    {
+        let delivery_targets = if should_federate_to_followers(&status.visibility) {
+            prefetch_create_delivery_targets(&build_account_service(&state)).await
+        } else {
+            Vec::new()
+        };
+        let reply_target_account_address =
+            match resolve_reply_context(&state, &status_service, status.in_reply_to_uri.as_deref())
+                .await
+            {
+                Ok(context) => context.target_account_address,
+                Err(error) => {
+                    tracing::debug!(%error, "Reply target of edited status is no longer known");
+                    None
+                }
+            };
+        spawn_update_delivery(
+            &state,
+            &account,
+            &status,
+            mentions,
+            former_mentions,
+            delivery_targets,
+            reply_target_account_address,
+        );
+    }
 
     // Return updated status
     let response = crate::api::status_to_response(
@@ -1494,14 +1891,12 @@ pub async fn get_status_history(
 ) -> Result<Json<Vec<serde_json::Value>>, AppError> {
     let status_service = build_status_service(&state);
 
-    // Get the status
+    // Get the status; remote statuses keep the revisions federated to us
     let status = status_service.get(&id).await?;
-    if !status.is_local {
-        return Err(AppError::Forbidden);
-    }
 
     // Get account
     let account = build_account_service(&state).get_account().await?;
+    let author = crate::api::status_author_to_response(&status, &account, &state.config);
 
     let edits = status_service.get_edit_history(&id, 40).await?;
     let current_revision_created_at = edits
@@ -1513,7 +1908,7 @@ pub async fn get_status_history(
         "spoiler_text": status.content_warning.clone().unwrap_or_default(),
         "sensitive": status.content_warning.is_some(),
         "created_at": current_revision_created_at.to_rfc3339(),
-        "account": crate::api::account_to_response(&account, &state.config),
+        "account": author,
     });
 
     let mut history = vec![current_version];
@@ -1523,7 +1918,7 @@ pub async fn get_status_history(
             "spoiler_text": content_warning.clone().unwrap_or_default(),
             "sensitive": content_warning.is_some(),
             "created_at": created_at.to_rfc3339(),
-            "account": crate::api::account_to_response(&account, &state.config),
+            "account": author,
         }));
     }
 
@@ -1553,6 +1948,19 @@ pub async fn pin_status(
     // Get account
     let account = build_account_service(&state).get_account().await?;
 
+    spawn_featured_delivery(
+        &state,
+        &account,
+        &status,
+        "pin_status",
+        |delivery, status_uri, status_visibility, inboxes| async move {
+            delivery
+                .send_add(&status_uri, &status_visibility, inboxes)
+                .await
+        },
+    )
+    .await;
+
     let response = crate::api::status_to_response(
         &status,
         &account,
@@ -1583,6 +1991,19 @@ pub async fn unpin_status(
     // Get account
     let account = build_account_service(&state).get_account().await?;
 
+    spawn_featured_delivery(
+        &state,
+        &account,
+        &status,
+        "unpin_status",
+        |delivery, status_uri, status_visibility, inboxes| async move {
+            delivery
+                .send_remove(&status_uri, &status_visibility, inboxes)
+                .await
+        },
+    )
+    .await;
+
     let response = crate::api::status_to_response(
         &status,
         &account,
@@ -1,21 +1,28 @@
 //! Streaming API endpoints
 //!
-//! Provides real-time updates via Server-Sent Events (SSE)
+//! Provides real-time updates via Server-Sent Events (SSE) and the
+//! multiplexed WebSocket protocol.
 
 use axum::{
-    extract::{Query, State},
-    response::IntoResponse,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, Method, header},
     response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
 };
 use futures::stream::{self, Stream};
 use serde::Deserialize;
 use std::convert::Infallible;
 use std::time::Duration;
-use tokio_stream::StreamExt as _;
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::AppState;
-use crate::auth::CurrentUser;
+use crate::auth::{BearerGrant, CurrentUser, resolve_bearer_token};
+use crate::data::{Account, Status};
 use crate::error::AppError;
+use crate::service::{FilterContext, FilterService, StreamEvent};
 
 #[derive(Debug, Deserialize)]
 pub struct StreamParams {
@@ -25,6 +32,201 @@ pub struct StreamParams {
     list: Option<String>,
 }
 
+/// Timeline a streaming client is subscribed to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StreamTimeline {
+    /// Home timeline and notifications
+    User,
+    /// Notifications only
+    Notifications,
+    /// Public statuses, optionally restricted to local ones
+    Public { local_only: bool },
+    /// Public statuses carrying a hashtag (lowercase, without `#`)
+    Hashtag { tag: String, local_only: bool },
+    /// Statuses from accounts in a list
+    List { list_id: String },
+    /// Direct messages
+    Direct,
+}
+
+impl StreamTimeline {
+    /// Filter context applied to statuses on this timeline
+    fn filter_context(&self) -> Option<FilterContext> {
+        match self {
+            Self::User | Self::List { .. } => Some(FilterContext::Home),
+            Self::Notifications => Some(FilterContext::Notifications),
+            Self::Public { .. } | Self::Hashtag { .. } => Some(FilterContext::Public),
+            Self::Direct => None,
+        }
+    }
+
+    /// Whether a status belongs on this timeline
+    async fn includes(&self, state: &AppState, account: &Account, status: &Status) -> bool {
+        match self {
+            Self::User => true,
+            Self::Notifications => false,
+            Self::Public { local_only } => {
+                status.visibility == "public" && (!local_only || status.is_local)
+            }
+            Self::Hashtag { tag, local_only } => {
+                status.visibility == "public"
+                    && (!local_only || status.is_local)
+                    && crate::data::extract_hashtags_from_content(&status.content)
+                        .iter()
+                        .any(|status_tag| status_tag == tag)
+            }
+            Self::List { list_id } => {
+                let Ok(list_accounts) = state.db.get_list_accounts(list_id).await else {
+                    return false;
+                };
+                if status.is_local || status.account_address.is_empty() {
+                    let local_address =
+                        format!("{}@{}", account.username, state.config.server.domain);
+                    list_accounts.iter().any(|address| {
+                        address.eq_ignore_ascii_case(&local_address) || *address == account.id
+                    })
+                } else {
+                    list_accounts
+                        .iter()
+                        .any(|address| address.eq_ignore_ascii_case(&status.account_address))
+                }
+            }
+            Self::Direct => status.visibility == "direct",
+        }
+    }
+}
+
+/// Render a bus event for a timeline as an (event name, payload) pair.
+///
+/// Returns `None` when the event does not belong on the timeline.
+pub(crate) async fn render_stream_event(
+    state: &AppState,
+    timeline: &StreamTimeline,
+    event: &StreamEvent,
+) -> Option<(&'static str, String)> {
+    match event {
+        StreamEvent::Update(status) | StreamEvent::StatusUpdate(status) => {
+            let account = state.db.get_account().await.ok()??;
+            if !timeline.includes(state, &account, status).await {
+                return None;
+            }
+            let filter_matches = match timeline.filter_context() {
+                Some(context) => FilterService::new(state.db.clone())
+                    .status_filter(context)
+                    .await
+                    .ok()?
+                    .matches(status),
+                None => Vec::new(),
+            };
+            if filter_matches
+                .iter()
+                .any(|filter_match| filter_match.hides())
+            {
+                return None;
+            }
+            let event_name = if matches!(event, StreamEvent::Update(_)) {
+                "update"
+            } else {
+                "status.update"
+            };
+            let mut response = crate::api::status_to_response(
+                status,
+                &account,
+                &state.config,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            response.filtered = crate::api::filter_matches_to_response(&filter_matches);
+            Some((event_name, serde_json::to_string(&response).ok()?))
+        }
+        StreamEvent::Delete(status_id) => {
+            if *timeline == StreamTimeline::Notifications {
+                return None;
+            }
+            Some(("delete", status_id.clone()))
+        }
+        StreamEvent::Notification(notification) => {
+            if !matches!(
+                timeline,
+                StreamTimeline::User | StreamTimeline::Notifications
+            ) {
+                return None;
+            }
+            let account = state.db.get_account().await.ok()??;
+            let status_filter = super::notifications::notification_filter(state)
+                .await
+                .ok()?;
+            let response = super::notifications::notification_to_response(
+                state,
+                &account,
+                notification,
+                &status_filter,
+            )
+            .await;
+            if super::notifications::is_hidden_by_filter(&response) {
+                return None;
+            }
+            Some(("notification", serde_json::to_string(&response).ok()?))
+        }
+    }
+}
+
+/// Build an SSE stream of bus events filtered for a timeline
+///
+/// Subscribes immediately so events published after the request is
+/// accepted are not missed.
+fn event_stream(
+    state: AppState,
+    timeline: StreamTimeline,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let receiver = state.streaming.subscribe();
+    stream::unfold(
+        (state, timeline, receiver),
+        |(state, timeline, mut receiver)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if let Some((event_name, payload)) =
+                            render_stream_event(&state, &timeline, &event).await
+                        {
+                            let event = Event::default().event(event_name).data(payload);
+                            return Some((Ok(event), (state, timeline, receiver)));
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Streaming client lagged behind; events dropped");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    )
+}
+
+/// Normalize a hashtag stream parameter to the stored tag form
+pub(crate) fn normalize_stream_tag(tag: Option<String>) -> Result<String, AppError> {
+    tag.map(|tag| tag.trim().trim_start_matches('#').to_ascii_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .ok_or(AppError::Validation("tag parameter required".to_string()))
+}
+
+/// Resolve a list stream parameter, verifying the list exists
+pub(crate) async fn resolve_stream_list(
+    state: &AppState,
+    list: Option<String>,
+) -> Result<String, AppError> {
+    let list_id = list.ok_or(AppError::Validation("list parameter required".to_string()))?;
+    state
+        .db
+        .get_list(&list_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(list_id)
+}
+
 /// GET /api/v1/streaming/health
 /// Health check for streaming endpoint
 pub async fn streaming_health() -> impl IntoResponse {
@@ -34,100 +236,344 @@ pub async fn streaming_health() -> impl IntoResponse {
 /// GET /api/v1/streaming/user
 /// Stream events for the authenticated user
 pub async fn stream_user(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    // Create a stream that sends periodic updates
-    let stream = stream::repeat_with(|| {
-        // In a full implementation, this would:
-        // 1. Listen to a message queue/channel for new events
-        // 2. Send events when statuses, notifications, etc. are created
-        // For now, send periodic heartbeats
-        Event::default().event("update").data("{}")
-    })
-    .map(Ok)
-    .throttle(Duration::from_secs(30));
-
+    let stream = event_stream(state, StreamTimeline::User);
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/streaming/public
 /// Stream public statuses
 pub async fn stream_public(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(_params): Query<StreamParams>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    // Create a stream for public timeline
-    let stream = stream::repeat_with(|| Event::default().event("update").data("{}"))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
-
+    let stream = event_stream(state, StreamTimeline::Public { local_only: false });
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/streaming/public/local
 /// Stream local public statuses
 pub async fn stream_public_local(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    // Create a stream for local public timeline
-    let stream = stream::repeat_with(|| Event::default().event("update").data("{}"))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
-
+    let stream = event_stream(state, StreamTimeline::Public { local_only: true });
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/streaming/hashtag
 /// Stream statuses with a specific hashtag
 pub async fn stream_hashtag(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<StreamParams>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    let _tag = params
-        .tag
-        .ok_or(AppError::Validation("tag parameter required".to_string()))?;
-
-    // Create a stream for hashtag timeline
-    let stream = stream::repeat_with(|| Event::default().event("update").data("{}"))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
-
+    let tag = normalize_stream_tag(params.tag)?;
+    let stream = event_stream(
+        state,
+        StreamTimeline::Hashtag {
+            tag,
+            local_only: false,
+        },
+    );
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/streaming/list
 /// Stream statuses from a specific list
 pub async fn stream_list(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
     Query(params): Query<StreamParams>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    let _list_id = params
-        .list
-        .ok_or(AppError::Validation("list parameter required".to_string()))?;
-
-    // Verify list exists
-    // state.db.get_list(&list_id).await?.ok_or(AppError::NotFound)?;
-
-    // Create a stream for list timeline
-    let stream = stream::repeat_with(|| Event::default().event("update").data("{}"))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
-
+    let list_id = resolve_stream_list(&state, params.list).await?;
+    let stream = event_stream(state, StreamTimeline::List { list_id });
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/streaming/direct
 /// Stream direct messages
 pub async fn stream_direct(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
-    // Create a stream for direct messages
-    let stream = stream::repeat_with(|| Event::default().event("update").data("{}"))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
-
+    let stream = event_stream(state, StreamTimeline::Direct);
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
+
+// =========================================================================
+// WebSocket streaming
+// =========================================================================
+
+/// Interval between WebSocket pings that keep idle connections open
+const WEBSOCKET_PING_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct WebSocketParams {
+    /// Access token for clients that cannot set headers
+    access_token: Option<String>,
+    /// Stream to subscribe to when the connection opens
+    stream: Option<String>,
+    /// Only for hashtag stream
+    tag: Option<String>,
+    /// Only for list stream
+    list: Option<String>,
+}
+
+/// Message sent by the client over the WebSocket connection
+#[derive(Debug, Deserialize)]
+struct ClientMessage {
+    #[serde(rename = "type")]
+    message_type: String,
+    stream: String,
+    tag: Option<String>,
+    list: Option<String>,
+}
+
+/// Active subscription on a WebSocket connection
+struct Subscription {
+    /// Stream identifier echoed back with each event, e.g. `["hashtag", "rust"]`
+    stream: Vec<String>,
+    timeline: StreamTimeline,
+}
+
+/// Resolve a Mastodon stream name into a subscription.
+///
+/// Each stream is authorized against the matching SSE route, so OAuth
+/// tokens need the same scopes regardless of transport.
+async fn resolve_subscription(
+    state: &AppState,
+    grant: &BearerGrant,
+    stream_name: &str,
+    tag: Option<String>,
+    list: Option<String>,
+) -> Result<Subscription, AppError> {
+    let scope_path = match stream_name {
+        "user" => "/api/v1/streaming/user",
+        "user:notification" => "/api/v1/notifications",
+        "public" => "/api/v1/streaming/public",
+        "public:local" => "/api/v1/streaming/public/local",
+        "hashtag" | "hashtag:local" => "/api/v1/streaming/hashtag",
+        "list" => "/api/v1/streaming/list",
+        "direct" => "/api/v1/streaming/direct",
+        _ => {
+            return Err(AppError::Validation(format!(
+                "Unknown stream type: {stream_name}"
+            )));
+        }
+    };
+    grant.authorize(&Method::GET, scope_path)?;
+
+    let stream_name = stream_name.to_string();
+    let subscription = match stream_name.as_str() {
+        "user" => Subscription {
+            stream: vec![stream_name],
+            timeline: StreamTimeline::User,
+        },
+        "user:notification" => Subscription {
+            stream: vec![stream_name],
+            timeline: StreamTimeline::Notifications,
+        },
+        "public" | "public:local" => Subscription {
+            timeline: StreamTimeline::Public {
+                local_only: stream_name == "public:local",
+            },
+            stream: vec![stream_name],
+        },
+        "hashtag" | "hashtag:local" => {
+            let tag = normalize_stream_tag(tag)?;
+            Subscription {
+                timeline: StreamTimeline::Hashtag {
+                    tag: tag.clone(),
+                    local_only: stream_name == "hashtag:local",
+                },
+                stream: vec![stream_name, tag],
+            }
+        }
+        "list" => {
+            let list_id = resolve_stream_list(state, list).await?;
+            Subscription {
+                stream: vec![stream_name, list_id.clone()],
+                timeline: StreamTimeline::List { list_id },
+            }
+        }
+        _ => Subscription {
+            stream: vec![stream_name],
+            timeline: StreamTimeline::Direct,
+        },
+    };
+
+    Ok(subscription)
+}
+
+/// Extract the access token from the query, Authorization header or
+/// `Sec-WebSocket-Protocol` header, in that order.
+fn websocket_access_token(params: &WebSocketParams, headers: &HeaderMap) -> Option<String> {
+    let non_empty = |value: &str| {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    };
+
+    params
+        .access_token
+        .as_deref()
+        .and_then(non_empty)
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .and_then(non_empty)
+        })
+        .or_else(|| {
+            headers
+                .get(header::SEC_WEBSOCKET_PROTOCOL)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .and_then(non_empty)
+        })
+}
+
+/// Render an error as a Mastodon streaming error message
+fn websocket_error_message(error: AppError) -> String {
+    let message = match &error {
+        AppError::Validation(message) | AppError::Unprocessable(message) => message.clone(),
+        _ => error.to_string(),
+    };
+    let status = error.into_response().status();
+    let message = if status.is_server_error() {
+        "Internal server error".to_string()
+    } else {
+        message
+    };
+
+    serde_json::json!({
+        "error": message,
+        "status": status.as_u16(),
+    })
+    .to_string()
+}
+
+/// Apply a subscribe/unsubscribe message from the client
+async fn handle_client_message(
+    state: &AppState,
+    grant: &BearerGrant,
+    subscriptions: &mut Vec<Subscription>,
+    text: &str,
+) -> Result<(), AppError> {
+    let message: ClientMessage = serde_json::from_str(text)
+        .map_err(|_| AppError::Validation("Invalid streaming message".to_string()))?;
+    let subscription =
+        resolve_subscription(state, grant, &message.stream, message.tag, message.list).await?;
+
+    match message.message_type.as_str() {
+        "subscribe" => {
+            if !subscriptions
+                .iter()
+                .any(|existing| existing.stream == subscription.stream)
+            {
+                subscriptions.push(subscription);
+            }
+        }
+        "unsubscribe" => {
+            subscriptions.retain(|existing| existing.stream != subscription.stream);
+        }
+        other => {
+            return Err(AppError::Validation(format!(
+                "Unknown message type: {other}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Drive a multiplexed WebSocket connection until either side closes it
+async fn run_websocket(
+    mut socket: WebSocket,
+    state: AppState,
+    grant: BearerGrant,
+    mut subscriptions: Vec<Subscription>,
+) {
+    let mut receiver = state.streaming.subscribe();
+    let ping_period = Duration::from_secs(WEBSOCKET_PING_INTERVAL_SECS);
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + ping_period, ping_period);
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                if let Err(error) =
+                    handle_client_message(&state, &grant, &mut subscriptions, &text).await
+                {
+                    let reply = websocket_error_message(error);
+                    if socket.send(Message::Text(reply)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            event = receiver.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Streaming client lagged behind; events dropped");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                for subscription in &subscriptions {
+                    let Some((event_name, payload)) =
+                        render_stream_event(&state, &subscription.timeline, &event).await
+                    else {
+                        continue;
+                    };
+                    let message = serde_json::json!({
+                        "stream": subscription.stream,
+                        "event": event_name,
+                        "payload": payload,
+                    });
+                    if socket.send(Message::Text(message.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            _ = ping.tick() => {
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// GET /api/v1/streaming
+/// Multiplexed WebSocket streaming
+///
+/// Authenticates via `access_token` query, Authorization header or
+/// `Sec-WebSocket-Protocol`, then accepts `subscribe`/`unsubscribe`
+/// messages for any number of streams on the same connection.
+pub async fn stream_websocket(
+    State(state): State<AppState>,
+    Query(params): Query<WebSocketParams>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let token = websocket_access_token(&params, &headers).ok_or(AppError::Unauthorized)?;
+    let grant = resolve_bearer_token(&state, &token).await?;
+
+    let mut subscriptions = Vec::new();
+    if let Some(stream_name) = params.stream.as_deref() {
+        subscriptions.push(
+            resolve_subscription(&state, &grant, stream_name, params.tag, params.list).await?,
+        );
+    }
+
+    // Clients that pass the token as a subprotocol expect it echoed back.
+    Ok(ws
+        .protocols([token])
+        .on_upgrade(move |socket| run_websocket(socket, state, grant, subscriptions)))
+}
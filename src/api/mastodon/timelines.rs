@@ -2,13 +2,15 @@
 
 use axum::{
     extract::{Query, State},
+    http::{HeaderMap, header},
     response::Json,
 };
 use serde::Deserialize;
 
 use super::accounts::PaginationParams;
+use super::statuses::apply_status_counts;
 use crate::AppState;
-use crate::auth::CurrentUser;
+use crate::auth::{CurrentUser, resolve_bearer_token};
 use crate::error::AppError;
 use crate::metrics::{
     DB_QUERIES_TOTAL, DB_QUERY_DURATION_SECONDS, HTTP_REQUEST_DURATION_SECONDS, HTTP_REQUESTS_TOTAL,
@@ -22,12 +24,27 @@ pub struct PublicTimelineParams {
     pub local: Option<bool>,
 }
 
+/// Whether the request carries a valid access token
+///
+/// The public timeline is served without the auth middleware, so the
+/// owner's filters only apply when the client sends its token anyway.
+async fn is_authenticated_request(state: &AppState, headers: &HeaderMap) -> bool {
+    let Some(token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    resolve_bearer_token(state, token).await.is_ok()
+}
+
 /// GET /api/v1/timelines/home
 pub async fn home_timeline(
     State(state): State<AppState>,
     CurrentUser(_session): CurrentUser,
     Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+) -> Result<Json<Vec<crate::api::StatusResponse>>, AppError> {
     // Start timing the request
     let _timer = HTTP_REQUEST_DURATION_SECONDS
         .with_label_values(&["GET", "/api/v1/timelines/home"])
@@ -61,10 +78,10 @@ pub async fn home_timeline(
     db_timer.observe_duration();
 
     // Convert to API responses
-    let responses: Vec<_> = timeline_items
+    let mut responses: Vec<_> = timeline_items
         .iter()
         .map(|item| {
-            let response = crate::api::status_to_response(
+            let mut response = crate::api::status_to_response(
                 &item.status,
                 &account,
                 &state.config,
@@ -74,9 +91,11 @@ pub async fn home_timeline(
                 Some(item.bookmarked),
                 None,
             );
-            serde_json::to_value(response).unwrap()
+            response.filtered = crate::api::filter_matches_to_response(&item.filtered);
+            response
         })
         .collect();
+    apply_status_counts(&state, &mut responses).await?;
 
     // Record successful request
     HTTP_REQUESTS_TOTAL
@@ -89,8 +108,9 @@ pub async fn home_timeline(
 /// GET /api/v1/timelines/public
 pub async fn public_timeline(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Query(params): Query<PublicTimelineParams>,
-) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+) -> Result<Json<Vec<crate::api::StatusResponse>>, AppError> {
     // Start timing the request
     let _timer = HTTP_REQUEST_DURATION_SECONDS
         .with_label_values(&["GET", "/api/v1/timelines/public"])
@@ -112,7 +132,8 @@ pub async fn public_timeline(
         state.db.clone(),
         state.timeline_cache.clone(),
         state.profile_cache.clone(),
-    );
+    )
+    .with_filters(is_authenticated_request(&state, &headers).await);
     let db_timer = DB_QUERY_DURATION_SECONDS
         .with_label_values(&["SELECT", "statuses"])
         .start_timer();
@@ -125,10 +146,10 @@ pub async fn public_timeline(
     db_timer.observe_duration();
 
     // Convert to API responses
-    let responses: Vec<_> = timeline_items
+    let mut responses: Vec<_> = timeline_items
         .iter()
         .map(|item| {
-            let response = crate::api::status_to_response(
+            let mut response = crate::api::status_to_response(
                 &item.status,
                 &account,
                 &state.config,
@@ -138,9 +159,11 @@ pub async fn public_timeline(
                 Some(item.bookmarked),
                 None,
             );
-            serde_json::to_value(response).unwrap()
+            response.filtered = crate::api::filter_matches_to_response(&item.filtered);
+            response
         })
         .collect();
+    apply_status_counts(&state, &mut responses).await?;
 
     // Record successful request
     HTTP_REQUESTS_TOTAL
@@ -156,7 +179,7 @@ pub async fn tag_timeline(
     State(state): State<AppState>,
     axum::extract::Path(hashtag): axum::extract::Path<String>,
     Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+) -> Result<Json<Vec<crate::api::StatusResponse>>, AppError> {
     let account = state.db.get_account().await?.ok_or(AppError::NotFound)?;
 
     let limit = params.limit.unwrap_or(20).min(40);
@@ -174,10 +197,10 @@ pub async fn tag_timeline(
         )
         .await?;
 
-    let responses: Vec<_> = timeline_items
+    let mut responses: Vec<_> = timeline_items
         .iter()
         .map(|item| {
-            let response = crate::api::status_to_response(
+            let mut response = crate::api::status_to_response(
                 &item.status,
                 &account,
                 &state.config,
@@ -187,9 +210,11 @@ pub async fn tag_timeline(
                 Some(item.bookmarked),
                 None,
             );
-            serde_json::to_value(response).unwrap()
+            response.filtered = crate::api::filter_matches_to_response(&item.filtered);
+            response
         })
         .collect();
+    apply_status_counts(&state, &mut responses).await?;
 
     Ok(Json(responses))
 }
@@ -201,7 +226,7 @@ pub async fn list_timeline(
     CurrentUser(_session): CurrentUser,
     axum::extract::Path(list_id): axum::extract::Path<String>,
     Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<serde_json::Value>>, AppError> {
+) -> Result<Json<Vec<crate::api::StatusResponse>>, AppError> {
     let list = state
         .db
         .get_list(&list_id)
@@ -274,10 +299,10 @@ pub async fn list_timeline(
             .await?
     };
 
-    let responses: Vec<_> = timeline_items
+    let mut responses: Vec<_> = timeline_items
         .iter()
         .map(|item| {
-            let response = crate::api::status_to_response(
+            let mut response = crate::api::status_to_response(
                 &item.status,
                 &account,
                 &state.config,
@@ -287,9 +312,11 @@ pub async fn list_timeline(
                 Some(item.bookmarked),
                 None,
             );
-            serde_json::to_value(response).unwrap()
+            response.filtered = crate::api::filter_matches_to_response(&item.filtered);
+            response
         })
         .collect();
+    apply_status_counts(&state, &mut responses).await?;
 
     Ok(Json(responses))
 }
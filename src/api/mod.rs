@@ -18,9 +18,11 @@ mod wellknown;
 pub use converters::*;
 pub use dto::*;
 
-pub use activitypub::activitypub_router;
+pub use activitypub::{activitypub_router, process_due_inbox_jobs};
 pub use admin::admin_router;
 pub use mastodon::mastodon_api_router;
+pub use mastodon::polls::close_expired_polls;
+pub use mastodon::scheduled_statuses::publish_due_scheduled_statuses;
 pub use metrics::metrics_router;
 pub use oauth::oauth_router;
 pub use wellknown::wellknown_router;
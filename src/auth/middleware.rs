@@ -51,6 +51,7 @@ fn required_oauth_scopes(method: &Method, path: &str) -> Option<&'static [&'stat
     const READ_FILTERS: &[&str] = &["read:filters"];
     const WRITE_FILTERS: &[&str] = &["write:filters"];
     const READ_SEARCH: &[&str] = &["read:search"];
+    const WRITE_REPORTS: &[&str] = &["write:reports"];
 
     let path = normalize_mastodon_path(path);
     if path.starts_with("/v1/admin/") {
@@ -109,6 +110,11 @@ fn required_oauth_scopes(method: &Method, path: &str) -> Option<&'static [&'stat
             } else if path_matches("/v1/filters", path)
                 || path_matches("/v1/filters/:id", path)
                 || path_matches("/v2/filters", path)
+                || path_matches("/v2/filters/:id", path)
+                || path_matches("/v2/filters/:id/keywords", path)
+                || path_matches("/v2/filters/:id/statuses", path)
+                || path_matches("/v2/filters/keywords/:id", path)
+                || path_matches("/v2/filters/statuses/:id", path)
             {
                 Some(READ_FILTERS)
             } else if path_matches("/v1/search", path) || path_matches("/v2/search", path) {
@@ -164,8 +170,14 @@ fn required_oauth_scopes(method: &Method, path: &str) -> Option<&'static [&'stat
                 || path_matches("/v1/lists/:id/accounts", path)
             {
                 Some(WRITE_LISTS)
-            } else if path_matches("/v1/filters", path) {
+            } else if path_matches("/v1/filters", path)
+                || path_matches("/v2/filters", path)
+                || path_matches("/v2/filters/:id/keywords", path)
+                || path_matches("/v2/filters/:id/statuses", path)
+            {
                 Some(WRITE_FILTERS)
+            } else if path_matches("/v1/reports", path) {
+                Some(WRITE_REPORTS)
             } else {
                 None
             }
@@ -179,7 +191,10 @@ fn required_oauth_scopes(method: &Method, path: &str) -> Option<&'static [&'stat
                 Some(WRITE_MEDIA)
             } else if path_matches("/v1/lists/:id", path) {
                 Some(WRITE_LISTS)
-            } else if path_matches("/v1/filters/:id", path) {
+            } else if path_matches("/v1/filters/:id", path)
+                || path_matches("/v2/filters/:id", path)
+                || path_matches("/v2/filters/keywords/:id", path)
+            {
                 Some(WRITE_FILTERS)
             } else {
                 None
@@ -195,7 +210,11 @@ fn required_oauth_scopes(method: &Method, path: &str) -> Option<&'static [&'stat
                 || path_matches("/v1/lists/:id/accounts", path)
             {
                 Some(WRITE_LISTS)
-            } else if path_matches("/v1/filters/:id", path) {
+            } else if path_matches("/v1/filters/:id", path)
+                || path_matches("/v2/filters/:id", path)
+                || path_matches("/v2/filters/keywords/:id", path)
+                || path_matches("/v2/filters/statuses/:id", path)
+            {
                 Some(WRITE_FILTERS)
             } else {
                 None
@@ -246,6 +265,67 @@ fn build_oauth_session(state: &AppState) -> Session {
     }
 }
 
+/// Access granted by a bearer token
+pub(crate) enum BearerGrant {
+    /// Signed session token with full access
+    Session(Session),
+    /// OAuth access token limited to its granted scopes
+    OAuth(HashSet<String>),
+}
+
+impl BearerGrant {
+    /// Check that this grant may call `method` on `path`.
+    ///
+    /// Session tokens are always allowed. OAuth tokens must carry a scope
+    /// listed by `required_oauth_scopes`; unmapped Mastodon API paths fail closed.
+    pub(crate) fn authorize(&self, method: &Method, path: &str) -> Result<(), AppError> {
+        let Self::OAuth(scope_set) = self else {
+            return Ok(());
+        };
+
+        if let Some(required_scopes) = required_oauth_scopes(method, path) {
+            // Empty required scope list means session-only endpoint.
+            if required_scopes.is_empty() || !has_required_scope(scope_set, required_scopes) {
+                return Err(AppError::Forbidden);
+            }
+        } else {
+            let normalized_path = normalize_mastodon_path(path);
+            if normalized_path.starts_with("/v1/") || normalized_path.starts_with("/v2/") {
+                return Err(AppError::Forbidden);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Session to attach to the request for downstream extractors
+    pub(crate) fn into_session(self, state: &AppState) -> Session {
+        match self {
+            Self::Session(session) => session,
+            Self::OAuth(_) => build_oauth_session(state),
+        }
+    }
+}
+
+/// Resolve a bearer token to a session token or an OAuth grant.
+///
+/// Only `authorization_code` OAuth tokens act on behalf of the user.
+pub(crate) async fn resolve_bearer_token(
+    state: &AppState,
+    token: &str,
+) -> Result<BearerGrant, AppError> {
+    if let Ok(session) = verify_session_token(token, &state.config.auth.session_secret) {
+        return Ok(BearerGrant::Session(session));
+    }
+
+    match state.db.get_oauth_token(token).await? {
+        Some(oauth_token) if oauth_token.grant_type == "authorization_code" => {
+            Ok(BearerGrant::OAuth(parse_scope_set(&oauth_token.scopes)))
+        }
+        _ => Err(AppError::Unauthorized),
+    }
+}
+
 /// Middleware to require session authentication only.
 ///
 /// Accepts signed session tokens from Authorization bearer or session cookie.
@@ -300,36 +380,9 @@ pub async fn require_auth(
         .and_then(|h| h.strip_prefix("Bearer "));
 
     if let Some(token) = bearer_token {
-        if let Ok(session) = verify_session_token(token, &state.config.auth.session_secret) {
-            request.extensions_mut().insert(session);
-        } else if let Some(oauth_token) = state.db.get_oauth_token(token).await? {
-            if oauth_token.grant_type != "authorization_code" {
-                return Err(AppError::Unauthorized);
-            }
-
-            let scope_set = parse_scope_set(&oauth_token.scopes);
-            if let Some(required_scopes) =
-                required_oauth_scopes(request.method(), request.uri().path())
-            {
-                // Empty required scope list means session-only endpoint.
-                if required_scopes.is_empty() {
-                    return Err(AppError::Forbidden);
-                }
-                if !has_required_scope(&scope_set, required_scopes) {
-                    return Err(AppError::Forbidden);
-                }
-            } else {
-                // Fail closed for unmapped OAuth-protected Mastodon API endpoints.
-                let normalized_path = normalize_mastodon_path(request.uri().path());
-                if normalized_path.starts_with("/v1/") || normalized_path.starts_with("/v2/") {
-                    return Err(AppError::Forbidden);
-                }
-            }
-
-            request.extensions_mut().insert(build_oauth_session(&state));
-        } else {
-            return Err(AppError::Unauthorized);
-        }
+        let grant = resolve_bearer_token(&state, token).await?;
+        grant.authorize(request.method(), request.uri().path())?;
+        request.extensions_mut().insert(grant.into_session(&state));
     } else if let Some(cookie_token) = jar.get("session").map(|cookie| cookie.value()) {
         let session = verify_session_token(cookie_token, &state.config.auth.session_secret)?;
         request.extensions_mut().insert(session);
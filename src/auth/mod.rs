@@ -9,6 +9,7 @@ mod middleware;
 mod oauth;
 pub mod session;
 
+pub(crate) use middleware::{BearerGrant, resolve_bearer_token};
 pub use middleware::{CurrentUser, require_auth, require_session_auth};
 pub use oauth::auth_router;
 pub use session::{Session, create_session_token, verify_session_token};
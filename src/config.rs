@@ -19,6 +19,8 @@ pub struct AppConfig {
     pub instance: InstanceConfig,
     pub admin: AdminConfig,
     pub cache: CacheConfig,
+    #[serde(default)]
+    pub federation: FederationConfig,
     pub logging: LoggingConfig,
 }
 
@@ -232,6 +234,10 @@ pub struct AdminConfig {
     pub email: Option<String>,
     /// Admin bio/note
     pub note: Option<String>,
+    /// Actor URIs of previous accounts, published as `alsoKnownAs` so they
+    /// can move to this one
+    #[serde(default)]
+    pub also_known_as: Vec<String>,
 }
 
 fn default_admin_username() -> String {
@@ -251,6 +257,71 @@ pub struct CacheConfig {
     pub profile_ttl: u64,
 }
 
+/// Federation configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct FederationConfig {
+    /// Maximum allowed difference between a signed `Date` header and the
+    /// local clock, in seconds (default: 300)
+    #[serde(default = "default_signature_max_skew_seconds")]
+    pub signature_max_skew_seconds: u64,
+    /// How long an inbound activity is remembered for deduplication, in
+    /// seconds (default: 86400)
+    #[serde(default = "default_seen_activity_ttl_seconds")]
+    pub seen_activity_ttl_seconds: u64,
+    /// Maximum number of remembered inbound activities (default: 10000)
+    #[serde(default = "default_seen_activity_max_entries")]
+    pub seen_activity_max_entries: usize,
+    /// Inbound activities processed concurrently (default: 8)
+    #[serde(default = "default_inbox_workers")]
+    pub inbox_workers: usize,
+    /// Inbound activities processed concurrently per remote domain
+    /// (default: 1, which keeps each domain's activities in order)
+    #[serde(default = "default_inbox_per_domain_concurrency")]
+    pub inbox_per_domain_concurrency: usize,
+    /// Require a valid HTTP signature on ActivityPub GETs (secure mode)
+    /// and refuse keys from blocked domains (default: false)
+    #[serde(default)]
+    pub authorized_fetch: bool,
+    /// Publish only the counts of the followers and following collections,
+    /// not who is in them (default: false)
+    #[serde(default)]
+    pub hide_social_graph: bool,
+}
+
+impl Default for FederationConfig {
+    fn default() -> Self {
+        Self {
+            signature_max_skew_seconds: default_signature_max_skew_seconds(),
+            seen_activity_ttl_seconds: default_seen_activity_ttl_seconds(),
+            seen_activity_max_entries: default_seen_activity_max_entries(),
+            inbox_workers: default_inbox_workers(),
+            inbox_per_domain_concurrency: default_inbox_per_domain_concurrency(),
+            authorized_fetch: false,
+            hide_social_graph: false,
+        }
+    }
+}
+
+fn default_signature_max_skew_seconds() -> u64 {
+    300
+}
+
+fn default_seen_activity_ttl_seconds() -> u64 {
+    86_400
+}
+
+fn default_seen_activity_max_entries() -> usize {
+    10_000
+}
+
+fn default_inbox_workers() -> usize {
+    8
+}
+
+fn default_inbox_per_domain_concurrency() -> usize {
+    1
+}
+
 /// Logging configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct LoggingConfig {
@@ -425,11 +496,13 @@ mod tests {
                 display_name: "Admin".to_string(),
                 email: None,
                 note: None,
+                also_known_as: Vec::new(),
             },
             cache: CacheConfig {
                 timeline_max_items: 2000,
                 profile_ttl: 86_400,
             },
+            federation: FederationConfig::default(),
             logging: LoggingConfig {
                 level: "info".to_string(),
                 format: "pretty".to_string(),
@@ -9,6 +9,7 @@ use std::sync::atomic::{AtomicI64, Ordering};
 use turso::{Builder, Connection, Value};
 
 use crate::error::AppError;
+use crate::federation::{ACTIVITY_JSON_ACCEPT, FetchSigner, fetch_with_validated_redirects};
 
 const TIMELINE_TTL_MS: i64 = 7 * 24 * 60 * 60 * 1000;
 const PROFILE_PRUNE_INTERVAL_MS: i64 = 60 * 1000;
@@ -33,6 +34,29 @@ pub struct CachedStatus {
     pub attachments: Vec<CachedAttachment>,
     pub reply_to_uri: Option<String>,
     pub boost_of_uri: Option<String>,
+    /// Content warning (the object's `summary`)
+    pub content_warning: Option<String>,
+}
+
+impl CachedStatus {
+    /// Build a transient `Status` view of this cached entry for API rendering
+    pub fn to_status(&self) -> super::Status {
+        super::Status {
+            id: self.id.clone(),
+            uri: self.uri.clone(),
+            content: self.content.clone(),
+            content_warning: self.content_warning.clone(),
+            visibility: self.visibility.clone(),
+            language: None,
+            account_address: self.account_address.clone(),
+            is_local: false,
+            in_reply_to_uri: self.reply_to_uri.clone(),
+            boost_of_uri: self.boost_of_uri.clone(),
+            persisted_reason: "cache_only".to_string(),
+            created_at: self.created_at,
+            fetched_at: Some(Utc::now()),
+        }
+    }
 }
 
 /// Cached media attachment
@@ -182,6 +206,7 @@ fn parse_actor_uri_address(address: &str) -> Option<String> {
 async fn discover_actor_uri(
     http_client: &reqwest::Client,
     address: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<String, AppError> {
     if let Some(actor_uri) = parse_actor_uri_address(address) {
         return Ok(actor_uri);
@@ -202,11 +227,13 @@ async fn discover_actor_uri(
     let mut last_error = None;
 
     for webfinger_url in webfinger_urls {
-        let response = match http_client
-            .get(webfinger_url.clone())
-            .header("Accept", "application/jrd+json, application/json")
-            .send()
-            .await
+        let response = match fetch_with_validated_redirects(
+            http_client,
+            &webfinger_url,
+            "application/jrd+json, application/json",
+            signer,
+        )
+        .await
         {
             Ok(response) => response,
             Err(error) => {
@@ -260,21 +287,17 @@ async fn discover_actor_uri(
 async fn fetch_actor_document(
     http_client: &reqwest::Client,
     actor_uri: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<serde_json::Value, AppError> {
-    let response = http_client
-        .get(actor_uri)
-        .header(
-            "Accept",
-            "application/activity+json, application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
-        )
-        .send()
-        .await
-        .map_err(|error| {
-            AppError::Federation(format!(
-                "Actor fetch failed for {}: {}",
-                actor_uri, error
-            ))
-        })?;
+    let actor_url = url::Url::parse(actor_uri).map_err(|error| {
+        AppError::Federation(format!("Invalid actor URI {}: {}", actor_uri, error))
+    })?;
+    let response =
+        fetch_with_validated_redirects(http_client, &actor_url, ACTIVITY_JSON_ACCEPT, signer)
+            .await
+            .map_err(|error| {
+                AppError::Federation(format!("Actor fetch failed for {}: {}", actor_uri, error))
+            })?;
 
     if !response.status().is_success() {
         return Err(AppError::Federation(format!(
@@ -388,7 +411,8 @@ impl TimelineCache {
                 attachments_json TEXT NOT NULL,
                 reply_to_uri TEXT,
                 boost_of_uri TEXT,
-                inserted_at_ms INTEGER NOT NULL
+                inserted_at_ms INTEGER NOT NULL,
+                content_warning TEXT
             );
             CREATE INDEX IF NOT EXISTS idx_timeline_statuses_created_at
                 ON timeline_statuses(created_at_ms DESC);
@@ -450,6 +474,7 @@ impl TimelineCache {
             attachments: deserialize_attachments(&attachments_json),
             reply_to_uri: row.get(7)?,
             boost_of_uri: row.get(8)?,
+            content_warning: row.get(9)?,
         })
     }
 
@@ -474,9 +499,10 @@ impl TimelineCache {
                 r#"
                 INSERT INTO timeline_statuses (
                     id, uri, content, account_address, created_at_ms, visibility,
-                    attachments_json, reply_to_uri, boost_of_uri, inserted_at_ms
+                    attachments_json, reply_to_uri, boost_of_uri, inserted_at_ms,
+                    content_warning
                 )
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                 ON CONFLICT(id) DO UPDATE SET
                     uri = excluded.uri,
                     content = excluded.content,
@@ -486,7 +512,8 @@ impl TimelineCache {
                     attachments_json = excluded.attachments_json,
                     reply_to_uri = excluded.reply_to_uri,
                     boost_of_uri = excluded.boost_of_uri,
-                    inserted_at_ms = excluded.inserted_at_ms
+                    inserted_at_ms = excluded.inserted_at_ms,
+                    content_warning = excluded.content_warning
                 "#,
                 (
                     status.id,
@@ -499,6 +526,7 @@ impl TimelineCache {
                     status.reply_to_uri,
                     status.boost_of_uri,
                     inserted_at_ms,
+                    status.content_warning,
                 ),
             )
             .await;
@@ -547,7 +575,7 @@ impl TimelineCache {
             .query(
                 r#"
                 SELECT id, uri, content, account_address, created_at_ms, visibility,
-                       attachments_json, reply_to_uri, boost_of_uri
+                       attachments_json, reply_to_uri, boost_of_uri, content_warning
                 FROM timeline_statuses
                 WHERE id = ?1
                 LIMIT 1
@@ -600,7 +628,7 @@ impl TimelineCache {
             .query(
                 r#"
                 SELECT id, uri, content, account_address, created_at_ms, visibility,
-                       attachments_json, reply_to_uri, boost_of_uri
+                       attachments_json, reply_to_uri, boost_of_uri, content_warning
                 FROM timeline_statuses
                 WHERE uri = ?1
                 LIMIT 1
@@ -665,6 +693,25 @@ impl TimelineCache {
         }
     }
 
+    /// Remove every cached status by an account.
+    pub async fn remove_by_account(&self, account_address: &str) {
+        if let Err(error) = self
+            .conn
+            .execute(
+                "DELETE FROM timeline_statuses WHERE lower(account_address) = lower(?1)",
+                [account_address],
+            )
+            .await
+        {
+            tracing::warn!(%error, "Failed to remove timeline cache entries by account");
+            return;
+        }
+
+        if let Err(error) = self.update_size_metric().await {
+            tracing::warn!(%error, "Failed to update timeline cache metrics");
+        }
+    }
+
     /// Get home timeline
     ///
     /// Returns statuses from followees, sorted by created_at desc.
@@ -690,7 +737,7 @@ impl TimelineCache {
         let mut sql = String::from(
             r#"
             SELECT id, uri, content, account_address, created_at_ms, visibility,
-                   attachments_json, reply_to_uri, boost_of_uri
+                   attachments_json, reply_to_uri, boost_of_uri, content_warning
             FROM timeline_statuses
             WHERE account_address IN (
             "#,
@@ -755,7 +802,7 @@ impl TimelineCache {
             (
                 r#"
                 SELECT id, uri, content, account_address, created_at_ms, visibility,
-                       attachments_json, reply_to_uri, boost_of_uri
+                       attachments_json, reply_to_uri, boost_of_uri, content_warning
                 FROM timeline_statuses
                 WHERE visibility = 'public' AND id < ?1
                 ORDER BY created_at_ms DESC
@@ -767,7 +814,7 @@ impl TimelineCache {
             (
                 r#"
                 SELECT id, uri, content, account_address, created_at_ms, visibility,
-                       attachments_json, reply_to_uri, boost_of_uri
+                       attachments_json, reply_to_uri, boost_of_uri, content_warning
                 FROM timeline_statuses
                 WHERE visibility = 'public'
                 ORDER BY created_at_ms DESC
@@ -980,10 +1027,12 @@ impl ProfileCache {
     /// # Arguments
     /// * `addresses` - List of addresses (user@domain) to fetch
     /// * `http_client` - HTTP client for fetching
+    /// * `signer` - Key used to sign the fetches, if any
     pub async fn initialize_from_addresses(
         &self,
         addresses: &[String],
         http_client: &reqwest::Client,
+        signer: Option<&FetchSigner>,
     ) {
         // Fetch profiles in parallel (max 10 concurrent)
         use futures::stream::{self, StreamExt};
@@ -999,7 +1048,7 @@ impl ProfileCache {
 
         stream::iter(unique_addresses)
             .map(|address| async move {
-                let actor_uri = match discover_actor_uri(http_client, &address).await {
+                let actor_uri = match discover_actor_uri(http_client, &address, signer).await {
                     Ok(actor_uri) => actor_uri,
                     Err(error) => {
                         tracing::warn!(address = %address, %error, "Failed to discover actor URI for profile cache");
@@ -1007,7 +1056,7 @@ impl ProfileCache {
                     }
                 };
 
-                let actor_document = match fetch_actor_document(http_client, &actor_uri).await {
+                let actor_document = match fetch_actor_document(http_client, &actor_uri, signer).await {
                     Ok(actor_document) => actor_document,
                     Err(error) => {
                         tracing::warn!(address = %address, actor_uri = %actor_uri, %error, "Failed to fetch actor document for profile cache");
@@ -1327,6 +1376,7 @@ mod tests {
             attachments: Vec::new(),
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         }
     }
 
@@ -1465,64 +1515,67 @@ mod tests {
         assert_eq!(fetched.uri, profile.uri);
     }
 
+    #[test]
+    fn profile_is_built_from_webfinger_and_actor_document() {
+        let webfinger = serde_json::json!({
+            "subject": "acct:alice@remote.example",
+            "links": [{
+                "rel": "self",
+                "type": "application/activity+json",
+                "href": "https://remote.example/users/alice",
+            }]
+        });
+        let actor_uri = extract_actor_uri_from_webfinger(&webfinger).expect("actor uri");
+        assert_eq!(actor_uri, "https://remote.example/users/alice");
+
+        let actor_document = serde_json::json!({
+            "id": "https://remote.example/users/alice",
+            "name": "Alice",
+            "summary": "<p>Hello</p>",
+            "inbox": "https://remote.example/users/alice/inbox",
+            "outbox": "https://remote.example/users/alice/outbox",
+            "publicKey": {
+                "id": "https://remote.example/users/alice#main-key",
+                "publicKeyPem": "test-public-key"
+            },
+            "followersCount": 12,
+            "followingCount": 34
+        });
+
+        let profile =
+            build_cached_profile_from_actor("alice@remote.example", &actor_uri, &actor_document)
+                .expect("profile");
+        assert_eq!(profile.uri, actor_uri);
+        assert_eq!(
+            profile.inbox_uri,
+            "https://remote.example/users/alice/inbox"
+        );
+        assert_eq!(profile.display_name.as_deref(), Some("Alice"));
+        assert_eq!(profile.public_key_pem, "test-public-key");
+        assert_eq!(profile.followers_count, Some(12));
+        assert_eq!(profile.following_count, Some(34));
+    }
+
     #[tokio::test]
-    async fn profile_initialize_from_addresses_fetches_webfinger_and_actor() {
-        use axum::{Json, Router, extract::Query, routing::get};
-        use serde::Deserialize;
+    async fn profile_initialize_from_addresses_refuses_loopback_targets() {
+        use axum::{Router, routing::get};
+        use std::sync::atomic::AtomicUsize;
         use tokio::net::TcpListener;
 
-        #[derive(Deserialize)]
-        struct WebFingerQuery {
-            resource: String,
-        }
-
         let listener = TcpListener::bind("127.0.0.1:0")
             .await
             .expect("bind test server");
         let addr = listener.local_addr().expect("server address");
-        let actor_uri = format!("http://{addr}/users/alice");
-        let inbox_uri = format!("http://{addr}/users/alice/inbox");
-
-        let actor_uri_for_webfinger = actor_uri.clone();
-        let app = Router::new()
-            .route(
-                "/.well-known/webfinger",
-                get(move |Query(query): Query<WebFingerQuery>| {
-                    let actor_uri = actor_uri_for_webfinger.clone();
-                    async move {
-                        assert_eq!(query.resource, format!("acct:alice@{addr}"));
-                        Json(serde_json::json!({
-                            "subject": format!("acct:alice@{addr}"),
-                            "links": [{
-                                "rel": "self",
-                                "type": "application/activity+json",
-                                "href": actor_uri,
-                            }]
-                        }))
-                    }
-                }),
-            )
-            .route(
-                "/users/alice",
-                get(move || {
-                    let inbox_uri = inbox_uri.clone();
-                    async move {
-                        Json(serde_json::json!({
-                            "id": format!("http://{addr}/users/alice"),
-                            "name": "Alice",
-                            "summary": "<p>Hello</p>",
-                            "inbox": inbox_uri,
-                            "outbox": format!("http://{addr}/users/alice/outbox"),
-                            "publicKey": {
-                                "id": format!("http://{addr}/users/alice#main-key"),
-                                "publicKeyPem": "test-public-key"
-                            },
-                            "followersCount": 12,
-                            "followingCount": 34
-                        }))
-                    }
-                }),
-            );
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let hits_for_handler = hits.clone();
+        let app = Router::new().fallback(get(move || {
+            let hits = hits_for_handler.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                "{}"
+            }
+        }));
 
         tokio::spawn(async move {
             axum::serve(listener, app).await.expect("serve test server");
@@ -1531,19 +1584,21 @@ mod tests {
         let cache = ProfileCache::new(60).await.expect("cache init");
         let http_client = reqwest::Client::new();
         cache
-            .initialize_from_addresses(&[format!("alice@{addr}")], &http_client)
+            .initialize_from_addresses(
+                &[format!("alice@{addr}"), format!("http://{addr}/users/bob")],
+                &http_client,
+                None,
+            )
             .await;
 
-        let profile = cache.get(&format!("alice@{addr}")).await.expect("profile");
-        assert_eq!(profile.uri, actor_uri);
-        assert_eq!(
-            profile.inbox_uri,
-            format!("http://{addr}/users/alice/inbox")
+        assert!(cache.get(&format!("alice@{addr}")).await.is_none());
+        assert!(
+            cache
+                .get_by_uri(&format!("http://{addr}/users/bob"))
+                .await
+                .is_none()
         );
-        assert_eq!(profile.display_name.as_deref(), Some("Alice"));
-        assert_eq!(profile.public_key_pem, "test-public-key");
-        assert_eq!(profile.followers_count, Some(12));
-        assert_eq!(profile.following_count, Some(34));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
     }
 
     #[tokio::test]
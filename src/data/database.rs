@@ -5,7 +5,7 @@
 
 use chrono::{DateTime, Utc};
 use sqlx::{Pool, QueryBuilder, Row, Sqlite, SqlitePool};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::time::Instant;
 
@@ -64,12 +64,39 @@ fn skip_html_tag(content: &str, start: usize) -> Option<usize> {
     None
 }
 
-fn extract_hashtags_from_content(content: &str) -> Vec<String> {
+/// End of the code element starting at `start`, if `content[start..]` opens one
+fn skip_code_element(content: &str, start: usize) -> Option<usize> {
+    let rest = &content[start..];
+    let name = ["pre", "code"].into_iter().find(|name| {
+        rest.strip_prefix('<')
+            .and_then(|rest| rest.strip_prefix(name))
+            .is_some_and(|rest| rest.starts_with(['>', ' ']))
+    })?;
+    let close = format!("</{}>", name);
+    Some(
+        rest.find(&close)
+            .map_or(content.len(), |offset| start + offset + close.len()),
+    )
+}
+
+/// Extract lowercase hashtag names (without `#`) from status HTML content.
+///
+/// Text inside `<pre>` and `<code>` elements is ignored.
+pub fn extract_hashtags_from_content(content: &str) -> Vec<String> {
     let mut hashtags = Vec::new();
     let mut seen = HashSet::new();
     let mut chars = content.char_indices().peekable();
 
     while let Some((index, ch)) = chars.next() {
+        if ch == '<' {
+            if let Some(after_code) = skip_code_element(content, index) {
+                while chars
+                    .next_if(|(next_index, _)| *next_index < after_code)
+                    .is_some()
+                {}
+            }
+            continue;
+        }
         if ch != '#' {
             continue;
         }
@@ -254,6 +281,52 @@ fn find_matching_addresses(
         .collect()
 }
 
+/// Format queue timestamps with a fixed width so lexical ordering in SQLite
+/// matches chronological ordering.
+/// Append an ID cursor window and ordering to a `WHERE` clause
+///
+/// With `min_id` the rows right after the cursor are selected oldest first,
+/// so callers reverse them to keep newest-first order.
+fn push_id_window(
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    max_id: Option<&str>,
+    min_id: Option<&str>,
+    limit: usize,
+) {
+    if let Some(max_id) = max_id {
+        query_builder.push(" AND id < ");
+        query_builder.push_bind(max_id.to_string());
+    }
+    if let Some(min_id) = min_id {
+        query_builder.push(" AND id > ");
+        query_builder.push_bind(min_id.to_string());
+        query_builder.push(" ORDER BY id ASC LIMIT ");
+    } else {
+        query_builder.push(" ORDER BY id DESC LIMIT ");
+    }
+    query_builder.push_bind(limit as i64);
+}
+
+fn queue_timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Mark a scheduled status response that failed to publish
+///
+/// The Mastodon entity has no failure fields, so they live under a
+/// `rustresort` extension object that is only present on failed rows.
+fn attach_scheduled_status_failure(
+    scheduled: &mut serde_json::Value,
+    row: &sqlx::sqlite::SqliteRow,
+) {
+    if row.get::<String, _>("status") == "failed" {
+        scheduled["rustresort"] = serde_json::json!({
+            "failed": true,
+            "error": row.get::<Option<String>, _>("last_error"),
+        });
+    }
+}
+
 fn parse_json_value(raw: Option<String>) -> Option<serde_json::Value> {
     raw.and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
 }
@@ -1049,99 +1122,109 @@ impl Database {
         let mut conn = self.pool.acquire().await?;
         sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
 
-        let result: Result<(), AppError> = async {
+        let result = self
+            .insert_status_rows_in_connection(&mut conn, status, media_ids, poll)
+            .await;
+
+        match result {
+            Ok(()) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(())
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
+    /// Insert status, hashtag, media and poll rows inside an open transaction.
+    async fn insert_status_rows_in_connection(
+        &self,
+        conn: &mut sqlx::pool::PoolConnection<Sqlite>,
+        status: &Status,
+        media_ids: &[String],
+        poll: Option<(&[String], i64, bool)>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO statuses (
+                id, uri, content, content_warning, visibility, language,
+                account_address, is_local, in_reply_to_uri, boost_of_uri,
+                persisted_reason, created_at, fetched_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&status.id)
+        .bind(&status.uri)
+        .bind(&status.content)
+        .bind(&status.content_warning)
+        .bind(&status.visibility)
+        .bind(&status.language)
+        .bind(&status.account_address)
+        .bind(status.is_local)
+        .bind(&status.in_reply_to_uri)
+        .bind(&status.boost_of_uri)
+        .bind(&status.persisted_reason)
+        .bind(&status.created_at)
+        .bind(&status.fetched_at)
+        .execute(&mut **conn)
+        .await?;
+
+        self.replace_status_hashtags_in_connection(conn, &status.id, &status.content)
+            .await?;
+
+        for media_id in media_ids {
+            let updated = sqlx::query(
+                "UPDATE media_attachments SET status_id = ? WHERE id = ? AND status_id IS NULL",
+            )
+            .bind(&status.id)
+            .bind(media_id)
+            .execute(&mut **conn)
+            .await?;
+
+            if updated.rows_affected() == 0 {
+                return Err(AppError::Validation(format!(
+                    "media attachment is unavailable: {}",
+                    media_id
+                )));
+            }
+        }
+
+        if let Some((poll_options, expires_in, multiple)) = poll {
+            let poll_id = EntityId::new().0;
+            let expires_at = chrono::Utc::now() + chrono::Duration::seconds(expires_in);
             sqlx::query(
                 r#"
-                INSERT INTO statuses (
-                    id, uri, content, content_warning, visibility, language,
-                    account_address, is_local, in_reply_to_uri, boost_of_uri,
-                    persisted_reason, created_at, fetched_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                INSERT INTO polls (id, status_id, expires_at, expired, multiple, votes_count, voters_count, created_at)
+                VALUES (?, ?, ?, 0, ?, 0, 0, datetime('now'))
                 "#,
             )
+            .bind(&poll_id)
             .bind(&status.id)
-            .bind(&status.uri)
-            .bind(&status.content)
-            .bind(&status.content_warning)
-            .bind(&status.visibility)
-            .bind(&status.language)
-            .bind(&status.account_address)
-            .bind(status.is_local)
-            .bind(&status.in_reply_to_uri)
-            .bind(&status.boost_of_uri)
-            .bind(&status.persisted_reason)
-            .bind(&status.created_at)
-            .bind(&status.fetched_at)
-            .execute(&mut *conn)
+            .bind(expires_at.to_rfc3339())
+            .bind(multiple as i64)
+            .execute(&mut **conn)
             .await?;
 
-            self.replace_status_hashtags_in_connection(&mut conn, &status.id, &status.content)
-                .await?;
-
-            for media_id in media_ids {
-                let updated = sqlx::query(
-                    "UPDATE media_attachments SET status_id = ? WHERE id = ? AND status_id IS NULL",
-                )
-                .bind(&status.id)
-                .bind(media_id)
-                .execute(&mut *conn)
-                .await?;
-
-                if updated.rows_affected() == 0 {
-                    return Err(AppError::Validation(format!(
-                        "media attachment is unavailable: {}",
-                        media_id
-                    )));
-                }
-            }
-
-            if let Some((poll_options, expires_in, multiple)) = poll {
-                let poll_id = EntityId::new().0;
-                let expires_at = chrono::Utc::now() + chrono::Duration::seconds(expires_in);
+            for (index, option) in poll_options.iter().enumerate() {
+                let option_id = EntityId::new().0;
                 sqlx::query(
                     r#"
-                    INSERT INTO polls (id, status_id, expires_at, expired, multiple, votes_count, voters_count, created_at)
-                    VALUES (?, ?, ?, 0, ?, 0, 0, datetime('now'))
+                    INSERT INTO poll_options (id, poll_id, title, votes_count, option_index, created_at)
+                    VALUES (?, ?, ?, 0, ?, datetime('now'))
                     "#,
                 )
+                .bind(&option_id)
                 .bind(&poll_id)
-                .bind(&status.id)
-                .bind(expires_at.to_rfc3339())
-                .bind(multiple as i64)
-                .execute(&mut *conn)
+                .bind(option)
+                .bind(index as i64)
+                .execute(&mut **conn)
                 .await?;
-
-                for (index, option) in poll_options.iter().enumerate() {
-                    let option_id = EntityId::new().0;
-                    sqlx::query(
-                        r#"
-                        INSERT INTO poll_options (id, poll_id, title, votes_count, option_index, created_at)
-                        VALUES (?, ?, ?, 0, ?, datetime('now'))
-                        "#,
-                    )
-                    .bind(&option_id)
-                    .bind(&poll_id)
-                    .bind(option)
-                    .bind(index as i64)
-                    .execute(&mut *conn)
-                    .await?;
-                }
             }
-
-            Ok(())
         }
-        .await;
 
-        match result {
-            Ok(()) => {
-                sqlx::query("COMMIT").execute(&mut *conn).await?;
-                Ok(())
-            }
-            Err(error) => {
-                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
-                Err(error)
-            }
-        }
+        Ok(())
     }
 
     /// Update an existing status
@@ -1861,40 +1944,135 @@ impl Database {
 
     /// Get statuses safe to expose in ActivityPub outbox.
     ///
-    /// Outbox must never leak private/direct statuses.
+    /// Outbox must never leak private/direct statuses. Results are newest
+    /// first and ordered by ID so IDs can be used as page cursors.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of results
+    /// * `max_id` - Return statuses older than this ID (exclusive)
+    /// * `min_id` - Return the statuses immediately newer than this ID (exclusive)
     pub async fn get_local_outbox_statuses(
         &self,
         limit: usize,
         max_id: Option<&str>,
+        min_id: Option<&str>,
     ) -> Result<Vec<Status>, AppError> {
-        let statuses = if let Some(max_id) = max_id {
-            sqlx::query_as::<_, Status>(
-                r#"
-                SELECT * FROM statuses
-                WHERE is_local = 1 AND visibility IN ('public', 'unlisted') AND id < ?
-                ORDER BY created_at DESC
-                LIMIT ?
-                "#,
-            )
-            .bind(max_id)
-            .bind(limit as i64)
+        let mut query_builder = QueryBuilder::<Sqlite>::new(
+            "SELECT * FROM statuses WHERE is_local = 1 AND visibility IN ('public', 'unlisted')",
+        );
+        push_id_window(&mut query_builder, max_id, min_id, limit);
+
+        let mut statuses = query_builder
+            .build_query_as::<Status>()
             .fetch_all(&self.pool)
-            .await?
-        } else {
-            sqlx::query_as::<_, Status>(
+            .await?;
+        if min_id.is_some() {
+            statuses.reverse();
+        }
+
+        Ok(statuses)
+    }
+
+    /// Count statuses exposed in the ActivityPub outbox
+    pub async fn count_local_outbox_statuses(&self) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM statuses WHERE is_local = 1 AND visibility IN ('public', 'unlisted')",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    // =========================================================================
+    // Status Mentions
+    // =========================================================================
+
+    /// Replace the remote accounts mentioned by a status
+    pub async fn replace_status_mentions(
+        &self,
+        status_id: &str,
+        mentions: &[StatusMention],
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM status_mentions WHERE status_id = ?")
+            .bind(status_id)
+            .execute(&mut *tx)
+            .await?;
+        for mention in mentions {
+            sqlx::query(
                 r#"
-                SELECT * FROM statuses
-                WHERE is_local = 1 AND visibility IN ('public', 'unlisted')
-                ORDER BY created_at DESC
-                LIMIT ?
+                INSERT OR IGNORE INTO status_mentions (status_id, account_address, actor_uri)
+                VALUES (?, ?, ?)
                 "#,
             )
-            .bind(limit as i64)
-            .fetch_all(&self.pool)
-            .await?
-        };
+            .bind(status_id)
+            .bind(&mention.account_address)
+            .bind(&mention.actor_uri)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
 
-        Ok(statuses)
+        Ok(())
+    }
+
+    /// Get the remote accounts mentioned by a status
+    pub async fn get_status_mentions(
+        &self,
+        status_id: &str,
+    ) -> Result<Vec<StatusMention>, AppError> {
+        let mentions = sqlx::query_as::<_, StatusMention>(
+            r#"
+            SELECT status_id, account_address, actor_uri
+            FROM status_mentions
+            WHERE status_id = ?
+            ORDER BY rowid ASC
+            "#,
+        )
+        .bind(status_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(mentions)
+    }
+
+    // =========================================================================
+    // Status Sources
+    // =========================================================================
+
+    /// Store the source of a local status
+    pub async fn upsert_status_source(&self, source: &StatusSource) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO status_sources (status_id, text, content_type)
+            VALUES (?, ?, ?)
+            ON CONFLICT(status_id) DO UPDATE SET
+                text = excluded.text,
+                content_type = excluded.content_type
+            "#,
+        )
+        .bind(&source.status_id)
+        .bind(&source.text)
+        .bind(&source.content_type)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the source of a local status
+    pub async fn get_status_source(
+        &self,
+        status_id: &str,
+    ) -> Result<Option<StatusSource>, AppError> {
+        let source = sqlx::query_as::<_, StatusSource>(
+            "SELECT status_id, text, content_type FROM status_sources WHERE status_id = ?",
+        )
+        .bind(status_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(source)
     }
 
     // =========================================================================
@@ -2075,6 +2253,15 @@ impl Database {
         Ok(addresses)
     }
 
+    /// Count the local account's statuses, boosts included
+    pub async fn count_local_statuses(&self) -> Result<i64, AppError> {
+        let count =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM statuses WHERE is_local = 1")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(count)
+    }
+
     /// Count follows.
     pub async fn count_follow_addresses(&self) -> Result<i64, AppError> {
         let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM follows")
@@ -2105,6 +2292,58 @@ impl Database {
         Ok(count)
     }
 
+    /// Get a page of followers, newest first
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of results
+    /// * `max_id` - Return followers older than this ID (exclusive)
+    /// * `min_id` - Return the followers immediately newer than this ID (exclusive)
+    pub async fn get_followers_page(
+        &self,
+        limit: usize,
+        max_id: Option<&str>,
+        min_id: Option<&str>,
+    ) -> Result<Vec<Follower>, AppError> {
+        let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT * FROM followers WHERE 1 = 1");
+        push_id_window(&mut query_builder, max_id, min_id, limit);
+
+        let mut followers = query_builder
+            .build_query_as::<Follower>()
+            .fetch_all(&self.pool)
+            .await?;
+        if min_id.is_some() {
+            followers.reverse();
+        }
+
+        Ok(followers)
+    }
+
+    /// Get a page of follows, newest first
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of results
+    /// * `max_id` - Return follows older than this ID (exclusive)
+    /// * `min_id` - Return the follows immediately newer than this ID (exclusive)
+    pub async fn get_follows_page(
+        &self,
+        limit: usize,
+        max_id: Option<&str>,
+        min_id: Option<&str>,
+    ) -> Result<Vec<Follow>, AppError> {
+        let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT * FROM follows WHERE 1 = 1");
+        push_id_window(&mut query_builder, max_id, min_id, limit);
+
+        let mut follows = query_builder
+            .build_query_as::<Follow>()
+            .fetch_all(&self.pool)
+            .await?;
+        if min_id.is_some() {
+            follows.reverse();
+        }
+
+        Ok(follows)
+    }
+
     /// Get follower inbox URIs for activity delivery
     pub async fn get_follower_inboxes(&self) -> Result<Vec<String>, AppError> {
         let inboxes = sqlx::query_scalar::<_, String>("SELECT DISTINCT inbox_uri FROM followers")
@@ -2226,17 +2465,41 @@ impl Database {
         Ok(())
     }
 
-    /// Insert new follower
-    pub async fn insert_follower(&self, follower: &Follower) -> Result<(), AppError> {
-        sqlx::query(
-            "INSERT INTO followers (id, follower_address, inbox_uri, uri, created_at) VALUES (?, ?, ?, ?, ?)"
-        )
-        .bind(&follower.id)
-        .bind(&follower.follower_address)
-        .bind(&follower.inbox_uri)
-        .bind(&follower.uri)
-        .bind(&follower.created_at)
-        .execute(&self.pool)
+    /// Delete follow relationship by target address and Follow activity URI
+    pub async fn delete_follow_by_address_and_uri(
+        &self,
+        target_address: &str,
+        follow_uri: &str,
+        default_port: Option<u16>,
+    ) -> Result<bool, AppError> {
+        let existing_addresses = self.get_all_follow_addresses().await?;
+        let matches = find_matching_addresses(&existing_addresses, target_address, default_port);
+        let mut removed = false;
+        for existing in matches {
+            let result = sqlx::query(
+                "DELETE FROM follows WHERE target_address COLLATE NOCASE = ? AND uri = ?",
+            )
+            .bind(existing)
+            .bind(follow_uri)
+            .execute(&self.pool)
+            .await?;
+            removed |= result.rows_affected() > 0;
+        }
+
+        Ok(removed)
+    }
+
+    /// Insert new follower
+    pub async fn insert_follower(&self, follower: &Follower) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO followers (id, follower_address, inbox_uri, uri, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(&follower.id)
+        .bind(&follower.follower_address)
+        .bind(&follower.inbox_uri)
+        .bind(&follower.uri)
+        .bind(&follower.created_at)
+        .execute(&self.pool)
         .await?;
 
         Ok(())
@@ -2386,6 +2649,268 @@ impl Database {
         Ok(())
     }
 
+    // =========================================================================
+    // Inbound interactions
+    // =========================================================================
+
+    /// Record a remote favourite or boost of a local status and its notification
+    ///
+    /// Returns false, without creating the notification, when the activity or
+    /// the account's interaction with the status is already recorded.
+    pub async fn insert_status_interaction(
+        &self,
+        interaction: &StatusInteraction,
+        notification: &Notification,
+    ) -> Result<bool, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result = async {
+            let inserted = sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO status_interactions (
+                    id, activity_uri, interaction_type, account_address, status_uri,
+                    notification_id, created_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&interaction.id)
+            .bind(&interaction.activity_uri)
+            .bind(&interaction.interaction_type)
+            .bind(&interaction.account_address)
+            .bind(&interaction.status_uri)
+            .bind(&interaction.notification_id)
+            .bind(interaction.created_at)
+            .execute(&mut *conn)
+            .await?;
+            if inserted.rows_affected() == 0 {
+                return Ok(false);
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO notifications (
+                    id, notification_type, origin_account_address, status_uri, read, created_at
+                ) VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&notification.id)
+            .bind(&notification.notification_type)
+            .bind(&notification.origin_account_address)
+            .bind(&notification.status_uri)
+            .bind(notification.read)
+            .bind(notification.created_at)
+            .execute(&mut *conn)
+            .await?;
+
+            Ok(true)
+        }
+        .await;
+
+        match result {
+            Ok(inserted) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(inserted)
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
+    /// Remove a remote favourite or boost by its activity ID
+    ///
+    /// Only the account that made the interaction can remove it.
+    pub async fn delete_status_interaction_by_activity_uri(
+        &self,
+        activity_uri: &str,
+        account_address: &str,
+    ) -> Result<Option<StatusInteraction>, AppError> {
+        let interaction = sqlx::query_as::<_, StatusInteraction>(
+            r#"
+            SELECT * FROM status_interactions
+            WHERE activity_uri = ? AND account_address = ? COLLATE NOCASE
+            "#,
+        )
+        .bind(activity_uri)
+        .bind(account_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        self.remove_status_interaction(interaction).await
+    }
+
+    /// Remove an account's favourite or boost of a status
+    pub async fn delete_status_interaction(
+        &self,
+        interaction_type: &str,
+        account_address: &str,
+        status_uri: &str,
+    ) -> Result<Option<StatusInteraction>, AppError> {
+        let interaction = sqlx::query_as::<_, StatusInteraction>(
+            r#"
+            SELECT * FROM status_interactions
+            WHERE status_uri = ? AND interaction_type = ? AND account_address = ? COLLATE NOCASE
+            "#,
+        )
+        .bind(status_uri)
+        .bind(interaction_type)
+        .bind(account_address)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        self.remove_status_interaction(interaction).await
+    }
+
+    /// Delete an interaction row together with its notification
+    async fn remove_status_interaction(
+        &self,
+        interaction: Option<StatusInteraction>,
+    ) -> Result<Option<StatusInteraction>, AppError> {
+        let Some(interaction) = interaction else {
+            return Ok(None);
+        };
+
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result = async {
+            let deleted = sqlx::query("DELETE FROM status_interactions WHERE id = ?")
+                .bind(&interaction.id)
+                .execute(&mut *conn)
+                .await?;
+            if deleted.rows_affected() == 0 {
+                return Ok(false);
+            }
+
+            if let Some(notification_id) = &interaction.notification_id {
+                sqlx::query("DELETE FROM notifications WHERE id = ?")
+                    .bind(notification_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+
+            Ok(true)
+        }
+        .await;
+
+        match result {
+            Ok(deleted) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(deleted.then_some(interaction))
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
+    /// Reply, boost and favourite counts of stored statuses, keyed by URI
+    ///
+    /// Boosts and favourites are the remote ones in `status_interactions` plus
+    /// the local account's own. Statuses that are not stored are left out.
+    pub async fn get_status_counts(
+        &self,
+        uris: &[String],
+    ) -> Result<HashMap<String, StatusCounts>, AppError> {
+        if uris.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut query_builder = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT
+                s.uri,
+                (SELECT COUNT(*) FROM statuses r WHERE r.in_reply_to_uri = s.uri),
+                (SELECT COUNT(*) FROM status_interactions i
+                    WHERE i.status_uri = s.uri AND i.interaction_type = 'reblog')
+                    + (SELECT COUNT(*) FROM reposts p WHERE p.status_id = s.id),
+                (SELECT COUNT(*) FROM status_interactions i
+                    WHERE i.status_uri = s.uri AND i.interaction_type = 'favourite')
+                    + (SELECT COUNT(*) FROM favourites f WHERE f.status_id = s.id)
+            FROM statuses s
+            WHERE s.uri IN (
+            "#,
+        );
+        {
+            let mut separated = query_builder.separated(", ");
+            for uri in uris {
+                separated.push_bind(uri);
+            }
+        }
+        query_builder.push(")");
+
+        let rows = query_builder
+            .build_query_as::<(String, i64, i64, i64)>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(uri, replies_count, reblogs_count, favourites_count)| {
+                (
+                    uri,
+                    StatusCounts {
+                        replies_count,
+                        reblogs_count,
+                        favourites_count,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Addresses of remote accounts that favourited or boosted a status
+    ///
+    /// Newest first.
+    pub async fn get_status_interaction_addresses(
+        &self,
+        status_uri: &str,
+        interaction_type: &str,
+        limit: usize,
+    ) -> Result<Vec<String>, AppError> {
+        let addresses = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT account_address FROM status_interactions
+            WHERE status_uri = ? AND interaction_type = ?
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(status_uri)
+        .bind(interaction_type)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(addresses)
+    }
+
+    /// Count remote favourites and boosts of a status
+    ///
+    /// Returns `(favourites, reblogs)`.
+    pub async fn get_status_interaction_counts(
+        &self,
+        status_uri: &str,
+    ) -> Result<(i64, i64), AppError> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COALESCE(SUM(interaction_type = 'favourite'), 0),
+                COALESCE(SUM(interaction_type = 'reblog'), 0)
+            FROM status_interactions
+            WHERE status_uri = ?
+            "#,
+        )
+        .bind(status_uri)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
     // =========================================================================
     // Favourites / Bookmarks / Reposts
     // =========================================================================
@@ -2762,6 +3287,137 @@ impl Database {
         Ok(count > 0)
     }
 
+    /// Get local pinned statuses that may be published, most recently pinned first
+    pub async fn get_local_pinned_statuses(&self) -> Result<Vec<Status>, AppError> {
+        let statuses = sqlx::query_as::<_, Status>(
+            r#"
+            SELECT s.* FROM pinned_statuses p
+            INNER JOIN statuses s ON s.id = p.status_id
+            WHERE s.is_local = 1 AND s.visibility IN ('public', 'unlisted')
+            ORDER BY p.created_at DESC, p.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(statuses)
+    }
+
+    /// Get the statuses a remote account has pinned, newest first
+    pub async fn get_remote_pinned_statuses(
+        &self,
+        account_address: &str,
+    ) -> Result<Vec<Status>, AppError> {
+        let statuses = sqlx::query_as::<_, Status>(
+            r#"
+            SELECT s.* FROM featured_statuses f
+            INNER JOIN statuses s ON s.id = f.status_id
+            WHERE s.is_local = 0 AND s.account_address = ? COLLATE NOCASE
+            ORDER BY s.created_at DESC
+            "#,
+        )
+        .bind(account_address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(statuses)
+    }
+
+    /// Record a status pinned by its remote author
+    ///
+    /// Unknown statuses are stored as given (with the `featured` reason);
+    /// known ones are pinned as they are. Remote pins are kept in
+    /// `featured_statuses`, apart from the local account's pins.
+    ///
+    /// # Returns
+    /// The stored status
+    pub async fn insert_remote_status_pin(&self, status: &Status) -> Result<Status, AppError> {
+        let stored = match self.get_status_by_uri(&status.uri).await? {
+            Some(existing) => existing,
+            None => {
+                self.insert_status(status).await?;
+                status.clone()
+            }
+        };
+        if stored.is_local {
+            return Err(AppError::Validation(
+                "Remote pins cannot reference local statuses".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO featured_statuses (id, status_id, created_at) VALUES (?, ?, datetime('now'))",
+        )
+        .bind(EntityId::new().0)
+        .bind(&stored.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(stored)
+    }
+
+    /// Remove a status pinned by its remote author
+    ///
+    /// Statuses stored only because they were pinned are deleted as well.
+    pub async fn delete_remote_status_pin(
+        &self,
+        account_address: &str,
+        status_uri: &str,
+    ) -> Result<(), AppError> {
+        let Some(status_id) = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM statuses WHERE uri = ? AND is_local = 0 AND account_address = ? COLLATE NOCASE",
+        )
+        .bind(status_uri)
+        .bind(account_address)
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(());
+        };
+
+        sqlx::query("DELETE FROM featured_statuses WHERE status_id = ?")
+            .bind(&status_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM statuses
+            WHERE id = ? AND persisted_reason = 'featured'
+              AND NOT EXISTS (SELECT 1 FROM bookmarks WHERE status_id = statuses.id)
+              AND NOT EXISTS (SELECT 1 FROM favourites WHERE status_id = statuses.id)
+              AND NOT EXISTS (SELECT 1 FROM reposts WHERE status_id = statuses.id)
+            "#,
+        )
+        .bind(&status_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the statuses a remote account has pinned
+    ///
+    /// Used after reading the account's `featured` collection.
+    pub async fn replace_remote_status_pins(
+        &self,
+        account_address: &str,
+        statuses: &[Status],
+    ) -> Result<(), AppError> {
+        let featured_uris: HashSet<&str> =
+            statuses.iter().map(|status| status.uri.as_str()).collect();
+        for pinned in self.get_remote_pinned_statuses(account_address).await? {
+            if !featured_uris.contains(pinned.uri.as_str()) {
+                self.delete_remote_status_pin(account_address, &pinned.uri)
+                    .await?;
+            }
+        }
+
+        for status in statuses {
+            self.insert_remote_status_pin(status).await?;
+        }
+
+        Ok(())
+    }
+
     /// Insert conversation mute marker for a thread URI.
     pub async fn insert_muted_thread(&self, thread_uri: &str) -> Result<(), AppError> {
         let id = EntityId::new().0;
@@ -2872,6 +3528,183 @@ impl Database {
         self.block_domain(domain).await
     }
 
+    // =========================================================================
+    // Featured tags
+    // =========================================================================
+
+    /// Get hashtags featured on the local profile
+    pub async fn get_featured_tags(&self) -> Result<Vec<FeaturedTag>, AppError> {
+        let tags = sqlx::query_as::<_, FeaturedTag>(
+            "SELECT * FROM featured_tags ORDER BY created_at ASC, id ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    /// Feature a hashtag, returning the existing row if already featured
+    pub async fn insert_featured_tag(&self, name: &str) -> Result<FeaturedTag, AppError> {
+        sqlx::query("INSERT OR IGNORE INTO featured_tags (id, name, created_at) VALUES (?, ?, ?)")
+            .bind(EntityId::new().0)
+            .bind(name)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        let tag = sqlx::query_as::<_, FeaturedTag>("SELECT * FROM featured_tags WHERE name = ?")
+            .bind(name)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(tag)
+    }
+
+    /// Stop featuring a hashtag
+    ///
+    /// Returns false when no featured tag has this ID.
+    pub async fn delete_featured_tag(&self, id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM featured_tags WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count the local public statuses using a hashtag and when it was last used
+    pub async fn get_local_hashtag_usage(
+        &self,
+        name: &str,
+    ) -> Result<(i64, Option<DateTime<Utc>>), AppError> {
+        let usage = sqlx::query_as::<_, (i64, Option<DateTime<Utc>>)>(
+            r#"
+            SELECT COUNT(*), MAX(s.created_at)
+            FROM statuses s
+            INNER JOIN status_hashtags sh ON sh.status_id = s.id
+            INNER JOIN hashtags h ON h.id = sh.hashtag_id
+            WHERE h.name = ? COLLATE NOCASE
+              AND s.is_local = 1
+              AND s.visibility IN ('public', 'unlisted')
+            "#,
+        )
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    // =========================================================================
+    // Reports
+    // =========================================================================
+
+    /// Insert a report
+    ///
+    /// Returns false when a report with the same Flag URI already exists.
+    pub async fn insert_report(&self, report: &Report) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO reports (
+                id, uri, account_address, target_account_address, status_uris, comment,
+                category, forwarded, assigned, action_taken, action_taken_at, created_at,
+                updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&report.id)
+        .bind(&report.uri)
+        .bind(&report.account_address)
+        .bind(&report.target_account_address)
+        .bind(&report.status_uris)
+        .bind(&report.comment)
+        .bind(&report.category)
+        .bind(report.forwarded)
+        .bind(report.assigned)
+        .bind(report.action_taken)
+        .bind(report.action_taken_at)
+        .bind(report.created_at)
+        .bind(report.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get report by ID
+    pub async fn get_report(&self, id: &str) -> Result<Option<Report>, AppError> {
+        let report = sqlx::query_as::<_, Report>("SELECT * FROM reports WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(report)
+    }
+
+    /// Get reports, newest first
+    ///
+    /// `resolved` restricts the result to resolved or unresolved reports.
+    pub async fn get_reports(
+        &self,
+        resolved: Option<bool>,
+        limit: usize,
+        max_id: Option<&str>,
+    ) -> Result<Vec<Report>, AppError> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM reports WHERE 1 = 1");
+        if let Some(resolved) = resolved {
+            query.push(" AND action_taken = ").push_bind(resolved);
+        }
+        if let Some(max_id) = max_id {
+            query.push(" AND id < ").push_bind(max_id);
+        }
+        query
+            .push(" ORDER BY id DESC LIMIT ")
+            .push_bind(limit as i64);
+
+        let reports = query
+            .build_query_as::<Report>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(reports)
+    }
+
+    /// Assign a report to the local account or release it
+    ///
+    /// Returns false when the report does not exist.
+    pub async fn set_report_assigned(&self, id: &str, assigned: bool) -> Result<bool, AppError> {
+        let result = sqlx::query("UPDATE reports SET assigned = ?, updated_at = ? WHERE id = ?")
+            .bind(assigned)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resolve or reopen a report
+    ///
+    /// Returns false when the report does not exist.
+    pub async fn set_report_action_taken(
+        &self,
+        id: &str,
+        action_taken: bool,
+    ) -> Result<bool, AppError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            "UPDATE reports SET action_taken = ?, action_taken_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(action_taken)
+        .bind(action_taken.then_some(now))
+        .bind(now)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     // =========================================================================
     // Settings
     // =========================================================================
@@ -3151,6 +3984,65 @@ impl Database {
             .any(|existing| account_addresses_match(existing, target_address, default_port)))
     }
 
+    /// Record that a remote account blocks the local account
+    ///
+    /// Returns false when the block was already recorded.
+    pub async fn insert_blocked_by(
+        &self,
+        account_address: &str,
+        default_port: Option<u16>,
+    ) -> Result<bool, AppError> {
+        if self.is_blocked_by(account_address, default_port).await? {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO blocked_by (id, account_address, created_at) VALUES (?, ?, datetime('now'))",
+        )
+        .bind(EntityId::new().0)
+        .bind(account_address)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Forget a remote account's block of the local account
+    pub async fn delete_blocked_by(
+        &self,
+        account_address: &str,
+        default_port: Option<u16>,
+    ) -> Result<bool, AppError> {
+        let existing = sqlx::query_scalar::<_, String>("SELECT account_address FROM blocked_by")
+            .fetch_all(&self.pool)
+            .await?;
+        let matches = find_matching_addresses(&existing, account_address, default_port);
+        let mut removed = false;
+        for existing in matches {
+            let result = sqlx::query("DELETE FROM blocked_by WHERE account_address = ?")
+                .bind(existing)
+                .execute(&self.pool)
+                .await?;
+            removed |= result.rows_affected() > 0;
+        }
+
+        Ok(removed)
+    }
+
+    /// Check if a remote account blocks the local account
+    pub async fn is_blocked_by(
+        &self,
+        account_address: &str,
+        default_port: Option<u16>,
+    ) -> Result<bool, AppError> {
+        let existing = sqlx::query_scalar::<_, String>("SELECT account_address FROM blocked_by")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(existing
+            .iter()
+            .any(|existing| account_addresses_match(existing, account_address, default_port)))
+    }
+
     /// Get blocked account addresses
     pub async fn get_blocked_accounts(&self, limit: usize) -> Result<Vec<String>, AppError> {
         let addresses = sqlx::query_scalar::<_, String>(
@@ -3415,6 +4307,32 @@ impl Database {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Reject follow request, treating default-port variants as equivalent.
+    pub async fn reject_follow_request_with_default_port(
+        &self,
+        requester_address: &str,
+        default_port: Option<u16>,
+    ) -> Result<bool, AppError> {
+        let candidates = equivalent_account_address_candidates(requester_address, default_port);
+        if candidates.is_empty() {
+            return Ok(false);
+        }
+
+        let mut query_builder = QueryBuilder::<Sqlite>::new(
+            "DELETE FROM follow_requests WHERE LOWER(requester_address) IN (",
+        );
+        {
+            let mut separated = query_builder.separated(", ");
+            for candidate in candidates {
+                separated.push_bind(candidate.to_ascii_lowercase());
+            }
+        }
+        query_builder.push(")");
+
+        let result = query_builder.build().execute(&self.pool).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     // =========================================================================
     // Lists (Phase 2)
     // =========================================================================
@@ -3644,6 +4562,9 @@ impl Database {
     // =========================================================================
 
     /// Create a filter (v1 API)
+    ///
+    /// The phrase is stored as the filter title and as a keyword sharing the
+    /// filter's ID, so the filter is also usable through the v2 API.
     pub async fn create_filter(
         &self,
         phrase: &str,
@@ -3653,21 +4574,40 @@ impl Database {
         whole_word: bool,
     ) -> Result<String, AppError> {
         let id = EntityId::new().0;
+        let filter_action = if irreversible { "hide" } else { "warn" };
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
-            INSERT INTO filters (id, phrase, context, expires_at, irreversible, whole_word, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+            INSERT INTO filters (id, title, phrase, context, expires_at, filter_action, irreversible, whole_word, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
             "#,
         )
         .bind(&id)
         .bind(phrase)
+        .bind(phrase)
         .bind(context)
         .bind(expires_at)
+        .bind(filter_action)
         .bind(irreversible as i64)
         .bind(whole_word as i64)
-        .execute(&self.pool)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO filter_keywords (id, filter_id, keyword, whole_word, created_at)
+            VALUES (?, ?, ?, ?, datetime('now'))
+            "#,
+        )
+        .bind(&id)
+        .bind(&id)
+        .bind(phrase)
+        .bind(whole_word as i64)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
         Ok(id)
     }
 
@@ -3724,7 +4664,10 @@ impl Database {
             .collect())
     }
 
-    /// Update filter
+    /// Update filter (v1 API)
+    ///
+    /// Also updates the keyword created alongside the filter, if it still
+    /// exists. A `blur` action is kept unless the filter becomes irreversible.
     pub async fn update_filter(
         &self,
         id: &str,
@@ -3734,22 +4677,43 @@ impl Database {
         irreversible: bool,
         whole_word: bool,
     ) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+
         let result = sqlx::query(
             r#"
             UPDATE filters 
-            SET phrase = ?, context = ?, expires_at = ?, irreversible = ?, whole_word = ?, updated_at = datetime('now')
+            SET title = ?, phrase = ?, context = ?, expires_at = ?, irreversible = ?, whole_word = ?,
+                filter_action = CASE
+                    WHEN ? THEN 'hide'
+                    WHEN filter_action = 'hide' THEN 'warn'
+                    ELSE filter_action
+                END,
+                updated_at = datetime('now')
             WHERE id = ?
             "#,
         )
         .bind(phrase)
+        .bind(phrase)
         .bind(context)
         .bind(expires_at)
         .bind(irreversible as i64)
         .bind(whole_word as i64)
+        .bind(irreversible as i64)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE filter_keywords SET keyword = ?, whole_word = ? WHERE id = ? AND filter_id = ?",
+        )
+        .bind(phrase)
+        .bind(whole_word as i64)
+        .bind(id)
+        .bind(id)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -3763,70 +4727,427 @@ impl Database {
         Ok(result.rows_affected() > 0)
     }
 
-    // =========================================================================
-    // Polls (Phase 3)
-    // =========================================================================
-
-    /// Create a poll
-    pub async fn create_poll(
+    /// Create a filter with its keywords (v2 API)
+    pub async fn create_filter_v2(
         &self,
-        status_id: &str,
-        options: &[String],
-        expires_in: i64,
-        multiple: bool,
+        title: &str,
+        context: &str,
+        expires_at: Option<&str>,
+        filter_action: &str,
+        keywords: &[(String, bool)],
     ) -> Result<String, AppError> {
-        let poll_id = EntityId::new().0;
-        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(expires_in);
+        let id = EntityId::new().0;
+        let mut tx = self.pool.begin().await?;
 
         sqlx::query(
             r#"
-            INSERT INTO polls (id, status_id, expires_at, expired, multiple, votes_count, voters_count, created_at)
-            VALUES (?, ?, ?, 0, ?, 0, 0, datetime('now'))
+            INSERT INTO filters (id, title, phrase, context, expires_at, filter_action, irreversible, whole_word, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 1, datetime('now'), datetime('now'))
             "#,
         )
-        .bind(&poll_id)
-        .bind(status_id)
-        .bind(expires_at.to_rfc3339())
-        .bind(multiple as i64)
-        .execute(&self.pool)
+        .bind(&id)
+        .bind(title)
+        .bind(title)
+        .bind(context)
+        .bind(expires_at)
+        .bind(filter_action)
+        .bind((filter_action == "hide") as i64)
+        .execute(&mut *tx)
         .await?;
 
-        // Create poll options
-        for (index, option) in options.iter().enumerate() {
-            let option_id = EntityId::new().0;
+        for (keyword, whole_word) in keywords {
             sqlx::query(
                 r#"
-                INSERT INTO poll_options (id, poll_id, title, votes_count, option_index, created_at)
-                VALUES (?, ?, ?, 0, ?, datetime('now'))
+                INSERT INTO filter_keywords (id, filter_id, keyword, whole_word, created_at)
+                VALUES (?, ?, ?, ?, datetime('now'))
                 "#,
             )
-            .bind(&option_id)
-            .bind(&poll_id)
-            .bind(option)
-            .bind(index as i64)
-            .execute(&self.pool)
+            .bind(EntityId::new().0)
+            .bind(&id)
+            .bind(keyword)
+            .bind(*whole_word as i64)
+            .execute(&mut *tx)
             .await?;
         }
 
-        Ok(poll_id)
+        tx.commit().await?;
+        Ok(id)
     }
 
-    /// Get poll by ID
-    pub async fn get_poll(
+    /// Update a filter and apply keyword changes in one transaction (v2 API)
+    ///
+    /// `keywords_to_update` and `keywords_to_delete` only touch keywords
+    /// belonging to this filter.
+    ///
+    /// # Returns
+    /// `false` if the filter does not exist.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_filter_v2(
         &self,
-        poll_id: &str,
-    ) -> Result<Option<(String, String, bool, bool, i64, i64)>, AppError> {
-        let result = sqlx::query_as::<_, (String, String, i64, i64, i64, i64)>(
-            "SELECT id, expires_at, expired, multiple, votes_count, voters_count FROM polls WHERE id = ?",
-        )
-        .bind(poll_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        id: &str,
+        title: &str,
+        context: &str,
+        expires_at: Option<&str>,
+        filter_action: &str,
+        keywords_to_add: &[(String, bool)],
+        keywords_to_update: &[FilterKeyword],
+        keywords_to_delete: &[String],
+    ) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
 
-        Ok(result.map(
-            |(id, expires_at, expired, multiple, votes_count, voters_count)| {
-                (
-                    id,
+        let result = sqlx::query(
+            r#"
+            UPDATE filters
+            SET title = ?, phrase = ?, context = ?, expires_at = ?, filter_action = ?, irreversible = ?,
+                updated_at = datetime('now')
+            WHERE id = ?
+            "#,
+        )
+        .bind(title)
+        .bind(title)
+        .bind(context)
+        .bind(expires_at)
+        .bind(filter_action)
+        .bind((filter_action == "hide") as i64)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        for keyword_id in keywords_to_delete {
+            sqlx::query("DELETE FROM filter_keywords WHERE id = ? AND filter_id = ?")
+                .bind(keyword_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for keyword in keywords_to_update {
+            sqlx::query(
+                "UPDATE filter_keywords SET keyword = ?, whole_word = ? WHERE id = ? AND filter_id = ?",
+            )
+            .bind(&keyword.keyword)
+            .bind(keyword.whole_word as i64)
+            .bind(&keyword.id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for (keyword, whole_word) in keywords_to_add {
+            sqlx::query(
+                r#"
+                INSERT INTO filter_keywords (id, filter_id, keyword, whole_word, created_at)
+                VALUES (?, ?, ?, ?, datetime('now'))
+                "#,
+            )
+            .bind(EntityId::new().0)
+            .bind(id)
+            .bind(keyword)
+            .bind(*whole_word as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Get filter by ID with its v2 attributes
+    pub async fn get_filter_v2(&self, id: &str) -> Result<Option<Filter>, AppError> {
+        let filter = sqlx::query_as::<_, Filter>(
+            r#"
+            SELECT id, title, phrase, context, expires_at, filter_action, irreversible, whole_word
+            FROM filters
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(filter)
+    }
+
+    /// Get all filters with their v2 attributes, including expired ones
+    pub async fn get_all_filters_v2(&self) -> Result<Vec<Filter>, AppError> {
+        let filters = sqlx::query_as::<_, Filter>(
+            r#"
+            SELECT id, title, phrase, context, expires_at, filter_action, irreversible, whole_word
+            FROM filters
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(filters)
+    }
+
+    /// Get filters that have not expired as of `now`
+    pub async fn get_active_filters(&self, now: DateTime<Utc>) -> Result<Vec<Filter>, AppError> {
+        let filters = sqlx::query_as::<_, Filter>(
+            r#"
+            SELECT id, title, phrase, context, expires_at, filter_action, irreversible, whole_word
+            FROM filters
+            WHERE expires_at IS NULL OR julianday(expires_at) > julianday(?)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(filters)
+    }
+
+    /// Get keywords attached to the given filters
+    pub async fn get_filter_keywords_batch(
+        &self,
+        filter_ids: &[String],
+    ) -> Result<Vec<FilterKeyword>, AppError> {
+        if filter_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = QueryBuilder::<Sqlite>::new(
+            "SELECT id, filter_id, keyword, whole_word FROM filter_keywords WHERE filter_id IN (",
+        );
+        {
+            let mut separated = query_builder.separated(", ");
+            for filter_id in filter_ids {
+                separated.push_bind(filter_id);
+            }
+        }
+        query_builder.push(") ORDER BY created_at ASC, id ASC");
+
+        let keywords = query_builder
+            .build_query_as::<FilterKeyword>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(keywords)
+    }
+
+    /// Get a filter keyword by ID
+    pub async fn get_filter_keyword(&self, id: &str) -> Result<Option<FilterKeyword>, AppError> {
+        let keyword = sqlx::query_as::<_, FilterKeyword>(
+            "SELECT id, filter_id, keyword, whole_word FROM filter_keywords WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(keyword)
+    }
+
+    /// Add a keyword to a filter
+    pub async fn create_filter_keyword(
+        &self,
+        filter_id: &str,
+        keyword: &str,
+        whole_word: bool,
+    ) -> Result<FilterKeyword, AppError> {
+        let id = EntityId::new().0;
+        sqlx::query(
+            r#"
+            INSERT INTO filter_keywords (id, filter_id, keyword, whole_word, created_at)
+            VALUES (?, ?, ?, ?, datetime('now'))
+            "#,
+        )
+        .bind(&id)
+        .bind(filter_id)
+        .bind(keyword)
+        .bind(whole_word as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(FilterKeyword {
+            id,
+            filter_id: filter_id.to_string(),
+            keyword: keyword.to_string(),
+            whole_word,
+        })
+    }
+
+    /// Update a filter keyword
+    pub async fn update_filter_keyword(
+        &self,
+        id: &str,
+        keyword: &str,
+        whole_word: bool,
+    ) -> Result<bool, AppError> {
+        let result =
+            sqlx::query("UPDATE filter_keywords SET keyword = ?, whole_word = ? WHERE id = ?")
+                .bind(keyword)
+                .bind(whole_word as i64)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a filter keyword
+    pub async fn delete_filter_keyword(&self, id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM filter_keywords WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get statuses attached to the given filters
+    pub async fn get_filter_statuses_batch(
+        &self,
+        filter_ids: &[String],
+    ) -> Result<Vec<FilterStatus>, AppError> {
+        if filter_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = QueryBuilder::<Sqlite>::new(
+            "SELECT id, filter_id, status_id FROM filter_statuses WHERE filter_id IN (",
+        );
+        {
+            let mut separated = query_builder.separated(", ");
+            for filter_id in filter_ids {
+                separated.push_bind(filter_id);
+            }
+        }
+        query_builder.push(") ORDER BY created_at ASC, id ASC");
+
+        let statuses = query_builder
+            .build_query_as::<FilterStatus>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(statuses)
+    }
+
+    /// Get a filter status by ID
+    pub async fn get_filter_status(&self, id: &str) -> Result<Option<FilterStatus>, AppError> {
+        let status = sqlx::query_as::<_, FilterStatus>(
+            "SELECT id, filter_id, status_id FROM filter_statuses WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status)
+    }
+
+    /// Attach a status to a filter
+    ///
+    /// # Returns
+    /// `None` if the status is already attached to the filter.
+    pub async fn create_filter_status(
+        &self,
+        filter_id: &str,
+        status_id: &str,
+    ) -> Result<Option<FilterStatus>, AppError> {
+        let id = EntityId::new().0;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO filter_statuses (id, filter_id, status_id, created_at)
+            VALUES (?, ?, ?, datetime('now'))
+            ON CONFLICT(filter_id, status_id) DO NOTHING
+            "#,
+        )
+        .bind(&id)
+        .bind(filter_id)
+        .bind(status_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(FilterStatus {
+            id,
+            filter_id: filter_id.to_string(),
+            status_id: status_id.to_string(),
+        }))
+    }
+
+    /// Detach a status from a filter
+    pub async fn delete_filter_status(&self, id: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM filter_statuses WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // =========================================================================
+    // Polls (Phase 3)
+    // =========================================================================
+
+    /// Create a poll
+    pub async fn create_poll(
+        &self,
+        status_id: &str,
+        options: &[String],
+        expires_in: i64,
+        multiple: bool,
+    ) -> Result<String, AppError> {
+        let poll_id = EntityId::new().0;
+        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(expires_in);
+
+        sqlx::query(
+            r#"
+            INSERT INTO polls (id, status_id, expires_at, expired, multiple, votes_count, voters_count, created_at)
+            VALUES (?, ?, ?, 0, ?, 0, 0, datetime('now'))
+            "#,
+        )
+        .bind(&poll_id)
+        .bind(status_id)
+        .bind(expires_at.to_rfc3339())
+        .bind(multiple as i64)
+        .execute(&self.pool)
+        .await?;
+
+        // Create poll options
+        for (index, option) in options.iter().enumerate() {
+            let option_id = EntityId::new().0;
+            sqlx::query(
+                r#"
+                INSERT INTO poll_options (id, poll_id, title, votes_count, option_index, created_at)
+                VALUES (?, ?, ?, 0, ?, datetime('now'))
+                "#,
+            )
+            .bind(&option_id)
+            .bind(&poll_id)
+            .bind(option)
+            .bind(index as i64)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(poll_id)
+    }
+
+    /// Get poll by ID
+    pub async fn get_poll(
+        &self,
+        poll_id: &str,
+    ) -> Result<Option<(String, String, bool, bool, i64, i64)>, AppError> {
+        let result = sqlx::query_as::<_, (String, String, i64, i64, i64, i64)>(
+            "SELECT id, expires_at, expired, multiple, votes_count, voters_count FROM polls WHERE id = ?",
+        )
+        .bind(poll_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result.map(
+            |(id, expires_at, expired, multiple, votes_count, voters_count)| {
+                (
+                    id,
                     expires_at.clone(),
                     poll_is_expired(&expires_at, expired),
                     multiple != 0,
@@ -3884,6 +5205,32 @@ impl Database {
         poll_id: &str,
         voter_address: &str,
         option_ids: &[String],
+    ) -> Result<(), AppError> {
+        self.record_poll_votes(poll_id, voter_address, option_ids, false)
+            .await
+    }
+
+    /// Record one option of a remote voter's ballot
+    ///
+    /// Remote servers send a separate vote per chosen option, so on
+    /// multiple-choice polls an existing ballot is extended instead of
+    /// rejected.
+    pub async fn add_remote_poll_vote(
+        &self,
+        poll_id: &str,
+        voter_address: &str,
+        option_id: &str,
+    ) -> Result<(), AppError> {
+        self.record_poll_votes(poll_id, voter_address, &[option_id.to_string()], true)
+            .await
+    }
+
+    async fn record_poll_votes(
+        &self,
+        poll_id: &str,
+        voter_address: &str,
+        option_ids: &[String],
+        extend_ballot: bool,
     ) -> Result<(), AppError> {
         if option_ids.is_empty() {
             return Err(AppError::Validation(
@@ -3934,7 +5281,7 @@ impl Database {
             .fetch_one(&mut *conn)
             .await?;
 
-            if existing_vote > 0 {
+            if existing_vote > 0 && !(extend_ballot && poll.3) {
                 return Err(AppError::Validation(
                     "Already voted in this poll".to_string(),
                 ));
@@ -3983,6 +5330,23 @@ impl Database {
                 }
             }
 
+            // Remote polls only hold our own ballot; add it to the author's tally.
+            let is_remote: bool =
+                sqlx::query_scalar("SELECT uri IS NOT NULL FROM polls WHERE id = ?")
+                    .bind(poll_id)
+                    .fetch_one(&mut *conn)
+                    .await?;
+            if is_remote {
+                sqlx::query(
+                    "UPDATE polls SET votes_count = votes_count + ?, voters_count = voters_count + 1 WHERE id = ?",
+                )
+                .bind(option_ids.len() as i64)
+                .bind(poll_id)
+                .execute(&mut *conn)
+                .await?;
+                return Ok(());
+            }
+
             // Update poll totals inside the same transaction.
             let total_votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM poll_votes WHERE poll_id = ?")
                 .bind(poll_id)
@@ -4017,6 +5381,158 @@ impl Database {
         }
     }
 
+    /// Insert or refresh the poll of a remote Question by its URI
+    ///
+    /// Options are updated in place while their titles are unchanged, so
+    /// locally recorded votes survive tally refreshes.
+    pub async fn upsert_remote_poll(&self, poll: &RemotePoll) -> Result<String, AppError> {
+        let votes_count: i64 = poll.options.iter().map(|(_, count)| count).sum();
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result: Result<String, AppError> = async {
+            let existing_id =
+                sqlx::query_scalar::<_, String>("SELECT id FROM polls WHERE uri = ?")
+                    .bind(&poll.uri)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+            let (poll_id, replace_options) = match existing_id {
+                Some(poll_id) => {
+                    sqlx::query(
+                        r#"
+                        UPDATE polls
+                        SET expires_at = ?, expired = ?, multiple = ?, votes_count = ?, voters_count = ?
+                        WHERE id = ?
+                        "#,
+                    )
+                    .bind(&poll.expires_at)
+                    .bind(poll.expired as i64)
+                    .bind(poll.multiple as i64)
+                    .bind(votes_count)
+                    .bind(poll.voters_count)
+                    .bind(&poll_id)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    let titles = sqlx::query_scalar::<_, String>(
+                        "SELECT title FROM poll_options WHERE poll_id = ? ORDER BY option_index",
+                    )
+                    .bind(&poll_id)
+                    .fetch_all(&mut *conn)
+                    .await?;
+                    let unchanged = titles.len() == poll.options.len()
+                        && titles
+                            .iter()
+                            .zip(&poll.options)
+                            .all(|(title, (option_title, _))| title == option_title);
+                    if unchanged {
+                        for (index, (_, option_votes)) in poll.options.iter().enumerate() {
+                            sqlx::query(
+                                "UPDATE poll_options SET votes_count = ? WHERE poll_id = ? AND option_index = ?",
+                            )
+                            .bind(option_votes)
+                            .bind(&poll_id)
+                            .bind(index as i64)
+                            .execute(&mut *conn)
+                            .await?;
+                        }
+                    } else {
+                        sqlx::query("DELETE FROM poll_options WHERE poll_id = ?")
+                            .bind(&poll_id)
+                            .execute(&mut *conn)
+                            .await?;
+                    }
+                    (poll_id, !unchanged)
+                }
+                None => {
+                    let poll_id = EntityId::new().0;
+                    sqlx::query(
+                        r#"
+                        INSERT INTO polls (id, status_id, expires_at, expired, multiple, votes_count, voters_count, uri, account_address, created_at)
+                        VALUES (?, NULL, ?, ?, ?, ?, ?, ?, ?, datetime('now'))
+                        "#,
+                    )
+                    .bind(&poll_id)
+                    .bind(&poll.expires_at)
+                    .bind(poll.expired as i64)
+                    .bind(poll.multiple as i64)
+                    .bind(votes_count)
+                    .bind(poll.voters_count)
+                    .bind(&poll.uri)
+                    .bind(&poll.account_address)
+                    .execute(&mut *conn)
+                    .await?;
+                    (poll_id, true)
+                }
+            };
+
+            if replace_options {
+                for (index, (title, option_votes)) in poll.options.iter().enumerate() {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO poll_options (id, poll_id, title, votes_count, option_index, created_at)
+                        VALUES (?, ?, ?, ?, ?, datetime('now'))
+                        "#,
+                    )
+                    .bind(EntityId::new().0)
+                    .bind(&poll_id)
+                    .bind(title)
+                    .bind(option_votes)
+                    .bind(index as i64)
+                    .execute(&mut *conn)
+                    .await?;
+                }
+            }
+
+            Ok(poll_id)
+        }
+        .await;
+
+        match result {
+            Ok(poll_id) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(poll_id)
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
+    /// Get the Question URI and author of a remote poll
+    ///
+    /// Returns `None` for local polls.
+    pub async fn get_remote_poll_origin(
+        &self,
+        poll_id: &str,
+    ) -> Result<Option<(String, String)>, AppError> {
+        let origin = sqlx::query_as::<_, (String, String)>(
+            "SELECT uri, account_address FROM polls WHERE id = ? AND uri IS NOT NULL AND account_address IS NOT NULL",
+        )
+        .bind(poll_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(origin)
+    }
+
+    /// Get poll ID and author of a remote poll by its Question URI
+    pub async fn get_remote_poll_by_uri(
+        &self,
+        uri: &str,
+    ) -> Result<Option<(String, String)>, AppError> {
+        let poll = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, account_address FROM polls WHERE uri = ? AND account_address IS NOT NULL",
+        )
+        .bind(uri)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(poll)
+    }
+
     /// Update poll vote counts
     async fn update_poll_counts(&self, poll_id: &str) -> Result<(), AppError> {
         // Count total votes
@@ -4061,6 +5577,72 @@ impl Database {
         Ok(option_ids)
     }
 
+    /// Get the distinct addresses that voted in a poll
+    pub async fn get_poll_voter_addresses(&self, poll_id: &str) -> Result<Vec<String>, AppError> {
+        let addresses = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT voter_address FROM poll_votes WHERE poll_id = ? ORDER BY voter_address",
+        )
+        .bind(poll_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(addresses)
+    }
+
+    /// Mark polls whose closing time has passed as expired
+    ///
+    /// Each poll is returned by exactly one call, so callers can notify and
+    /// federate results without duplicates.
+    pub async fn close_expired_polls(&self, limit: usize) -> Result<Vec<ClosedPoll>, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result: Result<Vec<ClosedPoll>, AppError> = async {
+            let rows =
+                sqlx::query_as::<_, (String, Option<String>, Option<String>, Option<String>)>(
+                    r#"
+                SELECT id, status_id, uri, account_address
+                FROM polls
+                WHERE expired = 0 AND julianday(expires_at) <= julianday('now')
+                ORDER BY expires_at
+                LIMIT ?
+                "#,
+                )
+                .bind(limit as i64)
+                .fetch_all(&mut *conn)
+                .await?;
+
+            for (poll_id, _, _, _) in &rows {
+                sqlx::query("UPDATE polls SET expired = 1 WHERE id = ?")
+                    .bind(poll_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+
+            Ok(rows
+                .into_iter()
+                .map(|(id, status_id, uri, account_address)| ClosedPoll {
+                    id,
+                    status_id,
+                    uri,
+                    account_address,
+                })
+                .collect())
+        }
+        .await;
+
+        match result {
+            Ok(closed) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(closed)
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
     // =========================================================================
     // Scheduled Statuses (Phase 3)
     // =========================================================================
@@ -4077,6 +5659,7 @@ impl Database {
         poll_options: Option<&str>,
         poll_expires_in: Option<i64>,
         poll_multiple: bool,
+        content_type: &str,
     ) -> Result<String, AppError> {
         let id = EntityId::new().0;
         sqlx::query(
@@ -4084,8 +5667,8 @@ impl Database {
             INSERT INTO scheduled_statuses (
                 id, scheduled_at, status_text, visibility, content_warning,
                 in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
+                content_type, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))
             "#,
         )
         .bind(&id)
@@ -4098,6 +5681,7 @@ impl Database {
         .bind(poll_options)
         .bind(poll_expires_in)
         .bind(poll_multiple as i64)
+        .bind(content_type)
         .execute(&self.pool)
         .await?;
 
@@ -4112,7 +5696,8 @@ impl Database {
         let result = sqlx::query(
             r#"
             SELECT id, scheduled_at, status_text, visibility, content_warning,
-                   in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple
+                   in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple,
+                   content_type, status, attempts, last_error
             FROM scheduled_statuses WHERE id = ?
             "#,
         )
@@ -4123,11 +5708,12 @@ impl Database {
         if let Some(row) = result {
             let media_ids = parse_json_value(row.get::<Option<String>, _>("media_ids"));
             let poll_options = parse_json_value(row.get::<Option<String>, _>("poll_options"));
-            Ok(Some(serde_json::json!({
+            let mut scheduled = serde_json::json!({
                 "id": row.get::<String, _>("id"),
                 "scheduled_at": row.get::<String, _>("scheduled_at"),
                 "params": {
                     "text": row.get::<String, _>("status_text"),
+                    "content_type": row.get::<String, _>("content_type"),
                     "visibility": row.get::<String, _>("visibility"),
                     "spoiler_text": row.get::<Option<String>, _>("content_warning"),
                     "in_reply_to_id": row.get::<Option<String>, _>("in_reply_to_id"),
@@ -4142,27 +5728,36 @@ impl Database {
                         None
                     }
                 },
-                "media_attachments": []
-            })))
+                "media_attachments": [],
+            });
+            attach_scheduled_status_failure(&mut scheduled, &row);
+            Ok(Some(scheduled))
         } else {
             Ok(None)
         }
     }
 
     /// Get all scheduled statuses
+    ///
+    /// Lists pending rows, or only the rows that failed to publish when
+    /// `failed` is set.
     pub async fn get_all_scheduled_statuses(
         &self,
         limit: usize,
+        failed: bool,
     ) -> Result<Vec<serde_json::Value>, AppError> {
         let rows = sqlx::query(
             r#"
             SELECT id, scheduled_at, status_text, visibility, content_warning,
-                   in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple
+                   in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple,
+                   content_type, status, attempts, last_error
             FROM scheduled_statuses
+            WHERE status = ?
             ORDER BY scheduled_at ASC
             LIMIT ?
             "#,
         )
+        .bind(if failed { "failed" } else { "pending" })
         .bind(limit as i64)
         .fetch_all(&self.pool)
         .await?;
@@ -4171,11 +5766,12 @@ impl Database {
         for row in rows {
             let media_ids = parse_json_value(row.get::<Option<String>, _>("media_ids"));
             let poll_options = parse_json_value(row.get::<Option<String>, _>("poll_options"));
-            results.push(serde_json::json!({
+            let mut scheduled = serde_json::json!({
                 "id": row.get::<String, _>("id"),
                 "scheduled_at": row.get::<String, _>("scheduled_at"),
                 "params": {
                     "text": row.get::<String, _>("status_text"),
+                    "content_type": row.get::<String, _>("content_type"),
                     "visibility": row.get::<String, _>("visibility"),
                     "spoiler_text": row.get::<Option<String>, _>("content_warning"),
                     "in_reply_to_id": row.get::<Option<String>, _>("in_reply_to_id"),
@@ -4190,21 +5786,31 @@ impl Database {
                         None
                     }
                 },
-                "media_attachments": []
-            }));
+                "media_attachments": [],
+            });
+            attach_scheduled_status_failure(&mut scheduled, &row);
+            results.push(scheduled);
         }
 
         Ok(results)
     }
 
     /// Update scheduled status time
+    ///
+    /// Rescheduling starts over: a failed row becomes pending again with a
+    /// fresh attempt budget.
     pub async fn update_scheduled_status(
         &self,
         id: &str,
         scheduled_at: &str,
     ) -> Result<bool, AppError> {
         let result = sqlx::query(
-            "UPDATE scheduled_statuses SET scheduled_at = ?, updated_at = datetime('now') WHERE id = ?",
+            r#"
+            UPDATE scheduled_statuses
+            SET scheduled_at = ?, status = 'pending', attempts = 0, last_error = NULL,
+                next_attempt_at = NULL, updated_at = datetime('now')
+            WHERE id = ?
+            "#,
         )
         .bind(scheduled_at)
         .bind(id)
@@ -4224,6 +5830,127 @@ impl Database {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Get pending scheduled statuses whose publication time (or retry time,
+    /// after a failed attempt) is at or before `now`
+    pub async fn get_due_scheduled_statuses(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ScheduledStatus>, AppError> {
+        let rows = sqlx::query_as::<_, ScheduledStatus>(
+            r#"
+            SELECT id, scheduled_at, status_text, visibility, content_warning,
+                   in_reply_to_id, media_ids, poll_options, poll_expires_in, poll_multiple,
+                   content_type, status, attempts, last_error
+            FROM scheduled_statuses
+            WHERE status = 'pending'
+              AND julianday(COALESCE(next_attempt_at, scheduled_at)) <= julianday(?)
+            ORDER BY scheduled_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Record a failed publication attempt and retry at `next_attempt_at`
+    pub async fn reschedule_scheduled_status_attempt(
+        &self,
+        id: &str,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE scheduled_statuses
+            SET attempts = attempts + 1, last_error = ?, next_attempt_at = ?,
+                updated_at = datetime('now')
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(error)
+        .bind(next_attempt_at.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Stop publishing a scheduled status, keeping it for the owner to see
+    pub async fn mark_scheduled_status_failed(
+        &self,
+        id: &str,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE scheduled_statuses
+            SET status = 'failed', attempts = attempts + 1, last_error = ?,
+                next_attempt_at = NULL, updated_at = datetime('now')
+            WHERE id = ? AND status = 'pending'
+            "#,
+        )
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Publish a scheduled status atomically
+    ///
+    /// Removes the scheduled row and inserts the status (with media and poll)
+    /// in a single transaction. Returns `false` without inserting anything
+    /// when the scheduled row no longer exists, so a row can never be
+    /// published twice even if several publishers race or a previous run
+    /// crashed midway.
+    pub async fn publish_scheduled_status(
+        &self,
+        scheduled_id: &str,
+        status: &Status,
+        media_ids: &[String],
+        poll: Option<(&[String], i64, bool)>,
+    ) -> Result<bool, AppError> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+        let result: Result<bool, AppError> = async {
+            let deleted = sqlx::query("DELETE FROM scheduled_statuses WHERE id = ?")
+                .bind(scheduled_id)
+                .execute(&mut *conn)
+                .await?;
+            if deleted.rows_affected() == 0 {
+                return Ok(false);
+            }
+
+            self.insert_status_rows_in_connection(&mut conn, status, media_ids, poll)
+                .await?;
+            Ok(true)
+        }
+        .await;
+
+        match result {
+            Ok(true) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                Ok(true)
+            }
+            Ok(false) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Ok(false)
+            }
+            Err(error) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                Err(error)
+            }
+        }
+    }
+
     // =========================================================================
     // Conversations (Phase 3)
     // =========================================================================
@@ -4439,4 +6166,314 @@ impl Database {
 
         Ok(hashtags)
     }
+
+    // =========================================================================
+    // Outbound delivery queue
+    // =========================================================================
+
+    /// Enqueue outbound deliveries atomically
+    pub async fn enqueue_deliveries(&self, jobs: &[DeliveryJob]) -> Result<(), AppError> {
+        if jobs.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for job in jobs {
+            sqlx::query(
+                r#"
+                INSERT INTO delivery_queue (
+                    id, inbox_uri, activity_id, activity_type, payload, status, attempts,
+                    last_error, last_status_code, next_attempt_at, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&job.id)
+            .bind(&job.inbox_uri)
+            .bind(&job.activity_id)
+            .bind(&job.activity_type)
+            .bind(&job.payload)
+            .bind(&job.status)
+            .bind(job.attempts)
+            .bind(&job.last_error)
+            .bind(job.last_status_code)
+            .bind(queue_timestamp(job.next_attempt_at))
+            .bind(queue_timestamp(job.created_at))
+            .bind(queue_timestamp(job.updated_at))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Get pending deliveries whose next attempt is due
+    pub async fn get_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<DeliveryJob>, AppError> {
+        let jobs = sqlx::query_as::<_, DeliveryJob>(
+            r#"
+            SELECT * FROM delivery_queue
+            WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(queue_timestamp(now))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Get a queued delivery by ID
+    pub async fn get_delivery(&self, id: &str) -> Result<Option<DeliveryJob>, AppError> {
+        let job = sqlx::query_as::<_, DeliveryJob>("SELECT * FROM delivery_queue WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(job)
+    }
+
+    /// Remove a delivered job from the queue
+    pub async fn delete_delivery(&self, id: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM delivery_queue WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed attempt and schedule the next one
+    pub async fn reschedule_delivery(
+        &self,
+        id: &str,
+        attempts: i64,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+        status_code: Option<u16>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE delivery_queue
+            SET attempts = ?, next_attempt_at = ?, last_error = ?, last_status_code = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(attempts)
+        .bind(queue_timestamp(next_attempt_at))
+        .bind(error)
+        .bind(status_code.map(i64::from))
+        .bind(queue_timestamp(Utc::now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move a job to the dead-letter state; it will not be retried
+    pub async fn dead_letter_delivery(
+        &self,
+        id: &str,
+        attempts: i64,
+        error: &str,
+        status_code: Option<u16>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE delivery_queue
+            SET status = 'dead', attempts = ?, last_error = ?, last_status_code = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(attempts)
+        .bind(error)
+        .bind(status_code.map(i64::from))
+        .bind(queue_timestamp(Utc::now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get dead-lettered deliveries (most recent first)
+    pub async fn get_dead_deliveries(&self, limit: usize) -> Result<Vec<DeliveryJob>, AppError> {
+        let jobs = sqlx::query_as::<_, DeliveryJob>(
+            "SELECT * FROM delivery_queue WHERE status = 'dead' ORDER BY updated_at DESC LIMIT ?",
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Count deliveries still waiting to be sent
+    pub async fn count_pending_deliveries(&self) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM delivery_queue WHERE status = 'pending'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    // =========================================================================
+    // Inbound activity queue
+    // =========================================================================
+
+    /// Enqueue a verified inbound activity
+    pub async fn enqueue_inbox_job(&self, job: &InboxJob) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO inbox_queue (
+                id, actor_uri, actor_domain, activity_id, activity_type, payload, status,
+                attempts, last_error, next_attempt_at, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&job.id)
+        .bind(&job.actor_uri)
+        .bind(&job.actor_domain)
+        .bind(&job.activity_id)
+        .bind(&job.activity_type)
+        .bind(&job.payload)
+        .bind(&job.status)
+        .bind(job.attempts)
+        .bind(&job.last_error)
+        .bind(queue_timestamp(job.next_attempt_at))
+        .bind(queue_timestamp(job.created_at))
+        .bind(queue_timestamp(job.updated_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get pending inbound activities whose next attempt is due, oldest first
+    pub async fn get_due_inbox_jobs(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<InboxJob>, AppError> {
+        let jobs = sqlx::query_as::<_, InboxJob>(
+            r#"
+            SELECT * FROM inbox_queue
+            WHERE status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at ASC, created_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(queue_timestamp(now))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// Get a queued inbound activity by ID
+    pub async fn get_inbox_job(&self, id: &str) -> Result<Option<InboxJob>, AppError> {
+        let job = sqlx::query_as::<_, InboxJob>("SELECT * FROM inbox_queue WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(job)
+    }
+
+    /// Remove a processed or rejected inbound activity from the queue
+    pub async fn delete_inbox_job(&self, id: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM inbox_queue WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed processing attempt and schedule the next one
+    pub async fn reschedule_inbox_job(
+        &self,
+        id: &str,
+        attempts: i64,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE inbox_queue
+            SET attempts = ?, next_attempt_at = ?, last_error = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(attempts)
+        .bind(queue_timestamp(next_attempt_at))
+        .bind(error)
+        .bind(queue_timestamp(Utc::now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move an inbound activity to the dead-letter state; it will not be retried
+    pub async fn dead_letter_inbox_job(
+        &self,
+        id: &str,
+        attempts: i64,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE inbox_queue
+            SET status = 'dead', attempts = ?, last_error = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(attempts)
+        .bind(error)
+        .bind(queue_timestamp(Utc::now()))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete dead-lettered inbound activities last updated before `cutoff`
+    ///
+    /// # Returns
+    /// Number of activities deleted
+    pub async fn delete_dead_inbox_jobs(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let result =
+            sqlx::query("DELETE FROM inbox_queue WHERE status = 'dead' AND updated_at < ?")
+                .bind(queue_timestamp(cutoff))
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Count inbound activities still waiting to be processed
+    pub async fn count_pending_inbox_jobs(&self) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM inbox_queue WHERE status = 'pending'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
 }
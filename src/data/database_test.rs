@@ -1,6 +1,7 @@
 //! Database tests
 
 use super::*;
+use crate::error::AppError;
 use chrono::Utc;
 use sqlx::SqlitePool;
 use std::sync::Arc;
@@ -532,6 +533,44 @@ async fn test_follower_operations() {
     assert_eq!(addresses.len(), 0);
 }
 
+#[tokio::test]
+async fn test_followers_page_windows() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let mut ids = Vec::new();
+    for index in 0..5 {
+        let id = EntityId::new().0;
+        db.insert_follower(&Follower {
+            id: id.clone(),
+            follower_address: format!("follower{}@example.com", index),
+            inbox_uri: "https://example.com/inbox".to_string(),
+            uri: format!("https://example.com/follows/{}", index),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+        ids.push(id);
+    }
+    ids.sort();
+    ids.reverse();
+
+    let page_ids = |followers: Vec<Follower>| -> Vec<String> {
+        followers.into_iter().map(|follower| follower.id).collect()
+    };
+
+    // Newest first
+    let first = db.get_followers_page(2, None, None).await.unwrap();
+    assert_eq!(page_ids(first), ids[0..2]);
+
+    // Older than the cursor
+    let next = db.get_followers_page(2, Some(&ids[1]), None).await.unwrap();
+    assert_eq!(page_ids(next), ids[2..4]);
+
+    // Immediately newer than the cursor, still newest first
+    let prev = db.get_followers_page(2, None, Some(&ids[4])).await.unwrap();
+    assert_eq!(page_ids(prev), ids[2..4]);
+}
+
 #[tokio::test]
 async fn test_delete_follower_matches_missing_default_https_port() {
     let (db, _temp_dir) = create_test_db().await;
@@ -971,6 +1010,104 @@ async fn test_status_pin_and_mute_operations() {
     assert!(!db.is_thread_muted(&root.uri).await.unwrap());
 }
 
+fn test_featured_remote_status(id: &str) -> Status {
+    Status {
+        id: id.to_string(),
+        uri: format!("https://remote.example/users/bob/statuses/{}", id),
+        content: "<p>Pinned by bob</p>".to_string(),
+        content_warning: None,
+        visibility: "public".to_string(),
+        language: None,
+        account_address: "bob@remote.example".to_string(),
+        is_local: false,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "featured".to_string(),
+        created_at: Utc::now(),
+        fetched_at: Some(Utc::now()),
+    }
+}
+
+#[tokio::test]
+async fn test_remote_status_pins_are_replaced_and_cleaned_up() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let first = test_featured_remote_status("featured-1");
+    let second = test_featured_remote_status("featured-2");
+    db.replace_remote_status_pins("bob@remote.example", &[first.clone(), second.clone()])
+        .await
+        .unwrap();
+    assert_eq!(
+        db.get_remote_pinned_statuses("BOB@remote.example")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    // Remote pins are not the local account's pins
+    assert!(!db.is_status_pinned(&first.id).await.unwrap());
+
+    // A bookmarked status survives being unpinned
+    db.insert_bookmark(&second.id).await.unwrap();
+    db.replace_remote_status_pins("bob@remote.example", &[])
+        .await
+        .unwrap();
+
+    assert!(
+        db.get_remote_pinned_statuses("bob@remote.example")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(db.get_status_by_uri(&first.uri).await.unwrap().is_none());
+    assert!(db.get_status_by_uri(&second.uri).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_remote_status_pin_rejects_local_status() {
+    let (db, _temp_dir) = create_test_db().await;
+    let local = test_local_status("local-pin");
+    db.insert_status(&local).await.unwrap();
+
+    let mut claimed = test_featured_remote_status("claimed");
+    claimed.uri = local.uri.clone();
+    assert!(matches!(
+        db.insert_remote_status_pin(&claimed).await,
+        Err(AppError::Validation(_))
+    ));
+    assert!(!db.is_status_pinned(&local.id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_featured_tag_operations() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let rust = db.insert_featured_tag("rust").await.unwrap();
+    let again = db.insert_featured_tag("Rust").await.unwrap();
+    assert_eq!(again.id, rust.id);
+    db.insert_featured_tag("fediverse").await.unwrap();
+
+    let names: Vec<String> = db
+        .get_featured_tags()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tag| tag.name)
+        .collect();
+    assert_eq!(names, vec!["rust", "fediverse"]);
+
+    let mut status = test_local_status("tagged");
+    status.content = "<p>Learning #Rust</p>".to_string();
+    db.insert_status(&status).await.unwrap();
+    let (count, last_status_at) = db.get_local_hashtag_usage("rust").await.unwrap();
+    assert_eq!(count, 1);
+    assert!(last_status_at.is_some());
+
+    assert!(db.delete_featured_tag(&rust.id).await.unwrap());
+    assert!(!db.delete_featured_tag(&rust.id).await.unwrap());
+    assert_eq!(db.get_featured_tags().await.unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn test_status_reply_lookup_and_edit_history_operations() {
     let (db, _temp_dir) = create_test_db().await;
@@ -2016,6 +2153,191 @@ async fn test_vote_in_poll_rejects_option_from_other_poll() {
     assert_eq!(poll_2_after.5, 0);
 }
 
+fn remote_poll(options: &[(&str, i64)]) -> RemotePoll {
+    RemotePoll {
+        uri: "https://remote.example/users/bob/statuses/1".to_string(),
+        account_address: "bob@remote.example".to_string(),
+        expires_at: (Utc::now() + chrono::Duration::hours(1)).to_rfc3339(),
+        expired: false,
+        multiple: false,
+        voters_count: options.iter().map(|(_, votes)| votes).sum(),
+        options: options
+            .iter()
+            .map(|(title, votes)| (title.to_string(), *votes))
+            .collect(),
+    }
+}
+
+#[tokio::test]
+async fn test_upsert_remote_poll_refreshes_tallies_and_keeps_own_vote() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let poll_id = db
+        .upsert_remote_poll(&remote_poll(&[("yes", 2), ("no", 1)]))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.get_remote_poll_origin(&poll_id).await.unwrap(),
+        Some((
+            "https://remote.example/users/bob/statuses/1".to_string(),
+            "bob@remote.example".to_string()
+        ))
+    );
+
+    let options = db.get_poll_options(&poll_id).await.unwrap();
+    db.vote_in_poll(&poll_id, "alice@local.example", &[options[1].0.clone()])
+        .await
+        .unwrap();
+    let poll = db.get_poll(&poll_id).await.unwrap().unwrap();
+    assert_eq!(poll.4, 4);
+    assert_eq!(poll.5, 4);
+
+    let refreshed_id = db
+        .upsert_remote_poll(&remote_poll(&[("yes", 5), ("no", 2)]))
+        .await
+        .unwrap();
+    assert_eq!(refreshed_id, poll_id);
+    let options = db.get_poll_options(&poll_id).await.unwrap();
+    assert_eq!(options[0].2, 5);
+    assert_eq!(options[1].2, 2);
+    let poll = db.get_poll(&poll_id).await.unwrap().unwrap();
+    assert_eq!(poll.4, 7);
+    assert_eq!(poll.5, 7);
+    assert_eq!(
+        db.get_user_poll_votes(&poll_id, "alice@local.example")
+            .await
+            .unwrap(),
+        vec![options[1].0.clone()]
+    );
+}
+
+#[tokio::test]
+async fn test_upsert_remote_poll_replaces_changed_options() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let poll_id = db
+        .upsert_remote_poll(&remote_poll(&[("yes", 0), ("no", 0)]))
+        .await
+        .unwrap();
+    db.upsert_remote_poll(&remote_poll(&[("maybe", 1)]))
+        .await
+        .unwrap();
+
+    let options = db.get_poll_options(&poll_id).await.unwrap();
+    assert_eq!(options.len(), 1);
+    assert_eq!(options[0].1, "maybe");
+    assert_eq!(options[0].2, 1);
+}
+
+#[tokio::test]
+async fn test_get_remote_poll_origin_is_none_for_local_poll() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let status = Status {
+        id: EntityId::new().0,
+        uri: "https://example.com/status/local-poll".to_string(),
+        content: "<p>Poll</p>".to_string(),
+        content_warning: None,
+        visibility: "public".to_string(),
+        language: Some("en".to_string()),
+        account_address: "".to_string(),
+        is_local: true,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "own".to_string(),
+        created_at: Utc::now(),
+        fetched_at: None,
+    };
+    db.insert_status(&status).await.unwrap();
+    let poll_id = db
+        .create_poll(&status.id, &["a".to_string(), "b".to_string()], 600, false)
+        .await
+        .unwrap();
+
+    assert!(db.get_remote_poll_origin(&poll_id).await.unwrap().is_none());
+}
+
+async fn insert_local_poll(db: &Database, multiple: bool, expires_in: i64) -> String {
+    let status = Status {
+        id: EntityId::new().0,
+        uri: format!("https://example.com/status/{}", EntityId::new().0),
+        content: "<p>Poll</p>".to_string(),
+        content_warning: None,
+        visibility: "public".to_string(),
+        language: Some("en".to_string()),
+        account_address: "".to_string(),
+        is_local: true,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "own".to_string(),
+        created_at: Utc::now(),
+        fetched_at: None,
+    };
+    db.insert_status(&status).await.unwrap();
+    db.create_poll(
+        &status.id,
+        &["a".to_string(), "b".to_string()],
+        expires_in,
+        multiple,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_add_remote_poll_vote_extends_ballot_only_for_multiple_poll() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let multiple_poll = insert_local_poll(&db, true, 600).await;
+    let options = db.get_poll_options(&multiple_poll).await.unwrap();
+    for (option_id, _, _) in &options {
+        db.add_remote_poll_vote(&multiple_poll, "bob@remote.example", option_id)
+            .await
+            .unwrap();
+    }
+    assert!(
+        db.add_remote_poll_vote(&multiple_poll, "bob@remote.example", &options[0].0)
+            .await
+            .is_err()
+    );
+    let poll = db.get_poll(&multiple_poll).await.unwrap().unwrap();
+    assert_eq!(poll.4, 2);
+    assert_eq!(poll.5, 1);
+
+    let single_poll = insert_local_poll(&db, false, 600).await;
+    let options = db.get_poll_options(&single_poll).await.unwrap();
+    db.add_remote_poll_vote(&single_poll, "bob@remote.example", &options[0].0)
+        .await
+        .unwrap();
+    assert!(
+        db.add_remote_poll_vote(&single_poll, "bob@remote.example", &options[1].0)
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        db.get_poll_voter_addresses(&single_poll).await.unwrap(),
+        vec!["bob@remote.example".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_close_expired_polls_returns_each_poll_once() {
+    let (db, _temp_dir) = create_test_db().await;
+
+    let expired_poll = insert_local_poll(&db, false, 0).await;
+    let open_poll = insert_local_poll(&db, false, 600).await;
+
+    let closed = db.close_expired_polls(20).await.unwrap();
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].id, expired_poll);
+    assert!(closed[0].status_id.is_some());
+    assert!(closed[0].uri.is_none());
+    assert!(db.close_expired_polls(20).await.unwrap().is_empty());
+
+    assert!(db.get_poll(&expired_poll).await.unwrap().unwrap().2);
+    assert!(!db.get_poll(&open_poll).await.unwrap().unwrap().2);
+}
+
 #[tokio::test]
 async fn test_vote_in_poll_rejects_second_ballot_for_multiple_poll() {
     let (db, _temp_dir) = create_test_db().await;
@@ -2418,3 +2740,540 @@ async fn test_accept_follow_request_rolls_back_on_follower_insert_failure() {
         ))
     );
 }
+
+fn test_delivery_job(inbox_uri: &str, next_attempt_at: chrono::DateTime<Utc>) -> DeliveryJob {
+    let now = Utc::now();
+    DeliveryJob {
+        id: EntityId::new().0,
+        inbox_uri: inbox_uri.to_string(),
+        activity_id: None,
+        activity_type: "Create".to_string(),
+        payload: "{\"type\":\"Create\"}".to_string(),
+        status: "pending".to_string(),
+        attempts: 0,
+        last_error: None,
+        last_status_code: None,
+        next_attempt_at,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_get_due_deliveries_skips_future_and_dead_jobs() {
+    let (db, _temp_dir) = create_test_db().await;
+    let now = Utc::now();
+
+    let due = test_delivery_job(
+        "https://due.example/inbox",
+        now - chrono::Duration::seconds(5),
+    );
+    let future = test_delivery_job(
+        "https://future.example/inbox",
+        now + chrono::Duration::minutes(5),
+    );
+    let dead = test_delivery_job(
+        "https://dead.example/inbox",
+        now - chrono::Duration::hours(1),
+    );
+    db.enqueue_deliveries(&[due.clone(), future.clone(), dead.clone()])
+        .await
+        .unwrap();
+    db.dead_letter_delivery(&dead.id, 16, "gone", Some(410))
+        .await
+        .unwrap();
+
+    let jobs = db.get_due_deliveries(now, 10).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, due.id);
+    assert_eq!(db.count_pending_deliveries().await.unwrap(), 2);
+
+    let dead_jobs = db.get_dead_deliveries(10).await.unwrap();
+    assert_eq!(dead_jobs.len(), 1);
+    assert_eq!(dead_jobs[0].last_status_code, Some(410));
+
+    db.delete_delivery(&due.id).await.unwrap();
+    assert!(db.get_delivery(&due.id).await.unwrap().is_none());
+}
+
+fn test_local_status(id: &str) -> Status {
+    Status {
+        id: id.to_string(),
+        uri: format!("https://example.com/users/alice/statuses/{}", id),
+        content: "<p>Scheduled</p>".to_string(),
+        content_warning: None,
+        visibility: "public".to_string(),
+        language: Some("en".to_string()),
+        account_address: "".to_string(),
+        is_local: true,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "own".to_string(),
+        created_at: Utc::now(),
+        fetched_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_get_due_scheduled_statuses_skips_future_rows() {
+    let (db, _temp_dir) = create_test_db().await;
+    let now = Utc::now();
+
+    let due_id = db
+        .create_scheduled_status(
+            &(now - chrono::Duration::seconds(5)).to_rfc3339(),
+            "due",
+            "public",
+            None,
+            None,
+            None,
+            Some("[\"A\",\"B\"]"),
+            Some(600),
+            true,
+            "text/plain",
+        )
+        .await
+        .unwrap();
+    db.create_scheduled_status(
+        &(now + chrono::Duration::minutes(5)).to_rfc3339(),
+        "future",
+        "public",
+        None,
+        None,
+        None,
+        None,
+        None,
+        false,
+        "text/plain",
+    )
+    .await
+    .unwrap();
+
+    let due = db.get_due_scheduled_statuses(now, 10).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, due_id);
+    assert_eq!(due[0].status_text, "due");
+    assert_eq!(
+        due[0].poll(),
+        Some((vec!["A".to_string(), "B".to_string()], 600, true))
+    );
+    assert!(due[0].media_id_list().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_scheduled_status_attempts_back_off_then_stop() {
+    let (db, _temp_dir) = create_test_db().await;
+    let now = Utc::now();
+    let scheduled_id = db
+        .create_scheduled_status(
+            &(now - chrono::Duration::seconds(5)).to_rfc3339(),
+            "flaky",
+            "public",
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            "text/plain",
+        )
+        .await
+        .unwrap();
+
+    db.reschedule_scheduled_status_attempt(
+        &scheduled_id,
+        now + chrono::Duration::seconds(30),
+        "database is locked",
+    )
+    .await
+    .unwrap();
+    assert!(
+        db.get_due_scheduled_statuses(now, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let due = db
+        .get_due_scheduled_statuses(now + chrono::Duration::seconds(31), 10)
+        .await
+        .unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].attempts, 1);
+
+    db.mark_scheduled_status_failed(&scheduled_id, "gave up")
+        .await
+        .unwrap();
+    assert!(
+        db.get_due_scheduled_statuses(now + chrono::Duration::days(1), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let failed = db
+        .get_scheduled_status(&scheduled_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(failed["rustresort"]["failed"], true);
+    assert_eq!(failed["rustresort"]["error"], "gave up");
+    assert!(
+        db.get_all_scheduled_statuses(20, false)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        db.get_all_scheduled_statuses(20, true).await.unwrap().len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_publish_scheduled_status_consumes_row_exactly_once() {
+    let (db, _temp_dir) = create_test_db().await;
+    let scheduled_id = db
+        .create_scheduled_status(
+            &Utc::now().to_rfc3339(),
+            "Scheduled",
+            "public",
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            "text/plain",
+        )
+        .await
+        .unwrap();
+    let poll_options = vec!["A".to_string(), "B".to_string()];
+
+    let first = test_local_status(&EntityId::new().0);
+    assert!(
+        db.publish_scheduled_status(
+            &scheduled_id,
+            &first,
+            &[],
+            Some((poll_options.as_slice(), 600, false))
+        )
+        .await
+        .unwrap()
+    );
+    assert!(
+        db.get_scheduled_status(&scheduled_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(db.get_poll_by_status_id(&first.id).await.unwrap().is_some());
+
+    let second = test_local_status(&EntityId::new().0);
+    assert!(
+        !db.publish_scheduled_status(&scheduled_id, &second, &[], None)
+            .await
+            .unwrap()
+    );
+    assert!(db.get_status(&second.id).await.unwrap().is_none());
+    assert_eq!(db.get_local_statuses(10, None).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_publish_scheduled_status_keeps_row_when_insert_fails() {
+    let (db, _temp_dir) = create_test_db().await;
+    let scheduled_id = db
+        .create_scheduled_status(
+            &Utc::now().to_rfc3339(),
+            "Scheduled",
+            "public",
+            None,
+            None,
+            Some("[\"missing-media\"]"),
+            None,
+            None,
+            false,
+            "text/plain",
+        )
+        .await
+        .unwrap();
+
+    let status = test_local_status(&EntityId::new().0);
+    let result = db
+        .publish_scheduled_status(&scheduled_id, &status, &["missing-media".to_string()], None)
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+    assert!(
+        db.get_scheduled_status(&scheduled_id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(db.get_status(&status.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_replace_status_mentions_and_source() {
+    let (db, _temp_dir) = create_test_db().await;
+    let status = test_local_status("status-1");
+    db.insert_status(&status).await.unwrap();
+
+    let mention = |address: &str| StatusMention {
+        status_id: status.id.clone(),
+        account_address: address.to_string(),
+        actor_uri: format!("https://remote.example/users/{}", address),
+        inbox_uri: None,
+    };
+    db.replace_status_mentions(&status.id, &[mention("bob"), mention("carol")])
+        .await
+        .unwrap();
+    db.replace_status_mentions(&status.id, &[mention("dave")])
+        .await
+        .unwrap();
+    let mentions = db.get_status_mentions(&status.id).await.unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0].account_address, "dave");
+
+    assert!(db.get_status_source(&status.id).await.unwrap().is_none());
+    let source = |text: &str, content_type: &str| StatusSource {
+        status_id: status.id.clone(),
+        text: text.to_string(),
+        content_type: content_type.to_string(),
+    };
+    db.upsert_status_source(&source("first", "text/plain"))
+        .await
+        .unwrap();
+    db.upsert_status_source(&source("**second**", "text/markdown"))
+        .await
+        .unwrap();
+    let stored = db.get_status_source(&status.id).await.unwrap().unwrap();
+    assert_eq!(stored.text, "**second**");
+    assert_eq!(stored.content_type, "text/markdown");
+
+    db.delete_status(&status.id).await.unwrap();
+    assert!(db.get_status_mentions(&status.id).await.unwrap().is_empty());
+    assert!(db.get_status_source(&status.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_get_status_counts_combines_remote_and_own_interactions() {
+    let (db, _temp_dir) = create_test_db().await;
+    let status = test_local_status("status-1");
+    db.insert_status(&status).await.unwrap();
+    let mut reply = test_local_status("status-2");
+    reply.in_reply_to_uri = Some(status.uri.clone());
+    db.insert_status(&reply).await.unwrap();
+
+    for (address, interaction_type) in [
+        ("bob@remote.example", "favourite"),
+        ("carol@remote.example", "favourite"),
+        ("carol@remote.example", "reblog"),
+    ] {
+        let notification = Notification {
+            id: EntityId::new().0,
+            notification_type: interaction_type.to_string(),
+            origin_account_address: address.to_string(),
+            status_uri: Some(status.uri.clone()),
+            read: false,
+            created_at: Utc::now(),
+        };
+        let interaction = StatusInteraction {
+            id: EntityId::new().0,
+            activity_uri: None,
+            interaction_type: interaction_type.to_string(),
+            account_address: address.to_string(),
+            status_uri: status.uri.clone(),
+            notification_id: Some(notification.id.clone()),
+            created_at: Utc::now(),
+        };
+        assert!(
+            db.insert_status_interaction(&interaction, &notification)
+                .await
+                .unwrap()
+        );
+    }
+    db.insert_favourite(&status.id).await.unwrap();
+
+    let counts = db
+        .get_status_counts(&[
+            status.uri.clone(),
+            reply.uri.clone(),
+            "https://missing.example/1".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(
+        counts[&status.uri],
+        StatusCounts {
+            replies_count: 1,
+            reblogs_count: 1,
+            favourites_count: 3,
+        }
+    );
+    assert_eq!(counts[&reply.uri], StatusCounts::default());
+    assert_eq!(counts.len(), 2);
+    assert_eq!(
+        db.get_status_interaction_addresses(&status.uri, "favourite", 1)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_delete_status_removes_its_interactions() {
+    let (db, _temp_dir) = create_test_db().await;
+    let status = test_local_status("status-1");
+    db.insert_status(&status).await.unwrap();
+    let other = test_local_status("status-2");
+    db.insert_status(&other).await.unwrap();
+
+    let mut notification_ids = Vec::new();
+    for target in [&status, &other] {
+        let notification = Notification {
+            id: EntityId::new().0,
+            notification_type: "favourite".to_string(),
+            origin_account_address: "bob@remote.example".to_string(),
+            status_uri: Some(target.uri.clone()),
+            read: false,
+            created_at: Utc::now(),
+        };
+        let interaction = StatusInteraction {
+            id: EntityId::new().0,
+            activity_uri: Some(format!("https://remote.example/likes/{}", target.id)),
+            interaction_type: "favourite".to_string(),
+            account_address: "bob@remote.example".to_string(),
+            status_uri: target.uri.clone(),
+            notification_id: Some(notification.id.clone()),
+            created_at: Utc::now(),
+        };
+        assert!(
+            db.insert_status_interaction(&interaction, &notification)
+                .await
+                .unwrap()
+        );
+        notification_ids.push(notification.id);
+    }
+
+    db.delete_status(&status.id).await.unwrap();
+
+    assert!(
+        db.get_status_interaction_addresses(&status.uri, "favourite", 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        db.get_notification(&notification_ids[0])
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        db.get_status_interaction_addresses(&other.uri, "favourite", 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(
+        db.get_notification(&notification_ids[1])
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+fn test_report(id: &str, uri: Option<&str>) -> Report {
+    let now = Utc::now();
+    Report {
+        id: id.to_string(),
+        uri: uri.map(str::to_string),
+        account_address: "mod@remote.example".to_string(),
+        target_account_address: "testuser@test.example.com".to_string(),
+        status_uris: "[]".to_string(),
+        comment: "spam".to_string(),
+        category: "other".to_string(),
+        forwarded: false,
+        assigned: false,
+        action_taken: false,
+        action_taken_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn test_insert_report_ignores_duplicate_uri() {
+    let (db, _temp_dir) = create_test_db().await;
+    let uri = Some("https://remote.example/flags/1");
+
+    assert!(
+        db.insert_report(&test_report("report-1", uri))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !db.insert_report(&test_report("report-2", uri))
+            .await
+            .unwrap()
+    );
+    assert!(
+        db.insert_report(&test_report("report-3", None))
+            .await
+            .unwrap()
+    );
+    assert!(
+        db.insert_report(&test_report("report-4", None))
+            .await
+            .unwrap()
+    );
+
+    let reports = db.get_reports(None, 10, None).await.unwrap();
+    let ids: Vec<_> = reports.iter().map(|report| report.id.as_str()).collect();
+    assert_eq!(ids, vec!["report-4", "report-3", "report-1"]);
+}
+
+#[tokio::test]
+async fn test_report_state_changes_filter_reports() {
+    let (db, _temp_dir) = create_test_db().await;
+    db.insert_report(&test_report("report-1", None))
+        .await
+        .unwrap();
+    db.insert_report(&test_report("report-2", None))
+        .await
+        .unwrap();
+
+    assert!(db.set_report_assigned("report-1", true).await.unwrap());
+    assert!(db.set_report_action_taken("report-1", true).await.unwrap());
+    assert!(!db.set_report_action_taken("missing", true).await.unwrap());
+
+    let resolved = db.get_report("report-1").await.unwrap().unwrap();
+    assert!(resolved.assigned);
+    assert!(resolved.action_taken);
+    assert!(resolved.action_taken_at.is_some());
+
+    let open = db.get_reports(Some(false), 10, None).await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].id, "report-2");
+    let closed = db.get_reports(Some(true), 10, None).await.unwrap();
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].id, "report-1");
+    let older = db.get_reports(None, 10, Some("report-2")).await.unwrap();
+    assert_eq!(older.len(), 1);
+    assert_eq!(older[0].id, "report-1");
+
+    assert!(db.set_report_action_taken("report-1", false).await.unwrap());
+    let reopened = db.get_report("report-1").await.unwrap().unwrap();
+    assert!(!reopened.action_taken);
+    assert!(reopened.action_taken_at.is_none());
+}
+
+#[test]
+fn test_extract_hashtags_skips_code_elements() {
+    assert_eq!(
+        extract_hashtags_from_content(
+            "<p>#one <code>#two</code></p><pre><code>#three\n</code></pre><p>#four<codex>#five</codex></p>"
+        ),
+        vec!["one", "four", "five"]
+    );
+}
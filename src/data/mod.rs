@@ -11,7 +11,7 @@ mod models;
 mod sync;
 
 pub use cache::{CachedAttachment, CachedProfile, CachedStatus, ProfileCache, TimelineCache};
-pub use database::{Database, TursoSyncOptions};
+pub use database::{Database, TursoSyncOptions, extract_hashtags_from_content};
 pub use models::*;
 pub use sync::{sync_to_d1, validate_d1_sync_environment};
 
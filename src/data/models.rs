@@ -91,13 +91,21 @@ pub struct Status {
     /// URI of the post this boosts
     pub boost_of_uri: Option<String>,
     /// Why this remote status was persisted
-    /// Values: own, reposted, favourited, bookmarked, reply_to_own
+    /// Values: own, reposted, favourited, bookmarked, reply_to_own, featured
     pub persisted_reason: String,
     pub created_at: DateTime<Utc>,
     /// When this remote status was fetched
     pub fetched_at: Option<DateTime<Utc>>,
 }
 
+/// Engagement counts of a status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusCounts {
+    pub replies_count: i64,
+    pub reblogs_count: i64,
+    pub favourites_count: i64,
+}
+
 /// Reason for persisting a remote status
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PersistedReason {
@@ -111,6 +119,8 @@ pub enum PersistedReason {
     Bookmarked,
     /// Reply to user's own post
     ReplyToOwn,
+    /// Pinned by its remote author (their `featured` collection)
+    Featured,
 }
 
 impl PersistedReason {
@@ -121,10 +131,35 @@ impl PersistedReason {
             Self::Favourited => "favourited",
             Self::Bookmarked => "bookmarked",
             Self::ReplyToOwn => "reply_to_own",
+            Self::Featured => "featured",
         }
     }
 }
 
+/// Remote account mentioned by a local status
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StatusMention {
+    pub status_id: String,
+    /// Account address (user@domain)
+    pub account_address: String,
+    /// ActivityPub actor URI
+    pub actor_uri: String,
+    /// Inbox found while resolving the mention, so delivery can skip a
+    /// second lookup; not stored
+    #[sqlx(skip)]
+    #[serde(skip)]
+    pub inbox_uri: Option<String>,
+}
+
+/// Text a local status was rendered from
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StatusSource {
+    pub status_id: String,
+    pub text: String,
+    /// `text/plain` or `text/markdown`
+    pub content_type: String,
+}
+
 // =============================================================================
 // Media Attachment
 // =============================================================================
@@ -212,6 +247,22 @@ pub struct Notification {
     pub created_at: DateTime<Utc>,
 }
 
+/// A remote favourite or boost of a local status
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StatusInteraction {
+    pub id: String,
+    /// Like/Announce activity ID
+    pub activity_uri: Option<String>,
+    /// favourite or reblog
+    pub interaction_type: String,
+    /// Interacting account (user@domain)
+    pub account_address: String,
+    pub status_uri: String,
+    /// Notification created for this interaction
+    pub notification_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Notification types
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NotificationType {
@@ -272,6 +323,15 @@ pub struct DomainBlock {
     pub created_at: DateTime<Utc>,
 }
 
+/// Hashtag featured on the local profile
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeaturedTag {
+    pub id: String,
+    /// Hashtag name without `#`
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Key-value settings
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct Setting {
@@ -320,3 +380,224 @@ pub struct OAuthAuthorizationCode {
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
 }
+
+// =============================================================================
+// Outbound delivery queue
+// =============================================================================
+
+/// A queued outbound activity for a single remote inbox
+///
+/// The payload is stored unsigned; signatures are generated at send time
+/// so the `Date` header is always fresh.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DeliveryJob {
+    pub id: String,
+    /// Target inbox URI
+    pub inbox_uri: String,
+    /// Activity `id` (if present in the payload)
+    pub activity_id: Option<String>,
+    /// Activity `type` (Create, Follow, ...)
+    pub activity_type: String,
+    /// Serialized activity JSON
+    pub payload: String,
+    /// Status: pending, dead
+    pub status: String,
+    /// Number of delivery attempts made so far
+    pub attempts: i64,
+    /// Error message from the most recent failed attempt
+    pub last_error: Option<String>,
+    /// HTTP status code from the most recent failed attempt
+    pub last_status_code: Option<i64>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// Inbound activity queue
+// =============================================================================
+
+/// A verified inbound activity waiting to be processed
+///
+/// The signature has already been checked when the row is written, so the
+/// worker trusts `actor_uri` as the signing actor.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct InboxJob {
+    pub id: String,
+    /// Actor that signed the delivery
+    pub actor_uri: String,
+    /// Normalized domain of the signing actor, used for per-domain concurrency
+    pub actor_domain: String,
+    /// Activity `id` (if present in the payload)
+    pub activity_id: Option<String>,
+    /// Activity `type` (Create, Follow, ...)
+    pub activity_type: String,
+    /// Serialized activity JSON
+    pub payload: String,
+    /// Status: pending, dead
+    pub status: String,
+    /// Number of processing attempts made so far
+    pub attempts: i64,
+    /// Error message from the most recent failed attempt
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// =============================================================================
+// Remote polls
+// =============================================================================
+
+/// Poll of a remote `Question`, as last seen from its author
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemotePoll {
+    /// ActivityPub ID of the Question
+    pub uri: String,
+    /// Author of the Question (user@domain)
+    pub account_address: String,
+    /// RFC3339 closing time
+    pub expires_at: String,
+    pub expired: bool,
+    pub multiple: bool,
+    pub voters_count: i64,
+    /// Option titles with their vote counts, in display order
+    pub options: Vec<(String, i64)>,
+}
+
+/// Poll that was just closed by the expiry job
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosedPoll {
+    pub id: String,
+    /// Local status carrying the poll; `None` for remote polls
+    pub status_id: Option<String>,
+    /// Question URI of a remote poll
+    pub uri: Option<String>,
+    /// Author of a remote poll (user@domain)
+    pub account_address: Option<String>,
+}
+
+// =============================================================================
+// Reports
+// =============================================================================
+
+/// Abuse report, filed locally or received as a `Flag`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Report {
+    pub id: String,
+    /// Flag activity ID, if the report was federated
+    pub uri: Option<String>,
+    /// Reporter (user@domain); a remote instance actor for received reports
+    pub account_address: String,
+    /// Reported account (user@domain)
+    pub target_account_address: String,
+    /// JSON array of reported status URIs
+    pub status_uris: String,
+    pub comment: String,
+    /// spam, legal, violation or other
+    pub category: String,
+    /// Whether the report was sent to the target's instance
+    pub forwarded: bool,
+    /// Whether the local account took the report
+    pub assigned: bool,
+    /// Whether the report is resolved
+    pub action_taken: bool,
+    pub action_taken_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Report {
+    /// Reported status URIs
+    pub fn status_uri_list(&self) -> Vec<String> {
+        serde_json::from_str(&self.status_uris).unwrap_or_default()
+    }
+}
+
+// =============================================================================
+// Scheduled statuses
+// =============================================================================
+
+/// A status waiting to be published at `scheduled_at`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ScheduledStatus {
+    pub id: String,
+    /// RFC3339 publication time
+    pub scheduled_at: String,
+    /// Status body as submitted by the client, in `content_type`
+    pub status_text: String,
+    pub visibility: String,
+    pub content_warning: Option<String>,
+    /// Status ID or URI the post replies to
+    pub in_reply_to_id: Option<String>,
+    /// JSON array of media attachment IDs
+    pub media_ids: Option<String>,
+    /// JSON array of poll option titles
+    pub poll_options: Option<String>,
+    pub poll_expires_in: Option<i64>,
+    pub poll_multiple: bool,
+    /// `text/plain` or `text/markdown`
+    pub content_type: String,
+    /// Publication attempts that failed so far
+    pub attempts: i64,
+}
+
+impl ScheduledStatus {
+    /// Media attachment IDs to attach on publication
+    pub fn media_id_list(&self) -> Vec<String> {
+        self.media_ids
+            .as_deref()
+            .and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_default()
+    }
+
+    /// Poll options, expiry and multiple-choice flag, if a poll was scheduled
+    pub fn poll(&self) -> Option<(Vec<String>, i64, bool)> {
+        let options: Vec<String> = serde_json::from_str(self.poll_options.as_deref()?).ok()?;
+        if options.is_empty() {
+            return None;
+        }
+        Some((options, self.poll_expires_in?, self.poll_multiple))
+    }
+}
+
+// =============================================================================
+// Filters
+// =============================================================================
+
+/// Keyword filter owned by the local account
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Filter {
+    pub id: String,
+    /// Filter title (v2)
+    pub title: String,
+    /// Filter phrase (v1); mirrors the title for filters created through v2
+    pub phrase: String,
+    /// Comma-separated contexts: home, notifications, public, thread, account
+    pub context: String,
+    /// RFC3339 expiry time; None = never expires
+    pub expires_at: Option<String>,
+    /// warn, hide or blur
+    pub filter_action: String,
+    /// Drop matching statuses (v1); true when `filter_action` is hide
+    pub irreversible: bool,
+    /// Match `phrase` on word boundaries only (v1)
+    pub whole_word: bool,
+}
+
+/// Keyword attached to a filter
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FilterKeyword {
+    pub id: String,
+    pub filter_id: String,
+    pub keyword: String,
+    pub whole_word: bool,
+}
+
+/// Individual status attached to a filter
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FilterStatus {
+    pub id: String,
+    pub filter_id: String,
+    pub status_id: String,
+}
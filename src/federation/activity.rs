@@ -4,12 +4,15 @@
 
 #![allow(dead_code)]
 
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use chrono::{DateTime, Utc};
 
-use crate::data::{CachedAttachment, CachedStatus, Database, ProfileCache, TimelineCache};
+use crate::data::{
+    CachedAttachment, CachedStatus, Database, ProfileCache, RemotePoll, TimelineCache,
+};
 use crate::error::AppError;
+use crate::service::{StreamEvent, StreamingBus};
 
 /// Return true when a Follow target references the local actor.
 ///
@@ -129,8 +132,56 @@ fn follow_addresses_match(
     actor_port == follow_port
 }
 
-fn sanitize_remote_html(content: &str) -> String {
-    ammonia::clean(content)
+/// Sanitization policy for status HTML: ammonia's defaults plus the
+/// microformat classes Mastodon uses for mentions, hashtags and links.
+static STATUS_HTML_POLICY: LazyLock<ammonia::Builder<'static>> = LazyLock::new(|| {
+    let mut builder = ammonia::Builder::default();
+    builder
+        .add_allowed_classes("a", &["mention", "hashtag", "u-url"])
+        .add_allowed_classes("span", &["h-card", "invisible", "ellipsis"]);
+    builder
+});
+
+/// Sanitize status HTML from remote servers or rendered from local Markdown
+pub fn sanitize_status_html(content: &str) -> String {
+    STATUS_HTML_POLICY.clean(content).to_string()
+}
+
+/// Derive a Mastodon visibility from an object's `to`/`cc` audience
+pub(crate) fn extract_visibility(object: &serde_json::Value) -> String {
+    const PUBLIC_AUDIENCE: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+    let contains_public = |audience: &serde_json::Value| -> bool {
+        if let Some(value) = audience.as_str() {
+            return value == PUBLIC_AUDIENCE;
+        }
+        audience
+            .as_array()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .any(|value| value == PUBLIC_AUDIENCE)
+            })
+            .unwrap_or(false)
+    };
+
+    if object.get("to").is_some_and(contains_public) {
+        "public".to_string()
+    } else if object.get("cc").is_some_and(contains_public) {
+        "unlisted".to_string()
+    } else {
+        "private".to_string()
+    }
+}
+
+/// Content warning of an object (its non-empty `summary`)
+fn extract_content_warning(object: &serde_json::Value) -> Option<String> {
+    object
+        .get("summary")
+        .and_then(|summary| summary.as_str())
+        .filter(|summary| !summary.is_empty())
+        .map(str::to_string)
 }
 
 fn extract_follow_target(activity: &serde_json::Value) -> Result<String, AppError> {
@@ -172,6 +223,75 @@ fn extract_delete_target_uri(activity: &serde_json::Value) -> Option<String> {
         .map(str::to_string)
 }
 
+/// Read an activity field that may be a URI or an object with an `id`
+fn extract_object_reference(value: Option<&serde_json::Value>) -> Option<String> {
+    let value = value?;
+    value
+        .as_str()
+        .or_else(|| value.get("id").and_then(serde_json::Value::as_str))
+        .map(str::to_string)
+}
+
+/// Read the poll of a `Question` object
+///
+/// Options come from `oneOf` (single choice) or `anyOf` (multiple choice)
+/// with their tallies in `replies.totalItems`. A poll is expired once it
+/// carries `closed` or its `endTime` has passed.
+fn parse_question(object: &serde_json::Value, account_address: &str) -> Option<RemotePoll> {
+    let uri = object.get("id").and_then(serde_json::Value::as_str)?;
+    let (choices, multiple) = match object.get("anyOf").and_then(serde_json::Value::as_array) {
+        Some(choices) => (choices, true),
+        None => (
+            object.get("oneOf").and_then(serde_json::Value::as_array)?,
+            false,
+        ),
+    };
+    let options: Vec<(String, i64)> = choices
+        .iter()
+        .filter_map(|choice| {
+            let title = choice.get("name").and_then(serde_json::Value::as_str)?;
+            let votes = choice
+                .get("replies")
+                .and_then(|replies| replies.get("totalItems"))
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0);
+            Some((title.to_string(), votes.max(0)))
+        })
+        .collect();
+    if options.is_empty() {
+        return None;
+    }
+
+    let closed_at = object
+        .get("closed")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|closed| DateTime::parse_from_rfc3339(closed).ok());
+    let end_time = object
+        .get("endTime")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|end_time| DateTime::parse_from_rfc3339(end_time).ok());
+    let expires_at = end_time
+        .or(closed_at)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    let expired =
+        object.get("closed").is_some_and(|closed| !closed.is_null()) || expires_at <= Utc::now();
+    let voters_count = object
+        .get("votersCount")
+        .and_then(serde_json::Value::as_i64)
+        .unwrap_or_else(|| options.iter().map(|(_, votes)| votes).sum());
+
+    Some(RemotePoll {
+        uri: uri.to_string(),
+        account_address: account_address.to_string(),
+        expires_at: expires_at.to_rfc3339(),
+        expired,
+        multiple,
+        voters_count: voters_count.max(0),
+        options,
+    })
+}
+
 fn actor_domains_for_blocklist(actor_uri: &str) -> Vec<String> {
     let mut candidates = Vec::new();
 
@@ -199,6 +319,20 @@ fn actor_domains_for_blocklist(actor_uri: &str) -> Vec<String> {
     candidates
 }
 
+/// Check whether an actor's domain, or a parent domain, is blocked
+pub(crate) async fn is_actor_domain_blocked(
+    db: &Database,
+    actor_uri: &str,
+) -> Result<bool, AppError> {
+    for candidate in actor_domains_for_blocklist(actor_uri) {
+        if db.is_domain_blocked(&candidate).await? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
 fn is_local_follow_target(local_address: &str, local_protocol: &str, object: &str) -> bool {
     let object = object.trim();
     if object.is_empty() {
@@ -275,6 +409,10 @@ pub enum ActivityType {
     Like,
     Announce,
     Block,
+    Move,
+    Flag,
+    Add,
+    Remove,
     // Add more as needed
 }
 
@@ -292,6 +430,10 @@ impl ActivityType {
             "Like" => Some(Self::Like),
             "Announce" => Some(Self::Announce),
             "Block" => Some(Self::Block),
+            "Move" => Some(Self::Move),
+            "Flag" => Some(Self::Flag),
+            "Add" => Some(Self::Add),
+            "Remove" => Some(Self::Remove),
             _ => None,
         }
     }
@@ -315,6 +457,7 @@ pub struct ActivityProcessor {
     db: Arc<Database>,
     timeline_cache: Arc<TimelineCache>,
     profile_cache: Arc<ProfileCache>,
+    /// Client for SSRF-validated actor fetches (must not follow redirects)
     http_client: Arc<reqwest::Client>,
     /// Local account address for comparison
     local_address: String,
@@ -322,6 +465,10 @@ pub struct ActivityProcessor {
     local_protocol: String,
     /// Activity delivery service for sending responses
     delivery: Option<Arc<super::ActivityDelivery>>,
+    /// Streaming bus notified about new posts, deletes and notifications
+    streaming: Option<Arc<StreamingBus>>,
+    /// Instance actor whose key signs actor fetches
+    instance_actor: Option<Arc<super::InstanceActor>>,
 }
 
 impl ActivityProcessor {
@@ -342,6 +489,8 @@ impl ActivityProcessor {
             local_address,
             local_protocol,
             delivery: None,
+            streaming: None,
+            instance_actor: None,
         }
     }
 
@@ -353,6 +502,24 @@ impl ActivityProcessor {
         self
     }
 
+    /// Set streaming bus
+    ///
+    /// This allows connected streaming clients to receive followee posts,
+    /// remote deletes and notifications as they arrive.
+    pub fn with_streaming(mut self, streaming: Arc<StreamingBus>) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    /// Set instance actor
+    ///
+    /// Actor fetches are signed with its key so servers running authorized
+    /// fetch answer them.
+    pub fn with_instance_actor(mut self, instance_actor: Arc<super::InstanceActor>) -> Self {
+        self.instance_actor = Some(instance_actor);
+        self
+    }
+
     /// Process an incoming activity
     ///
     /// # Arguments
@@ -382,14 +549,7 @@ impl ActivityProcessor {
         })?;
 
         // 2. Check if domain is blocked
-        let mut actor_is_blocked = false;
-        for candidate in actor_domains_for_blocklist(actor_uri) {
-            if self.db.is_domain_blocked(&candidate).await? {
-                actor_is_blocked = true;
-                break;
-            }
-        }
-        if actor_is_blocked {
+        if is_actor_domain_blocked(&self.db, actor_uri).await? {
             return Err(AppError::Forbidden);
         }
 
@@ -415,11 +575,15 @@ impl ActivityProcessor {
             ActivityType::Delete => self.handle_delete(activity, actor_uri).await,
             ActivityType::Follow => self.handle_follow(activity, actor_uri).await,
             ActivityType::Accept => self.handle_accept(activity, actor_uri).await,
-            ActivityType::Reject => Ok(()), // Ignore for now
+            ActivityType::Reject => self.handle_reject(activity, actor_uri).await,
             ActivityType::Undo => self.handle_undo(activity, actor_uri).await,
             ActivityType::Like => self.handle_like(activity, actor_uri).await,
             ActivityType::Announce => self.handle_announce(activity, actor_uri).await,
-            ActivityType::Block => Ok(()), // Ignore blocks from remote
+            ActivityType::Block => self.handle_block(activity, actor_uri).await,
+            ActivityType::Move => self.handle_move(activity, actor_uri).await,
+            ActivityType::Flag => self.handle_flag(activity, actor_uri).await,
+            ActivityType::Add => self.handle_add(activity, actor_uri).await,
+            ActivityType::Remove => self.handle_remove(activity, actor_uri).await,
         }
     }
 
@@ -495,14 +659,43 @@ impl ActivityProcessor {
                 // Ownership is verified in handle_delete().
                 PersistenceDecision::CacheOnly
             }
+            Some(ActivityType::Update) => {
+                // Edits and profile updates only touch what we already hold.
+                // Ownership is verified in handle_update().
+                PersistenceDecision::CacheOnly
+            }
             Some(ActivityType::Accept) => {
                 // Accept of our Follow -> Persist
                 PersistenceDecision::Persist
             }
+            Some(ActivityType::Reject) => {
+                // Reject of our Follow -> Persist (removes follow)
+                PersistenceDecision::Persist
+            }
             Some(ActivityType::Undo) => {
                 // Undo Follow -> Persist (removes follower)
                 PersistenceDecision::Persist
             }
+            Some(ActivityType::Block) => {
+                // Block of the local account -> Persist (severs relationships)
+                // The handler checks that the local account is the target.
+                PersistenceDecision::Persist
+            }
+            Some(ActivityType::Move) => {
+                // Followee migrated -> Persist (moves our follow)
+                // The handler checks that we follow the actor.
+                PersistenceDecision::Persist
+            }
+            Some(ActivityType::Flag) => {
+                // Report forwarded by another instance -> Persist (report queue)
+                // The handler checks that it is about us.
+                PersistenceDecision::Persist
+            }
+            Some(ActivityType::Add) | Some(ActivityType::Remove) => {
+                // Followee pinned/unpinned a post -> Persist (pinned posts)
+                // The handler checks the target is the actor's featured collection.
+                PersistenceDecision::Persist
+            }
             _ => {
                 // Others -> Ignore
                 PersistenceDecision::Ignore
@@ -532,11 +725,18 @@ impl ActivityProcessor {
             .and_then(|t| t.as_str())
             .unwrap_or("Unknown");
 
-        // We mainly care about Note objects (posts)
-        if object_type != "Note" && object_type != "Article" {
+        // We mainly care about Note objects (posts) and Question polls
+        if !matches!(object_type, "Note" | "Article" | "Question") {
             return Ok(()); // Ignore other object types for now
         }
 
+        // Votes on our polls are Notes with the option title as `name`.
+        if let Some(poll_status_uri) = self.poll_vote_target(object) {
+            return self
+                .handle_poll_vote(object, &poll_status_uri, actor_uri)
+                .await;
+        }
+
         // Extract actor address
         let actor_address = self.extract_actor_address(actor_uri);
         let should_persist_notification =
@@ -561,7 +761,7 @@ impl ActivityProcessor {
                 created_at: chrono::Utc::now(),
             };
 
-            self.db.insert_notification(&notification).await?;
+            self.insert_notification(&notification).await?;
         }
 
         // 4. Check if reply to our post -> create notification
@@ -585,7 +785,7 @@ impl ActivityProcessor {
                             created_at: chrono::Utc::now(),
                         };
 
-                        self.db.insert_notification(&notification).await?;
+                        self.insert_notification(&notification).await?;
                     }
                 }
             }
@@ -600,7 +800,7 @@ impl ActivityProcessor {
                     .and_then(|published| DateTime::parse_from_rfc3339(published).ok())
                     .map(|timestamp| timestamp.with_timezone(&Utc))
                     .unwrap_or_else(Utc::now);
-                let sanitized_content = sanitize_remote_html(
+                let sanitized_content = sanitize_status_html(
                     object
                         .get("content")
                         .and_then(|content| content.as_str())
@@ -613,33 +813,249 @@ impl ActivityProcessor {
                     content: sanitized_content,
                     account_address: actor_address,
                     created_at,
-                    visibility: self.extract_visibility(object),
+                    visibility: extract_visibility(object),
                     attachments: self.extract_cached_attachments(object),
                     reply_to_uri: object
                         .get("inReplyTo")
                         .and_then(|reply| reply.as_str())
                         .map(str::to_string),
                     boost_of_uri: None,
+                    content_warning: extract_content_warning(object),
                 };
+                self.publish(StreamEvent::Update(cached.to_status()));
                 self.timeline_cache.insert(cached).await;
             }
         }
 
+        if object_type == "Question" {
+            self.store_remote_poll(object, actor_uri).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Local status a poll vote Note replies to, if the object is one
+    ///
+    /// Votes carry a `name` and no `content`, unlike ordinary replies.
+    fn poll_vote_target(&self, object: &serde_json::Value) -> Option<String> {
+        if object
+            .get("type")
+            .and_then(|object_type| object_type.as_str())
+            != Some("Note")
+        {
+            return None;
+        }
+        object.get("name").and_then(|name| name.as_str())?;
+        let has_content = object
+            .get("content")
+            .and_then(|content| content.as_str())
+            .is_some_and(|content| !content.trim().is_empty());
+        if has_content {
+            return None;
+        }
+        let in_reply_to = object.get("inReplyTo").and_then(|reply| reply.as_str())?;
+        self.is_local_status(in_reply_to)
+            .then(|| in_reply_to.to_string())
+    }
+
+    /// Record a remote vote on a local poll
+    ///
+    /// Votes for unknown options or on closed polls are dropped.
+    async fn handle_poll_vote(
+        &self,
+        object: &serde_json::Value,
+        status_uri: &str,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let choice = object
+            .get("name")
+            .and_then(|name| name.as_str())
+            .unwrap_or_default();
+        let Some(status) = self
+            .db
+            .get_status_by_uri(status_uri)
+            .await?
+            .filter(|status| status.is_local)
+        else {
+            return Ok(());
+        };
+        let Some((poll_id, ..)) = self.db.get_poll_by_status_id(&status.id).await? else {
+            return Ok(());
+        };
+        let options = self.db.get_poll_options(&poll_id).await?;
+        let Some((option_id, ..)) = options.iter().find(|(_, title, _)| title == choice) else {
+            tracing::debug!(
+                "Vote for unknown option {:?} on poll {}, ignoring",
+                choice,
+                poll_id
+            );
+            return Ok(());
+        };
+
+        let voter_address = self.extract_actor_address(actor_uri);
+        match self
+            .db
+            .add_remote_poll_vote(&poll_id, &voter_address, option_id)
+            .await
+        {
+            Ok(()) => Ok(()),
+            Err(AppError::Validation(reason)) => {
+                tracing::debug!(
+                    "Rejected vote by {} on poll {}: {}",
+                    voter_address,
+                    poll_id,
+                    reason
+                );
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Save or refresh the poll of a remote Question
+    ///
+    /// A poll already stored for another author is left untouched.
+    async fn store_remote_poll(
+        &self,
+        object: &serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let actor_address = self.extract_actor_address(actor_uri);
+        let Some(poll) = parse_question(object, &actor_address) else {
+            return Ok(());
+        };
+
+        if let Some((_, owner_address)) = self.db.get_remote_poll_by_uri(&poll.uri).await? {
+            let actor_scheme = url::Url::parse(actor_uri)
+                .ok()
+                .map(|url| url.scheme().to_ascii_lowercase());
+            if !follow_addresses_match(&actor_address, &owner_address, actor_scheme.as_deref()) {
+                tracing::debug!(
+                    "Actor {} does not own poll {}, ignoring",
+                    actor_address,
+                    poll.uri
+                );
+                return Ok(());
+            }
+        } else if self.is_local_status(&poll.uri) {
+            return Ok(());
+        }
+
+        self.db.upsert_remote_poll(&poll).await?;
         Ok(())
     }
 
-    /// Handle Update activity (profile update)
+    /// Handle Update activity (edited post or profile update)
     async fn handle_update(
         &self,
         activity: serde_json::Value,
         actor_uri: &str,
     ) -> Result<(), AppError> {
+        let object_type = activity
+            .get("object")
+            .and_then(|object| object.get("type"))
+            .and_then(|object_type| object_type.as_str());
+        if matches!(object_type, Some("Note" | "Article" | "Question")) {
+            return self.handle_status_update(&activity, actor_uri).await;
+        }
+
         self.profile_cache
             .update_from_activity(actor_uri, activity)
             .await;
         Ok(())
     }
 
+    /// Apply a remote edit to the cached and persisted copies of a status
+    ///
+    /// Only the author may edit a status. Persisted copies keep the previous
+    /// revision in their edit history, and known polls take the new tallies.
+    async fn handle_status_update(
+        &self,
+        activity: &serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let object = &activity["object"];
+        let status_uri = object
+            .get("id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| AppError::Validation("Missing object id in Update".to_string()))?;
+        if object
+            .get("type")
+            .and_then(|object_type| object_type.as_str())
+            == Some("Question")
+            && self.db.get_remote_poll_by_uri(status_uri).await?.is_some()
+        {
+            self.store_remote_poll(object, actor_uri).await?;
+        }
+        let actor_address = self.extract_actor_address(actor_uri);
+        let actor_scheme = url::Url::parse(actor_uri)
+            .ok()
+            .map(|url| url.scheme().to_ascii_lowercase());
+        let content = sanitize_status_html(
+            object
+                .get("content")
+                .and_then(|content| content.as_str())
+                .unwrap_or_default(),
+        );
+        let content_warning = extract_content_warning(object);
+
+        if let Some(cached_status) = self.timeline_cache.get_by_uri(status_uri).await {
+            if follow_addresses_match(
+                &actor_address,
+                &cached_status.account_address,
+                actor_scheme.as_deref(),
+            ) {
+                let updated = CachedStatus {
+                    content: content.clone(),
+                    content_warning: content_warning.clone(),
+                    attachments: self.extract_cached_attachments(object),
+                    ..(*cached_status).clone()
+                };
+                self.publish(StreamEvent::StatusUpdate(updated.to_status()));
+                self.timeline_cache.insert(updated).await;
+            } else {
+                tracing::debug!(
+                    "Update actor {} does not own cached status {}, ignoring",
+                    actor_address,
+                    status_uri
+                );
+            }
+        }
+
+        if let Some(status) = self.db.get_status_by_uri(status_uri).await? {
+            if status.is_local {
+                return Ok(());
+            }
+            if !follow_addresses_match(
+                &actor_address,
+                &status.account_address,
+                actor_scheme.as_deref(),
+            ) {
+                tracing::debug!(
+                    "Update actor {} does not own persisted status {}, ignoring",
+                    actor_address,
+                    status_uri
+                );
+                return Ok(());
+            }
+
+            if status.content != content || status.content_warning != content_warning {
+                let updated = crate::data::Status {
+                    content,
+                    content_warning,
+                    fetched_at: Some(Utc::now()),
+                    ..status.clone()
+                };
+                self.db
+                    .update_status_with_edit_snapshot(&status, &updated)
+                    .await?;
+                self.publish(StreamEvent::StatusUpdate(updated));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Handle Delete activity
     async fn handle_delete(
         &self,
@@ -661,6 +1077,7 @@ impl ActivityProcessor {
                     actor_scheme.as_deref(),
                 ) {
                     self.timeline_cache.remove_by_uri(&uri).await;
+                    self.publish(StreamEvent::Delete(cached_status.id.clone()));
                 } else {
                     tracing::debug!(
                         "Delete actor {} does not own cached status {}, ignoring",
@@ -679,6 +1096,7 @@ impl ActivityProcessor {
                     )
                 {
                     self.db.delete_status(&status.id).await?;
+                    self.publish(StreamEvent::Delete(status.id.clone()));
                 } else if !status.is_local {
                     tracing::debug!(
                         "Delete actor {} does not own persisted status {}, ignoring",
@@ -692,6 +1110,264 @@ impl ActivityProcessor {
         Ok(())
     }
 
+    /// Handle Move activity (account migration)
+    ///
+    /// Only Moves of an actor we follow are applied, and only when the new
+    /// actor lists the old one in `alsoKnownAs`.
+    async fn handle_move(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let origin_uri = extract_object_reference(activity.get("object"))
+            .ok_or_else(|| AppError::Validation("Missing object in Move".to_string()))?;
+        if origin_uri != actor_uri {
+            return Err(AppError::Validation(
+                "Move object must be the sending actor".to_string(),
+            ));
+        }
+        let target_uri = extract_object_reference(activity.get("target"))
+            .ok_or_else(|| AppError::Validation("Missing target in Move".to_string()))?;
+
+        let origin_address = self.extract_actor_address(actor_uri);
+        let origin_default_port = url::Url::parse(actor_uri)
+            .ok()
+            .and_then(|url| default_port_for_scheme(url.scheme()));
+        if self
+            .db
+            .get_follow_uri(&origin_address, origin_default_port)
+            .await?
+            .is_none()
+        {
+            tracing::debug!("Ignoring Move of {} because it is not followed", actor_uri);
+            return Ok(());
+        }
+
+        let signer = self.fetch_signer().await?;
+        let target_document =
+            super::webfinger::fetch_actor(&target_uri, &self.http_client, signer.as_ref()).await?;
+        let target = super::webfinger::parse_actor(&target_document)?;
+        if target.id != target_uri || !target.also_known_as.iter().any(|alias| alias == actor_uri) {
+            return Err(AppError::Validation(
+                "Move target does not list the moved actor in alsoKnownAs".to_string(),
+            ));
+        }
+
+        self.move_follow(&origin_address, origin_default_port, &target)
+            .await
+    }
+
+    /// Replace our follow of a migrated account with a follow of its new actor
+    async fn move_follow(
+        &self,
+        origin_address: &str,
+        origin_default_port: Option<u16>,
+        target: &super::ParsedActor,
+    ) -> Result<(), AppError> {
+        let target_address = self.extract_actor_address(&target.id);
+        let target_default_port = url::Url::parse(&target.id)
+            .ok()
+            .and_then(|url| default_port_for_scheme(url.scheme()));
+        let follow_id = crate::data::EntityId::new().0;
+        let follow = crate::data::Follow {
+            id: follow_id.clone(),
+            target_address: target_address.clone(),
+            uri: format!("{}/follow/{}", self.local_actor_uri(), follow_id),
+            created_at: Utc::now(),
+        };
+
+        self.db
+            .delete_follow(origin_address, origin_default_port)
+            .await?;
+        if !self
+            .db
+            .insert_follow_if_absent(&follow, target_default_port)
+            .await?
+        {
+            // Already following the new account
+            return Ok(());
+        }
+
+        if let Some(ref delivery) = self.delivery {
+            if let Err(error) = delivery
+                .send_follow_with_id(&follow.uri, &target.id, &target.inbox)
+                .await
+            {
+                tracing::error!(
+                    "Failed to send Follow to moved account {}: {}",
+                    target_address,
+                    error
+                );
+            }
+        } else {
+            tracing::warn!("No delivery service configured, cannot follow moved account");
+        }
+
+        tracing::info!("Moved follow from {} to {}", origin_address, target_address);
+        Ok(())
+    }
+
+    /// Handle Add activity (remote post pinned)
+    ///
+    /// Only pins by followed actors to their own `featured` collection are
+    /// kept.
+    async fn handle_add(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        if !self
+            .targets_featured_collection(&activity, actor_uri)
+            .await?
+        {
+            return Ok(());
+        }
+        if !self.is_followee(actor_uri).await {
+            tracing::debug!("Ignoring Add from {} because it is not followed", actor_uri);
+            return Ok(());
+        }
+
+        let object = match activity.get("object") {
+            Some(object) if object.is_object() => object.clone(),
+            Some(serde_json::Value::String(object_uri)) => {
+                let signer = self.fetch_signer().await?;
+                super::featured::fetch_object(object_uri, &self.http_client, signer.as_ref())
+                    .await?
+            }
+            _ => return Err(AppError::Validation("Missing object in Add".to_string())),
+        };
+
+        let actor_address = self.extract_actor_address(actor_uri);
+        let Some(status) = super::featured_status_from_object(&object, actor_uri, &actor_address)
+        else {
+            return Err(AppError::Validation(
+                "Add object must be a post by the actor".to_string(),
+            ));
+        };
+
+        self.db.insert_remote_status_pin(&status).await?;
+        Ok(())
+    }
+
+    /// Handle Remove activity (remote post unpinned)
+    async fn handle_remove(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        if !self
+            .targets_featured_collection(&activity, actor_uri)
+            .await?
+        {
+            return Ok(());
+        }
+
+        let object_uri = extract_object_reference(activity.get("object"))
+            .ok_or_else(|| AppError::Validation("Missing object in Remove".to_string()))?;
+        let actor_address = self.extract_actor_address(actor_uri);
+        self.db
+            .delete_remote_status_pin(&actor_address, &object_uri)
+            .await
+    }
+
+    /// Whether an Add/Remove targets the actor's `featured` collection
+    ///
+    /// The usual `{actor}/collections/featured` location is accepted as is;
+    /// any other target is checked against the actor document.
+    async fn targets_featured_collection(
+        &self,
+        activity: &serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<bool, AppError> {
+        let Some(target) = extract_object_reference(activity.get("target")) else {
+            return Ok(false);
+        };
+        if target == super::featured_collection_uri(actor_uri) {
+            return Ok(true);
+        }
+
+        let signer = self.fetch_signer().await?;
+        let actor_document =
+            super::webfinger::fetch_actor(actor_uri, &self.http_client, signer.as_ref()).await?;
+        let actor = super::webfinger::parse_actor(&actor_document)?;
+        Ok(actor.featured.as_deref() == Some(target.as_str()))
+    }
+
+    /// Signer for fetches, when the instance actor is configured
+    async fn fetch_signer(&self) -> Result<Option<super::FetchSigner>, AppError> {
+        match &self.instance_actor {
+            Some(instance_actor) => Ok(Some(instance_actor.fetch_signer().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Handle Flag activity (report forwarded by another instance)
+    ///
+    /// Only reports about the local account or its statuses are kept.
+    /// Redelivered Flags are recognised by their activity ID.
+    async fn handle_flag(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let objects: Vec<String> = match activity.get("object") {
+            Some(serde_json::Value::Array(objects)) => objects
+                .iter()
+                .filter_map(|object| extract_object_reference(Some(object)))
+                .collect(),
+            object => extract_object_reference(object).into_iter().collect(),
+        };
+        let local_actor_uri = self.local_actor_uri();
+        let status_uris: Vec<String> = objects
+            .iter()
+            .filter(|uri| **uri != local_actor_uri && self.is_local_status(uri))
+            .cloned()
+            .collect();
+        if !objects.contains(&local_actor_uri) && status_uris.is_empty() {
+            tracing::debug!("Ignoring Flag from {} about another server", actor_uri);
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let reporter_address = self.extract_actor_address(actor_uri);
+        let report = crate::data::Report {
+            id: crate::data::EntityId::new().0,
+            uri: activity
+                .get("id")
+                .and_then(|id| id.as_str())
+                .map(str::to_string),
+            account_address: reporter_address.clone(),
+            target_account_address: self.local_address.clone(),
+            status_uris: serde_json::to_string(&status_uris)
+                .map_err(|error| AppError::Internal(error.into()))?,
+            comment: activity
+                .get("content")
+                .and_then(|content| content.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            category: "other".to_string(),
+            forwarded: false,
+            assigned: false,
+            action_taken: false,
+            action_taken_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        if !self.db.insert_report(&report).await? {
+            return Ok(());
+        }
+
+        let notification = crate::data::Notification {
+            id: crate::data::EntityId::new().0,
+            notification_type: "admin.report".to_string(),
+            origin_account_address: reporter_address,
+            status_uri: None,
+            read: false,
+            created_at: now,
+        };
+        self.insert_notification(&notification).await
+    }
+
     /// Handle Follow activity
     async fn handle_follow(
         &self,
@@ -753,7 +1429,7 @@ impl ActivityProcessor {
             created_at: chrono::Utc::now(),
         };
 
-        self.db.insert_notification(&notification).await?;
+        self.insert_notification(&notification).await?;
 
         // 5. Send Accept activity
         if let Some(ref delivery) = self.delivery {
@@ -794,8 +1470,12 @@ impl ActivityProcessor {
         Ok(())
     }
 
-    /// Handle Undo activity
-    async fn handle_undo(
+    /// Handle Reject activity
+    ///
+    /// A rejected Follow is removed from `follows`. When the Follow carries
+    /// an ID, only the row with that URI is removed, so a stale Reject does
+    /// not drop a newer follow.
+    async fn handle_reject(
         &self,
         activity: serde_json::Value,
         actor_uri: &str,
@@ -805,10 +1485,110 @@ impl ActivityProcessor {
             .ok()
             .and_then(|url| default_port_for_scheme(url.scheme()));
 
-        // 1. Get the undone activity
-        let object = activity.get("object");
+        let Some(object) = activity.get("object") else {
+            return Ok(());
+        };
+        let follow_uri = match object.get("type").and_then(|t| t.as_str()) {
+            Some("Follow") => {
+                let follower = extract_object_reference(object.get("actor"));
+                if follower.is_some_and(|follower| {
+                    !is_local_follow_target(&self.local_address, &self.local_protocol, &follower)
+                }) {
+                    tracing::debug!("Reject Follow actor is not local actor, ignoring");
+                    return Ok(());
+                }
+                object.get("id").and_then(|id| id.as_str())
+            }
+            Some(_) => return Ok(()),
+            None => object.as_str(),
+        };
 
-        if let Some(obj) = object {
+        if let Some(follow_uri) = follow_uri {
+            let removed = self
+                .db
+                .delete_follow_by_address_and_uri(&actor_address, follow_uri, actor_default_port)
+                .await?;
+            if removed {
+                tracing::info!(
+                    "Follow of {} rejected via Follow activity URI {}",
+                    actor_address,
+                    follow_uri
+                );
+            } else {
+                tracing::debug!(
+                    "Reject did not match follow row for actor {}, uri {}",
+                    actor_address,
+                    follow_uri
+                );
+            }
+        } else {
+            // Fallback for minimal Reject payloads that omit Follow.id.
+            self.db
+                .delete_follow(&actor_address, actor_default_port)
+                .await?;
+            tracing::info!("Follow of {} rejected via address fallback", actor_address);
+        }
+
+        Ok(())
+    }
+
+    /// Handle Block activity
+    ///
+    /// Drops the follow relationships in both directions, records the block
+    /// so nothing more is delivered to the actor, and removes the actor's
+    /// cached statuses.
+    async fn handle_block(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let Some(target) = extract_object_reference(activity.get("object")) else {
+            tracing::debug!("Block missing target object, ignoring");
+            return Ok(());
+        };
+        if !is_local_follow_target(&self.local_address, &self.local_protocol, &target) {
+            tracing::debug!("Block target is not local actor, ignoring");
+            return Ok(());
+        }
+
+        let actor_address = self.extract_actor_address(actor_uri);
+        let actor_default_port = url::Url::parse(actor_uri)
+            .ok()
+            .and_then(|url| default_port_for_scheme(url.scheme()));
+
+        self.db
+            .insert_blocked_by(&actor_address, actor_default_port)
+            .await?;
+        self.db
+            .delete_follower(&actor_address, actor_default_port)
+            .await?;
+        self.db
+            .delete_follow(&actor_address, actor_default_port)
+            .await?;
+        self.db
+            .reject_follow_request_with_default_port(&actor_address, actor_default_port)
+            .await?;
+        self.timeline_cache.remove_by_account(&actor_address).await;
+        tracing::info!("Blocked by {}", actor_address);
+
+        Ok(())
+    }
+
+    /// Handle Undo activity
+    async fn handle_undo(
+        &self,
+        activity: serde_json::Value,
+        actor_uri: &str,
+    ) -> Result<(), AppError> {
+        let actor_address = self.extract_actor_address(actor_uri);
+        let actor_default_port = url::Url::parse(actor_uri)
+            .ok()
+            .and_then(|url| default_port_for_scheme(url.scheme()));
+
+        // 1. Get the undone activity
+        let object = activity.get("object");
+
+        if let Some(obj) = object {
             // Check the type of the undone activity
             if let Some(obj_type) = obj.get("type").and_then(|t| t.as_str()) {
                 match obj_type {
@@ -857,19 +1637,66 @@ impl ActivityProcessor {
                         }
                         Ok(())
                     }
+                    "Block" => {
+                        if self
+                            .db
+                            .delete_blocked_by(&actor_address, actor_default_port)
+                            .await?
+                        {
+                            tracing::info!("Unblocked by {}", actor_address);
+                        }
+                        Ok(())
+                    }
                     "Like" | "Announce" => {
-                        // Could remove notification, but for simplicity just ignore
+                        let interaction_type = if obj_type == "Like" {
+                            "favourite"
+                        } else {
+                            "reblog"
+                        };
+                        let mut removed = match obj.get("id").and_then(|id| id.as_str()) {
+                            Some(activity_uri) => {
+                                self.db
+                                    .delete_status_interaction_by_activity_uri(
+                                        activity_uri,
+                                        &actor_address,
+                                    )
+                                    .await?
+                            }
+                            None => None,
+                        };
+                        if removed.is_none() {
+                            // Fallback for Undo payloads that omit or change the activity ID.
+                            if let Some(status_uri) = extract_object_reference(obj.get("object")) {
+                                removed = self
+                                    .db
+                                    .delete_status_interaction(
+                                        interaction_type,
+                                        &actor_address,
+                                        &status_uri,
+                                    )
+                                    .await?;
+                            }
+                        }
+                        if let Some(interaction) = removed {
+                            tracing::info!(
+                                "Removed {} of {} by {}",
+                                interaction.interaction_type,
+                                interaction.status_uri,
+                                actor_address
+                            );
+                        }
                         Ok(())
                     }
                     _ => Ok(()),
                 }
-            } else if let Some(follow_uri) = obj.as_str() {
-                // Compact Undo representation where object is the Follow activity URI.
+            } else if let Some(activity_uri) = obj.as_str() {
+                // Compact Undo representation where object is the undone activity URI,
+                // either a Follow or a Like/Announce of a local status.
                 let removed = self
                     .db
                     .delete_follower_by_address_and_uri(
                         &actor_address,
-                        follow_uri,
+                        activity_uri,
                         actor_default_port,
                     )
                     .await?;
@@ -877,13 +1704,25 @@ impl ActivityProcessor {
                     tracing::info!(
                         "Unfollowed by {} via follow activity URI {}",
                         actor_address,
-                        follow_uri
+                        activity_uri
+                    );
+                } else if let Some(interaction) = self
+                    .db
+                    .delete_status_interaction_by_activity_uri(activity_uri, &actor_address)
+                    .await?
+                {
+                    tracing::info!(
+                        "Removed {} of {} by {} via activity URI {}",
+                        interaction.interaction_type,
+                        interaction.status_uri,
+                        actor_address,
+                        activity_uri
                     );
                 } else {
                     tracing::debug!(
-                        "Undo with URI object did not match follower row for actor {}, uri {}",
+                        "Undo with URI object did not match follower row or interaction for actor {}, uri {}",
                         actor_address,
-                        follow_uri
+                        activity_uri
                     );
                 }
                 Ok(())
@@ -915,19 +1754,9 @@ impl ActivityProcessor {
         // Extract actor address
         let actor_address = self.extract_actor_address(actor_uri);
 
-        // 2. Create notification
-        let notification = crate::data::Notification {
-            id: crate::data::EntityId::new().0,
-            notification_type: "favourite".to_string(),
-            origin_account_address: actor_address,
-            status_uri: Some(object.to_string()),
-            read: false,
-            created_at: chrono::Utc::now(),
-        };
-
-        self.db.insert_notification(&notification).await?;
-
-        Ok(())
+        // 2. Record the favourite and notify
+        self.insert_status_interaction(&activity, "favourite", actor_address, object)
+            .await
     }
 
     /// Handle Announce activity (boost)
@@ -964,24 +1793,16 @@ impl ActivityProcessor {
                     created_at: chrono::Utc::now(),
                 };
 
-                self.db.insert_notification(&notification).await?;
+                self.insert_notification(&notification).await?;
             }
             // If quote doesn't mention us, ignore (future: could cache if from followee)
         } else if let Some(object_uri) = object.as_str() {
             // Regular boost: just a URI reference
             // Check if it's our status being boosted
             if self.is_local_status(object_uri) {
-                // Create reblog notification for boost of our status
-                let notification = crate::data::Notification {
-                    id: crate::data::EntityId::new().0,
-                    notification_type: "reblog".to_string(),
-                    origin_account_address: actor_address,
-                    status_uri: Some(object_uri.to_string()),
-                    read: false,
-                    created_at: chrono::Utc::now(),
-                };
-
-                self.db.insert_notification(&notification).await?;
+                // Record the boost of our status and notify
+                self.insert_status_interaction(&activity, "reblog", actor_address, object_uri)
+                    .await?;
             }
             // If boosting someone else's status, ignore (future: could cache if from followee)
         }
@@ -993,41 +1814,73 @@ impl ActivityProcessor {
     // Helpers
     // =========================================================================
 
-    fn extract_visibility(&self, object: &serde_json::Value) -> String {
-        const PUBLIC_AUDIENCE: &str = "https://www.w3.org/ns/activitystreams#Public";
+    fn publish(&self, event: StreamEvent) {
+        if let Some(streaming) = &self.streaming {
+            streaming.publish(event);
+        }
+    }
 
-        let contains_public = |audience: &serde_json::Value| -> bool {
-            if let Some(value) = audience.as_str() {
-                return value == PUBLIC_AUDIENCE;
-            }
-            audience
-                .as_array()
-                .map(|entries| {
-                    entries
-                        .iter()
-                        .filter_map(serde_json::Value::as_str)
-                        .any(|value| value == PUBLIC_AUDIENCE)
-                })
-                .unwrap_or(false)
+    /// Persist a notification and push it to streaming clients
+    async fn insert_notification(
+        &self,
+        notification: &crate::data::Notification,
+    ) -> Result<(), AppError> {
+        self.db.insert_notification(notification).await?;
+        self.publish(StreamEvent::Notification(notification.clone()));
+        Ok(())
+    }
+
+    /// Record a favourite or boost of a local status
+    ///
+    /// The notification is only created the first time, so redelivered
+    /// activities do not notify twice.
+    async fn insert_status_interaction(
+        &self,
+        activity: &serde_json::Value,
+        interaction_type: &str,
+        actor_address: String,
+        status_uri: &str,
+    ) -> Result<(), AppError> {
+        let now = Utc::now();
+        let notification = crate::data::Notification {
+            id: crate::data::EntityId::new().0,
+            notification_type: interaction_type.to_string(),
+            origin_account_address: actor_address.clone(),
+            status_uri: Some(status_uri.to_string()),
+            read: false,
+            created_at: now,
+        };
+        let interaction = crate::data::StatusInteraction {
+            id: crate::data::EntityId::new().0,
+            activity_uri: activity
+                .get("id")
+                .and_then(|id| id.as_str())
+                .map(str::to_string),
+            interaction_type: interaction_type.to_string(),
+            account_address: actor_address,
+            status_uri: status_uri.to_string(),
+            notification_id: Some(notification.id.clone()),
+            created_at: now,
         };
 
-        if object.get("to").is_some_and(contains_public) {
-            "public".to_string()
-        } else if object.get("cc").is_some_and(contains_public) {
-            "unlisted".to_string()
-        } else {
-            "private".to_string()
+        if self
+            .db
+            .insert_status_interaction(&interaction, &notification)
+            .await?
+        {
+            self.publish(StreamEvent::Notification(notification));
         }
+        Ok(())
     }
 
     fn extract_cached_attachments(&self, object: &serde_json::Value) -> Vec<CachedAttachment> {
         let mut attachments = Vec::new();
 
-        let Some(values) = object
-            .get("attachment")
-            .and_then(serde_json::Value::as_array)
-        else {
-            return attachments;
+        // `attachment` may be a single object or link rather than an array
+        let values = match object.get("attachment") {
+            Some(serde_json::Value::Array(values)) => values.clone(),
+            Some(value) => vec![value.clone()],
+            None => return attachments,
         };
 
         for value in values {
@@ -1065,6 +1918,15 @@ impl ActivityProcessor {
         attachments
     }
 
+    /// Actor URI of the local account
+    fn local_actor_uri(&self) -> String {
+        let (username, domain) = self
+            .local_address
+            .split_once('@')
+            .unwrap_or((self.local_address.as_str(), ""));
+        super::local_actor_uri(&format!("{}://{}", self.local_protocol, domain), username)
+    }
+
     /// Extract actor address from actor URI
     /// Example: https://example.com/users/alice -> alice@example.com
     fn extract_actor_address(&self, actor_uri: &str) -> String {
@@ -1169,10 +2031,12 @@ impl ActivityProcessor {
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_follow_target, is_local_follow_target};
+    use super::{
+        extract_follow_target, is_local_follow_target, parse_question, sanitize_status_html,
+    };
     use crate::data::{
-        CachedProfile, CachedStatus, Database, EntityId, Follow, Follower, ProfileCache,
-        TimelineCache,
+        CachedAttachment, CachedProfile, CachedStatus, Database, EntityId, Follow, Follower,
+        ProfileCache, TimelineCache,
     };
     use crate::error::AppError;
     use chrono::Utc;
@@ -1234,6 +2098,15 @@ mod tests {
         (processor, db, temp_dir)
     }
 
+    #[test]
+    fn sanitize_status_html_keeps_mention_classes_only() {
+        let html = r#"<p><span class="h-card"><a href="https://remote.example/@bob" class="u-url mention evil">@<span>bob</span></a></span><script>alert(1)</script><b onclick="x()">hi</b></p>"#;
+        assert_eq!(
+            sanitize_status_html(html),
+            r#"<p><span class="h-card"><a href="https://remote.example/@bob" class="u-url mention" rel="noopener noreferrer">@<span>bob</span></a></span><b>hi</b></p>"#
+        );
+    }
+
     #[test]
     fn is_local_follow_target_accepts_local_address_forms() {
         let local = "alice@example.com";
@@ -1518,18 +2391,487 @@ mod tests {
             }
         });
 
-        processor.handle_update(activity, actor_uri).await.unwrap();
+        processor.handle_update(activity, actor_uri).await.unwrap();
+
+        let updated = profile_cache
+            .get("bob@remote.example")
+            .await
+            .expect("profile should exist");
+        assert_eq!(updated.display_name.as_deref(), Some("Bob Updated"));
+        assert_eq!(updated.note.as_deref(), Some("after"));
+        assert_eq!(updated.public_key_pem, "new-key");
+        assert_eq!(updated.inbox_uri, "https://remote.example/inbox-new");
+        assert_eq!(updated.followers_count, Some(10));
+        assert_eq!(updated.following_count, Some(20));
+    }
+
+    fn remote_status(status_uri: &str, account_address: &str) -> crate::data::Status {
+        crate::data::Status {
+            id: EntityId::new().0,
+            uri: status_uri.to_string(),
+            content: "<p>Before edit</p>".to_string(),
+            content_warning: None,
+            visibility: "public".to_string(),
+            language: Some("en".to_string()),
+            account_address: account_address.to_string(),
+            is_local: false,
+            in_reply_to_uri: None,
+            boost_of_uri: None,
+            persisted_reason: "bookmarked".to_string(),
+            created_at: Utc::now(),
+            fetched_at: Some(Utc::now()),
+        }
+    }
+
+    fn update_note_activity(actor_uri: &str, status_uri: &str) -> serde_json::Value {
+        json!({
+            "type": "Update",
+            "actor": actor_uri,
+            "object": {
+                "type": "Note",
+                "id": status_uri,
+                "summary": "edited",
+                "content": "<p>After edit</p><script>alert(1)</script>",
+                "published": "2026-01-01T00:00:00Z",
+                "updated": "2026-01-02T00:00:00Z",
+                "to": ["https://www.w3.org/ns/activitystreams#Public"]
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn process_update_note_rewrites_cached_status() {
+        let (processor, _db, timeline_cache, _temp_dir) =
+            create_test_processor_with_timeline("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://remote.example/users/bob/statuses/edit-cached";
+
+        timeline_cache
+            .insert(CachedStatus {
+                id: status_uri.to_string(),
+                uri: status_uri.to_string(),
+                content: "<p>Before edit</p>".to_string(),
+                account_address: "bob@remote.example".to_string(),
+                created_at: Utc::now(),
+                visibility: "public".to_string(),
+                attachments: vec![CachedAttachment {
+                    url: "https://remote.example/media/old.png".to_string(),
+                    thumbnail_url: None,
+                    content_type: "image/png".to_string(),
+                    description: None,
+                    blurhash: None,
+                }],
+                reply_to_uri: None,
+                boost_of_uri: None,
+                content_warning: None,
+            })
+            .await;
+
+        let mut activity = update_note_activity(actor_uri, status_uri);
+        activity["object"]["attachment"] = json!({
+            "type": "Document",
+            "mediaType": "image/jpeg",
+            "url": "https://remote.example/media/new.jpg",
+            "name": "new alt"
+        });
+        processor.process(activity, actor_uri).await.unwrap();
+
+        let cached = timeline_cache.get_by_uri(status_uri).await.unwrap();
+        assert_eq!(cached.content, "<p>After edit</p>");
+        assert_eq!(cached.visibility, "public");
+        assert_eq!(cached.content_warning.as_deref(), Some("edited"));
+        assert_eq!(
+            cached.to_status().content_warning.as_deref(),
+            Some("edited")
+        );
+        assert_eq!(cached.attachments.len(), 1);
+        assert_eq!(
+            cached.attachments[0].url,
+            "https://remote.example/media/new.jpg"
+        );
+        assert_eq!(
+            cached.attachments[0].description.as_deref(),
+            Some("new alt")
+        );
+
+        // An edit without attachments removes them
+        processor
+            .process(update_note_activity(actor_uri, status_uri), actor_uri)
+            .await
+            .unwrap();
+        let cached = timeline_cache.get_by_uri(status_uri).await.unwrap();
+        assert!(cached.attachments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_update_note_records_edit_of_persisted_status() {
+        let (processor, db, _timeline_cache, _temp_dir) =
+            create_test_processor_with_timeline("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://remote.example/users/bob/statuses/edit-persisted";
+        let status = remote_status(status_uri, "bob@remote.example");
+        db.insert_status(&status).await.unwrap();
+
+        processor
+            .process(update_note_activity(actor_uri, status_uri), actor_uri)
+            .await
+            .unwrap();
+
+        let updated = db.get_status_by_uri(status_uri).await.unwrap().unwrap();
+        assert_eq!(updated.content, "<p>After edit</p>");
+        assert_eq!(updated.content_warning.as_deref(), Some("edited"));
+
+        let edits = db.get_status_edits(&status.id, 10).await.unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].1, "<p>Before edit</p>");
+        assert_eq!(edits[0].2, None);
+    }
+
+    #[tokio::test]
+    async fn process_update_note_does_not_edit_status_owned_by_another_actor() {
+        let (processor, db, _timeline_cache, _temp_dir) =
+            create_test_processor_with_timeline("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://another.example/users/carol/statuses/edit-foreign";
+        let status = remote_status(status_uri, "carol@another.example");
+        db.insert_status(&status).await.unwrap();
+
+        processor
+            .process(update_note_activity(actor_uri, status_uri), actor_uri)
+            .await
+            .unwrap();
+
+        let unchanged = db.get_status_by_uri(status_uri).await.unwrap().unwrap();
+        assert_eq!(unchanged.content, "<p>Before edit</p>");
+        assert!(
+            db.get_status_edits(&status.id, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn question_activity(
+        activity_type: &str,
+        actor_uri: &str,
+        question_uri: &str,
+        yes_votes: i64,
+    ) -> serde_json::Value {
+        json!({
+            "type": activity_type,
+            "actor": actor_uri,
+            "object": {
+                "type": "Question",
+                "id": question_uri,
+                "content": "<p>Ship it?</p>",
+                "published": "2026-01-01T00:00:00Z",
+                "endTime": "2999-01-01T00:00:00Z",
+                "votersCount": yes_votes + 1,
+                "oneOf": [
+                    {"type": "Note", "name": "Yes", "replies": {"type": "Collection", "totalItems": yes_votes}},
+                    {"type": "Note", "name": "No", "replies": {"type": "Collection", "totalItems": 1}}
+                ],
+                "to": ["https://www.w3.org/ns/activitystreams#Public"]
+            }
+        })
+    }
+
+    #[test]
+    fn parse_question_reads_options_and_closed_state() {
+        let mut question = question_activity(
+            "Create",
+            "https://remote.example/users/bob",
+            "https://remote.example/users/bob/statuses/poll",
+            3,
+        )["object"]
+            .clone();
+        let poll = parse_question(&question, "bob@remote.example").unwrap();
+        assert!(!poll.multiple);
+        assert!(!poll.expired);
+        assert_eq!(poll.voters_count, 4);
+        assert_eq!(
+            poll.options,
+            vec![("Yes".to_string(), 3), ("No".to_string(), 1)]
+        );
+
+        question["anyOf"] = question["oneOf"].take();
+        question["closed"] = json!("2026-01-02T00:00:00Z");
+        let poll = parse_question(&question, "bob@remote.example").unwrap();
+        assert!(poll.multiple);
+        assert!(poll.expired);
+    }
+
+    #[tokio::test]
+    async fn process_create_and_update_question_store_remote_poll() {
+        let (processor, db, timeline_cache, _temp_dir) =
+            create_test_processor_with_timeline("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let question_uri = "https://remote.example/users/bob/statuses/poll";
+        db.insert_follow(&Follow {
+            id: EntityId::new().0,
+            target_address: "bob@remote.example".to_string(),
+            uri: "https://example.com/users/alice/follow/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        processor
+            .process(
+                question_activity("Create", actor_uri, question_uri, 3),
+                actor_uri,
+            )
+            .await
+            .unwrap();
+        assert!(timeline_cache.get_by_uri(question_uri).await.is_some());
+        let (poll_id, _) = db
+            .get_remote_poll_by_uri(question_uri)
+            .await
+            .unwrap()
+            .unwrap();
+
+        processor
+            .process(
+                question_activity("Update", actor_uri, question_uri, 8),
+                actor_uri,
+            )
+            .await
+            .unwrap();
+        let options = db.get_poll_options(&poll_id).await.unwrap();
+        assert_eq!(options[0].2, 8);
+        let poll = db.get_poll(&poll_id).await.unwrap().unwrap();
+        assert_eq!(poll.5, 9);
+    }
+
+    #[tokio::test]
+    async fn process_update_question_from_other_actor_keeps_tallies() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let question_uri = "https://remote.example/users/bob/statuses/poll";
+        let poll_id = db
+            .upsert_remote_poll(
+                &parse_question(
+                    &question_activity("Create", "", question_uri, 3)["object"],
+                    "bob@remote.example",
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let mallory_uri = "https://evil.example/users/mallory";
+        processor
+            .process(
+                question_activity("Update", mallory_uri, question_uri, 100),
+                mallory_uri,
+            )
+            .await
+            .unwrap();
+
+        let options = db.get_poll_options(&poll_id).await.unwrap();
+        assert_eq!(options[0].2, 3);
+    }
+
+    fn poll_vote_activity(actor_uri: &str, status_uri: &str, choice: &str) -> serde_json::Value {
+        json!({
+            "type": "Create",
+            "actor": actor_uri,
+            "object": {
+                "type": "Note",
+                "id": format!("{}#votes/{}", actor_uri, EntityId::new().0),
+                "name": choice,
+                "attributedTo": actor_uri,
+                "inReplyTo": status_uri,
+                "to": ["https://example.com/users/alice"]
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn process_create_poll_vote_records_vote_without_notification() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let status_uri = "https://example.com/users/alice/statuses/poll";
+        let mut status = remote_status(status_uri, "");
+        status.is_local = true;
+        status.persisted_reason = "own".to_string();
+        db.insert_status(&status).await.unwrap();
+        let poll_id = db
+            .create_poll(
+                &status.id,
+                &["Yes".to_string(), "No".to_string()],
+                600,
+                false,
+            )
+            .await
+            .unwrap();
+
+        let actor_uri = "https://remote.example/users/bob";
+        processor
+            .process(poll_vote_activity(actor_uri, status_uri, "No"), actor_uri)
+            .await
+            .unwrap();
+        // A second ballot and votes for unknown options are dropped.
+        processor
+            .process(poll_vote_activity(actor_uri, status_uri, "Yes"), actor_uri)
+            .await
+            .unwrap();
+        processor
+            .process(
+                poll_vote_activity(actor_uri, status_uri, "Maybe"),
+                actor_uri,
+            )
+            .await
+            .unwrap();
+
+        let options = db.get_poll_options(&poll_id).await.unwrap();
+        assert_eq!(options[0].2, 0);
+        assert_eq!(options[1].2, 1);
+        assert_eq!(
+            db.get_poll_voter_addresses(&poll_id).await.unwrap(),
+            vec!["bob@remote.example".to_string()]
+        );
+        assert!(
+            db.get_notifications(20, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn process_flag_about_local_account_creates_report_once() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/mod";
+        let activity = json!({
+            "id": "https://remote.example/flags/1",
+            "type": "Flag",
+            "actor": actor_uri,
+            "content": "Spam links",
+            "object": [
+                "https://example.com/users/alice",
+                "https://example.com/users/alice/statuses/1",
+                "https://remote.example/users/bob/statuses/2"
+            ]
+        });
+
+        processor
+            .process(activity.clone(), actor_uri)
+            .await
+            .unwrap();
+        processor.process(activity, actor_uri).await.unwrap();
+
+        let reports = db.get_reports(None, 10, None).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].account_address, "mod@remote.example");
+        assert_eq!(reports[0].target_account_address, "alice@example.com");
+        assert_eq!(reports[0].comment, "Spam links");
+        assert_eq!(
+            reports[0].status_uri_list(),
+            vec!["https://example.com/users/alice/statuses/1".to_string()]
+        );
+
+        let notifications = db.get_notifications(20, None, false).await.unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].notification_type, "admin.report");
+        assert_eq!(
+            notifications[0].origin_account_address,
+            "mod@remote.example"
+        );
+    }
+
+    #[tokio::test]
+    async fn process_flag_about_another_server_is_ignored() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/actor";
+        let activity = json!({
+            "id": "https://remote.example/flags/2",
+            "type": "Flag",
+            "actor": actor_uri,
+            "object": "https://other.example/users/carol"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+
+        assert!(db.get_reports(None, 10, None).await.unwrap().is_empty());
+        assert!(
+            db.get_notifications(20, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn process_move_rejects_object_other_than_actor() {
+        let (processor, _db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let activity = json!({
+            "type": "Move",
+            "actor": actor_uri,
+            "object": "https://remote.example/users/carol",
+            "target": "https://new.example/users/bob"
+        });
+
+        let result = processor.process(activity, actor_uri).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn process_move_of_unfollowed_actor_is_ignored() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let activity = json!({
+            "type": "Move",
+            "actor": actor_uri,
+            "object": actor_uri,
+            "target": "https://new.example/users/bob"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(db.get_all_follow_addresses().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn move_follow_replaces_followed_address() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let follow = Follow {
+            id: EntityId::new().0,
+            target_address: "bob@remote.example".to_string(),
+            uri: "https://example.com/users/alice/follow/move".to_string(),
+            created_at: Utc::now(),
+        };
+        db.insert_follow(&follow).await.unwrap();
+
+        let target = crate::federation::ParsedActor {
+            id: "https://new.example/users/bob".to_string(),
+            username: "bob".to_string(),
+            display_name: None,
+            summary: None,
+            avatar_url: None,
+            header_url: None,
+            inbox: "https://new.example/users/bob/inbox".to_string(),
+            outbox: None,
+            followers: None,
+            following: None,
+            featured: None,
+            public_key_id: "https://new.example/users/bob#main-key".to_string(),
+            public_key_pem: "key".to_string(),
+            also_known_as: vec!["https://remote.example/users/bob".to_string()],
+        };
+        processor
+            .move_follow("bob@remote.example", Some(443), &target)
+            .await
+            .unwrap();
 
-        let updated = profile_cache
-            .get("bob@remote.example")
+        assert_eq!(
+            db.get_all_follow_addresses().await.unwrap(),
+            vec!["bob@new.example".to_string()]
+        );
+        let follow_uri = db
+            .get_follow_uri("bob@new.example", Some(443))
             .await
-            .expect("profile should exist");
-        assert_eq!(updated.display_name.as_deref(), Some("Bob Updated"));
-        assert_eq!(updated.note.as_deref(), Some("after"));
-        assert_eq!(updated.public_key_pem, "new-key");
-        assert_eq!(updated.inbox_uri, "https://remote.example/inbox-new");
-        assert_eq!(updated.followers_count, Some(10));
-        assert_eq!(updated.following_count, Some(20));
+            .unwrap()
+            .unwrap();
+        assert!(follow_uri.starts_with("https://example.com/users/alice/follow/"));
     }
 
     #[tokio::test]
@@ -1742,6 +3084,363 @@ mod tests {
         assert!(follower_addresses.contains(&"bob@remote.example".to_string()));
     }
 
+    fn like_activity(actor_uri: &str, id: &str, status_uri: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "type": "Like",
+            "actor": actor_uri,
+            "object": status_uri
+        })
+    }
+
+    #[tokio::test]
+    async fn process_like_redelivery_notifies_once() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://example.com/users/alice/statuses/1";
+        let like = like_activity(actor_uri, "https://remote.example/likes/1", status_uri);
+
+        processor.process(like.clone(), actor_uri).await.unwrap();
+        processor.process(like, actor_uri).await.unwrap();
+
+        let notifications = db.get_notifications(20, None, false).await.unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].notification_type, "favourite");
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (1, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn process_undo_like_removes_notification_and_count() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://example.com/users/alice/statuses/1";
+        let like = like_activity(actor_uri, "https://remote.example/likes/1", status_uri);
+        processor.process(like.clone(), actor_uri).await.unwrap();
+
+        let activity = json!({
+            "type": "Undo",
+            "actor": actor_uri,
+            "object": like
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(
+            db.get_notifications(20, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn process_undo_like_with_uri_object_removes_notification() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://example.com/users/alice/statuses/1";
+        processor
+            .process(
+                like_activity(actor_uri, "https://remote.example/likes/1", status_uri),
+                actor_uri,
+            )
+            .await
+            .unwrap();
+
+        let activity = json!({
+            "type": "Undo",
+            "actor": actor_uri,
+            "object": "https://remote.example/likes/1"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(
+            db.get_notifications(20, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn process_undo_announce_without_id_removes_reblog() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://example.com/users/alice/statuses/1";
+        let announce = json!({
+            "id": "https://remote.example/users/bob/statuses/2/activity",
+            "type": "Announce",
+            "actor": actor_uri,
+            "object": status_uri
+        });
+        processor.process(announce, actor_uri).await.unwrap();
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (0, 1)
+        );
+
+        let activity = json!({
+            "type": "Undo",
+            "actor": actor_uri,
+            "object": {
+                "type": "Announce",
+                "object": status_uri
+            }
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(
+            db.get_notifications(20, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn process_undo_like_from_other_actor_keeps_notification() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://example.com/users/alice/statuses/1";
+        processor
+            .process(
+                like_activity(actor_uri, "https://remote.example/likes/1", status_uri),
+                actor_uri,
+            )
+            .await
+            .unwrap();
+
+        let other_actor_uri = "https://remote.example/users/mallory";
+        let activity = json!({
+            "type": "Undo",
+            "actor": other_actor_uri,
+            "object": "https://remote.example/likes/1"
+        });
+
+        processor.process(activity, other_actor_uri).await.unwrap();
+        assert_eq!(
+            db.get_notifications(20, None, false).await.unwrap().len(),
+            1
+        );
+        assert_eq!(
+            db.get_status_interaction_counts(status_uri).await.unwrap(),
+            (1, 0)
+        );
+    }
+
+    async fn insert_test_follow(db: &Database, uri: &str) {
+        db.insert_follow(&Follow {
+            id: EntityId::new().0,
+            target_address: "bob@remote.example".to_string(),
+            uri: uri.to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn process_reject_follow_removes_follow() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        insert_test_follow(&db, "https://example.com/users/alice/follow/1").await;
+
+        let activity = json!({
+            "type": "Reject",
+            "actor": actor_uri,
+            "object": {
+                "type": "Follow",
+                "id": "https://example.com/users/alice/follow/1",
+                "actor": "https://example.com/users/alice",
+                "object": actor_uri
+            }
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(db.get_all_follow_addresses().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_reject_follow_with_uri_object_removes_follow() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        insert_test_follow(&db, "https://example.com/users/alice/follow/1").await;
+
+        let activity = json!({
+            "type": "Reject",
+            "actor": actor_uri,
+            "object": "https://example.com/users/alice/follow/1"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(db.get_all_follow_addresses().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_reject_follow_with_mismatched_follow_id_keeps_follow() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        insert_test_follow(&db, "https://example.com/users/alice/follow/current").await;
+
+        let activity = json!({
+            "type": "Reject",
+            "actor": actor_uri,
+            "object": {
+                "type": "Follow",
+                "id": "https://example.com/users/alice/follow/old",
+                "actor": "https://example.com/users/alice",
+                "object": actor_uri
+            }
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert_eq!(
+            db.get_all_follow_addresses().await.unwrap(),
+            vec!["bob@remote.example".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_reject_follow_from_other_actor_keeps_follow() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        insert_test_follow(&db, "https://example.com/users/alice/follow/1").await;
+
+        let actor_uri = "https://remote.example/users/mallory";
+        let activity = json!({
+            "type": "Reject",
+            "actor": actor_uri,
+            "object": "https://example.com/users/alice/follow/1"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert_eq!(
+            db.get_all_follow_addresses().await.unwrap(),
+            vec!["bob@remote.example".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_block_severs_relationships_and_hides_cached_statuses() {
+        let (processor, db, timeline_cache, _temp_dir) =
+            create_test_processor_with_timeline("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://remote.example/users/bob/statuses/1";
+        insert_test_follow(&db, "https://example.com/users/alice/follow/1").await;
+        db.insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: "https://remote.example/users/bob/inbox".to_string(),
+            uri: "https://remote.example/follows/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+        db.insert_follow_request(
+            "bob@remote.example:443",
+            "https://remote.example/users/bob/inbox",
+            "https://remote.example/follows/2",
+        )
+        .await
+        .unwrap();
+        timeline_cache
+            .insert(CachedStatus {
+                id: status_uri.to_string(),
+                uri: status_uri.to_string(),
+                content: "<p>Hello</p>".to_string(),
+                account_address: "bob@remote.example".to_string(),
+                created_at: Utc::now(),
+                visibility: "public".to_string(),
+                attachments: vec![],
+                reply_to_uri: None,
+                boost_of_uri: None,
+                content_warning: None,
+            })
+            .await;
+
+        let activity = json!({
+            "type": "Block",
+            "actor": actor_uri,
+            "object": "https://example.com/users/alice"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert!(db.get_all_follow_addresses().await.unwrap().is_empty());
+        assert!(db.get_all_follower_addresses().await.unwrap().is_empty());
+        assert!(
+            !db.has_follow_request("bob@remote.example:443")
+                .await
+                .unwrap()
+        );
+        assert!(timeline_cache.get_by_uri(status_uri).await.is_none());
+        assert!(
+            db.is_blocked_by("bob@remote.example", Some(443))
+                .await
+                .unwrap()
+        );
+
+        let undo = json!({
+            "type": "Undo",
+            "actor": actor_uri,
+            "object": {
+                "type": "Block",
+                "actor": actor_uri,
+                "object": "https://example.com/users/alice"
+            }
+        });
+
+        processor.process(undo, actor_uri).await.unwrap();
+        assert!(
+            !db.is_blocked_by("bob@remote.example", Some(443))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn process_block_with_non_local_target_keeps_follower() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        db.insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: "https://remote.example/users/bob/inbox".to_string(),
+            uri: "https://remote.example/follows/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        let activity = json!({
+            "type": "Block",
+            "actor": actor_uri,
+            "object": "https://other.example/users/alice"
+        });
+
+        processor.process(activity, actor_uri).await.unwrap();
+        assert_eq!(
+            db.get_all_follower_addresses().await.unwrap(),
+            vec!["bob@remote.example".to_string()]
+        );
+        assert!(
+            !db.is_blocked_by("bob@remote.example", Some(443))
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn process_create_from_followee_caches_status_without_db_persist_case_insensitive_match()
     {
@@ -2038,6 +3737,7 @@ mod tests {
                 attachments: vec![],
                 reply_to_uri: None,
                 boost_of_uri: None,
+                content_warning: None,
             })
             .await;
         assert!(timeline_cache.get_by_uri(status_uri).await.is_some());
@@ -2260,6 +3960,7 @@ mod tests {
                 attachments: vec![],
                 reply_to_uri: None,
                 boost_of_uri: None,
+                content_warning: None,
             })
             .await;
         assert!(timeline_cache.get_by_uri(status_uri).await.is_some());
@@ -2307,4 +4008,83 @@ mod tests {
 
         assert!(db.get_status_by_uri(status_uri).await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn process_add_and_remove_track_followee_pins() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+        let status_uri = "https://remote.example/users/bob/statuses/9";
+        db.insert_follow(&Follow {
+            id: EntityId::new().0,
+            target_address: "bob@remote.example".to_string(),
+            uri: "https://example.com/users/alice/follow/bob".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        let add_activity = json!({
+            "type": "Add",
+            "actor": actor_uri,
+            "object": {
+                "id": status_uri,
+                "type": "Note",
+                "attributedTo": actor_uri,
+                "content": "<p>Pinned</p>",
+                "to": ["https://www.w3.org/ns/activitystreams#Public"]
+            },
+            "target": "https://remote.example/users/bob/collections/featured"
+        });
+        processor.process(add_activity, actor_uri).await.unwrap();
+
+        let pinned = db
+            .get_remote_pinned_statuses("bob@remote.example")
+            .await
+            .unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].uri, status_uri);
+        assert_eq!(pinned[0].persisted_reason, "featured");
+
+        let remove_activity = json!({
+            "type": "Remove",
+            "actor": actor_uri,
+            "object": status_uri,
+            "target": "https://remote.example/users/bob/collections/featured"
+        });
+        processor.process(remove_activity, actor_uri).await.unwrap();
+
+        assert!(
+            db.get_remote_pinned_statuses("bob@remote.example")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(db.get_status_by_uri(status_uri).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn process_add_ignores_actors_that_are_not_followed() {
+        let (processor, db, _temp_dir) = create_test_processor("alice@example.com", "https").await;
+        let actor_uri = "https://remote.example/users/bob";
+
+        let add_activity = json!({
+            "type": "Add",
+            "actor": actor_uri,
+            "object": {
+                "id": "https://remote.example/users/bob/statuses/10",
+                "type": "Note",
+                "attributedTo": actor_uri,
+                "content": "<p>Pinned</p>"
+            },
+            "target": "https://remote.example/users/bob/collections/featured"
+        });
+        processor.process(add_activity, actor_uri).await.unwrap();
+
+        assert!(
+            db.get_remote_pinned_statuses("bob@remote.example")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,302 @@
+//! Inbound Activity Deduplication
+//!
+//! Remembers recently received activities so retried or relayed
+//! deliveries are not processed twice.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use super::signature::generate_digest;
+
+/// When and in which order an activity key was recorded
+#[derive(Clone, Copy)]
+struct SeenEntry {
+    seen_at: Instant,
+    /// Recording sequence number, unique for the lifetime of the store
+    seq: u64,
+}
+
+/// Seen entries with their insertion order
+///
+/// `order` lists keys oldest first. Entries removed or re-recorded leave a
+/// stale item behind, recognised by a sequence number that no longer matches
+/// `entries` and dropped lazily, so pruning and eviction only look at the
+/// front.
+#[derive(Default)]
+struct SeenState {
+    /// Activity key -> when it was recorded
+    entries: HashMap<String, SeenEntry>,
+    /// Keys in the order they were recorded, with their sequence number
+    order: VecDeque<(String, u64)>,
+    /// Sequence number of the next recorded key
+    next_seq: u64,
+}
+
+impl SeenState {
+    /// The entry an order item refers to, unless the item is stale
+    fn live_entry(&self, key: &str, seq: u64) -> Option<SeenEntry> {
+        self.entries
+            .get(key)
+            .copied()
+            .filter(|entry| entry.seq == seq)
+    }
+
+    /// Drop expired entries and stale items from the front of the order
+    fn prune_expired(&mut self, ttl: Duration) {
+        while let Some((key, seq)) = self.order.front() {
+            let live = self.live_entry(key, *seq);
+            if live.is_some_and(|entry| !SeenActivities::is_expired(entry.seen_at, ttl)) {
+                break;
+            }
+            if let Some((key, _)) = self.order.pop_front()
+                && live.is_some()
+            {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    /// Remove the oldest live entry
+    fn evict_oldest(&mut self) {
+        while let Some((key, seq)) = self.order.pop_front() {
+            if self.live_entry(&key, seq).is_some() {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    fn record(&mut self, key: &str, max_entries: usize) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.insert(
+            key.to_string(),
+            SeenEntry {
+                seen_at: Instant::now(),
+                seq,
+            },
+        );
+        self.order.push_back((key.to_string(), seq));
+
+        // Keep stale items from piling up when keys are removed or re-recorded.
+        if self.order.len() > max_entries.saturating_mul(2) {
+            let entries = &self.entries;
+            self.order
+                .retain(|(key, seq)| entries.get(key).is_some_and(|entry| entry.seq == *seq));
+        }
+    }
+}
+
+/// Bounded store of recently seen inbound activities
+///
+/// Activities are keyed by the signing actor and their `id`, so one actor
+/// cannot suppress another actor's activities by reusing its ids. Activities
+/// without an `id` are keyed by the SHA-256 digest of the request body.
+/// Entries expire after the TTL; when the store is full the oldest entry is
+/// evicted.
+pub struct SeenActivities {
+    /// Seen entries and their insertion order
+    state: Arc<RwLock<SeenState>>,
+    /// How long an activity is remembered
+    ttl: Duration,
+    /// Maximum number of remembered activities
+    max_entries: usize,
+}
+
+impl SeenActivities {
+    /// Create new seen-activity store
+    ///
+    /// # Arguments
+    /// * `ttl` - How long an activity is remembered
+    /// * `max_entries` - Maximum number of remembered activities
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(SeenState::default())),
+            ttl,
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    /// Build the deduplication key for an inbound activity
+    pub fn activity_key(actor_id: &str, activity: &serde_json::Value, body: &[u8]) -> String {
+        match activity
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        {
+            Some(id) => format!("{} id:{}", actor_id, id),
+            None => format!("{} digest:{}", actor_id, generate_digest(body)),
+        }
+    }
+
+    fn is_expired(seen_at: Instant, ttl: Duration) -> bool {
+        seen_at.elapsed() >= ttl
+    }
+
+    /// Record an activity key
+    ///
+    /// Expired entries are pruned from the oldest end and, when the store is
+    /// full, the oldest entry is evicted, both in amortized constant time.
+    ///
+    /// # Returns
+    /// true if the key was not seen within the TTL, false for a duplicate
+    pub async fn insert(&self, key: &str) -> bool {
+        let mut state = self.state.write().await;
+
+        if let Some(entry) = state.entries.get(key)
+            && !Self::is_expired(entry.seen_at, self.ttl)
+        {
+            return false;
+        }
+
+        state.prune_expired(self.ttl);
+        if !state.entries.contains_key(key) && state.entries.len() >= self.max_entries {
+            state.evict_oldest();
+        }
+
+        state.record(key, self.max_entries);
+        true
+    }
+
+    /// Forget an activity key
+    ///
+    /// Used when processing fails so that a retried delivery is handled.
+    pub async fn remove(&self, key: &str) {
+        let mut state = self.state.write().await;
+        state.entries.remove(key);
+    }
+
+    /// Number of remembered activities, including expired ones not yet pruned
+    pub async fn len(&self) -> usize {
+        self.state.read().await.entries.len()
+    }
+
+    /// Check whether no activities are remembered
+    pub async fn is_empty(&self) -> bool {
+        self.state.read().await.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_duplicate_activity_is_detected() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 10);
+
+        assert!(seen.insert("id:https://remote.example/activities/1").await);
+        assert!(!seen.insert("id:https://remote.example/activities/1").await);
+        assert!(seen.insert("id:https://remote.example/activities/2").await);
+    }
+
+    #[tokio::test]
+    async fn test_expired_activity_is_accepted_again() {
+        let seen = SeenActivities::new(Duration::from_millis(50), 10);
+
+        assert!(seen.insert("id:https://remote.example/activities/1").await);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(seen.insert("id:https://remote.example/activities/1").await);
+    }
+
+    #[tokio::test]
+    async fn test_removed_activity_is_accepted_again() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 10);
+
+        assert!(seen.insert("id:https://remote.example/activities/1").await);
+        seen.remove("id:https://remote.example/activities/1").await;
+        assert!(seen.insert("id:https://remote.example/activities/1").await);
+    }
+
+    #[tokio::test]
+    async fn test_max_entries_evicts_oldest_entry() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 2);
+
+        assert!(seen.insert("a").await);
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(seen.insert("b").await);
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert!(seen.insert("c").await);
+
+        assert_eq!(seen.len().await, 2);
+        assert!(!seen.insert("b").await);
+        assert!(!seen.insert("c").await);
+        assert!(seen.insert("a").await);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_skips_removed_and_re_recorded_entries() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 2);
+
+        assert!(seen.insert("a").await);
+        assert!(seen.insert("b").await);
+        seen.remove("a").await;
+        assert!(seen.insert("c").await);
+
+        // "a" was forgotten, so "b" is the oldest entry and goes first
+        assert!(seen.insert("d").await);
+        assert_eq!(seen.len().await, 2);
+        assert!(!seen.insert("c").await);
+        assert!(!seen.insert("d").await);
+        assert!(seen.insert("b").await);
+    }
+
+    #[tokio::test]
+    async fn test_re_recorded_key_is_not_evicted_by_its_stale_item() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 2);
+
+        assert!(seen.insert("a").await);
+        assert!(seen.insert("b").await);
+        seen.remove("a").await;
+        assert!(seen.insert("a").await);
+
+        // The stale item for the first "a" is skipped; "b" is now the oldest
+        assert!(seen.insert("c").await);
+        assert!(!seen.insert("a").await);
+        assert!(!seen.insert("c").await);
+        assert!(seen.insert("b").await);
+    }
+
+    #[tokio::test]
+    async fn test_stale_order_items_are_compacted() {
+        let seen = SeenActivities::new(Duration::from_secs(60), 4);
+
+        for round in 0..50 {
+            let key = format!("key-{}", round % 3);
+            assert!(seen.insert(&key).await);
+            seen.remove(&key).await;
+        }
+
+        let state = seen.state.read().await;
+        assert!(state.entries.is_empty());
+        assert!(state.order.len() <= 8);
+    }
+
+    #[test]
+    fn test_activity_key_prefers_id_and_falls_back_to_digest() {
+        let actor = "https://remote.example/users/alice";
+        let with_id = json!({"id": "https://remote.example/activities/1", "type": "Like"});
+        assert_eq!(
+            SeenActivities::activity_key(actor, &with_id, b"ignored"),
+            "https://remote.example/users/alice id:https://remote.example/activities/1"
+        );
+        assert_ne!(
+            SeenActivities::activity_key("https://other.example/users/bob", &with_id, b"ignored"),
+            SeenActivities::activity_key(actor, &with_id, b"ignored")
+        );
+
+        let without_id = json!({"type": "Like"});
+        let body = br#"{"type":"Like"}"#;
+        assert_eq!(
+            SeenActivities::activity_key(actor, &without_id, body),
+            format!(
+                "https://remote.example/users/alice digest:{}",
+                generate_digest(body)
+            )
+        );
+    }
+}
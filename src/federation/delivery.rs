@@ -4,11 +4,14 @@
 
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
 
+use super::{DeliveryQueue, SignatureScheme, extract_domain};
 use crate::data::Account;
 use crate::error::AppError;
 
@@ -24,6 +27,41 @@ pub struct ActivityDelivery {
     key_id: String,
     /// Private key for signing
     private_key_pem: String,
+    /// Persistent queue; when set, deliveries are enqueued instead of sent inline
+    queue: Option<Arc<DeliveryQueue>>,
+    /// Signature format each remote domain is known to accept
+    signature_preferences: Arc<SignaturePreferences>,
+}
+
+/// Signature format accepted by each remote domain
+///
+/// Outgoing deliveries try RFC 9421 first and fall back to draft-cavage
+/// when the inbox answers `401`. The format that worked is remembered per
+/// domain so later deliveries skip the failing attempt.
+#[derive(Default)]
+pub struct SignaturePreferences {
+    /// Remote domain -> accepted signature format
+    schemes: RwLock<HashMap<String, SignatureScheme>>,
+}
+
+impl SignaturePreferences {
+    /// Create an empty preference store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signature format remembered for a domain
+    pub async fn get(&self, domain: &str) -> Option<SignatureScheme> {
+        self.schemes.read().await.get(domain).copied()
+    }
+
+    /// Remember the signature format a domain accepted
+    pub async fn set(&self, domain: &str, scheme: SignatureScheme) {
+        let mut schemes = self.schemes.write().await;
+        if schemes.get(domain) != Some(&scheme) {
+            schemes.insert(domain.to_string(), scheme);
+        }
+    }
 }
 
 pub fn local_actor_uri(base_url: &str, username: &str) -> String {
@@ -68,19 +106,58 @@ fn unique_inbox_targets(inbox_uris: Vec<String>) -> Vec<String> {
     targets
 }
 
-fn audience_for_visibility(actor_uri: &str, visibility: &str) -> (Vec<String>, Vec<String>) {
+/// Build `to` and `cc` for a status.
+///
+/// Mentioned actors are addressed directly for `direct` statuses and
+/// carbon-copied otherwise, matching Mastodon.
+pub(super) fn audience_for_visibility(
+    actor_uri: &str,
+    visibility: &str,
+    mentioned_actor_uris: &[String],
+) -> (Vec<String>, Vec<String>) {
     let public_audience = "https://www.w3.org/ns/activitystreams#Public".to_string();
     let followers_audience = format!("{}/followers", actor_uri);
+    let mentions = mentioned_actor_uris.iter().cloned();
 
     match visibility {
-        "public" => (vec![public_audience], vec![followers_audience]),
-        "unlisted" => (vec![followers_audience], vec![public_audience]),
-        "private" => (vec![followers_audience], Vec::new()),
-        "direct" => (Vec::new(), Vec::new()),
-        _ => (vec![public_audience], vec![followers_audience]),
+        "public" => (
+            vec![public_audience],
+            std::iter::once(followers_audience)
+                .chain(mentions)
+                .collect(),
+        ),
+        "unlisted" => (
+            vec![followers_audience],
+            std::iter::once(public_audience).chain(mentions).collect(),
+        ),
+        "private" => (vec![followers_audience], mentions.collect()),
+        "direct" => (mentions.collect(), Vec::new()),
+        _ => (
+            vec![public_audience],
+            std::iter::once(followers_audience)
+                .chain(mentions)
+                .collect(),
+        ),
     }
 }
 
+/// Split the `to`/`cc` audience of a serialized Note for its wrapping activity
+fn note_audience(note: &serde_json::Value) -> (Vec<String>, Vec<String>) {
+    let audience = |key: &str| -> Vec<String> {
+        note.get(key)
+            .and_then(serde_json::Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    (audience("to"), audience("cc"))
+}
+
 fn build_undo_object(
     activity_uri: &str,
     activity_type: Option<&str>,
@@ -116,9 +193,34 @@ impl ActivityDelivery {
             actor_uri,
             key_id,
             private_key_pem,
+            queue: None,
+            signature_preferences: Arc::new(SignaturePreferences::new()),
         }
     }
 
+    /// Route deliveries through a persistent queue
+    ///
+    /// Once set, `deliver_to_inbox`, `deliver_to_followers` and every `send_*`
+    /// helper enqueue one job per inbox and return as soon as the rows are
+    /// written. The queue worker performs the actual POSTs with retries.
+    pub fn with_queue(mut self, queue: Arc<DeliveryQueue>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Share per-domain signature format preferences
+    ///
+    /// Without this each `ActivityDelivery` learns preferences on its own.
+    pub fn with_signature_preferences(mut self, preferences: Arc<SignaturePreferences>) -> Self {
+        self.signature_preferences = preferences;
+        self
+    }
+
+    /// Local actor URI used as the `actor` of outgoing activities
+    pub fn actor_uri(&self) -> &str {
+        &self.actor_uri
+    }
+
     /// Deliver activity to a single inbox
     ///
     /// # Arguments
@@ -126,48 +228,173 @@ impl ActivityDelivery {
     /// * `activity` - Activity JSON
     ///
     /// # Errors
-    /// Returns error if delivery fails (network, signature, rejection)
+    /// Returns error if delivery fails (network, signature, rejection), or
+    /// if the job cannot be enqueued when a queue is configured
     pub async fn deliver_to_inbox(
         &self,
         inbox_uri: &str,
         activity: serde_json::Value,
     ) -> Result<(), AppError> {
+        if let Some(queue) = &self.queue {
+            queue.enqueue(&activity, &[inbox_uri.to_string()]).await?;
+            return Ok(());
+        }
+
+        self.deliver_now(inbox_uri, &activity).await
+    }
+
+    /// POST a signed activity to an inbox immediately, bypassing the queue
+    pub async fn deliver_now(
+        &self,
+        inbox_uri: &str,
+        activity: &serde_json::Value,
+    ) -> Result<(), AppError> {
+        self.post_signed(inbox_uri, activity)
+            .await
+            .map_err(|(_, error)| error)
+    }
+
+    /// Make a single signed POST attempt and report the outcome
+    ///
+    /// Unlike `deliver_now`, the HTTP status code of a rejected delivery is
+    /// preserved so callers can tell permanent failures from transient ones.
+    pub async fn attempt_delivery(
+        &self,
+        inbox_uri: &str,
+        activity: &serde_json::Value,
+    ) -> DeliveryResult {
+        match self.post_signed(inbox_uri, activity).await {
+            Ok(()) => DeliveryResult {
+                inbox_uri: inbox_uri.to_string(),
+                success: true,
+                error: None,
+                status_code: None,
+            },
+            Err((status_code, error)) => DeliveryResult {
+                inbox_uri: inbox_uri.to_string(),
+                success: false,
+                error: Some(error.to_string()),
+                status_code,
+            },
+        }
+    }
+
+    /// POST a signed activity, double-knocking on the signature format
+    ///
+    /// Tries RFC 9421 first unless the domain is known to need draft-cavage,
+    /// and retries with draft-cavage when RFC 9421 is answered with `401`.
+    async fn post_signed(
+        &self,
+        inbox_uri: &str,
+        activity: &serde_json::Value,
+    ) -> Result<(), (Option<u16>, AppError)> {
         // 1. Serialize activity
-        let body = serde_json::to_vec(&activity)
-            .map_err(|e| AppError::Validation(format!("Failed to serialize activity: {}", e)))?;
-
-        // 2. Sign request
-        let sig_headers = crate::federation::sign_request(
-            "POST",
-            inbox_uri,
-            Some(&body),
-            &self.private_key_pem,
-            &self.key_id,
-        )?;
-
-        // 3. POST to inbox with signed headers
+        let body = serde_json::to_vec(activity).map_err(|e| {
+            (
+                None,
+                AppError::Validation(format!("Failed to serialize activity: {}", e)),
+            )
+        })?;
+
+        // 2. Use the remembered format for this domain
+        let domain = extract_domain(inbox_uri);
+        if self.signature_preferences.get(&domain).await == Some(SignatureScheme::Cavage) {
+            return self
+                .post_with_scheme(inbox_uri, &body, SignatureScheme::Cavage)
+                .await;
+        }
+
+        // 3. Try RFC 9421, falling back to draft-cavage on 401
+        let scheme = match self
+            .post_with_scheme(inbox_uri, &body, SignatureScheme::Rfc9421)
+            .await
+        {
+            Ok(()) => SignatureScheme::Rfc9421,
+            Err((Some(401), _)) => {
+                tracing::debug!(
+                    inbox_uri = %inbox_uri,
+                    "RFC 9421 signature rejected, retrying with draft-cavage"
+                );
+                self.post_with_scheme(inbox_uri, &body, SignatureScheme::Cavage)
+                    .await?;
+                SignatureScheme::Cavage
+            }
+            Err(error) => return Err(error),
+        };
+        self.signature_preferences.set(&domain, scheme).await;
+
+        Ok(())
+    }
+
+    async fn post_with_scheme(
+        &self,
+        inbox_uri: &str,
+        body: &[u8],
+        scheme: SignatureScheme,
+    ) -> Result<(), (Option<u16>, AppError)> {
+        // 1. Sign request
+        let date = chrono::Utc::now()
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
         let mut request = self
             .http_client
             .post(inbox_uri)
-            .header("Content-Type", "application/activity+json")
-            .header("Date", sig_headers.date)
-            .header("Signature", sig_headers.signature);
-
-        if let Some(digest) = sig_headers.digest {
-            request = request.header("Digest", digest);
+            .header("Content-Type", "application/activity+json");
+
+        match scheme {
+            SignatureScheme::Rfc9421 => {
+                let sig_headers = crate::federation::sign_message(
+                    "POST",
+                    inbox_uri,
+                    Some(body),
+                    &self.private_key_pem,
+                    &self.key_id,
+                )
+                .map_err(|error| (None, error))?;
+                request = request
+                    .header("Date", date)
+                    .header("Signature-Input", sig_headers.signature_input)
+                    .header("Signature", sig_headers.signature);
+                if let Some(digest) = sig_headers.content_digest {
+                    request = request.header("Content-Digest", digest);
+                }
+            }
+            SignatureScheme::Cavage => {
+                let sig_headers = crate::federation::sign_request(
+                    "POST",
+                    inbox_uri,
+                    Some(body),
+                    &self.private_key_pem,
+                    &self.key_id,
+                )
+                .map_err(|error| (None, error))?;
+                request = request
+                    .header("Date", sig_headers.date)
+                    .header("Signature", sig_headers.signature);
+                if let Some(digest) = sig_headers.digest {
+                    request = request.header("Digest", digest);
+                }
+            }
         }
 
-        let response = request.body(body).send().await.map_err(|e| {
-            AppError::Federation(format!("Failed to deliver to {}: {}", inbox_uri, e))
+        // 2. POST to inbox with signed headers
+        let response = request.body(body.to_vec()).send().await.map_err(|e| {
+            (
+                None,
+                AppError::Federation(format!("Failed to deliver to {}: {}", inbox_uri, e)),
+            )
         })?;
 
-        // 4. Handle response
+        // 3. Handle response
         if !response.status().is_success() {
-            return Err(AppError::Federation(format!(
-                "Inbox {} rejected activity: HTTP {}",
-                inbox_uri,
-                response.status()
-            )));
+            return Err((
+                Some(response.status().as_u16()),
+                AppError::Federation(format!(
+                    "Inbox {} rejected activity: HTTP {}",
+                    inbox_uri,
+                    response.status()
+                )),
+            ));
         }
 
         tracing::info!("Successfully delivered activity to {}", inbox_uri);
@@ -194,6 +421,20 @@ impl ActivityDelivery {
         let total_targets = inbox_uris.len();
         let delivery_targets = unique_inbox_targets(inbox_uris);
 
+        // 2. With a queue configured, persist one job per inbox and return.
+        if let Some(queue) = &self.queue {
+            let enqueue_result = queue.enqueue(&activity, &delivery_targets).await;
+            return delivery_targets
+                .into_iter()
+                .map(|inbox_uri| DeliveryResult {
+                    inbox_uri,
+                    success: enqueue_result.is_ok(),
+                    error: enqueue_result.as_ref().err().map(|error| error.to_string()),
+                    status_code: None,
+                })
+                .collect();
+        }
+
         tracing::info!(
             "Delivering to {} unique inboxes (deduplicated from {} total)",
             delivery_targets.len(),
@@ -217,16 +458,7 @@ impl ActivityDelivery {
                 let _permit = semaphore.acquire().await.unwrap();
 
                 // Attempt delivery
-                let result = self_clone
-                    .deliver_to_inbox(&inbox_uri, (*activity).clone())
-                    .await;
-
-                DeliveryResult {
-                    inbox_uri: inbox_uri.clone(),
-                    success: result.is_ok(),
-                    error: result.err().map(|e| e.to_string()),
-                    status_code: None, // Could be extracted from error
-                }
+                self_clone.attempt_delivery(&inbox_uri, &activity).await
             });
 
             tasks.push(task);
@@ -408,57 +640,77 @@ impl ActivityDelivery {
     /// Send Create activity (for new status)
     ///
     /// # Arguments
-    /// * `status` - Status to create
+    /// * `note` - Object built by `NoteSerializer::note`; its audience is
+    ///   reused for the activity
     /// * `inbox_uris` - Target inboxes
     pub async fn send_create(
         &self,
-        status: &crate::data::Status,
+        note: serde_json::Value,
         inbox_uris: Vec<String>,
     ) -> Vec<DeliveryResult> {
-        let (to_audience, cc_audience) =
-            audience_for_visibility(&self.actor_uri, status.visibility.as_str());
-        let note_to: Vec<&str> = to_audience.iter().map(String::as_str).collect();
-        let note_cc: Vec<&str> = cc_audience.iter().map(String::as_str).collect();
-
-        // 1. Build Note object
-        let note = if let Some(ref in_reply_to) = status.in_reply_to_uri {
-            builder::note_reply(
-                &status.uri,
-                &self.actor_uri,
-                &status.content,
-                &status.created_at.to_rfc3339(),
-                in_reply_to,
-                note_to.clone(),
-                note_cc.clone(),
-            )
-        } else {
-            builder::note(
-                &status.uri,
-                &self.actor_uri,
-                &status.content,
-                &status.created_at.to_rfc3339(),
-                note_to.clone(),
-                note_cc.clone(),
-            )
-        };
-
-        // 2. Wrap in Create activity
         let create_id = format!(
             "{}/create/{}",
             self.actor_uri,
             crate::data::EntityId::new().0
         );
-        let activity = builder::create(&create_id, &self.actor_uri, note, note_to, note_cc);
+        let (to_audience, cc_audience) = note_audience(&note);
+        let mut activity = builder::create(
+            &create_id,
+            &self.actor_uri,
+            note,
+            to_audience.iter().map(String::as_str).collect(),
+            cc_audience.iter().map(String::as_str).collect(),
+        );
+        activity["@context"] = super::note_context();
+
+        self.deliver_to_followers(activity, inbox_uris).await
+    }
+
+    /// Send Update activity (for an edited status)
+    ///
+    /// # Arguments
+    /// * `note` - Edited object built by `NoteSerializer::note`; its audience
+    ///   is reused for the activity
+    /// * `former_mentioned_actor_uris` - Actors the edit stopped mentioning,
+    ///   added to `cc` so they also see the new revision
+    /// * `inbox_uris` - Target inboxes
+    pub async fn send_update(
+        &self,
+        note: serde_json::Value,
+        former_mentioned_actor_uris: &[String],
+        inbox_uris: Vec<String>,
+    ) -> Vec<DeliveryResult> {
+        let update_id = format!(
+            "{}/update/{}",
+            self.actor_uri,
+            crate::data::EntityId::new().0
+        );
+        let (to_audience, mut cc_audience) = note_audience(&note);
+        for actor_uri in former_mentioned_actor_uris {
+            if !to_audience.contains(actor_uri) && !cc_audience.contains(actor_uri) {
+                cc_audience.push(actor_uri.clone());
+            }
+        }
+        let mut activity = builder::update(
+            &update_id,
+            &self.actor_uri,
+            note,
+            to_audience.iter().map(String::as_str).collect(),
+            cc_audience.iter().map(String::as_str).collect(),
+        );
+        activity["@context"] = super::note_context();
 
-        // 3. Deliver to inboxes
         self.deliver_to_followers(activity, inbox_uris).await
     }
 
     /// Send Delete activity
+    ///
+    /// Addressed like the deleted object, including the actors it mentioned.
     pub async fn send_delete(
         &self,
         object_uri: &str,
         object_visibility: &str,
+        mentioned_actor_uris: &[String],
         inbox_uris: Vec<String>,
     ) -> Vec<DeliveryResult> {
         // Build and deliver Delete activity
@@ -468,7 +720,7 @@ impl ActivityDelivery {
             crate::data::EntityId::new().0
         );
         let (to_audience, cc_audience) =
-            audience_for_visibility(&self.actor_uri, object_visibility);
+            audience_for_visibility(&self.actor_uri, object_visibility, mentioned_actor_uris);
         let activity = builder::delete(
             &delete_id,
             &self.actor_uri,
@@ -480,6 +732,75 @@ impl ActivityDelivery {
         self.deliver_to_followers(activity, inbox_uris).await
     }
 
+    /// Send Add activity (status pinned to the featured collection)
+    pub async fn send_add(
+        &self,
+        status_uri: &str,
+        status_visibility: &str,
+        inbox_uris: Vec<String>,
+    ) -> Vec<DeliveryResult> {
+        let add_id = format!("{}/add/{}", self.actor_uri, crate::data::EntityId::new().0);
+        let (to_audience, cc_audience) =
+            audience_for_visibility(&self.actor_uri, status_visibility, &[]);
+        let activity = builder::add(
+            &add_id,
+            &self.actor_uri,
+            status_uri,
+            &super::featured_collection_uri(&self.actor_uri),
+            to_audience.iter().map(String::as_str).collect(),
+            cc_audience.iter().map(String::as_str).collect(),
+        );
+
+        self.deliver_to_followers(activity, inbox_uris).await
+    }
+
+    /// Send Remove activity (status unpinned from the featured collection)
+    pub async fn send_remove(
+        &self,
+        status_uri: &str,
+        status_visibility: &str,
+        inbox_uris: Vec<String>,
+    ) -> Vec<DeliveryResult> {
+        let remove_id = format!(
+            "{}/remove/{}",
+            self.actor_uri,
+            crate::data::EntityId::new().0
+        );
+        let (to_audience, cc_audience) =
+            audience_for_visibility(&self.actor_uri, status_visibility, &[]);
+        let activity = builder::remove(
+            &remove_id,
+            &self.actor_uri,
+            status_uri,
+            &super::featured_collection_uri(&self.actor_uri),
+            to_audience.iter().map(String::as_str).collect(),
+            cc_audience.iter().map(String::as_str).collect(),
+        );
+
+        self.deliver_to_followers(activity, inbox_uris).await
+    }
+
+    /// Send Move activity (migrate this account to `target_actor_uri`)
+    ///
+    /// The target must already list this actor in `alsoKnownAs`, otherwise
+    /// receiving servers reject the Move.
+    pub async fn send_move(
+        &self,
+        target_actor_uri: &str,
+        inbox_uris: Vec<String>,
+    ) -> Vec<DeliveryResult> {
+        let move_id = format!("{}/move/{}", self.actor_uri, crate::data::EntityId::new().0);
+        let followers_uri = format!("{}/followers", self.actor_uri);
+        let activity = builder::move_account(
+            &move_id,
+            &self.actor_uri,
+            target_actor_uri,
+            vec![followers_uri.as_str()],
+        );
+
+        self.deliver_to_followers(activity, inbox_uris).await
+    }
+
     /// Send Like activity
     pub async fn send_like(
         &self,
@@ -514,6 +835,82 @@ impl ActivityDelivery {
         Ok(())
     }
 
+    /// Send votes on a remote poll to its author
+    ///
+    /// Mastodon expects one `Create(Note)` per chosen option, with the option
+    /// title as `name` and the Question as `inReplyTo`.
+    pub async fn send_poll_votes(
+        &self,
+        question_uri: &str,
+        author_actor_uri: &str,
+        author_inbox_uri: &str,
+        choices: &[String],
+    ) -> Result<(), AppError> {
+        for choice in choices {
+            let vote_id = format!(
+                "{}#votes/{}",
+                self.actor_uri,
+                crate::data::EntityId::new().0
+            );
+            let note = builder::poll_vote(
+                &vote_id,
+                &self.actor_uri,
+                choice,
+                question_uri,
+                author_actor_uri,
+            );
+            let activity = builder::create(
+                &format!("{}/activity", vote_id),
+                &self.actor_uri,
+                note,
+                vec![author_actor_uri],
+                vec![],
+            );
+            self.deliver_to_inbox(author_inbox_uri, activity).await?;
+        }
+
+        tracing::info!(
+            "Sent {} poll vote(s) to {} for {}",
+            choices.len(),
+            author_inbox_uri,
+            question_uri
+        );
+
+        Ok(())
+    }
+
+    /// Send Flag activity (forward a report to the reported account's server)
+    ///
+    /// # Arguments
+    /// * `flag_activity_uri` - Activity ID of the Flag
+    /// * `target_actor_uri` - Reported actor
+    /// * `target_inbox_uri` - Inbox of the reported actor's server
+    /// * `status_uris` - Reported statuses
+    /// * `comment` - Reason given by the reporter
+    pub async fn send_flag(
+        &self,
+        flag_activity_uri: &str,
+        target_actor_uri: &str,
+        target_inbox_uri: &str,
+        status_uris: &[String],
+        comment: &str,
+    ) -> Result<(), AppError> {
+        let mut objects = vec![target_actor_uri];
+        objects.extend(status_uris.iter().map(String::as_str));
+        let activity = builder::flag(flag_activity_uri, &self.actor_uri, objects, comment);
+
+        self.deliver_to_inbox(target_inbox_uri, activity).await?;
+
+        tracing::info!(
+            "Sent Flag {} to {} for {}",
+            flag_activity_uri,
+            target_inbox_uri,
+            target_actor_uri
+        );
+
+        Ok(())
+    }
+
     /// Send Undo activity
     pub async fn send_undo(
         &self,
@@ -613,7 +1010,7 @@ impl ActivityDelivery {
         inbox_uris: Vec<String>,
     ) -> Vec<DeliveryResult> {
         let (to_audience, cc_audience) =
-            audience_for_visibility(&self.actor_uri, status_visibility);
+            audience_for_visibility(&self.actor_uri, status_visibility, &[]);
         let activity = builder::announce(
             announce_activity_uri,
             &self.actor_uri,
@@ -718,6 +1115,27 @@ pub mod builder {
         })
     }
 
+    /// Build an Update activity
+    ///
+    /// # Arguments
+    /// * `id` - Activity ID (unique URI)
+    /// * `actor` - Actor URI (editor)
+    /// * `object` - Updated object (usually a Note)
+    /// * `to` - Primary recipients
+    /// * `cc` - CC recipients
+    pub fn update(id: &str, actor: &str, object: Value, to: Vec<&str>, cc: Vec<&str>) -> Value {
+        serde_json::json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Update",
+            "id": id,
+            "actor": actor,
+            "object": object,
+            "to": to,
+            "cc": cc,
+            "published": chrono::Utc::now().to_rfc3339()
+        })
+    }
+
     /// Build a Delete activity
     ///
     /// # Arguments
@@ -755,6 +1173,38 @@ pub mod builder {
         })
     }
 
+    /// Build a poll vote Note
+    ///
+    /// # Arguments
+    /// * `id` - Note ID (unique URI)
+    /// * `actor` - Actor URI (voter)
+    /// * `name` - Title of the chosen option
+    /// * `question` - Question URI being voted on
+    /// * `author` - Actor URI of the poll author (sole recipient)
+    pub fn poll_vote(id: &str, actor: &str, name: &str, question: &str, author: &str) -> Value {
+        serde_json::json!({
+            "id": id,
+            "type": "Note",
+            "name": name,
+            "attributedTo": actor,
+            "inReplyTo": question,
+            "to": [author]
+        })
+    }
+
+    /// Build a Mention tag
+    ///
+    /// # Arguments
+    /// * `href` - Mentioned actor URI
+    /// * `name` - Mention as written (`@user@domain`)
+    pub fn mention(href: &str, name: &str) -> Value {
+        serde_json::json!({
+            "type": "Mention",
+            "href": href,
+            "name": name
+        })
+    }
+
     /// Build an Announce activity (boost/reblog)
     ///
     /// # Arguments
@@ -776,92 +1226,110 @@ pub mod builder {
         })
     }
 
-    /// Build an Undo activity
+    /// Build an Add activity (pin a status)
     ///
     /// # Arguments
     /// * `id` - Activity ID (unique URI)
-    /// * `actor` - Actor URI (undoer)
-    /// * `object` - Original activity being undone
-    pub fn undo(id: &str, actor: &str, object: Value) -> Value {
+    /// * `actor` - Actor URI (pinner)
+    /// * `object` - Object URI being pinned (status)
+    /// * `target` - Featured collection URI
+    pub fn add(
+        id: &str,
+        actor: &str,
+        object: &str,
+        target: &str,
+        to: Vec<&str>,
+        cc: Vec<&str>,
+    ) -> Value {
         serde_json::json!({
             "@context": "https://www.w3.org/ns/activitystreams",
-            "type": "Undo",
+            "type": "Add",
             "id": id,
             "actor": actor,
-            "object": object
+            "object": object,
+            "target": target,
+            "to": to,
+            "cc": cc
         })
     }
 
-    /// Build a Note object
+    /// Build a Remove activity (unpin a status)
     ///
     /// # Arguments
-    /// * `id` - Note ID (unique URI)
-    /// * `attributed_to` - Actor URI (author)
-    /// * `content` - HTML content
-    /// * `published` - Publication timestamp (RFC3339)
-    /// * `to` - Primary recipients
-    /// * `cc` - CC recipients
-    pub fn note(
+    /// * `id` - Activity ID (unique URI)
+    /// * `actor` - Actor URI (pinner)
+    /// * `object` - Object URI being unpinned (status)
+    /// * `target` - Featured collection URI
+    pub fn remove(
         id: &str,
-        attributed_to: &str,
-        content: &str,
-        published: &str,
+        actor: &str,
+        object: &str,
+        target: &str,
         to: Vec<&str>,
         cc: Vec<&str>,
     ) -> Value {
         serde_json::json!({
-            "type": "Note",
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Remove",
             "id": id,
-            "attributedTo": attributed_to,
-            "content": content,
-            "published": published,
+            "actor": actor,
+            "object": object,
+            "target": target,
             "to": to,
-            "cc": cc,
-            "sensitive": false,
-            "atomUri": id,
-            "inReplyToAtomUri": null,
-            "conversation": format!("tag:{},conversation", id.split("://").nth(1).unwrap_or("").split('/').next().unwrap_or("")),
-            "contentMap": {
-                "en": content
-            }
+            "cc": cc
         })
     }
 
-    /// Build a Note object with reply information
+    /// Build a Move activity (account migration)
     ///
     /// # Arguments
-    /// * `id` - Note ID (unique URI)
-    /// * `attributed_to` - Actor URI (author)
-    /// * `content` - HTML content
-    /// * `published` - Publication timestamp (RFC3339)
-    /// * `in_reply_to` - URI of status being replied to
-    /// * `to` - Primary recipients
-    /// * `cc` - CC recipients
-    pub fn note_reply(
-        id: &str,
-        attributed_to: &str,
-        content: &str,
-        published: &str,
-        in_reply_to: &str,
-        to: Vec<&str>,
-        cc: Vec<&str>,
-    ) -> Value {
+    /// * `id` - Activity ID (unique URI)
+    /// * `actor` - Actor URI (the account being moved, also the object)
+    /// * `target` - Actor URI of the new account
+    /// * `to` - Recipients (usually the followers collection)
+    pub fn move_account(id: &str, actor: &str, target: &str, to: Vec<&str>) -> Value {
         serde_json::json!({
-            "type": "Note",
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Move",
             "id": id,
-            "attributedTo": attributed_to,
-            "content": content,
-            "published": published,
-            "inReplyTo": in_reply_to,
-            "to": to,
-            "cc": cc,
-            "sensitive": false,
-            "atomUri": id,
-            "inReplyToAtomUri": in_reply_to,
-            "conversation": format!("tag:{},conversation", id.split("://").nth(1).unwrap_or("").split('/').next().unwrap_or("")),
-            "contentMap": {
-                "en": content
-            }
+            "actor": actor,
+            "object": actor,
+            "target": target,
+            "to": to
+        })
+    }
+
+    /// Build a Flag activity (report)
+    ///
+    /// # Arguments
+    /// * `id` - Activity ID (unique URI)
+    /// * `actor` - Actor URI (reporter)
+    /// * `objects` - Reported actor URI followed by reported status URIs
+    /// * `content` - Report comment
+    pub fn flag(id: &str, actor: &str, objects: Vec<&str>, content: &str) -> Value {
+        serde_json::json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Flag",
+            "id": id,
+            "actor": actor,
+            "object": objects,
+            "content": content
+        })
+    }
+
+    /// Build an Undo activity
+    ///
+    /// # Arguments
+    /// * `id` - Activity ID (unique URI)
+    /// * `actor` - Actor URI (undoer)
+    /// * `object` - Original activity being undone
+    pub fn undo(id: &str, actor: &str, object: Value) -> Value {
+        serde_json::json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "type": "Undo",
+            "id": id,
+            "actor": actor,
+            "object": object
         })
     }
 }
@@ -869,8 +1337,121 @@ pub mod builder {
 #[cfg(test)]
 mod tests {
     use super::{
-        audience_for_visibility, block_activity_uri, build_undo_object, unique_inbox_targets,
+        ActivityDelivery, SignaturePreferences, audience_for_visibility, block_activity_uri,
+        build_undo_object, unique_inbox_targets,
     };
+    use crate::federation::{MessageRequest, SignatureScheme, verify_request_signature};
+    use std::sync::{Arc, Mutex};
+
+    const TEST_PRIVATE_KEY_PEM: &str = include_str!("../../tests/fixtures/test_private_key.pem");
+    const TEST_PUBLIC_KEY_PEM: &str = include_str!("../../tests/fixtures/test_public_key.pem");
+
+    /// Start an inbox that records each request's signature scheme
+    ///
+    /// draft-cavage requests are accepted as is. RFC 9421 requests are
+    /// verified when `accept_rfc9421` is set and answered `401` otherwise.
+    async fn spawn_mock_inbox(accept_rfc9421: bool) -> (String, Arc<Mutex<Vec<SignatureScheme>>>) {
+        use axum::{Router, body::Bytes, routing::post};
+        use http::{HeaderMap, StatusCode};
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_for_route = received.clone();
+        let app = Router::new().route(
+            "/users/bob/inbox",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let received = received_for_route.clone();
+                async move {
+                    let scheme = SignatureScheme::detect(&headers).expect("signed request");
+                    received.lock().unwrap().push(scheme);
+                    if scheme == SignatureScheme::Cavage {
+                        return StatusCode::ACCEPTED;
+                    }
+                    if !accept_rfc9421 {
+                        return StatusCode::UNAUTHORIZED;
+                    }
+                    let request = MessageRequest {
+                        method: "POST",
+                        scheme: "http",
+                        path_and_query: "/users/bob/inbox",
+                        headers: &headers,
+                    };
+                    match verify_request_signature(
+                        &request,
+                        Some(&body),
+                        TEST_PUBLIC_KEY_PEM,
+                        std::time::Duration::from_secs(300),
+                    ) {
+                        Ok(()) => StatusCode::ACCEPTED,
+                        Err(_) => StatusCode::UNAUTHORIZED,
+                    }
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (format!("http://{addr}/users/bob/inbox"), received)
+    }
+
+    fn test_delivery(preferences: Arc<SignaturePreferences>) -> ActivityDelivery {
+        ActivityDelivery::new(
+            Arc::new(reqwest::Client::new()),
+            "https://example.com/users/alice".to_string(),
+            "https://example.com/users/alice#main-key".to_string(),
+            TEST_PRIVATE_KEY_PEM.to_string(),
+        )
+        .with_signature_preferences(preferences)
+    }
+
+    #[tokio::test]
+    async fn delivery_prefers_rfc9421_signatures() {
+        let (inbox_uri, received) = spawn_mock_inbox(true).await;
+        let preferences = Arc::new(SignaturePreferences::new());
+        let delivery = test_delivery(preferences.clone());
+        let activity = serde_json::json!({"type": "Follow", "actor": delivery.actor_uri()});
+
+        delivery.deliver_now(&inbox_uri, &activity).await.unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec![SignatureScheme::Rfc9421]);
+        assert_eq!(
+            preferences
+                .get(&crate::federation::extract_domain(&inbox_uri))
+                .await,
+            Some(SignatureScheme::Rfc9421)
+        );
+    }
+
+    #[tokio::test]
+    async fn delivery_falls_back_to_cavage_on_401_and_remembers_domain() {
+        let (inbox_uri, received) = spawn_mock_inbox(false).await;
+        let preferences = Arc::new(SignaturePreferences::new());
+        let delivery = test_delivery(preferences.clone());
+        let activity = serde_json::json!({"type": "Follow", "actor": delivery.actor_uri()});
+
+        delivery.deliver_now(&inbox_uri, &activity).await.unwrap();
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![SignatureScheme::Rfc9421, SignatureScheme::Cavage]
+        );
+
+        // A delivery sharing the preferences skips the RFC 9421 attempt
+        test_delivery(preferences)
+            .deliver_now(&inbox_uri, &activity)
+            .await
+            .unwrap();
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                SignatureScheme::Rfc9421,
+                SignatureScheme::Cavage,
+                SignatureScheme::Cavage
+            ]
+        );
+    }
 
     #[test]
     fn unique_inbox_targets_keeps_distinct_personal_inboxes_on_same_domain() {
@@ -916,32 +1497,58 @@ mod tests {
 
     #[test]
     fn audience_for_visibility_public_targets_public_then_followers() {
-        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "public");
+        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "public", &[]);
         assert_eq!(to, vec!["https://www.w3.org/ns/activitystreams#Public"]);
         assert_eq!(cc, vec!["https://example.com/users/alice/followers"]);
     }
 
     #[test]
     fn audience_for_visibility_unlisted_targets_followers_then_public_cc() {
-        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "unlisted");
+        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "unlisted", &[]);
         assert_eq!(to, vec!["https://example.com/users/alice/followers"]);
         assert_eq!(cc, vec!["https://www.w3.org/ns/activitystreams#Public"]);
     }
 
     #[test]
     fn audience_for_visibility_private_targets_only_followers() {
-        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "private");
+        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "private", &[]);
         assert_eq!(to, vec!["https://example.com/users/alice/followers"]);
         assert!(cc.is_empty());
     }
 
     #[test]
-    fn audience_for_visibility_direct_targets_empty_audience() {
-        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "direct");
+    fn audience_for_visibility_direct_without_mentions_targets_empty_audience() {
+        let (to, cc) = audience_for_visibility("https://example.com/users/alice", "direct", &[]);
         assert!(to.is_empty());
         assert!(cc.is_empty());
     }
 
+    #[test]
+    fn audience_for_visibility_addresses_mentions() {
+        let mentions = vec!["https://remote.example/users/bob".to_string()];
+
+        let (to, cc) =
+            audience_for_visibility("https://example.com/users/alice", "public", &mentions);
+        assert_eq!(to, vec!["https://www.w3.org/ns/activitystreams#Public"]);
+        assert_eq!(
+            cc,
+            vec![
+                "https://example.com/users/alice/followers",
+                "https://remote.example/users/bob"
+            ]
+        );
+
+        let (to, cc) =
+            audience_for_visibility("https://example.com/users/alice", "private", &mentions);
+        assert_eq!(to, vec!["https://example.com/users/alice/followers"]);
+        assert_eq!(cc, mentions);
+
+        let (to, cc) =
+            audience_for_visibility("https://example.com/users/alice", "direct", &mentions);
+        assert_eq!(to, mentions);
+        assert!(cc.is_empty());
+    }
+
     #[test]
     fn build_undo_object_includes_type_id_and_optional_object_target() {
         let undo_object = build_undo_object(
@@ -981,4 +1588,112 @@ mod tests {
         assert_eq!(activity["actor"], "https://local.example/users/alice");
         assert_eq!(activity["object"], "https://remote.example/users/bob");
     }
+
+    #[test]
+    fn builder_update_wraps_object_with_audience() {
+        let note = serde_json::json!({
+            "type": "Note",
+            "id": "https://local.example/users/alice/statuses/1",
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+            "cc": ["https://local.example/users/alice/followers"]
+        });
+        let (to, cc) = super::note_audience(&note);
+        let activity = super::builder::update(
+            "https://local.example/users/alice/update/1",
+            "https://local.example/users/alice",
+            note.clone(),
+            to.iter().map(String::as_str).collect(),
+            cc.iter().map(String::as_str).collect(),
+        );
+        assert_eq!(activity["type"], "Update");
+        assert_eq!(activity["object"], note);
+        assert_eq!(activity["to"], note["to"]);
+        assert_eq!(activity["cc"], note["cc"]);
+    }
+
+    #[test]
+    fn builder_move_account_moves_actor_to_target() {
+        let activity = super::builder::move_account(
+            "https://local.example/users/alice/move/1",
+            "https://local.example/users/alice",
+            "https://new.example/users/alice",
+            vec!["https://local.example/users/alice/followers"],
+        );
+        assert_eq!(activity["type"], "Move");
+        assert_eq!(activity["object"], "https://local.example/users/alice");
+        assert_eq!(activity["target"], "https://new.example/users/alice");
+        assert_eq!(
+            activity["to"][0],
+            "https://local.example/users/alice/followers"
+        );
+    }
+
+    #[test]
+    fn builder_add_and_remove_target_featured_collection() {
+        let featured = "https://local.example/users/alice/collections/featured";
+        let add = super::builder::add(
+            "https://local.example/users/alice/add/1",
+            "https://local.example/users/alice",
+            "https://local.example/users/alice/statuses/1",
+            featured,
+            vec!["https://www.w3.org/ns/activitystreams#Public"],
+            vec!["https://local.example/users/alice/followers"],
+        );
+        assert_eq!(add["type"], "Add");
+        assert_eq!(
+            add["object"],
+            "https://local.example/users/alice/statuses/1"
+        );
+        assert_eq!(add["target"], featured);
+
+        let remove = super::builder::remove(
+            "https://local.example/users/alice/remove/1",
+            "https://local.example/users/alice",
+            "https://local.example/users/alice/statuses/1",
+            featured,
+            vec![],
+            vec![],
+        );
+        assert_eq!(remove["type"], "Remove");
+        assert_eq!(remove["target"], featured);
+    }
+
+    #[test]
+    fn builder_poll_vote_replies_to_question_with_option_name() {
+        let note = super::builder::poll_vote(
+            "https://local.example/users/alice#votes/1",
+            "https://local.example/users/alice",
+            "Yes",
+            "https://remote.example/users/bob/statuses/1",
+            "https://remote.example/users/bob",
+        );
+        assert_eq!(note["type"], "Note");
+        assert_eq!(note["name"], "Yes");
+        assert_eq!(
+            note["inReplyTo"],
+            "https://remote.example/users/bob/statuses/1"
+        );
+        assert_eq!(note["to"][0], "https://remote.example/users/bob");
+        assert!(note.get("content").is_none());
+    }
+
+    #[test]
+    fn builder_flag_lists_target_account_first() {
+        let activity = super::builder::flag(
+            "https://local.example/users/alice/flags/1",
+            "https://local.example/users/alice",
+            vec![
+                "https://remote.example/users/bob",
+                "https://remote.example/users/bob/statuses/1",
+            ],
+            "spam",
+        );
+        assert_eq!(activity["type"], "Flag");
+        assert_eq!(activity["object"][0], "https://remote.example/users/bob");
+        assert_eq!(
+            activity["object"][1],
+            "https://remote.example/users/bob/statuses/1"
+        );
+        assert_eq!(activity["content"], "spam");
+    }
 }
@@ -0,0 +1,461 @@
+//! Persistent outbound delivery queue
+//!
+//! `ActivityDelivery` writes one row per target inbox to the `delivery_queue`
+//! table instead of POSTing inline. A background worker drains due rows and
+//! retries failures with exponential backoff for roughly two days before
+//! dead-lettering them.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::{Notify, Semaphore};
+
+use super::{ActivityDelivery, DeliveryResult};
+use crate::data::{Database, DeliveryJob, EntityId};
+use crate::error::AppError;
+use crate::metrics::{ACTIVITYPUB_ACTIVITIES_SENT, FEDERATION_REQUESTS_TOTAL};
+
+/// Attempts made before a job is dead-lettered.
+///
+/// With 30 second steps capped at 8 hours the final attempt happens ~48
+/// hours after the first.
+pub const MAX_DELIVERY_ATTEMPTS: i64 = 16;
+/// Concurrent POSTs per worker pass.
+const MAX_CONCURRENT_DELIVERIES: usize = 10;
+
+/// Exponential backoff before the next attempt
+///
+/// The first retry waits `base`, every further failure doubles the wait up
+/// to `cap`. Returns `None` once `attempts_made` reaches `max_attempts`.
+pub fn retry_delay(
+    attempts_made: i64,
+    base: Duration,
+    cap: Duration,
+    max_attempts: i64,
+) -> Option<Duration> {
+    if attempts_made >= max_attempts {
+        return None;
+    }
+
+    let exponent = attempts_made.saturating_sub(1).clamp(0, 30) as u32;
+    let delay_secs = base
+        .num_seconds()
+        .saturating_mul(1_i64 << exponent)
+        .min(cap.num_seconds());
+    Some(Duration::seconds(delay_secs))
+}
+
+/// Whether an HTTP status means retrying can never succeed
+///
+/// Mirrors Mastodon: client errors are final, except for auth hiccups
+/// (401, usually a key fetch race), timeouts (408) and rate limiting (429).
+pub fn is_permanent_failure(status_code: Option<u16>) -> bool {
+    matches!(status_code, Some(code) if (400..500).contains(&code) && !matches!(code, 401 | 408 | 429))
+}
+
+/// Summary of a single worker pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueRunStats {
+    /// Jobs delivered and removed from the queue
+    pub delivered: usize,
+    /// Jobs that failed and were rescheduled
+    pub retried: usize,
+    /// Jobs moved to the dead-letter state
+    pub dead_lettered: usize,
+}
+
+/// Persistent delivery queue backed by SQLite
+pub struct DeliveryQueue {
+    db: Arc<Database>,
+    wakeup: Notify,
+}
+
+impl DeliveryQueue {
+    /// Create a queue over the given database
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            db,
+            wakeup: Notify::new(),
+        }
+    }
+
+    /// Enqueue an activity for each inbox
+    ///
+    /// Jobs are due immediately; the worker is woken up so fresh posts do
+    /// not wait for the next poll tick.
+    ///
+    /// # Returns
+    /// IDs of the created jobs
+    pub async fn enqueue(
+        &self,
+        activity: &serde_json::Value,
+        inbox_uris: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        if inbox_uris.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let payload = serde_json::to_string(activity)
+            .map_err(|e| AppError::Validation(format!("Failed to serialize activity: {}", e)))?;
+        let activity_id = activity
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+        let activity_type = activity
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("Unknown")
+            .to_string();
+        let now = Utc::now();
+
+        let jobs: Vec<DeliveryJob> = inbox_uris
+            .iter()
+            .map(|inbox_uri| DeliveryJob {
+                id: EntityId::new().0,
+                inbox_uri: inbox_uri.clone(),
+                activity_id: activity_id.clone(),
+                activity_type: activity_type.clone(),
+                payload: payload.clone(),
+                status: "pending".to_string(),
+                attempts: 0,
+                last_error: None,
+                last_status_code: None,
+                next_attempt_at: now,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        self.db.enqueue_deliveries(&jobs).await?;
+        self.wakeup.notify_one();
+
+        tracing::debug!(
+            activity_type = %activity_type,
+            inboxes = jobs.len(),
+            "Enqueued outbound delivery"
+        );
+
+        Ok(jobs.into_iter().map(|job| job.id).collect())
+    }
+
+    /// Wait until new jobs are enqueued
+    pub async fn notified(&self) {
+        self.wakeup.notified().await;
+    }
+
+    /// Attempt every due job once
+    ///
+    /// # Arguments
+    /// * `delivery` - Signing delivery service used for the POSTs
+    /// * `limit` - Maximum number of jobs to attempt in this pass
+    pub async fn process_due(
+        &self,
+        delivery: &ActivityDelivery,
+        limit: usize,
+    ) -> Result<QueueRunStats, AppError> {
+        let jobs = self.db.get_due_deliveries(Utc::now(), limit).await?;
+        if jobs.is_empty() {
+            return Ok(QueueRunStats::default());
+        }
+
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DELIVERIES));
+        let mut tasks = Vec::with_capacity(jobs.len());
+
+        for job in jobs {
+            let semaphore = semaphore.clone();
+            let delivery = delivery.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire().await.ok();
+                let result = match serde_json::from_str::<serde_json::Value>(&job.payload) {
+                    Ok(activity) => delivery.attempt_delivery(&job.inbox_uri, &activity).await,
+                    Err(error) => DeliveryResult {
+                        inbox_uri: job.inbox_uri.clone(),
+                        success: false,
+                        error: Some(format!("Stored payload is not valid JSON: {}", error)),
+                        // Treat as a permanent client error; the row can never succeed.
+                        status_code: Some(400),
+                    },
+                };
+                (job, result)
+            }));
+        }
+
+        let mut stats = QueueRunStats::default();
+        for task in tasks {
+            let Ok((job, result)) = task.await else {
+                continue;
+            };
+            // Keep going on a recording error: the remaining jobs were already
+            // POSTed and would be delivered twice if left pending.
+            if let Err(error) = self
+                .record_attempt(&job, &result, Utc::now(), &mut stats)
+                .await
+            {
+                tracing::error!(
+                    job_id = %job.id,
+                    inbox_uri = %job.inbox_uri,
+                    %error,
+                    "Failed to record outbound delivery attempt"
+                );
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn record_attempt(
+        &self,
+        job: &DeliveryJob,
+        result: &DeliveryResult,
+        now: DateTime<Utc>,
+        stats: &mut QueueRunStats,
+    ) -> Result<(), AppError> {
+        let attempts = job.attempts + 1;
+
+        if result.success {
+            self.db.delete_delivery(&job.id).await?;
+            ACTIVITYPUB_ACTIVITIES_SENT
+                .with_label_values(&[job.activity_type.as_str()])
+                .inc();
+            FEDERATION_REQUESTS_TOTAL
+                .with_label_values(&["outbound", "success"])
+                .inc();
+            stats.delivered += 1;
+            return Ok(());
+        }
+
+        FEDERATION_REQUESTS_TOTAL
+            .with_label_values(&["outbound", "failure"])
+            .inc();
+        let error = result.error.as_deref().unwrap_or("unknown error");
+
+        let next_delay = if is_permanent_failure(result.status_code) {
+            None
+        } else {
+            retry_delay(
+                attempts,
+                Duration::seconds(30),
+                Duration::hours(8),
+                MAX_DELIVERY_ATTEMPTS,
+            )
+        };
+
+        match next_delay {
+            Some(delay) => {
+                self.db
+                    .reschedule_delivery(&job.id, attempts, now + delay, error, result.status_code)
+                    .await?;
+                tracing::info!(
+                    job_id = %job.id,
+                    inbox_uri = %job.inbox_uri,
+                    activity_type = %job.activity_type,
+                    attempts,
+                    retry_in_seconds = delay.num_seconds(),
+                    %error,
+                    "Outbound delivery failed; retry scheduled"
+                );
+                stats.retried += 1;
+            }
+            None => {
+                self.db
+                    .dead_letter_delivery(&job.id, attempts, error, result.status_code)
+                    .await?;
+                tracing::warn!(
+                    job_id = %job.id,
+                    inbox_uri = %job.inbox_uri,
+                    activity_type = %job.activity_type,
+                    attempts,
+                    status_code = ?result.status_code,
+                    %error,
+                    "Outbound delivery dead-lettered"
+                );
+                stats.dead_lettered += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeliveryQueue, MAX_DELIVERY_ATTEMPTS, is_permanent_failure, retry_delay};
+    use crate::data::Database;
+    use crate::federation::ActivityDelivery;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const TEST_PRIVATE_KEY_PEM: &str = include_str!("../../tests/fixtures/test_private_key.pem");
+
+    async fn create_test_queue() -> (DeliveryQueue, Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("delivery_queue_test.db");
+        let db = Arc::new(Database::connect(&db_path).await.unwrap());
+        (DeliveryQueue::new(db.clone()), db, temp_dir)
+    }
+
+    fn test_delivery() -> ActivityDelivery {
+        let actor_uri = "https://local.example/users/alice".to_string();
+        ActivityDelivery::new(
+            Arc::new(
+                reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(2))
+                    .build()
+                    .unwrap(),
+            ),
+            actor_uri.clone(),
+            format!("{actor_uri}#main-key"),
+            TEST_PRIVATE_KEY_PEM.to_string(),
+        )
+    }
+
+    fn delivery_retry_delay(attempts_made: i64) -> Option<Duration> {
+        retry_delay(
+            attempts_made,
+            Duration::seconds(30),
+            Duration::hours(8),
+            MAX_DELIVERY_ATTEMPTS,
+        )
+    }
+
+    #[test]
+    fn retry_delay_doubles_and_is_capped() {
+        assert_eq!(delivery_retry_delay(1), Some(Duration::seconds(30)));
+        assert_eq!(delivery_retry_delay(2), Some(Duration::seconds(60)));
+        assert_eq!(delivery_retry_delay(3), Some(Duration::seconds(120)));
+        assert_eq!(delivery_retry_delay(15), Some(Duration::hours(8)));
+        assert_eq!(delivery_retry_delay(MAX_DELIVERY_ATTEMPTS), None);
+    }
+
+    #[test]
+    fn retry_schedule_spans_roughly_two_days() {
+        let total: i64 = (1..MAX_DELIVERY_ATTEMPTS)
+            .filter_map(delivery_retry_delay)
+            .map(|delay| delay.num_seconds())
+            .sum();
+        assert!(total >= 46 * 3600, "schedule too short: {total}s");
+        assert!(total <= 50 * 3600, "schedule too long: {total}s");
+    }
+
+    #[test]
+    fn permanent_failure_excludes_retryable_client_errors() {
+        assert!(is_permanent_failure(Some(400)));
+        assert!(is_permanent_failure(Some(404)));
+        assert!(is_permanent_failure(Some(410)));
+        assert!(!is_permanent_failure(Some(401)));
+        assert!(!is_permanent_failure(Some(408)));
+        assert!(!is_permanent_failure(Some(429)));
+        assert!(!is_permanent_failure(Some(500)));
+        assert!(!is_permanent_failure(Some(503)));
+        assert!(!is_permanent_failure(None));
+    }
+
+    #[tokio::test]
+    async fn enqueue_creates_one_pending_job_per_inbox() {
+        let (queue, db, _temp_dir) = create_test_queue().await;
+        let activity = serde_json::json!({
+            "id": "https://local.example/users/alice/create/1",
+            "type": "Create",
+        });
+
+        let ids = queue
+            .enqueue(
+                &activity,
+                &[
+                    "https://remote1.example/inbox".to_string(),
+                    "https://remote2.example/inbox".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(ids.len(), 2);
+        assert_eq!(db.count_pending_deliveries().await.unwrap(), 2);
+        let job = db.get_delivery(&ids[0]).await.unwrap().unwrap();
+        assert_eq!(job.activity_type, "Create");
+        assert_eq!(
+            job.activity_id.as_deref(),
+            Some("https://local.example/users/alice/create/1")
+        );
+        assert_eq!(job.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn queued_delivery_enqueues_instead_of_sending() {
+        let (queue, db, _temp_dir) = create_test_queue().await;
+        let delivery = test_delivery().with_queue(Arc::new(queue));
+
+        let results = delivery
+            .deliver_to_followers(
+                serde_json::json!({ "type": "Delete", "id": "https://local.example/d/1" }),
+                vec![
+                    "http://127.0.0.1:1/inbox".to_string(),
+                    "http://127.0.0.1:1/inbox".to_string(),
+                ],
+            )
+            .await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+        assert_eq!(db.count_pending_deliveries().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn process_due_reschedules_transient_failures_with_backoff() {
+        let (queue, db, _temp_dir) = create_test_queue().await;
+        let ids = queue
+            .enqueue(
+                &serde_json::json!({ "type": "Like" }),
+                &["http://127.0.0.1:1/inbox".to_string()],
+            )
+            .await
+            .unwrap();
+
+        let before = Utc::now();
+        let stats = queue.process_due(&test_delivery(), 10).await.unwrap();
+        assert_eq!(stats.retried, 1);
+        assert_eq!(stats.delivered, 0);
+
+        let job = db.get_delivery(&ids[0]).await.unwrap().unwrap();
+        assert_eq!(job.status, "pending");
+        assert_eq!(job.attempts, 1);
+        assert!(job.last_error.is_some());
+        assert!(job.next_attempt_at >= before + Duration::seconds(30));
+
+        // Not due yet, so a second pass does nothing.
+        let stats = queue.process_due(&test_delivery(), 10).await.unwrap();
+        assert_eq!(stats, super::QueueRunStats::default());
+    }
+
+    #[tokio::test]
+    async fn process_due_dead_letters_after_retry_budget_is_exhausted() {
+        let (queue, db, _temp_dir) = create_test_queue().await;
+        let ids = queue
+            .enqueue(
+                &serde_json::json!({ "type": "Follow" }),
+                &["http://127.0.0.1:1/inbox".to_string()],
+            )
+            .await
+            .unwrap();
+        db.reschedule_delivery(
+            &ids[0],
+            MAX_DELIVERY_ATTEMPTS - 1,
+            Utc::now() - Duration::seconds(1),
+            "previous failure",
+            Some(503),
+        )
+        .await
+        .unwrap();
+
+        let stats = queue.process_due(&test_delivery(), 10).await.unwrap();
+        assert_eq!(stats.dead_lettered, 1);
+
+        let job = db.get_delivery(&ids[0]).await.unwrap().unwrap();
+        assert_eq!(job.status, "dead");
+        assert_eq!(job.attempts, MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(db.count_pending_deliveries().await.unwrap(), 0);
+        assert_eq!(db.get_dead_deliveries(10).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+//! Featured collections
+//!
+//! Pinned posts are published as the actor's `featured` collection and
+//! featured hashtags as `featuredTags`. Remote actors' `featured`
+//! collections are read to show their pinned posts.
+
+use chrono::{DateTime, Utc};
+
+use super::signature::FetchSigner;
+use super::webfinger::{ACTIVITY_JSON_ACCEPT, fetch_with_validated_redirects};
+use crate::data::{EntityId, PersistedReason, Status};
+use crate::error::AppError;
+
+/// Most pinned posts read from a remote `featured` collection
+pub const MAX_REMOTE_FEATURED_ITEMS: usize = 20;
+
+/// URI of an actor's featured (pinned posts) collection
+pub fn featured_collection_uri(actor_uri: &str) -> String {
+    format!("{}/collections/featured", actor_uri)
+}
+
+/// URI of an actor's featured hashtags collection
+pub fn featured_tags_collection_uri(actor_uri: &str) -> String {
+    format!("{}/collections/tags", actor_uri)
+}
+
+/// Fetch an ActivityPub object or collection
+pub(crate) async fn fetch_object(
+    uri: &str,
+    http_client: &reqwest::Client,
+    signer: Option<&FetchSigner>,
+) -> Result<serde_json::Value, AppError> {
+    let parsed = url::Url::parse(uri)
+        .map_err(|_| AppError::Validation("object URI must be a valid URL".to_string()))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(AppError::Validation(
+            "object URI must use http or https".to_string(),
+        ));
+    }
+
+    let response =
+        fetch_with_validated_redirects(http_client, &parsed, ACTIVITY_JSON_ACCEPT, signer).await?;
+    if !response.status().is_success() {
+        return Err(AppError::Federation(format!(
+            "Object fetch failed with HTTP {}",
+            response.status()
+        )));
+    }
+
+    response.json().await.map_err(|error| {
+        AppError::Federation(format!("Failed to decode object document: {}", error))
+    })
+}
+
+/// Items of a collection or collection page, embedded or by reference
+fn collection_items(collection: &serde_json::Value) -> Vec<serde_json::Value> {
+    ["orderedItems", "items"]
+        .iter()
+        .find_map(|key| collection.get(*key))
+        .map(|items| match items {
+            serde_json::Value::Array(items) => items.clone(),
+            item => vec![item.clone()],
+        })
+        .unwrap_or_default()
+}
+
+/// Fetch the objects of a remote `featured` collection
+///
+/// Follows `first` when the collection is paged and dereferences items
+/// given by URI. Items that cannot be fetched are skipped.
+pub async fn fetch_featured_objects(
+    featured_uri: &str,
+    http_client: &reqwest::Client,
+    signer: Option<&FetchSigner>,
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let collection = fetch_object(featured_uri, http_client, signer).await?;
+    let mut items = collection_items(&collection);
+    if items.is_empty() {
+        match collection.get("first") {
+            Some(serde_json::Value::String(first_uri)) => {
+                let page = fetch_object(first_uri, http_client, signer).await?;
+                items = collection_items(&page);
+            }
+            Some(first) if first.is_object() => items = collection_items(first),
+            _ => {}
+        }
+    }
+
+    let mut objects = Vec::new();
+    for item in items.into_iter().take(MAX_REMOTE_FEATURED_ITEMS) {
+        match item {
+            serde_json::Value::String(uri) => match fetch_object(&uri, http_client, signer).await {
+                Ok(object) => objects.push(object),
+                Err(error) => {
+                    tracing::debug!("Skipping featured item {}: {}", uri, error);
+                }
+            },
+            object if object.is_object() => objects.push(object),
+            _ => {}
+        }
+    }
+
+    Ok(objects)
+}
+
+fn attributed_to_actor(object: &serde_json::Value, actor_uri: &str) -> bool {
+    let matches = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .or_else(|| value.get("id").and_then(|id| id.as_str()))
+            == Some(actor_uri)
+    };
+
+    match object.get("attributedTo") {
+        Some(serde_json::Value::Array(values)) => values.iter().any(matches),
+        Some(value) => matches(value),
+        None => false,
+    }
+}
+
+fn same_host(left: &str, right: &str) -> bool {
+    match (url::Url::parse(left), url::Url::parse(right)) {
+        (Ok(left), Ok(right)) => left.host_str().is_some() && left.host_str() == right.host_str(),
+        _ => false,
+    }
+}
+
+/// Build the stored form of a post pinned by a remote actor
+///
+/// Returns `None` unless the object is a post by `actor_uri` hosted on
+/// the actor's server.
+pub fn featured_status_from_object(
+    object: &serde_json::Value,
+    actor_uri: &str,
+    account_address: &str,
+) -> Option<Status> {
+    let object_type = object.get("type").and_then(|t| t.as_str())?;
+    if !matches!(object_type, "Note" | "Article" | "Question") {
+        return None;
+    }
+
+    let uri = object.get("id").and_then(|id| id.as_str())?;
+    if !same_host(uri, actor_uri) || !attributed_to_actor(object, actor_uri) {
+        return None;
+    }
+
+    let content_warning = object
+        .get("summary")
+        .and_then(|summary| summary.as_str())
+        .map(super::sanitize_status_html)
+        .filter(|summary| !summary.is_empty());
+    let created_at = object
+        .get("published")
+        .and_then(|published| published.as_str())
+        .and_then(|published| DateTime::parse_from_rfc3339(published).ok())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    Some(Status {
+        id: EntityId::new().0,
+        uri: uri.to_string(),
+        content: super::sanitize_status_html(
+            object
+                .get("content")
+                .and_then(|content| content.as_str())
+                .unwrap_or_default(),
+        ),
+        content_warning,
+        visibility: super::activity::extract_visibility(object),
+        language: None,
+        account_address: account_address.to_string(),
+        is_local: false,
+        in_reply_to_uri: object
+            .get("inReplyTo")
+            .and_then(|reply| reply.as_str())
+            .map(str::to_string),
+        boost_of_uri: None,
+        persisted_reason: PersistedReason::Featured.as_str().to_string(),
+        created_at,
+        fetched_at: Some(Utc::now()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTOR: &str = "https://remote.example/users/bob";
+
+    fn note(id: &str, attributed_to: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": "Note",
+            "attributedTo": attributed_to,
+            "content": "<p>pinned<script>alert(1)</script></p>",
+            "published": "2026-01-02T03:04:05Z",
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        })
+    }
+
+    #[test]
+    fn featured_collection_uris_hang_off_the_actor() {
+        assert_eq!(
+            featured_collection_uri(ACTOR),
+            "https://remote.example/users/bob/collections/featured"
+        );
+        assert_eq!(
+            featured_tags_collection_uri(ACTOR),
+            "https://remote.example/users/bob/collections/tags"
+        );
+    }
+
+    #[test]
+    fn collection_items_reads_ordered_and_unordered_items() {
+        let ordered = serde_json::json!({"orderedItems": ["a", "b"]});
+        assert_eq!(collection_items(&ordered).len(), 2);
+        let unordered = serde_json::json!({"items": [{"id": "a"}]});
+        assert_eq!(collection_items(&unordered).len(), 1);
+        assert!(collection_items(&serde_json::json!({"totalItems": 0})).is_empty());
+    }
+
+    #[test]
+    fn featured_status_from_object_stores_sanitized_post() {
+        let object = note("https://remote.example/notes/1", ACTOR);
+        let status = featured_status_from_object(&object, ACTOR, "bob@remote.example").unwrap();
+
+        assert_eq!(status.uri, "https://remote.example/notes/1");
+        assert_eq!(status.account_address, "bob@remote.example");
+        assert_eq!(status.visibility, "public");
+        assert_eq!(status.persisted_reason, "featured");
+        assert!(!status.is_local);
+        assert!(!status.content.contains("script"));
+        assert_eq!(status.created_at.to_rfc3339(), "2026-01-02T03:04:05+00:00");
+    }
+
+    #[test]
+    fn featured_status_from_object_rejects_foreign_posts() {
+        let other_author = note(
+            "https://remote.example/notes/1",
+            "https://remote.example/users/carol",
+        );
+        assert!(featured_status_from_object(&other_author, ACTOR, "bob@remote.example").is_none());
+
+        let other_host = note("https://elsewhere.example/notes/1", ACTOR);
+        assert!(featured_status_from_object(&other_host, ACTOR, "bob@remote.example").is_none());
+
+        let mut person = note("https://remote.example/notes/1", ACTOR);
+        person["type"] = serde_json::json!("Person");
+        assert!(featured_status_from_object(&person, ACTOR, "bob@remote.example").is_none());
+    }
+}
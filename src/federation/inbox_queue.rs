@@ -0,0 +1,569 @@
+//! Persistent inbound activity queue
+//!
+//! The inbox handlers verify the HTTP signature, write the activity to the
+//! `inbox_queue` table and answer `202 Accepted` right away. A background
+//! worker runs `ActivityProcessor::process` on due rows, with a bounded
+//! number of concurrent activities per remote domain, and retries transient
+//! failures (fetches, database errors) with exponential backoff. Each
+//! activity gets a time limit, so a slow domain cannot hold up the others,
+//! and dead-lettered activities are purged after a week.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use tokio::sync::{Notify, Semaphore};
+
+use super::ActivityProcessor;
+use super::delivery_queue::retry_delay;
+use super::rate_limit::extract_domain;
+use crate::data::{Database, EntityId, InboxJob};
+use crate::error::AppError;
+
+/// Attempts made before an inbound activity is dead-lettered.
+///
+/// With 30 second steps the final attempt happens about an hour after the first.
+pub const MAX_INBOX_ATTEMPTS: i64 = 8;
+/// Time a single activity may take before it counts as a transient failure.
+///
+/// A pass also stops starting new activities after this long.
+const DEFAULT_JOB_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+/// Days a dead-lettered activity is kept for inspection before it is purged.
+pub const DEAD_INBOX_JOB_RETENTION_DAYS: i64 = 7;
+
+/// Whether a processing error may succeed on a later attempt
+///
+/// Remote fetches, storage and database failures are transient. Validation
+/// failures, blocked domains and missing objects will fail the same way
+/// every time, so those activities are dropped instead.
+pub fn is_transient_inbox_error(error: &AppError) -> bool {
+    matches!(
+        error,
+        AppError::Database(_)
+            | AppError::HttpClient(_)
+            | AppError::Federation(_)
+            | AppError::Storage(_)
+            | AppError::RateLimited
+            | AppError::Internal(_)
+    )
+}
+
+/// Summary of a single worker pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InboxRunStats {
+    /// Activities processed and removed from the queue
+    pub processed: usize,
+    /// Activities that failed permanently and were dropped
+    pub rejected: usize,
+    /// Activities that failed transiently and were rescheduled
+    pub retried: usize,
+    /// Activities moved to the dead-letter state
+    pub dead_lettered: usize,
+}
+
+/// Persistent inbound activity queue backed by SQLite
+pub struct InboxQueue {
+    db: Arc<Database>,
+    wakeup: Notify,
+    /// Activities processed concurrently across all domains
+    max_concurrent: usize,
+    /// Activities processed concurrently for a single domain
+    per_domain_concurrency: usize,
+    /// Time limit for a single activity, and for starting activities in a pass
+    job_timeout: std::time::Duration,
+}
+
+impl InboxQueue {
+    /// Create a queue over the given database
+    ///
+    /// # Arguments
+    /// * `max_concurrent` - Activities processed concurrently in total
+    /// * `per_domain_concurrency` - Activities processed concurrently per
+    ///   remote domain; 1 keeps each domain's activities in arrival order
+    pub fn new(db: Arc<Database>, max_concurrent: usize, per_domain_concurrency: usize) -> Self {
+        Self {
+            db,
+            wakeup: Notify::new(),
+            max_concurrent: max_concurrent.max(1),
+            per_domain_concurrency: per_domain_concurrency.max(1),
+            job_timeout: DEFAULT_JOB_TIMEOUT,
+        }
+    }
+
+    /// Override the time limit for a single activity
+    pub fn with_job_timeout(mut self, job_timeout: std::time::Duration) -> Self {
+        self.job_timeout = job_timeout;
+        self
+    }
+
+    /// Enqueue a verified activity from the signing actor
+    ///
+    /// The job is due immediately and the worker is woken up.
+    ///
+    /// # Returns
+    /// ID of the created job
+    pub async fn enqueue(
+        &self,
+        actor_uri: &str,
+        activity: &serde_json::Value,
+    ) -> Result<String, AppError> {
+        let payload = serde_json::to_string(activity)
+            .map_err(|e| AppError::Validation(format!("Failed to serialize activity: {}", e)))?;
+        let now = Utc::now();
+        let job = InboxJob {
+            id: EntityId::new().0,
+            actor_uri: actor_uri.to_string(),
+            actor_domain: extract_domain(actor_uri),
+            activity_id: activity
+                .get("id")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+            activity_type: activity
+                .get("type")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("Unknown")
+                .to_string(),
+            payload,
+            status: "pending".to_string(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.db.enqueue_inbox_job(&job).await?;
+        self.wakeup.notify_one();
+
+        tracing::debug!(
+            actor = %job.actor_uri,
+            activity_type = %job.activity_type,
+            "Enqueued inbound activity"
+        );
+
+        Ok(job.id)
+    }
+
+    /// Wait until new jobs are enqueued
+    pub async fn notified(&self) {
+        self.wakeup.notified().await;
+    }
+
+    /// Process every due job once
+    ///
+    /// Jobs are grouped by the signing actor's domain. Each domain is worked
+    /// through in queue order with at most `per_domain_concurrency` jobs in
+    /// flight, and at most `max_concurrent` jobs run across all domains.
+    /// A job that runs past the job timeout fails transiently, and jobs not
+    /// started within the job timeout are left due for the next pass, so a
+    /// slow domain delays the pass by at most about twice the timeout.
+    ///
+    /// # Arguments
+    /// * `processor` - Processor for the local account
+    /// * `limit` - Maximum number of jobs to attempt in this pass
+    pub async fn process_due(
+        &self,
+        processor: Arc<ActivityProcessor>,
+        limit: usize,
+    ) -> Result<InboxRunStats, AppError> {
+        let jobs = self.db.get_due_inbox_jobs(Utc::now(), limit).await?;
+        if jobs.is_empty() {
+            return Ok(InboxRunStats::default());
+        }
+
+        let mut domain_index: HashMap<String, usize> = HashMap::new();
+        let mut by_domain: Vec<Vec<InboxJob>> = Vec::new();
+        for job in jobs {
+            let index = *domain_index
+                .entry(job.actor_domain.clone())
+                .or_insert_with(|| {
+                    by_domain.push(Vec::new());
+                    by_domain.len() - 1
+                });
+            by_domain[index].push(job);
+        }
+
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
+        let per_domain = self.per_domain_concurrency;
+        let job_timeout = self.job_timeout;
+        let start_deadline = tokio::time::Instant::now() + job_timeout;
+        let mut tasks = Vec::with_capacity(by_domain.len());
+
+        for domain_jobs in by_domain {
+            let semaphore = semaphore.clone();
+            let processor = processor.clone();
+
+            tasks.push(tokio::spawn(async move {
+                futures::stream::iter(domain_jobs)
+                    .map(|job| {
+                        let semaphore = semaphore.clone();
+                        let processor = processor.clone();
+                        async move {
+                            let _permit = semaphore.acquire_owned().await.ok();
+                            if tokio::time::Instant::now() >= start_deadline {
+                                return (job, None);
+                            }
+                            let result = match serde_json::from_str(&job.payload) {
+                                Ok(activity) => {
+                                    run_with_timeout(
+                                        job_timeout,
+                                        processor.process(activity, &job.actor_uri),
+                                    )
+                                    .await
+                                }
+                                Err(error) => Err(AppError::Validation(format!(
+                                    "Stored payload is not valid JSON: {}",
+                                    error
+                                ))),
+                            };
+                            (job, Some(result))
+                        }
+                    })
+                    .buffered(per_domain)
+                    .collect::<Vec<_>>()
+                    .await
+            }));
+        }
+
+        let mut stats = InboxRunStats::default();
+        for task in tasks {
+            let Ok(results) = task.await else {
+                continue;
+            };
+            for (job, result) in results {
+                // Not started before the deadline; still due next pass.
+                let Some(result) = result else {
+                    continue;
+                };
+                // Keep going on a recording error: the remaining activities were
+                // already processed and would be applied twice if left pending.
+                if let Err(error) = self
+                    .record_attempt(&job, result, Utc::now(), &mut stats)
+                    .await
+                {
+                    tracing::error!(
+                        job_id = %job.id,
+                        actor = %job.actor_uri,
+                        %error,
+                        "Failed to record inbound activity attempt"
+                    );
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Delete dead-lettered activities older than the retention period
+    ///
+    /// # Returns
+    /// Number of activities deleted
+    pub async fn purge_dead(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        self.db
+            .delete_dead_inbox_jobs(now - Duration::days(DEAD_INBOX_JOB_RETENTION_DAYS))
+            .await
+    }
+
+    async fn record_attempt(
+        &self,
+        job: &InboxJob,
+        result: Result<(), AppError>,
+        now: DateTime<Utc>,
+        stats: &mut InboxRunStats,
+    ) -> Result<(), AppError> {
+        let error = match result {
+            Ok(()) => {
+                self.db.delete_inbox_job(&job.id).await?;
+                stats.processed += 1;
+                return Ok(());
+            }
+            Err(error) => error,
+        };
+
+        let attempts = job.attempts + 1;
+        if !is_transient_inbox_error(&error) {
+            self.db.delete_inbox_job(&job.id).await?;
+            tracing::info!(
+                job_id = %job.id,
+                actor = %job.actor_uri,
+                activity_type = %job.activity_type,
+                %error,
+                "Inbound activity rejected"
+            );
+            stats.rejected += 1;
+            return Ok(());
+        }
+
+        let error = error.to_string();
+        match retry_delay(
+            attempts,
+            Duration::seconds(30),
+            Duration::hours(1),
+            MAX_INBOX_ATTEMPTS,
+        ) {
+            Some(delay) => {
+                self.db
+                    .reschedule_inbox_job(&job.id, attempts, now + delay, &error)
+                    .await?;
+                tracing::info!(
+                    job_id = %job.id,
+                    actor = %job.actor_uri,
+                    activity_type = %job.activity_type,
+                    attempts,
+                    retry_in_seconds = delay.num_seconds(),
+                    %error,
+                    "Inbound activity failed; retry scheduled"
+                );
+                stats.retried += 1;
+            }
+            None => {
+                self.db
+                    .dead_letter_inbox_job(&job.id, attempts, &error)
+                    .await?;
+                tracing::warn!(
+                    job_id = %job.id,
+                    actor = %job.actor_uri,
+                    activity_type = %job.activity_type,
+                    attempts,
+                    %error,
+                    "Inbound activity dead-lettered"
+                );
+                stats.dead_lettered += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run an activity, failing transiently once `timeout` has passed
+async fn run_with_timeout(
+    timeout: std::time::Duration,
+    processing: impl std::future::Future<Output = Result<(), AppError>>,
+) -> Result<(), AppError> {
+    tokio::time::timeout(timeout, processing)
+        .await
+        .unwrap_or_else(|_| {
+            Err(AppError::Federation(format!(
+                "Processing timed out after {}s",
+                timeout.as_secs()
+            )))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEAD_INBOX_JOB_RETENTION_DAYS, InboxQueue, InboxRunStats, MAX_INBOX_ATTEMPTS,
+        is_transient_inbox_error, run_with_timeout,
+    };
+    use crate::data::{Database, EntityId, Follow, ProfileCache, TimelineCache};
+    use crate::error::AppError;
+    use crate::federation::ActivityProcessor;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn create_test_queue() -> (InboxQueue, Arc<ActivityProcessor>, Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("inbox_queue_test.db");
+        let db = Arc::new(Database::connect(&db_path).await.unwrap());
+        let processor = ActivityProcessor::new(
+            db.clone(),
+            Arc::new(TimelineCache::new(16).await.unwrap()),
+            Arc::new(ProfileCache::new(86400).await.unwrap()),
+            Arc::new(reqwest::Client::new()),
+            "alice@example.com".to_string(),
+            "https".to_string(),
+        );
+        (
+            InboxQueue::new(db.clone(), 4, 1),
+            Arc::new(processor),
+            db,
+            temp_dir,
+        )
+    }
+
+    #[test]
+    fn transient_errors_exclude_validation_and_authorization() {
+        assert!(is_transient_inbox_error(&AppError::Federation(
+            "timeout".to_string()
+        )));
+        assert!(is_transient_inbox_error(&AppError::RateLimited));
+        assert!(!is_transient_inbox_error(&AppError::Validation(
+            "bad".to_string()
+        )));
+        assert!(!is_transient_inbox_error(&AppError::Forbidden));
+        assert!(!is_transient_inbox_error(&AppError::NotFound));
+    }
+
+    #[tokio::test]
+    async fn enqueue_creates_pending_job_for_actor_domain() {
+        let (queue, _processor, db, _temp_dir) = create_test_queue().await;
+
+        let id = queue
+            .enqueue(
+                "https://Remote.Example/users/bob",
+                &json!({
+                    "id": "https://remote.example/likes/1",
+                    "type": "Like",
+                }),
+            )
+            .await
+            .unwrap();
+
+        let job = db.get_inbox_job(&id).await.unwrap().unwrap();
+        assert_eq!(job.actor_domain, "remote.example");
+        assert_eq!(job.activity_type, "Like");
+        assert_eq!(
+            job.activity_id.as_deref(),
+            Some("https://remote.example/likes/1")
+        );
+        assert_eq!(job.attempts, 0);
+        assert_eq!(db.count_pending_inbox_jobs().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn process_due_removes_processed_and_rejected_jobs() {
+        let (queue, processor, db, _temp_dir) = create_test_queue().await;
+        let actor_uri = "https://remote.example/users/bob";
+        queue
+            .enqueue(
+                actor_uri,
+                &json!({
+                    "type": "Like",
+                    "actor": actor_uri,
+                    "object": "https://elsewhere.example/statuses/1",
+                }),
+            )
+            .await
+            .unwrap();
+        queue
+            .enqueue(actor_uri, &json!({ "type": "Bogus", "actor": actor_uri }))
+            .await
+            .unwrap();
+
+        let stats = queue.process_due(processor, 10).await.unwrap();
+        assert_eq!(
+            stats,
+            InboxRunStats {
+                processed: 1,
+                rejected: 1,
+                ..InboxRunStats::default()
+            }
+        );
+        assert_eq!(db.count_pending_inbox_jobs().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn process_due_reschedules_transient_failures_and_dead_letters_last_attempt() {
+        let (queue, processor, db, _temp_dir) = create_test_queue().await;
+        db.insert_follow(&Follow {
+            id: EntityId::new().0,
+            target_address: "bob@remote.example".to_string(),
+            uri: "https://example.com/users/alice/follow/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+        let actor_uri = "https://remote.example/users/bob";
+        // The Move target cannot be fetched, which is a transient failure.
+        let id = queue
+            .enqueue(
+                actor_uri,
+                &json!({
+                    "type": "Move",
+                    "actor": actor_uri,
+                    "object": actor_uri,
+                    "target": "https://unreachable.invalid/users/bob",
+                }),
+            )
+            .await
+            .unwrap();
+
+        let before = Utc::now();
+        let stats = queue.process_due(processor.clone(), 10).await.unwrap();
+        assert_eq!(stats.retried, 1);
+
+        let job = db.get_inbox_job(&id).await.unwrap().unwrap();
+        assert_eq!(job.status, "pending");
+        assert_eq!(job.attempts, 1);
+        assert!(job.last_error.is_some());
+        assert!(job.next_attempt_at >= before + Duration::seconds(30));
+
+        // Not due yet, so a second pass does nothing.
+        let stats = queue.process_due(processor.clone(), 10).await.unwrap();
+        assert_eq!(stats, InboxRunStats::default());
+
+        db.reschedule_inbox_job(
+            &id,
+            MAX_INBOX_ATTEMPTS - 1,
+            Utc::now() - Duration::seconds(1),
+            "previous failure",
+        )
+        .await
+        .unwrap();
+        let stats = queue.process_due(processor, 10).await.unwrap();
+        assert_eq!(stats.dead_lettered, 1);
+
+        let job = db.get_inbox_job(&id).await.unwrap().unwrap();
+        assert_eq!(job.status, "dead");
+        assert_eq!(job.attempts, MAX_INBOX_ATTEMPTS);
+        assert_eq!(db.count_pending_inbox_jobs().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn run_with_timeout_fails_transiently_when_processing_hangs() {
+        let result = run_with_timeout(
+            std::time::Duration::from_millis(10),
+            std::future::pending::<Result<(), AppError>>(),
+        )
+        .await;
+        let error = result.unwrap_err();
+        assert!(is_transient_inbox_error(&error));
+
+        let result = run_with_timeout(std::time::Duration::from_secs(1), async { Ok(()) }).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn process_due_leaves_jobs_not_started_before_the_deadline() {
+        let (queue, processor, db, _temp_dir) = create_test_queue().await;
+        let queue = queue.with_job_timeout(std::time::Duration::ZERO);
+        let actor_uri = "https://remote.example/users/bob";
+        queue
+            .enqueue(actor_uri, &json!({ "type": "Bogus", "actor": actor_uri }))
+            .await
+            .unwrap();
+
+        let stats = queue.process_due(processor, 10).await.unwrap();
+        assert_eq!(stats, InboxRunStats::default());
+        assert_eq!(db.count_pending_inbox_jobs().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn purge_dead_removes_dead_letters_past_retention() {
+        let (queue, _processor, db, _temp_dir) = create_test_queue().await;
+        let actor_uri = "https://remote.example/users/bob";
+        let activity = json!({ "type": "Like", "actor": actor_uri });
+        let dead = queue.enqueue(actor_uri, &activity).await.unwrap();
+        let pending = queue.enqueue(actor_uri, &activity).await.unwrap();
+        db.dead_letter_inbox_job(&dead, MAX_INBOX_ATTEMPTS, "gone")
+            .await
+            .unwrap();
+
+        // Still within retention
+        assert_eq!(queue.purge_dead(Utc::now()).await.unwrap(), 0);
+        assert!(db.get_inbox_job(&dead).await.unwrap().is_some());
+
+        let purged = queue
+            .purge_dead(Utc::now() + Duration::days(DEAD_INBOX_JOB_RETENTION_DAYS + 1))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert!(db.get_inbox_job(&dead).await.unwrap().is_none());
+        assert!(db.get_inbox_job(&pending).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,236 @@
+//! Instance actor
+//!
+//! Server-wide `Application` actor served at `/actor`. Its key signs
+//! requests made on behalf of the server, such as public key fetches, so
+//! that peers running authorized fetch answer them. The actor document is
+//! always served without a signature, otherwise two secure-mode servers
+//! could never verify each other.
+
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+
+use super::ActivityDelivery;
+use super::signature::FetchSigner;
+use crate::data::Database;
+use crate::error::AppError;
+
+/// Path of the instance actor document
+pub const INSTANCE_ACTOR_PATH: &str = "/actor";
+
+/// Settings keys holding the instance actor keypair
+const PRIVATE_KEY_SETTING_KEY: &str = "instance_actor_private_key_pem";
+const PUBLIC_KEY_SETTING_KEY: &str = "instance_actor_public_key_pem";
+
+/// Instance actor keys are only used for fetches, so 2048 bits matches
+/// what other servers use for theirs.
+const INSTANCE_ACTOR_KEY_BITS: usize = 2048;
+
+struct InstanceActorKeys {
+    private_key_pem: String,
+    public_key_pem: String,
+}
+
+/// Server-wide actor used to sign outgoing fetches
+///
+/// The keypair is created on first use and stored in `settings`, so it
+/// survives restarts without slowing down startup.
+pub struct InstanceActor {
+    db: Arc<Database>,
+    /// Actor URI (`{base_url}/actor`)
+    actor_uri: String,
+    /// Local domain, used as `preferredUsername`
+    domain: String,
+    keys: OnceCell<InstanceActorKeys>,
+}
+
+impl InstanceActor {
+    /// Create the instance actor for a server
+    ///
+    /// # Arguments
+    /// * `db` - Database holding the keypair
+    /// * `base_url` - Server base URL (e.g. `https://social.example.com`)
+    /// * `domain` - Server domain
+    pub fn new(db: Arc<Database>, base_url: &str, domain: &str) -> Self {
+        Self {
+            db,
+            actor_uri: format!("{}{}", base_url, INSTANCE_ACTOR_PATH),
+            domain: domain.to_string(),
+            keys: OnceCell::new(),
+        }
+    }
+
+    /// Instance actor URI
+    pub fn actor_uri(&self) -> &str {
+        &self.actor_uri
+    }
+
+    /// Key ID of the instance actor key
+    pub fn key_id(&self) -> String {
+        super::local_key_id(&self.actor_uri)
+    }
+
+    async fn keys(&self) -> Result<&InstanceActorKeys, AppError> {
+        self.keys
+            .get_or_try_init(|| self.load_or_create_keys())
+            .await
+    }
+
+    async fn load_or_create_keys(&self) -> Result<InstanceActorKeys, AppError> {
+        let private_key_pem = self.db.get_setting(PRIVATE_KEY_SETTING_KEY).await?;
+        let public_key_pem = self.db.get_setting(PUBLIC_KEY_SETTING_KEY).await?;
+        if let (Some(private_key_pem), Some(public_key_pem)) = (private_key_pem, public_key_pem) {
+            return Ok(InstanceActorKeys {
+                private_key_pem,
+                public_key_pem,
+            });
+        }
+
+        tracing::info!("Generating instance actor keypair...");
+        let keys = tokio::task::spawn_blocking(generate_keys)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))??;
+
+        self.db
+            .set_setting(PRIVATE_KEY_SETTING_KEY, &keys.private_key_pem)
+            .await?;
+        self.db
+            .set_setting(PUBLIC_KEY_SETTING_KEY, &keys.public_key_pem)
+            .await?;
+
+        Ok(keys)
+    }
+
+    /// Public key of the instance actor in PEM format
+    pub async fn public_key_pem(&self) -> Result<String, AppError> {
+        Ok(self.keys().await?.public_key_pem.clone())
+    }
+
+    /// Signer for outgoing fetches made on behalf of the server
+    pub async fn fetch_signer(&self) -> Result<FetchSigner, AppError> {
+        Ok(FetchSigner::new(
+            self.key_id(),
+            self.keys().await?.private_key_pem.clone(),
+        ))
+    }
+
+    /// Delivery that signs and attributes activities to the instance actor
+    ///
+    /// Used for activities sent on behalf of the server rather than the
+    /// local account, such as forwarded reports. Deliveries are POSTed
+    /// directly, since the persistent queue signs as the local account.
+    pub async fn delivery(
+        &self,
+        http_client: Arc<reqwest::Client>,
+    ) -> Result<ActivityDelivery, AppError> {
+        Ok(ActivityDelivery::new(
+            http_client,
+            self.actor_uri.clone(),
+            self.key_id(),
+            self.keys().await?.private_key_pem.clone(),
+        ))
+    }
+
+    /// ActivityPub document of the instance actor
+    pub async fn document(&self) -> Result<serde_json::Value, AppError> {
+        let base_url = self
+            .actor_uri
+            .strip_suffix(INSTANCE_ACTOR_PATH)
+            .unwrap_or(&self.actor_uri);
+
+        Ok(serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                "https://w3id.org/security/v1"
+            ],
+            "type": "Application",
+            "id": self.actor_uri,
+            "preferredUsername": self.domain,
+            "inbox": format!("{}/inbox", base_url),
+            "outbox": format!("{}/outbox", self.actor_uri),
+            "manuallyApprovesFollowers": true,
+            "publicKey": {
+                "id": self.key_id(),
+                "owner": self.actor_uri,
+                "publicKeyPem": self.public_key_pem().await?
+            }
+        }))
+    }
+}
+
+fn generate_keys() -> Result<InstanceActorKeys, AppError> {
+    use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+    use rsa::{RsaPrivateKey, RsaPublicKey};
+
+    let mut rng = rand::thread_rng();
+    let private_key = RsaPrivateKey::new(&mut rng, INSTANCE_ACTOR_KEY_BITS)
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let public_key = RsaPublicKey::from(&private_key);
+
+    Ok(InstanceActorKeys {
+        private_key_pem: private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .map_err(|e| AppError::Internal(e.into()))?
+            .to_string(),
+        public_key_pem: public_key
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|e| AppError::Internal(e.into()))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn create_test_db() -> (Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::connect(&temp_dir.path().join("test.db"))
+            .await
+            .unwrap();
+        (Arc::new(db), temp_dir)
+    }
+
+    #[tokio::test]
+    async fn instance_actor_key_is_created_once_and_persisted() {
+        let (db, _temp_dir) = create_test_db().await;
+
+        let actor = InstanceActor::new(db.clone(), "https://example.com", "example.com");
+        let public_key_pem = actor.public_key_pem().await.unwrap();
+        assert!(public_key_pem.contains("BEGIN PUBLIC KEY"));
+        assert_eq!(actor.public_key_pem().await.unwrap(), public_key_pem);
+
+        // A restarted server loads the stored key instead of generating one
+        let restarted = InstanceActor::new(db, "https://example.com", "example.com");
+        assert_eq!(restarted.public_key_pem().await.unwrap(), public_key_pem);
+
+        let signer = restarted.fetch_signer().await.unwrap();
+        assert_eq!(signer.key_id(), "https://example.com/actor#main-key");
+
+        let delivery = restarted
+            .delivery(Arc::new(reqwest::Client::new()))
+            .await
+            .unwrap();
+        assert_eq!(delivery.actor_uri(), "https://example.com/actor");
+    }
+
+    #[tokio::test]
+    async fn instance_actor_document_is_an_application() {
+        let (db, _temp_dir) = create_test_db().await;
+        let actor = InstanceActor::new(db, "https://example.com", "example.com");
+
+        let document = actor.document().await.unwrap();
+        assert_eq!(document["type"], "Application");
+        assert_eq!(document["id"], "https://example.com/actor");
+        assert_eq!(document["preferredUsername"], "example.com");
+        assert_eq!(document["inbox"], "https://example.com/inbox");
+        assert_eq!(
+            document["publicKey"]["id"],
+            "https://example.com/actor#main-key"
+        );
+        assert_eq!(
+            document["publicKey"]["publicKeyPem"],
+            actor.public_key_pem().await.unwrap()
+        );
+    }
+}
@@ -78,7 +78,7 @@ impl PublicKeyCache {
 
         // 2. Cache miss or expired - fetch from remote
         tracing::debug!("Public key cache miss for {}, fetching...", key_id);
-        let pem = super::signature::fetch_public_key(key_id, &self.http_client).await?;
+        let pem = super::signature::fetch_public_key(key_id, &self.http_client, None).await?;
 
         // 3. Update cache (write lock)
         {
@@ -0,0 +1,152 @@
+//! Mention parsing
+//!
+//! Parses `@user` and `@user@domain` mentions in plain status text.
+
+/// Characters allowed inside a username (`.` and `-` only between others)
+fn is_username_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-')
+}
+
+/// Characters allowed inside a domain (`.` and `-` only between others)
+fn is_domain_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-')
+}
+
+/// Take the longest run matching `allowed`, without trailing `.` or `-`.
+fn take_segment(chars: &[char], start: usize, allowed: fn(char) -> bool) -> usize {
+    let mut end = start;
+    while end < chars.len() && allowed(chars[end]) {
+        end += 1;
+    }
+    while end > start && matches!(chars[end - 1], '.' | '-') {
+        end -= 1;
+    }
+    end
+}
+
+/// A mention token found in text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionToken {
+    /// Char index just past the mention
+    pub end: usize,
+    pub username: String,
+    /// Lowercased domain, `None` for a bare `@user` mention
+    pub domain: Option<String>,
+}
+
+/// Parse a mention starting at `chars[index]`, which must be `@`.
+///
+/// Follows Mastodon's mention syntax: the `@` must not follow a word
+/// character, `/` or `@`, so e-mail addresses and URL paths are skipped.
+pub fn parse_mention_at(chars: &[char], index: usize) -> Option<MentionToken> {
+    if chars.get(index) != Some(&'@') {
+        return None;
+    }
+    let preceded_by_word = index > 0 && {
+        let previous = chars[index - 1];
+        previous.is_alphanumeric() || matches!(previous, '_' | '/' | '@')
+    };
+    if preceded_by_word {
+        return None;
+    }
+
+    let username_start = index + 1;
+    let username_end = take_segment(chars, username_start, is_username_char);
+    if username_end == username_start {
+        return None;
+    }
+    let username: String = chars[username_start..username_end].iter().collect();
+
+    if chars.get(username_end) == Some(&'@') {
+        let domain_start = username_end + 1;
+        let domain_end = take_segment(chars, domain_start, is_domain_char);
+        if domain_end > domain_start {
+            let domain = chars[domain_start..domain_end]
+                .iter()
+                .collect::<String>()
+                .to_lowercase();
+            return Some(MentionToken {
+                end: domain_end,
+                username,
+                domain: Some(domain),
+            });
+        }
+    }
+
+    Some(MentionToken {
+        end: username_end,
+        username,
+        domain: None,
+    })
+}
+
+/// Extract remote account addresses (`user@domain`) mentioned in text.
+///
+/// Mentions without a domain are ignored. Domains are lowercased and the
+/// result is deduplicated case-insensitively, in order of first appearance.
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut mentions: Vec<String> = Vec::new();
+
+    let mut index = 0;
+    while index < chars.len() {
+        let Some(token) = parse_mention_at(&chars, index) else {
+            index += 1;
+            continue;
+        };
+        index = token.end;
+        let Some(domain) = token.domain else {
+            continue;
+        };
+
+        let address = format!("{}@{}", token.username, domain);
+        if !mentions
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(&address))
+        {
+            mentions.push(address);
+        }
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_mentions;
+
+    #[test]
+    fn extracts_remote_mentions_in_order() {
+        assert_eq!(
+            extract_mentions("@alice@example.com hi, cc @bob@Social.Example.org!"),
+            vec!["alice@example.com", "bob@social.example.org"]
+        );
+    }
+
+    #[test]
+    fn trims_trailing_punctuation() {
+        assert_eq!(
+            extract_mentions("thanks @alice@example.com."),
+            vec!["alice@example.com"]
+        );
+        assert_eq!(
+            extract_mentions("(@first.last@example.com)"),
+            vec!["first.last@example.com"]
+        );
+    }
+
+    #[test]
+    fn skips_emails_urls_and_local_mentions() {
+        assert!(extract_mentions("mail me at alice@example.com").is_empty());
+        assert!(extract_mentions("see https://example.com/@alice@example.com").is_empty());
+        assert!(extract_mentions("hello @alice and @ bob").is_empty());
+    }
+
+    #[test]
+    fn deduplicates_case_insensitively() {
+        assert_eq!(
+            extract_mentions("@Alice@Example.com @alice@example.com"),
+            vec!["Alice@example.com"]
+        );
+    }
+}
@@ -3,28 +3,63 @@
 //! Handles:
 //! - Activity processing (inbox)
 //! - Activity delivery (outbox)
+//! - Note serialization of local statuses
+//! - Featured (pinned posts and hashtags) collections
+//! - Persistent delivery queue with retries
+//! - Persistent inbound queue with retries
 //! - HTTP Signatures
+//! - Mention parsing
 //! - WebFinger
 //! - Actor fetching
 //! - Public key caching
 //! - Rate limiting
+//! - Inbound activity deduplication
 
 mod activity;
+mod dedup;
 mod delivery;
+mod delivery_queue;
+mod featured;
+mod inbox_queue;
+mod instance_actor;
 mod key_cache;
+mod mention;
+mod message_signature;
+mod note;
 mod rate_limit;
 mod signature;
 mod webfinger;
 
-pub use activity::{ActivityProcessor, ActivityType};
+pub(crate) use activity::is_actor_domain_blocked;
+pub use activity::{ActivityProcessor, ActivityType, sanitize_status_html};
+pub use dedup::SeenActivities;
 pub use delivery::{
-    ActivityDelivery, DeliveryResult, build_local_delivery, local_actor_uri, local_key_id,
+    ActivityDelivery, DeliveryResult, SignaturePreferences, build_local_delivery, local_actor_uri,
+    local_key_id,
 };
+pub use delivery_queue::{
+    DeliveryQueue, MAX_DELIVERY_ATTEMPTS, QueueRunStats, is_permanent_failure, retry_delay,
+};
+pub use featured::{
+    MAX_REMOTE_FEATURED_ITEMS, featured_collection_uri, featured_status_from_object,
+    featured_tags_collection_uri, fetch_featured_objects,
+};
+pub use inbox_queue::{InboxQueue, InboxRunStats, MAX_INBOX_ATTEMPTS, is_transient_inbox_error};
+pub use instance_actor::{INSTANCE_ACTOR_PATH, InstanceActor};
 pub use key_cache::{CacheStats, PublicKeyCache};
+pub use mention::{MentionToken, extract_mentions, parse_mention_at};
+pub use message_signature::{
+    MessageRequest, MessageSignatureHeaders, has_message_signature, message_signature_key_id,
+    sign_message, verify_message_signature,
+};
+pub use note::{NotePoll, NoteSerializer, StatusNote, note_context};
 pub use rate_limit::{RateLimitStats, RateLimiter, extract_domain};
 pub use signature::{
-    fetch_public_key, key_id_matches_actor, parse_signature_header, sign_request, verify_signature,
+    FetchSigner, SignatureScheme, fetch_public_key, key_id_matches_actor, parse_signature_header,
+    request_signature_key_id, sign_request, verify_request_signature, verify_signature,
 };
+pub(crate) use webfinger::{ACTIVITY_JSON_ACCEPT, fetch_with_validated_redirects};
 pub use webfinger::{
-    WebFingerResponse, WebFingerResult, generate_webfinger_response, resolve_webfinger,
+    ParsedActor, WebFingerResponse, WebFingerResult, fetch_actor, generate_webfinger_response,
+    parse_actor, resolve_webfinger,
 };
@@ -0,0 +1,457 @@
+//! Note serialization
+//!
+//! Converts local statuses into ActivityPub `Note` (or `Question`) objects.
+//! Delivery, the outbox and the object endpoint all serialize through
+//! `NoteSerializer` so remote servers see one representation of a post.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::delivery::audience_for_visibility;
+use crate::data::{
+    Database, MediaAttachment, Status, StatusMention, StatusSource, extract_hashtags_from_content,
+};
+use crate::error::AppError;
+use crate::service::ContentType;
+
+/// JSON-LD context for Notes, including the Mastodon extension terms we emit
+pub fn note_context() -> Value {
+    serde_json::json!([
+        "https://www.w3.org/ns/activitystreams",
+        {
+            "ostatus": "http://ostatus.org#",
+            "atomUri": "ostatus:atomUri",
+            "inReplyToAtomUri": "ostatus:inReplyToAtomUri",
+            "conversation": "ostatus:conversation",
+            "sensitive": "as:sensitive",
+            "Hashtag": "as:Hashtag",
+            "toot": "http://joinmastodon.org/ns#",
+            "blurhash": "toot:blurhash",
+            "focalPoint": {
+                "@container": "@list",
+                "@id": "toot:focalPoint"
+            },
+            "votersCount": "toot:votersCount"
+        }
+    ])
+}
+
+/// Poll attached to a local status
+#[derive(Debug, Clone)]
+pub struct NotePoll {
+    /// RFC3339 closing time
+    pub expires_at: String,
+    pub expired: bool,
+    pub multiple: bool,
+    pub voters_count: i64,
+    /// Option titles with their vote counts, in display order
+    pub options: Vec<(String, i64)>,
+}
+
+/// A local status together with everything needed to serialize it
+#[derive(Debug, Clone)]
+pub struct StatusNote {
+    pub status: Status,
+    pub source: Option<StatusSource>,
+    pub mentions: Vec<StatusMention>,
+    pub media: Vec<MediaAttachment>,
+    pub poll: Option<NotePoll>,
+    /// Time of the latest edit, if the status has been edited
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+impl StatusNote {
+    /// Load the source, mentions, media, poll and edit time of a local status
+    pub async fn load(db: &Database, status: Status) -> Result<Self, AppError> {
+        let source = db.get_status_source(&status.id).await?;
+        let mentions = db.get_status_mentions(&status.id).await?;
+        let media = db.get_media_by_status(&status.id).await?;
+        let poll = match db.get_poll_by_status_id(&status.id).await? {
+            Some((poll_id, expires_at, expired, multiple, _, voters_count)) => {
+                let options = db
+                    .get_poll_options(&poll_id)
+                    .await?
+                    .into_iter()
+                    .map(|(_, title, votes_count)| (title, votes_count))
+                    .collect();
+                Some(NotePoll {
+                    expires_at,
+                    expired,
+                    multiple,
+                    voters_count,
+                    options,
+                })
+            }
+            None => None,
+        };
+        let edited_at = db
+            .get_status_edits(&status.id, 1)
+            .await?
+            .into_iter()
+            .next()
+            .map(|(_, _, _, created_at)| created_at);
+
+        Ok(Self {
+            status,
+            source,
+            mentions,
+            media,
+            poll,
+            edited_at,
+        })
+    }
+}
+
+/// Serializes local statuses for federation
+#[derive(Debug, Clone)]
+pub struct NoteSerializer {
+    /// Local actor URI (author of every status)
+    actor_uri: String,
+    /// Instance base URL, used for hashtag links
+    base_url: String,
+    /// Public media base URL
+    media_url: String,
+}
+
+impl NoteSerializer {
+    /// Create a serializer for the local account `username`
+    pub fn new(base_url: &str, username: &str, media_url: &str) -> Self {
+        Self {
+            actor_uri: super::local_actor_uri(base_url, username),
+            base_url: base_url.to_string(),
+            media_url: media_url.to_string(),
+        }
+    }
+
+    /// Build the `Note`, or `Question` for polls, without `@context`
+    pub fn note(&self, note: &StatusNote) -> Value {
+        let status = &note.status;
+        let mentioned_actor_uris: Vec<String> = note
+            .mentions
+            .iter()
+            .map(|mention| mention.actor_uri.clone())
+            .collect();
+        let (to, cc) = audience_for_visibility(
+            &self.actor_uri,
+            status.visibility.as_str(),
+            &mentioned_actor_uris,
+        );
+        let conversation_host = status
+            .uri
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_default();
+
+        let mut object = serde_json::json!({
+            "type": if note.poll.is_some() { "Question" } else { "Note" },
+            "id": status.uri,
+            "url": status.uri,
+            "attributedTo": self.actor_uri,
+            "summary": status.content_warning,
+            "sensitive": status.content_warning.is_some(),
+            "content": status.content,
+            "published": status.created_at.to_rfc3339(),
+            "inReplyTo": status.in_reply_to_uri,
+            "to": to,
+            "cc": cc,
+            "atomUri": status.uri,
+            "inReplyToAtomUri": status.in_reply_to_uri,
+            "conversation": format!("tag:{},conversation", conversation_host),
+            "attachment": note
+                .media
+                .iter()
+                .map(|media| self.document(media))
+                .collect::<Vec<_>>(),
+            "tag": self.tags(note),
+        });
+
+        if let Some(language) = status.language.as_deref().filter(|lang| !lang.is_empty()) {
+            object["contentMap"] = serde_json::json!({ language: status.content });
+        }
+
+        if let Some(edited_at) = note.edited_at {
+            object["updated"] = serde_json::json!(edited_at.to_rfc3339());
+        }
+
+        // Plain text is fully represented by the HTML; other formats are
+        // shared so compatible servers can show or edit the original.
+        if let Some(source) = note
+            .source
+            .as_ref()
+            .filter(|source| source.content_type != ContentType::PlainText.as_str())
+        {
+            object["source"] = serde_json::json!({
+                "content": source.text,
+                "mediaType": source.content_type,
+            });
+        }
+
+        if let Some(poll) = &note.poll {
+            let options: Vec<Value> = poll
+                .options
+                .iter()
+                .map(|(title, votes_count)| {
+                    serde_json::json!({
+                        "type": "Note",
+                        "name": title,
+                        "replies": {
+                            "type": "Collection",
+                            "totalItems": votes_count
+                        }
+                    })
+                })
+                .collect();
+            let choice_key = if poll.multiple { "anyOf" } else { "oneOf" };
+            object[choice_key] = Value::Array(options);
+            object["endTime"] = serde_json::json!(poll.expires_at);
+            object["votersCount"] = serde_json::json!(poll.voters_count);
+            if poll.expired {
+                object["closed"] = serde_json::json!(poll.expires_at);
+            }
+        }
+
+        object
+    }
+
+    /// Build the `Create` wrapping a status, as listed in the outbox
+    pub fn create(&self, note: &StatusNote) -> Value {
+        let object = self.note(note);
+        serde_json::json!({
+            "type": "Create",
+            "id": format!("{}/activity", note.status.uri),
+            "actor": self.actor_uri,
+            "published": note.status.created_at.to_rfc3339(),
+            "to": object["to"].clone(),
+            "cc": object["cc"].clone(),
+            "object": object
+        })
+    }
+
+    fn document(&self, media: &MediaAttachment) -> Value {
+        let mut document = serde_json::json!({
+            "type": "Document",
+            "mediaType": media.content_type,
+            "url": format!("{}/{}", self.media_url, media.s3_key),
+            "name": media.description,
+            "blurhash": media.blurhash,
+        });
+        if let (Some(x), Some(y)) = (media.focus_x, media.focus_y) {
+            document["focalPoint"] = serde_json::json!([x, y]);
+        }
+        if let Some(width) = media.width {
+            document["width"] = serde_json::json!(width);
+        }
+        if let Some(height) = media.height {
+            document["height"] = serde_json::json!(height);
+        }
+        document
+    }
+
+    fn tags(&self, note: &StatusNote) -> Vec<Value> {
+        let mentions = note.mentions.iter().map(|mention| {
+            super::delivery::builder::mention(
+                &mention.actor_uri,
+                &format!("@{}", mention.account_address),
+            )
+        });
+        let hashtags = extract_hashtags_from_content(&note.status.content)
+            .into_iter()
+            .map(|name| {
+                serde_json::json!({
+                    "type": "Hashtag",
+                    "href": format!("{}/tags/{}", self.base_url, name),
+                    "name": format!("#{}", name),
+                })
+            });
+        mentions.chain(hashtags).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotePoll, NoteSerializer, StatusNote};
+    use crate::data::{MediaAttachment, Status, StatusMention, StatusSource};
+    use chrono::Utc;
+
+    fn serializer() -> NoteSerializer {
+        NoteSerializer::new(
+            "https://local.example",
+            "alice",
+            "https://media.local.example",
+        )
+    }
+
+    fn status_note(content: &str) -> StatusNote {
+        StatusNote {
+            status: Status {
+                id: "status-1".to_string(),
+                uri: "https://local.example/users/alice/statuses/status-1".to_string(),
+                content: content.to_string(),
+                content_warning: None,
+                visibility: "public".to_string(),
+                language: Some("ja".to_string()),
+                account_address: String::new(),
+                is_local: true,
+                in_reply_to_uri: None,
+                boost_of_uri: None,
+                persisted_reason: "own".to_string(),
+                created_at: Utc::now(),
+                fetched_at: None,
+            },
+            source: None,
+            mentions: Vec::new(),
+            media: Vec::new(),
+            poll: None,
+            edited_at: None,
+        }
+    }
+
+    #[test]
+    fn note_uses_status_language_and_content_warning() {
+        let mut note = status_note("<p>hello</p>");
+        note.status.content_warning = Some("spoiler".to_string());
+
+        let object = serializer().note(&note);
+
+        assert_eq!(object["type"], "Note");
+        assert_eq!(object["sensitive"], true);
+        assert_eq!(object["summary"], "spoiler");
+        assert_eq!(object["contentMap"]["ja"], "<p>hello</p>");
+        assert!(object["contentMap"].get("en").is_none());
+        assert!(object.get("source").is_none());
+    }
+
+    #[test]
+    fn note_includes_media_documents() {
+        let mut note = status_note("<p>photo</p>");
+        note.media.push(MediaAttachment {
+            id: "media-1".to_string(),
+            status_id: Some("status-1".to_string()),
+            s3_key: "media/photo.webp".to_string(),
+            thumbnail_s3_key: None,
+            content_type: "image/webp".to_string(),
+            file_size: 1024,
+            description: Some("a cat".to_string()),
+            blurhash: Some("LEHV6nWB2yk8".to_string()),
+            width: Some(640),
+            height: Some(480),
+            focus_x: Some(0.5),
+            focus_y: Some(-0.25),
+            created_at: Utc::now(),
+        });
+
+        let object = serializer().note(&note);
+        let document = &object["attachment"][0];
+
+        assert_eq!(document["type"], "Document");
+        assert_eq!(document["mediaType"], "image/webp");
+        assert_eq!(
+            document["url"],
+            "https://media.local.example/media/photo.webp"
+        );
+        assert_eq!(document["name"], "a cat");
+        assert_eq!(document["blurhash"], "LEHV6nWB2yk8");
+        assert_eq!(document["focalPoint"], serde_json::json!([0.5, -0.25]));
+        assert_eq!(document["width"], 640);
+    }
+
+    #[test]
+    fn note_tags_mentions_and_hashtags() {
+        let mut note = status_note(
+            "<p><a href=\"https://local.example/tags/rust\" class=\"mention hashtag\" rel=\"tag\">#<span>Rust</span></a> hi</p>",
+        );
+        note.mentions.push(StatusMention {
+            status_id: "status-1".to_string(),
+            account_address: "bob@remote.example".to_string(),
+            actor_uri: "https://remote.example/users/bob".to_string(),
+            inbox_uri: None,
+        });
+
+        let object = serializer().note(&note);
+        let tags = object["tag"].as_array().unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0]["type"], "Mention");
+        assert_eq!(tags[0]["href"], "https://remote.example/users/bob");
+        assert_eq!(tags[1]["type"], "Hashtag");
+        assert_eq!(tags[1]["name"], "#rust");
+        assert_eq!(tags[1]["href"], "https://local.example/tags/rust");
+        assert!(
+            object["cc"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!("https://remote.example/users/bob"))
+        );
+    }
+
+    #[test]
+    fn note_shares_non_plain_source() {
+        let mut note = status_note("<p><strong>hi</strong></p>");
+        note.source = Some(StatusSource {
+            status_id: "status-1".to_string(),
+            text: "**hi**".to_string(),
+            content_type: "text/markdown".to_string(),
+        });
+
+        let object = serializer().note(&note);
+
+        assert_eq!(object["source"]["content"], "**hi**");
+        assert_eq!(object["source"]["mediaType"], "text/markdown");
+    }
+
+    #[test]
+    fn poll_is_serialized_as_question() {
+        let mut note = status_note("<p>pick one</p>");
+        note.poll = Some(NotePoll {
+            expires_at: "2030-01-01T00:00:00+00:00".to_string(),
+            expired: false,
+            multiple: false,
+            voters_count: 3,
+            options: vec![("yes".to_string(), 2), ("no".to_string(), 1)],
+        });
+
+        let object = serializer().note(&note);
+
+        assert_eq!(object["type"], "Question");
+        assert_eq!(object["oneOf"][0]["name"], "yes");
+        assert_eq!(object["oneOf"][0]["replies"]["totalItems"], 2);
+        assert_eq!(object["votersCount"], 3);
+        assert_eq!(object["endTime"], "2030-01-01T00:00:00+00:00");
+        assert!(object.get("anyOf").is_none());
+        assert!(object.get("closed").is_none());
+
+        let poll = note.poll.as_mut().unwrap();
+        poll.multiple = true;
+        poll.expired = true;
+        let object = serializer().note(&note);
+        assert_eq!(object["anyOf"][1]["name"], "no");
+        assert_eq!(object["closed"], "2030-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn note_marks_edited_statuses_as_updated() {
+        let mut note = status_note("<p>hello</p>");
+        assert!(serializer().note(&note).get("updated").is_none());
+
+        let edited_at = Utc::now();
+        note.edited_at = Some(edited_at);
+        let object = serializer().note(&note);
+
+        assert_eq!(object["updated"], edited_at.to_rfc3339());
+    }
+
+    #[test]
+    fn create_wraps_note_with_stable_activity_id() {
+        let note = status_note("<p>hello</p>");
+
+        let activity = serializer().create(&note);
+
+        assert_eq!(activity["type"], "Create");
+        assert_eq!(
+            activity["id"],
+            "https://local.example/users/alice/statuses/status-1/activity"
+        );
+        assert_eq!(activity["to"], activity["object"]["to"]);
+    }
+}
@@ -4,6 +4,9 @@
 //! Implements signing and verification per:
 //! https://docs.joinmastodon.org/spec/security/
 
+use super::message_signature::{
+    MessageRequest, has_message_signature, message_signature_key_id, verify_message_signature,
+};
 use crate::error::AppError;
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use chrono::{DateTime, Utc};
@@ -12,7 +15,6 @@ use rsa::signature::Verifier;
 use rsa::{RsaPublicKey, pkcs1v15::Signature as Pkcs1v15Signature};
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
-use std::net::IpAddr;
 
 /// Sign an HTTP request
 ///
@@ -125,6 +127,41 @@ pub struct SignatureHeaders {
     pub digest: Option<String>,
 }
 
+/// Key that signs outgoing GET requests
+///
+/// Peers running authorized fetch refuse unsigned GETs, so federation
+/// fetches carry a draft-cavage signature from this key.
+#[derive(Debug, Clone)]
+pub struct FetchSigner {
+    /// Key ID (actor#main-key)
+    key_id: String,
+    /// Private key in PEM format
+    private_key_pem: String,
+}
+
+impl FetchSigner {
+    /// Create a signer from a key ID and its private key
+    pub fn new(key_id: String, private_key_pem: String) -> Self {
+        Self {
+            key_id,
+            private_key_pem,
+        }
+    }
+
+    /// Key ID the signatures refer to
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Sign a GET request
+    ///
+    /// # Returns
+    /// Headers to add: Signature, Date
+    pub fn sign_get(&self, url: &str) -> Result<SignatureHeaders, AppError> {
+        sign_request("GET", url, None, &self.private_key_pem, &self.key_id)
+    }
+}
+
 fn is_supported_signature_algorithm(algorithm: &str) -> bool {
     algorithm.eq_ignore_ascii_case("rsa-sha256") || algorithm.eq_ignore_ascii_case("hs2019")
 }
@@ -151,79 +188,78 @@ fn parse_actor_url(raw: &str) -> Result<url::Url, AppError> {
     Ok(parsed)
 }
 
-fn is_blocked_ip_address(ip: IpAddr) -> bool {
-    match ip {
-        IpAddr::V4(v4) => {
-            v4.is_loopback()
-                || v4.is_private()
-                || v4.is_link_local()
-                || v4.is_unspecified()
-                || v4.is_multicast()
-        }
-        IpAddr::V6(v6) => {
-            v6.is_loopback()
-                || v6.is_unique_local()
-                || v6.is_unicast_link_local()
-                || v6.is_unspecified()
-                || v6.is_multicast()
-        }
-    }
+/// Returns true when the actor URL derived from keyId matches activity actor.
+pub fn key_id_matches_actor(key_id: &str, actor_id: &str) -> Result<bool, AppError> {
+    let key_actor = parse_actor_url(key_id)?;
+    let actor = parse_actor_url(actor_id)
+        .map_err(|_| AppError::Validation("Invalid activity actor URL".to_string()))?;
+    Ok(key_actor == actor)
 }
 
-async fn validate_remote_actor_url(actor_url: &url::Url) -> Result<(), AppError> {
-    let host = actor_url
-        .host_str()
-        .ok_or_else(|| AppError::Validation("Actor URL in keyId must include a host".to_string()))?
-        .trim_end_matches('.')
-        .to_ascii_lowercase();
-
-    if host == "localhost" || host.ends_with(".localhost") {
-        return Err(AppError::Validation(
-            "Actor URL host is not allowed".to_string(),
-        ));
-    }
+/// HTTP signature format of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureScheme {
+    /// draft-cavage-http-signatures `Signature` header
+    Cavage,
+    /// RFC 9421 `Signature-Input`/`Signature` headers
+    Rfc9421,
+}
 
-    if let Ok(ip) = host.parse::<IpAddr>() {
-        if is_blocked_ip_address(ip) {
-            return Err(AppError::Validation(
-                "Actor URL host is not allowed".to_string(),
-            ));
+impl SignatureScheme {
+    /// Detect the signature format of a request
+    ///
+    /// RFC 9421 requests always carry `Signature-Input`; a lone
+    /// `Signature` header is draft-cavage.
+    pub fn detect(headers: &http::HeaderMap) -> Option<Self> {
+        if has_message_signature(headers) {
+            Some(Self::Rfc9421)
+        } else if headers.contains_key("signature") {
+            Some(Self::Cavage)
+        } else {
+            None
         }
     }
+}
 
-    let port = actor_url.port_or_known_default().ok_or_else(|| {
-        AppError::Validation("Actor URL in keyId must include a known default port".to_string())
-    })?;
-    let mut resolved_any = false;
-    let resolved = tokio::net::lookup_host((host.as_str(), port))
-        .await
-        .map_err(|error| {
-            AppError::Federation(format!("Failed to resolve actor host {}: {}", host, error))
-        })?;
-    for address in resolved {
-        resolved_any = true;
-        if is_blocked_ip_address(address.ip()) {
-            return Err(AppError::Validation(
-                "Actor URL host is not allowed".to_string(),
-            ));
+/// Key ID of the signature on a request, in either format
+pub fn request_signature_key_id(headers: &http::HeaderMap) -> Result<String, AppError> {
+    match SignatureScheme::detect(headers) {
+        Some(SignatureScheme::Rfc9421) => message_signature_key_id(headers),
+        Some(SignatureScheme::Cavage) => {
+            let signature = headers
+                .get("signature")
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| AppError::Validation("Invalid Signature header".to_string()))?;
+            Ok(parse_signature_header(signature)?.key_id)
         }
+        None => Err(AppError::Unauthorized),
     }
-    if !resolved_any {
-        return Err(AppError::Federation(format!(
-            "Actor host did not resolve to any IP addresses: {}",
-            host
-        )));
-    }
-
-    Ok(())
 }
 
-/// Returns true when the actor URL derived from keyId matches activity actor.
-pub fn key_id_matches_actor(key_id: &str, actor_id: &str) -> Result<bool, AppError> {
-    let key_actor = parse_actor_url(key_id)?;
-    let actor = parse_actor_url(actor_id)
-        .map_err(|_| AppError::Validation("Invalid activity actor URL".to_string()))?;
-    Ok(key_actor == actor)
+/// Verify the signature on a request, in either format
+///
+/// Dispatches to RFC 9421 verification when `Signature-Input` is present
+/// and to draft-cavage verification otherwise.
+pub fn verify_request_signature(
+    request: &MessageRequest<'_>,
+    body: Option<&[u8]>,
+    public_key_pem: &str,
+    max_date_skew: std::time::Duration,
+) -> Result<(), AppError> {
+    match SignatureScheme::detect(request.headers) {
+        Some(SignatureScheme::Rfc9421) => {
+            verify_message_signature(request, body, public_key_pem, max_date_skew)
+        }
+        Some(SignatureScheme::Cavage) => verify_signature(
+            request.method,
+            request.path_and_query,
+            request.headers,
+            body,
+            public_key_pem,
+            max_date_skew,
+        ),
+        None => Err(AppError::Unauthorized),
+    }
 }
 
 /// Verify an HTTP request signature
@@ -234,6 +270,7 @@ pub fn key_id_matches_actor(key_id: &str, actor_id: &str) -> Result<bool, AppErr
 /// * `headers` - All request headers
 /// * `body` - Request body (for digest verification)
 /// * `public_key_pem` - RSA public key in PEM format
+/// * `max_date_skew` - Maximum allowed distance of `Date` from the local clock
 ///
 /// # Returns
 /// Ok if signature is valid
@@ -247,6 +284,7 @@ pub fn verify_signature(
     headers: &http::HeaderMap,
     body: Option<&[u8]>,
     public_key_pem: &str,
+    max_date_skew: std::time::Duration,
 ) -> Result<(), AppError> {
     // 1. Parse Signature header
     let signature_header = headers
@@ -271,7 +309,7 @@ pub fn verify_signature(
         }
     }
 
-    // 2. Verify Date is recent (within 5 minutes)
+    // 2. Verify Date is within the allowed skew (replay protection)
     if let Some(date_header) = headers.get("date") {
         let date_str = date_header
             .to_str()
@@ -282,10 +320,9 @@ pub fn verify_signature(
             .map_err(|_| AppError::Validation("Invalid Date format".to_string()))?;
 
         let now = Utc::now();
-        let diff = (now.timestamp() - date.timestamp()).abs();
+        let diff = (now.timestamp() - date.timestamp()).unsigned_abs();
 
-        if diff > 300 {
-            // 5 minutes
+        if diff > max_date_skew.as_secs() {
             return Err(AppError::Validation(
                 "Date header too old or in future".to_string(),
             ));
@@ -449,35 +486,19 @@ pub fn generate_digest(body: &[u8]) -> String {
 /// # Arguments
 /// * `key_id` - Full URL to the key (e.g., actor#main-key)
 /// * `http_client` - HTTP client
+/// * `signer` - Key to sign the GET with, if any
 ///
 /// # Returns
 /// PEM-encoded public key
 pub async fn fetch_public_key(
     key_id: &str,
     http_client: &reqwest::Client,
+    signer: Option<&FetchSigner>,
 ) -> Result<String, AppError> {
     let actor_url = parse_actor_url(key_id)?;
-    validate_remote_actor_url(&actor_url).await?;
-
-    // Fetch actor document
-    let response = http_client
-        .get(actor_url.as_str())
-        .header("Accept", "application/activity+json")
-        .send()
-        .await
-        .map_err(|e| AppError::Federation(format!("Failed to fetch actor: {}", e)))?;
-
-    if !response.status().is_success() {
-        return Err(AppError::Federation(format!(
-            "Failed to fetch actor: HTTP {}",
-            response.status()
-        )));
-    }
 
-    let actor: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| AppError::Federation(format!("Failed to parse actor: {}", e)))?;
+    // Fetch actor document, signed so authorized-fetch servers answer
+    let actor = super::webfinger::fetch_actor(actor_url.as_str(), http_client, signer).await?;
 
     // Extract public key
     let public_key_pem = actor
@@ -492,10 +513,13 @@ pub async fn fetch_public_key(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::federation::message_signature::sign_message;
     use http::HeaderValue;
     use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
     use rsa::{RsaPrivateKey, RsaPublicKey};
 
+    const SKEW: std::time::Duration = std::time::Duration::from_secs(300);
+
     fn generate_test_keypair() -> (String, String) {
         let mut rng = rand::thread_rng();
         let private_key = RsaPrivateKey::new(&mut rng, 2048).unwrap();
@@ -533,8 +557,15 @@ mod tests {
         );
         // Intentionally omit Digest header.
 
-        let error = verify_signature("POST", "/inbox", &headers, Some(body), &public_key_pem)
-            .expect_err("digest header must be required");
+        let error = verify_signature(
+            "POST",
+            "/inbox",
+            &headers,
+            Some(body),
+            &public_key_pem,
+            SKEW,
+        )
+        .expect_err("digest header must be required");
         assert!(matches!(
             error,
             AppError::Validation(message) if message.contains("Missing digest header")
@@ -570,8 +601,15 @@ mod tests {
             HeaderValue::from_str(&generate_digest(body)).expect("valid digest"),
         );
 
-        let error = verify_signature("POST", "/inbox", &headers, Some(body), &public_key_pem)
-            .expect_err("digest must be part of signed headers");
+        let error = verify_signature(
+            "POST",
+            "/inbox",
+            &headers,
+            Some(body),
+            &public_key_pem,
+            SKEW,
+        )
+        .expect_err("digest must be part of signed headers");
         assert!(matches!(
             error,
             AppError::Validation(message)
@@ -611,8 +649,70 @@ mod tests {
             HeaderValue::from_str(&hs2019_signature).expect("valid signature header"),
         );
 
-        verify_signature("POST", "/inbox", &headers, Some(body), &public_key_pem)
-            .expect("hs2019 token should be accepted for rsa signatures");
+        verify_signature(
+            "POST",
+            "/inbox",
+            &headers,
+            Some(body),
+            &public_key_pem,
+            SKEW,
+        )
+        .expect("hs2019 token should be accepted for rsa signatures");
+    }
+
+    #[test]
+    fn verify_signature_rejects_date_outside_configured_skew() {
+        let (private_key_pem, public_key_pem) = generate_test_keypair();
+        let body = br#"{"type":"Create"}"#;
+        let signed = sign_request(
+            "POST",
+            "https://remote.example/inbox",
+            Some(body),
+            &private_key_pem,
+            "https://remote.example/users/alice#main-key",
+        )
+        .unwrap();
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert("host", HeaderValue::from_static("remote.example"));
+        headers.insert(
+            "date",
+            HeaderValue::from_str(&signed.date).expect("valid signed date"),
+        );
+        headers.insert(
+            "digest",
+            HeaderValue::from_str(&generate_digest(body)).expect("valid digest"),
+        );
+        headers.insert(
+            "signature",
+            HeaderValue::from_str(&signed.signature).expect("valid signature header"),
+        );
+
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+
+        let error = verify_signature(
+            "POST",
+            "/inbox",
+            &headers,
+            Some(body),
+            &public_key_pem,
+            std::time::Duration::ZERO,
+        )
+        .expect_err("date older than the allowed skew must be rejected");
+        assert!(matches!(
+            error,
+            AppError::Validation(message) if message.contains("Date header too old")
+        ));
+
+        verify_signature(
+            "POST",
+            "/inbox",
+            &headers,
+            Some(body),
+            &public_key_pem,
+            SKEW,
+        )
+        .expect("date within the allowed skew should be accepted");
     }
 
     #[test]
@@ -638,7 +738,7 @@ mod tests {
     #[tokio::test]
     async fn fetch_public_key_rejects_localhost_targets() {
         let client = reqwest::Client::new();
-        let error = fetch_public_key("http://127.0.0.1/users/alice#main-key", &client)
+        let error = fetch_public_key("http://127.0.0.1/users/alice#main-key", &client, None)
             .await
             .expect_err("localhost/private targets must be rejected");
         assert!(matches!(
@@ -646,4 +746,78 @@ mod tests {
             AppError::Validation(message) if message.contains("not allowed")
         ));
     }
+
+    #[test]
+    fn verify_request_signature_accepts_both_formats() {
+        let (private_key_pem, public_key_pem) = generate_test_keypair();
+        let body = br#"{"type":"Create"}"#;
+        let key_id = "https://remote.example/users/alice#main-key";
+
+        let cavage = sign_request(
+            "POST",
+            "https://remote.example/inbox",
+            Some(body),
+            &private_key_pem,
+            key_id,
+        )
+        .unwrap();
+        let mut cavage_headers = http::HeaderMap::new();
+        cavage_headers.insert("host", HeaderValue::from_static("remote.example"));
+        cavage_headers.insert("date", HeaderValue::from_str(&cavage.date).unwrap());
+        cavage_headers.insert(
+            "digest",
+            HeaderValue::from_str(cavage.digest.as_deref().unwrap()).unwrap(),
+        );
+        cavage_headers.insert(
+            "signature",
+            HeaderValue::from_str(&cavage.signature).unwrap(),
+        );
+
+        let rfc9421 = sign_message(
+            "POST",
+            "https://remote.example/inbox",
+            Some(body),
+            &private_key_pem,
+            key_id,
+        )
+        .unwrap();
+        let mut rfc9421_headers = http::HeaderMap::new();
+        rfc9421_headers.insert("host", HeaderValue::from_static("remote.example"));
+        rfc9421_headers.insert(
+            "content-digest",
+            HeaderValue::from_str(rfc9421.content_digest.as_deref().unwrap()).unwrap(),
+        );
+        rfc9421_headers.insert(
+            "signature-input",
+            HeaderValue::from_str(&rfc9421.signature_input).unwrap(),
+        );
+        rfc9421_headers.insert(
+            "signature",
+            HeaderValue::from_str(&rfc9421.signature).unwrap(),
+        );
+
+        for (headers, scheme) in [
+            (&cavage_headers, SignatureScheme::Cavage),
+            (&rfc9421_headers, SignatureScheme::Rfc9421),
+        ] {
+            assert_eq!(SignatureScheme::detect(headers), Some(scheme));
+            assert_eq!(request_signature_key_id(headers).unwrap(), key_id);
+            let request = MessageRequest {
+                method: "POST",
+                scheme: "https",
+                path_and_query: "/inbox",
+                headers,
+            };
+            assert!(verify_request_signature(&request, Some(body), &public_key_pem, SKEW).is_ok());
+            let wrong_path = MessageRequest {
+                path_and_query: "/users/alice/inbox",
+                ..request
+            };
+            assert!(
+                verify_request_signature(&wrong_path, Some(body), &public_key_pem, SKEW).is_err()
+            );
+        }
+
+        assert_eq!(SignatureScheme::detect(&http::HeaderMap::new()), None);
+    }
 }
@@ -6,10 +6,14 @@ use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
+use super::signature::FetchSigner;
 use crate::error::AppError;
 
 const MAX_FETCH_REDIRECTS: usize = 5;
 
+/// Accept header for ActivityPub documents
+pub(crate) const ACTIVITY_JSON_ACCEPT: &str = "application/activity+json, application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"";
+
 /// WebFinger result
 #[derive(Debug, Clone)]
 pub struct WebFingerResult {
@@ -80,11 +84,17 @@ fn validate_account_domain(domain: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+fn is_shared_ipv4_space(v4: std::net::Ipv4Addr) -> bool {
+    let octets = v4.octets();
+    octets[0] == 100 && (64..=127).contains(&octets[1])
+}
+
 fn is_blocked_ip_address(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(v4) => {
             v4.is_loopback()
                 || v4.is_private()
+                || is_shared_ipv4_space(v4)
                 || v4.is_link_local()
                 || v4.is_unspecified()
                 || v4.is_multicast()
@@ -156,10 +166,72 @@ async fn resolve_validated_remote_actor_fetch_target(
     Ok((host, resolved_addrs))
 }
 
-async fn fetch_with_validated_redirects(
+/// Send a GET, signed when a signer is given
+///
+/// A signed request answered with `401` is retried once without a
+/// signature, for servers that reject our key but serve public documents.
+///
+/// # Arguments
+/// * `http_client` - Client the request is built with
+/// * `executor` - Client the request is sent with
+/// * `url` - Target URL
+/// * `accept_header` - Accept header value
+/// * `signer` - Key to sign the request with, if any
+async fn send_get_with_signature_fallback(
+    http_client: &reqwest::Client,
+    executor: &reqwest::Client,
+    url: &url::Url,
+    accept_header: &str,
+    signer: Option<&FetchSigner>,
+) -> Result<reqwest::Response, AppError> {
+    let build_request = |signer: Option<&FetchSigner>| {
+        let mut request = http_client.get(url.clone()).header("Accept", accept_header);
+        if let Some(signer) = signer {
+            let sig_headers = signer.sign_get(url.as_str())?;
+            request = request
+                .header("Date", sig_headers.date)
+                .header("Signature", sig_headers.signature);
+        }
+        request.build().map_err(|error| {
+            AppError::Federation(format!(
+                "Failed to build validated fetch request for {}: {}",
+                url, error
+            ))
+        })
+    };
+    let send = |request| async {
+        executor
+            .execute(request)
+            .await
+            .map_err(|error| AppError::Federation(format!("Actor fetch failed: {}", error)))
+    };
+
+    let response = send(build_request(signer)?).await?;
+    if signer.is_some() && response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        tracing::debug!(url = %url, "Signed fetch rejected, retrying unsigned");
+        return send(build_request(None)?).await;
+    }
+
+    Ok(response)
+}
+
+/// GET a remote federation resource
+///
+/// Every hop, including redirects, must resolve to a public address, and
+/// the connection is pinned to the validated addresses. Requests are
+/// signed with `signer` when given so that servers running authorized
+/// fetch answer them.
+///
+/// # Arguments
+/// * `http_client` - HTTP client
+/// * `start_url` - URL to fetch
+/// * `accept_header` - Accept header value
+/// * `signer` - Key to sign the requests with, if any
+pub(crate) async fn fetch_with_validated_redirects(
     http_client: &reqwest::Client,
     start_url: &url::Url,
     accept_header: &str,
+    signer: Option<&FetchSigner>,
 ) -> Result<reqwest::Response, AppError> {
     let mut current = start_url.clone();
     for _ in 0..=MAX_FETCH_REDIRECTS {
@@ -175,21 +247,14 @@ async fn fetch_with_validated_redirects(
                 AppError::Federation(format!("Failed to build validated fetch client: {}", error))
             })?;
 
-        let request = http_client
-            .get(current.clone())
-            .header("Accept", accept_header)
-            .build()
-            .map_err(|error| {
-                AppError::Federation(format!(
-                    "Failed to build validated fetch request for {}: {}",
-                    current, error
-                ))
-            })?;
-
-        let response = validated_client
-            .execute(request)
-            .await
-            .map_err(|error| AppError::Federation(format!("Actor fetch failed: {}", error)))?;
+        let response = send_get_with_signature_fallback(
+            http_client,
+            &validated_client,
+            &current,
+            accept_header,
+            signer,
+        )
+        .await?;
 
         if response.status().is_redirection() {
             let Some(location) = response.headers().get(reqwest::header::LOCATION) else {
@@ -342,12 +407,13 @@ fn extract_url(value: &serde_json::Value) -> Option<String> {
 ///
 /// # Example
 /// ```ignore
-/// let result = resolve_webfinger("user@mastodon.social", &client).await?;
+/// let result = resolve_webfinger("user@mastodon.social", &client, None).await?;
 /// println!("Actor: {}", result.actor_uri);
 /// ```
 pub async fn resolve_webfinger(
     address: &str,
     http_client: &reqwest::Client,
+    signer: Option<&FetchSigner>,
 ) -> Result<WebFingerResult, AppError> {
     if let Some(actor_uri) = parse_actor_uri_address(address) {
         return Ok(WebFingerResult {
@@ -367,6 +433,7 @@ pub async fn resolve_webfinger(
             http_client,
             &webfinger_url,
             "application/jrd+json, application/json",
+            signer,
         )
         .await
         {
@@ -493,12 +560,14 @@ pub fn generate_webfinger_response(
 /// # Arguments
 /// * `actor_uri` - ActivityPub actor URI
 /// * `http_client` - HTTP client
+/// * `signer` - Key to sign the fetch with, if any
 ///
 /// # Returns
 /// Actor JSON document
 pub async fn fetch_actor(
     actor_uri: &str,
     http_client: &reqwest::Client,
+    signer: Option<&FetchSigner>,
 ) -> Result<serde_json::Value, AppError> {
     let parsed = url::Url::parse(actor_uri)
         .map_err(|_| AppError::Validation("actor URI must be a valid URL".to_string()))?;
@@ -508,12 +577,8 @@ pub async fn fetch_actor(
         ));
     }
 
-    let response = fetch_with_validated_redirects(
-        http_client,
-        &parsed,
-        "application/activity+json, application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
-    )
-    .await?;
+    let response =
+        fetch_with_validated_redirects(http_client, &parsed, ACTIVITY_JSON_ACCEPT, signer).await?;
 
     if !response.status().is_success() {
         return Err(AppError::Federation(format!(
@@ -603,11 +668,29 @@ pub fn parse_actor(actor: &serde_json::Value) -> Result<ParsedActor, AppError> {
             .get("following")
             .and_then(|value| value.as_str())
             .map(str::to_string),
+        featured: actor
+            .get("featured")
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
         public_key_id,
         public_key_pem,
+        also_known_as: extract_also_known_as(actor),
     })
 }
 
+/// Extract `alsoKnownAs` aliases, which may be a single URI or an array
+fn extract_also_known_as(actor: &serde_json::Value) -> Vec<String> {
+    match actor.get("alsoKnownAs") {
+        Some(serde_json::Value::String(alias)) => vec![alias.clone()],
+        Some(serde_json::Value::Array(aliases)) => aliases
+            .iter()
+            .filter_map(|alias| alias.as_str())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Parsed actor data
 #[derive(Debug, Clone)]
 pub struct ParsedActor {
@@ -621,13 +704,20 @@ pub struct ParsedActor {
     pub outbox: Option<String>,
     pub followers: Option<String>,
     pub following: Option<String>,
+    /// Collection of pinned posts (`featured`)
+    pub featured: Option<String>,
     pub public_key_id: String,
     pub public_key_pem: String,
+    /// Previous identities of this actor (`alsoKnownAs`)
+    pub also_known_as: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{fetch_actor, generate_webfinger_response, parse_account_address, parse_actor};
+    use super::{
+        ACTIVITY_JSON_ACCEPT, FetchSigner, fetch_actor, generate_webfinger_response,
+        parse_account_address, parse_actor, send_get_with_signature_fallback,
+    };
 
     #[test]
     fn generate_webfinger_response_contains_activitypub_self_link() {
@@ -677,6 +767,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_actor_accepts_single_and_array_also_known_as() {
+        let mut actor = serde_json::json!({
+            "id": "https://new.example/users/alice",
+            "preferredUsername": "alice",
+            "inbox": "https://new.example/users/alice/inbox",
+            "publicKeyPem": "key",
+            "alsoKnownAs": "https://old.example/users/alice"
+        });
+        let parsed = parse_actor(&actor).expect("actor should parse");
+        assert_eq!(
+            parsed.also_known_as,
+            vec!["https://old.example/users/alice"]
+        );
+
+        actor["alsoKnownAs"] = serde_json::json!([
+            "https://old.example/users/alice",
+            "https://older.example/users/alice"
+        ]);
+        let parsed = parse_actor(&actor).expect("actor should parse");
+        assert_eq!(parsed.also_known_as.len(), 2);
+
+        actor.as_object_mut().unwrap().remove("alsoKnownAs");
+        let parsed = parse_actor(&actor).expect("actor should parse");
+        assert!(parsed.also_known_as.is_empty());
+    }
+
     #[test]
     fn parse_actor_rejects_missing_public_key() {
         let actor = serde_json::json!({
@@ -744,7 +861,7 @@ mod tests {
     async fn fetch_actor_rejects_private_and_localhost_targets() {
         let http_client = reqwest::Client::new();
 
-        let loopback_error = fetch_actor("http://127.0.0.1:8080/actor", &http_client)
+        let loopback_error = fetch_actor("http://127.0.0.1:8080/actor", &http_client, None)
             .await
             .expect_err("loopback targets must be rejected");
         assert!(matches!(
@@ -753,7 +870,7 @@ mod tests {
                 if message.contains("host is not allowed")
         ));
 
-        let localhost_error = fetch_actor("http://localhost:8080/actor", &http_client)
+        let localhost_error = fetch_actor("http://localhost:8080/actor", &http_client, None)
             .await
             .expect_err("localhost targets must be rejected");
         assert!(matches!(
@@ -762,4 +879,74 @@ mod tests {
                 if message.contains("host is not allowed")
         ));
     }
+
+    #[tokio::test]
+    async fn signed_fetch_is_retried_unsigned_when_rejected() {
+        use axum::{Router, http::HeaderMap, http::StatusCode, routing::get};
+        use rsa::RsaPrivateKey;
+        use rsa::pkcs8::{EncodePrivateKey, LineEnding};
+        use std::sync::{Arc, Mutex};
+        use tokio::net::TcpListener;
+
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let private_key_pem = private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .unwrap()
+            .to_string();
+        let signer = FetchSigner::new(
+            "https://local.example/actor#main-key".to_string(),
+            private_key_pem,
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let addr = listener.local_addr().expect("server address");
+
+        // Rejects every signed request, like a server that cannot verify our key
+        let seen_signatures: Arc<Mutex<Vec<Option<String>>>> = Arc::default();
+        let seen_for_handler = seen_signatures.clone();
+        let app = Router::new().route(
+            "/users/alice",
+            get(move |headers: HeaderMap| {
+                let seen = seen_for_handler.clone();
+                async move {
+                    let signature = headers
+                        .get("signature")
+                        .and_then(|value| value.to_str().ok())
+                        .map(ToString::to_string);
+                    let signed = signature.is_some();
+                    seen.lock().unwrap().push(signature);
+                    if signed {
+                        StatusCode::UNAUTHORIZED
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("serve test server");
+        });
+
+        let http_client = reqwest::Client::new();
+        let url = url::Url::parse(&format!("http://{addr}/users/alice")).unwrap();
+        let response = send_get_with_signature_fallback(
+            &http_client,
+            &http_client,
+            &url,
+            ACTIVITY_JSON_ACCEPT,
+            Some(&signer),
+        )
+        .await
+        .expect("fetch succeeds");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        let seen = seen_signatures.lock().unwrap().clone();
+        assert_eq!(seen.len(), 2);
+        assert!(seen[0].as_deref().is_some_and(|signature| {
+            signature.contains("keyId=\"https://local.example/actor#main-key\"")
+        }));
+        assert!(seen[1].is_none());
+    }
 }
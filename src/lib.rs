@@ -82,6 +82,24 @@ pub struct AppState {
 
     /// Federation inbound rate limiter
     pub federation_rate_limiter: Arc<federation::RateLimiter>,
+
+    /// Recently seen inbound activities for deduplication
+    pub seen_activities: Arc<federation::SeenActivities>,
+
+    /// Persistent outbound delivery queue
+    pub delivery_queue: Arc<federation::DeliveryQueue>,
+
+    /// Persistent inbound activity queue
+    pub inbox_queue: Arc<federation::InboxQueue>,
+
+    /// Signature format accepted by each remote domain
+    pub signature_preferences: Arc<federation::SignaturePreferences>,
+
+    /// Server-wide actor whose key signs outgoing fetches
+    pub instance_actor: Arc<federation::InstanceActor>,
+
+    /// Streaming event bus for real-time clients
+    pub streaming: Arc<service::StreamingBus>,
 }
 
 impl AppState {
@@ -166,8 +184,12 @@ impl AppState {
             .build()
             .map_err(|e| error::AppError::Internal(e.into()))?;
 
-        // 4. Initialize federation inbound rate limiter
+        // 4. Initialize federation inbound rate limiter and deduplication
         let federation_rate_limiter = federation::RateLimiter::new(None, None);
+        let seen_activities = federation::SeenActivities::new(
+            std::time::Duration::from_secs(config.federation.seen_activity_ttl_seconds),
+            config.federation.seen_activity_max_entries,
+        );
 
         // 5. Connect to R2 storage
         let storage = storage::MediaStorage::new(&config.storage.media, &config.cloudflare).await?;
@@ -192,10 +214,33 @@ impl AppState {
             "Fetching profiles..."
         );
 
+        // Profile fetches are signed with the instance actor key so that
+        // authorized fetch servers answer them. The key is only loaded (or
+        // generated) when there is something to fetch.
+        let db = Arc::new(db);
+        let instance_actor = Arc::new(federation::InstanceActor::new(
+            db.clone(),
+            &config.server.base_url(),
+            &config.server.domain,
+        ));
+        let fetch_signer = if follow_addresses.is_empty() && follower_addresses.is_empty() {
+            None
+        } else {
+            Some(instance_actor.fetch_signer().await?)
+        };
+
         // Fetch profiles in parallel
         tokio::join!(
-            profile_cache.initialize_from_addresses(&follow_addresses, &http_client),
-            profile_cache.initialize_from_addresses(&follower_addresses, &http_client),
+            profile_cache.initialize_from_addresses(
+                &follow_addresses,
+                &http_client,
+                fetch_signer.as_ref()
+            ),
+            profile_cache.initialize_from_addresses(
+                &follower_addresses,
+                &http_client,
+                fetch_signer.as_ref()
+            ),
         );
 
         // 8. Initialize admin user
@@ -203,9 +248,16 @@ impl AppState {
 
         tracing::info!("Application state initialized successfully");
 
+        let delivery_queue = Arc::new(federation::DeliveryQueue::new(db.clone()));
+        let inbox_queue = Arc::new(federation::InboxQueue::new(
+            db.clone(),
+            config.federation.inbox_workers,
+            config.federation.inbox_per_domain_concurrency,
+        ));
+
         Ok(Self {
             config: Arc::new(config),
-            db: Arc::new(db),
+            db,
             timeline_cache: Arc::new(timeline_cache),
             profile_cache: Arc::new(profile_cache),
             storage: Arc::new(storage),
@@ -213,6 +265,12 @@ impl AppState {
             http_client: Arc::new(http_client),
             federation_fetch_client: Arc::new(federation_fetch_client),
             federation_rate_limiter: Arc::new(federation_rate_limiter),
+            seen_activities: Arc::new(seen_activities),
+            delivery_queue,
+            inbox_queue,
+            signature_preferences: Arc::new(federation::SignaturePreferences::new()),
+            instance_actor,
+            streaming: Arc::new(service::StreamingBus::default()),
         })
     }
 
@@ -351,6 +409,219 @@ pub fn build_router(state: AppState) -> axum::Router {
         .merge(api::metrics_router())
 }
 
+/// Interval between delivery queue polls when no new jobs arrive
+const DELIVERY_QUEUE_POLL_INTERVAL_SECS: u64 = 10;
+/// Maximum number of queued deliveries attempted per worker pass
+const DELIVERY_QUEUE_BATCH_SIZE: usize = 100;
+
+/// Spawn background worker that drains the outbound delivery queue
+///
+/// Wakes up when new jobs are enqueued and on a fixed interval so that
+/// rescheduled retries and jobs left over from a previous run are picked up.
+/// Shared by the binary and integration tests so queued deliveries behave
+/// the same in both.
+pub fn spawn_delivery_queue_worker(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            DELIVERY_QUEUE_POLL_INTERVAL_SECS,
+        ));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = state.delivery_queue.notified() => {}
+            }
+
+            let account = match state.db.get_account().await {
+                Ok(Some(account)) => account,
+                Ok(None) => continue,
+                Err(error) => {
+                    tracing::error!(%error, "Delivery queue worker failed to load account");
+                    continue;
+                }
+            };
+            // Build without a queue so attempts are sent directly.
+            let delivery = federation::build_local_delivery(
+                state.http_client.clone(),
+                &state.config.server.base_url(),
+                &account,
+            )
+            .with_signature_preferences(state.signature_preferences.clone());
+
+            loop {
+                match state
+                    .delivery_queue
+                    .process_due(&delivery, DELIVERY_QUEUE_BATCH_SIZE)
+                    .await
+                {
+                    Ok(stats) => {
+                        let processed = stats.delivered + stats.retried + stats.dead_lettered;
+                        if processed > 0 {
+                            tracing::debug!(
+                                delivered = stats.delivered,
+                                retried = stats.retried,
+                                dead_lettered = stats.dead_lettered,
+                                "Delivery queue pass completed"
+                            );
+                        }
+                        // Keep draining while full batches are coming back.
+                        if processed < DELIVERY_QUEUE_BATCH_SIZE {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "Delivery queue pass failed");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    tracing::info!("Delivery queue worker spawned");
+}
+
+/// Interval between inbox queue polls when no new jobs arrive
+const INBOX_QUEUE_POLL_INTERVAL_SECS: u64 = 10;
+/// Maximum number of queued inbound activities attempted per worker pass
+const INBOX_QUEUE_BATCH_SIZE: usize = 100;
+/// Interval between purges of old dead-lettered inbound activities
+const INBOX_QUEUE_CLEANUP_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Spawn background worker that drains the inbound activity queue
+///
+/// Wakes up when the inbox handlers enqueue an activity and on a fixed
+/// interval so that rescheduled retries and jobs left over from a previous
+/// run are picked up. Old dead letters are purged every hour.
+pub fn spawn_inbox_queue_worker(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            INBOX_QUEUE_POLL_INTERVAL_SECS,
+        ));
+        let mut cleanup = tokio::time::interval(std::time::Duration::from_secs(
+            INBOX_QUEUE_CLEANUP_INTERVAL_SECS,
+        ));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = state.inbox_queue.notified() => {}
+                _ = cleanup.tick() => {
+                    match state.inbox_queue.purge_dead(chrono::Utc::now()).await {
+                        Ok(0) => {}
+                        Ok(purged) => {
+                            tracing::info!(purged, "Purged old dead-lettered inbound activities");
+                        }
+                        Err(error) => {
+                            tracing::warn!(%error, "Inbox queue cleanup failed");
+                        }
+                    }
+                    continue;
+                }
+            }
+
+            loop {
+                match api::process_due_inbox_jobs(&state, INBOX_QUEUE_BATCH_SIZE).await {
+                    Ok(stats) => {
+                        let attempted =
+                            stats.processed + stats.rejected + stats.retried + stats.dead_lettered;
+                        if attempted > 0 {
+                            tracing::debug!(
+                                processed = stats.processed,
+                                rejected = stats.rejected,
+                                retried = stats.retried,
+                                dead_lettered = stats.dead_lettered,
+                                "Inbox queue pass completed"
+                            );
+                        }
+                        // Keep draining while full batches are coming back.
+                        if attempted < INBOX_QUEUE_BATCH_SIZE {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "Inbox queue pass failed");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    tracing::info!("Inbox queue worker spawned");
+}
+
+/// Interval between scheduled status publication passes
+const SCHEDULED_STATUS_POLL_INTERVAL_SECS: u64 = 5;
+/// Maximum number of scheduled statuses published per pass
+const SCHEDULED_STATUS_BATCH_SIZE: usize = 20;
+
+/// Spawn background task that publishes due scheduled statuses
+///
+/// Publication consumes the scheduled row atomically, so statuses that came
+/// due while the server was down are posted exactly once after a restart.
+pub fn spawn_scheduled_status_task(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            SCHEDULED_STATUS_POLL_INTERVAL_SECS,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            loop {
+                match api::publish_due_scheduled_statuses(&state, SCHEDULED_STATUS_BATCH_SIZE).await
+                {
+                    // Keep going while full batches are coming back.
+                    Ok(published) if published == SCHEDULED_STATUS_BATCH_SIZE => {}
+                    Ok(_) => break,
+                    Err(error) => {
+                        tracing::error!(%error, "Scheduled status pass failed");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    tracing::info!("Scheduled status task spawned");
+}
+
+/// Interval between poll expiry passes
+const POLL_EXPIRY_POLL_INTERVAL_SECS: u64 = 30;
+/// Maximum number of polls closed per pass
+const POLL_EXPIRY_BATCH_SIZE: usize = 20;
+
+/// Spawn background task that closes expired polls
+///
+/// Closing marks the poll expired before notifying, so a poll that ended
+/// while the server was down is announced exactly once after a restart.
+pub fn spawn_poll_expiry_task(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            POLL_EXPIRY_POLL_INTERVAL_SECS,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            loop {
+                match api::close_expired_polls(&state, POLL_EXPIRY_BATCH_SIZE).await {
+                    // Keep going while full batches are coming back.
+                    Ok(closed) if closed == POLL_EXPIRY_BATCH_SIZE => {}
+                    Ok(_) => break,
+                    Err(error) => {
+                        tracing::error!(%error, "Poll expiry pass failed");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    tracing::info!("Poll expiry task spawned");
+}
+
 fn build_cors_layer(server: &config::ServerConfig) -> tower_http::cors::CorsLayer {
     use axum::http::HeaderValue;
     use tower_http::cors::{Any, CorsLayer};
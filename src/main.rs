@@ -11,7 +11,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 /// 3. Initialize AppState
 /// 4. Build Axum router
 /// 5. Start HTTP server
-/// 6. Start background tasks (backup scheduler)
+/// 6. Start background tasks (backup scheduler, delivery queue worker)
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 1. Initialize tracing/logging
@@ -63,6 +63,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Public URL: {}", config.server.base_url());
 
     // 7. Start background tasks
+    rustresort::spawn_delivery_queue_worker(state.clone());
+    rustresort::spawn_inbox_queue_worker(state.clone());
+    rustresort::spawn_scheduled_status_task(state.clone());
+    rustresort::spawn_poll_expiry_task(state.clone());
     if config.storage.backup.enabled {
         spawn_backup_task(state.clone());
     }
@@ -66,6 +66,10 @@ lazy_static! {
         Opts::new("rustresort_activitypub_activities_sent_total", "Total number of ActivityPub activities sent"),
         &["activity_type"]
     ).expect("metric can be created");
+    pub static ref ACTIVITYPUB_DUPLICATE_ACTIVITIES_DROPPED: IntCounterVec = IntCounterVec::new(
+        Opts::new("rustresort_activitypub_duplicate_activities_dropped_total", "Total number of duplicate inbound ActivityPub activities dropped"),
+        &["activity_type"]
+    ).expect("metric can be created");
     pub static ref FEDERATION_REQUESTS_TOTAL: IntCounterVec = IntCounterVec::new(
         Opts::new("rustresort_federation_requests_total", "Total number of federation requests"),
         &["direction", "status"]
@@ -167,6 +171,9 @@ pub fn init_metrics() {
     REGISTRY
         .register(Box::new(ACTIVITYPUB_ACTIVITIES_SENT.clone()))
         .expect("ACTIVITYPUB_ACTIVITIES_SENT can be registered");
+    REGISTRY
+        .register(Box::new(ACTIVITYPUB_DUPLICATE_ACTIVITIES_DROPPED.clone()))
+        .expect("ACTIVITYPUB_DUPLICATE_ACTIVITIES_DROPPED can be registered");
     REGISTRY
         .register(Box::new(FEDERATION_REQUESTS_TOTAL.clone()))
         .expect("FEDERATION_REQUESTS_TOTAL can be registered");
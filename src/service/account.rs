@@ -8,6 +8,9 @@ use crate::data::{Account, Database, EntityId};
 use crate::error::AppError;
 use crate::storage::MediaStorage;
 
+/// Settings key holding the actor URI the account migrated to
+const MOVED_TO_SETTING_KEY: &str = "account.moved_to";
+
 #[cfg(test)]
 const ACCOUNT_KEY_BITS: usize = 2048;
 #[cfg(not(test))]
@@ -47,6 +50,20 @@ impl AccountService {
         self.db.get_follower_inboxes().await
     }
 
+    /// Get the actor URI this account has moved to, if it has migrated
+    pub async fn get_moved_to(&self) -> Result<Option<String>, AppError> {
+        self.db.get_setting(MOVED_TO_SETTING_KEY).await
+    }
+
+    /// Record that this account has moved to another actor
+    ///
+    /// Published as `movedTo` on the actor document.
+    pub async fn set_moved_to(&self, target_actor_uri: &str) -> Result<(), AppError> {
+        self.db
+            .set_setting(MOVED_TO_SETTING_KEY, target_actor_uri)
+            .await
+    }
+
     /// Initialize the admin account
     ///
     /// Creates a new account with generated RSA keypair.
@@ -352,6 +369,23 @@ mod tests {
         Arc::new(MediaStorage::new(&media, &cloudflare).await.unwrap())
     }
 
+    #[tokio::test]
+    async fn moved_to_round_trips_through_settings() {
+        let (db, _temp_dir) = create_test_db().await;
+        let storage = create_test_storage().await;
+        let service = AccountService::new(db, storage);
+
+        assert_eq!(service.get_moved_to().await.unwrap(), None);
+        service
+            .set_moved_to("https://new.example/users/alice")
+            .await
+            .unwrap();
+        assert_eq!(
+            service.get_moved_to().await.unwrap().as_deref(),
+            Some("https://new.example/users/alice")
+        );
+    }
+
     #[tokio::test]
     async fn initialize_account_creates_and_rejects_duplicate() {
         let (db, _temp_dir) = create_test_db().await;
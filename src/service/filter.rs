@@ -0,0 +1,536 @@
+//! Filter service
+//!
+//! Evaluates the local account's keyword filters against statuses.
+//! Timelines, notifications and streams load a [`StatusFilter`] for their
+//! context once and run every status through it: `hide` matches are dropped,
+//! `warn` and `blur` matches are reported through the `filtered` array of the
+//! status.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::data::{Database, Filter, FilterKeyword, FilterStatus, Status};
+use crate::error::AppError;
+
+/// Context a filter applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterContext {
+    /// Home and list timelines
+    Home,
+    /// Notifications
+    Notifications,
+    /// Public and hashtag timelines
+    Public,
+    /// Conversation threads
+    Thread,
+    /// Account profiles
+    Account,
+}
+
+impl FilterContext {
+    /// All contexts, in API order
+    pub const ALL: [Self; 5] = [
+        Self::Home,
+        Self::Notifications,
+        Self::Public,
+        Self::Thread,
+        Self::Account,
+    ];
+
+    /// Parse a context name
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|context| context.as_str() == value)
+    }
+
+    /// Context name as stored and exposed through the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Home => "home",
+            Self::Notifications => "notifications",
+            Self::Public => "public",
+            Self::Thread => "thread",
+            Self::Account => "account",
+        }
+    }
+}
+
+/// What happens to a status matching a filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Show the status behind a warning
+    Warn,
+    /// Remove the status entirely
+    Hide,
+    /// Show the status with its media blurred
+    Blur,
+}
+
+impl FilterAction {
+    /// Parse an action name
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(Self::Warn),
+            "hide" => Some(Self::Hide),
+            "blur" => Some(Self::Blur),
+            _ => None,
+        }
+    }
+
+    /// Action name as stored and exposed through the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Hide => "hide",
+            Self::Blur => "blur",
+        }
+    }
+}
+
+/// Keyword checked against status text
+#[derive(Debug, Clone)]
+struct Keyword {
+    text: String,
+    /// Lowercased characters used for matching
+    needle: Vec<char>,
+    whole_word: bool,
+}
+
+impl Keyword {
+    fn new(text: &str, whole_word: bool) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        Some(Self {
+            text: text.to_string(),
+            needle: text.to_lowercase().chars().collect(),
+            whole_word,
+        })
+    }
+
+    fn matches(&self, haystack: &[char]) -> bool {
+        let needle = &self.needle;
+        if needle.len() > haystack.len() {
+            return false;
+        }
+
+        // Like Mastodon, only require a boundary on sides of the keyword that
+        // are themselves word characters, so `#tag` or `c++` still match.
+        let check_start = self.whole_word && is_word_char(needle[0]);
+        let check_end = self.whole_word && is_word_char(needle[needle.len() - 1]);
+
+        (0..=haystack.len() - needle.len()).any(|start| {
+            let end = start + needle.len();
+            haystack[start..end] == needle[..]
+                && (!check_start || start == 0 || !is_word_char(haystack[start - 1]))
+                && (!check_end || end == haystack.len() || !is_word_char(haystack[end]))
+        })
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// A filter with its keywords and statuses loaded
+#[derive(Debug, Clone)]
+pub struct ActiveFilter {
+    pub id: String,
+    pub title: String,
+    pub context: Vec<String>,
+    pub expires_at: Option<String>,
+    pub action: FilterAction,
+    pub keywords: Vec<FilterKeyword>,
+    pub statuses: Vec<FilterStatus>,
+    matchers: Vec<Keyword>,
+}
+
+impl ActiveFilter {
+    fn new(filter: Filter, keywords: Vec<FilterKeyword>, statuses: Vec<FilterStatus>) -> Self {
+        let matchers = keywords
+            .iter()
+            .filter_map(|keyword| Keyword::new(&keyword.keyword, keyword.whole_word))
+            .collect();
+        Self {
+            context: filter_contexts(&filter.context)
+                .map(str::to_string)
+                .collect(),
+            // Unknown actions can't be written through the API; treat them as
+            // the least destructive one.
+            action: FilterAction::parse(&filter.filter_action).unwrap_or(FilterAction::Warn),
+            id: filter.id,
+            title: filter.title,
+            expires_at: filter.expires_at,
+            keywords,
+            statuses,
+            matchers,
+        }
+    }
+}
+
+/// A filter that matched a status
+#[derive(Debug, Clone)]
+pub struct FilterMatch {
+    pub filter: Arc<ActiveFilter>,
+    /// Keywords that matched, as configured on the filter
+    pub keyword_matches: Vec<String>,
+    /// IDs of statuses attached to the filter that matched
+    pub status_matches: Vec<String>,
+}
+
+impl FilterMatch {
+    /// Whether the status should be removed instead of shown with a warning
+    pub fn hides(&self) -> bool {
+        self.filter.action == FilterAction::Hide
+    }
+}
+
+/// Active filters for one context
+#[derive(Debug, Clone, Default)]
+pub struct StatusFilter {
+    filters: Vec<Arc<ActiveFilter>>,
+}
+
+impl StatusFilter {
+    /// Whether any filter is active
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Whether any active filter removes matching statuses
+    pub fn can_hide(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| filter.action == FilterAction::Hide)
+    }
+
+    /// Evaluate every active filter against a status
+    ///
+    /// Keywords match against the content warning and the text of the status
+    /// (HTML tags stripped, entities decoded), case-insensitively. Statuses
+    /// attached to a filter match by ID.
+    pub fn matches(&self, status: &Status) -> Vec<FilterMatch> {
+        if self.filters.is_empty() {
+            return Vec::new();
+        }
+
+        let haystack = searchable_text(status);
+        self.filters
+            .iter()
+            .filter_map(|filter| {
+                let keyword_matches: Vec<String> = filter
+                    .matchers
+                    .iter()
+                    .filter(|keyword| keyword.matches(&haystack))
+                    .map(|keyword| keyword.text.clone())
+                    .collect();
+                let status_matches: Vec<String> = filter
+                    .statuses
+                    .iter()
+                    .filter(|filter_status| filter_status.status_id == status.id)
+                    .map(|filter_status| filter_status.status_id.clone())
+                    .collect();
+                (!keyword_matches.is_empty() || !status_matches.is_empty()).then(|| FilterMatch {
+                    filter: filter.clone(),
+                    keyword_matches,
+                    status_matches,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether a status is removed by a `hide` filter
+    pub fn hides(&self, status: &Status) -> bool {
+        self.can_hide() && self.matches(status).iter().any(FilterMatch::hides)
+    }
+}
+
+/// Lowercased status text used for keyword matching
+fn searchable_text(status: &Status) -> Vec<char> {
+    let mut text = String::new();
+    if let Some(content_warning) = &status.content_warning {
+        text.push_str(content_warning);
+        text.push('\n');
+    }
+
+    // Block tags are replaced with spaces so adjacent paragraphs don't merge
+    // into a single word; inline link markup is dropped so `#<span>tag</span>`
+    // still reads as `#tag`.
+    let mut stripped = String::with_capacity(status.content.len());
+    let mut tag: Option<String> = None;
+    for ch in status.content.chars() {
+        match (&mut tag, ch) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(name), '>') => {
+                let name = name
+                    .trim_start_matches('/')
+                    .split(|ch: char| ch.is_whitespace() || ch == '/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                if !matches!(name.as_str(), "a" | "span") {
+                    stripped.push(' ');
+                }
+                tag = None;
+            }
+            (Some(name), _) => name.push(ch),
+            (None, _) => stripped.push(ch),
+        }
+    }
+    text.push_str(&html_escape::decode_html_entities(&stripped));
+
+    text.to_lowercase().chars().collect()
+}
+
+fn is_expired(expires_at: Option<&str>, now: DateTime<Utc>) -> bool {
+    expires_at
+        .and_then(|expires_at| DateTime::parse_from_rfc3339(expires_at).ok())
+        .is_some_and(|expires_at| expires_at <= now)
+}
+
+/// Filter service
+pub struct FilterService {
+    db: Arc<Database>,
+}
+
+impl FilterService {
+    /// Create new filter service
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Load the filters active in a context
+    pub async fn status_filter(&self, context: FilterContext) -> Result<StatusFilter, AppError> {
+        let now = Utc::now();
+        let filters: Vec<Filter> = self
+            .db
+            .get_active_filters(now)
+            .await?
+            .into_iter()
+            .filter(|filter| {
+                !is_expired(filter.expires_at.as_deref(), now)
+                    && filter_contexts(&filter.context).any(|ctx| ctx == context.as_str())
+            })
+            .collect();
+
+        let filters = self.load(filters).await?;
+        Ok(StatusFilter {
+            filters: filters.into_iter().map(Arc::new).collect(),
+        })
+    }
+
+    /// List every filter, including expired ones
+    pub async fn list(&self) -> Result<Vec<ActiveFilter>, AppError> {
+        let filters = self.db.get_all_filters_v2().await?;
+        self.load(filters).await
+    }
+
+    /// Get a filter by ID
+    pub async fn get(&self, id: &str) -> Result<ActiveFilter, AppError> {
+        let filter = self.db.get_filter_v2(id).await?.ok_or(AppError::NotFound)?;
+        Ok(self.load(vec![filter]).await?.remove(0))
+    }
+
+    /// Attach keywords and statuses to filter rows
+    async fn load(&self, filters: Vec<Filter>) -> Result<Vec<ActiveFilter>, AppError> {
+        if filters.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let filter_ids: Vec<String> = filters.iter().map(|filter| filter.id.clone()).collect();
+        let mut keywords: HashMap<String, Vec<FilterKeyword>> = HashMap::new();
+        for keyword in self.db.get_filter_keywords_batch(&filter_ids).await? {
+            keywords
+                .entry(keyword.filter_id.clone())
+                .or_default()
+                .push(keyword);
+        }
+        let mut statuses: HashMap<String, Vec<FilterStatus>> = HashMap::new();
+        for status in self.db.get_filter_statuses_batch(&filter_ids).await? {
+            statuses
+                .entry(status.filter_id.clone())
+                .or_default()
+                .push(status);
+        }
+
+        Ok(filters
+            .into_iter()
+            .map(|filter| {
+                let filter_keywords = keywords.remove(&filter.id).unwrap_or_default();
+                let filter_statuses = statuses.remove(&filter.id).unwrap_or_default();
+                ActiveFilter::new(filter, filter_keywords, filter_statuses)
+            })
+            .collect())
+    }
+}
+
+/// Parse the comma-separated context column
+fn filter_contexts(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(',').map(str::trim).filter(|ctx| !ctx.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword_matches(keyword: &str, whole_word: bool, text: &str) -> bool {
+        let haystack: Vec<char> = text.to_lowercase().chars().collect();
+        Keyword::new(keyword, whole_word)
+            .unwrap()
+            .matches(&haystack)
+    }
+
+    fn test_status(content: &str, content_warning: Option<&str>) -> Status {
+        Status {
+            id: "status-1".to_string(),
+            uri: "https://example.com/statuses/1".to_string(),
+            content: content.to_string(),
+            content_warning: content_warning.map(str::to_string),
+            visibility: "public".to_string(),
+            language: Some("en".to_string()),
+            account_address: String::new(),
+            is_local: true,
+            in_reply_to_uri: None,
+            boost_of_uri: None,
+            persisted_reason: "own".to_string(),
+            created_at: Utc::now(),
+            fetched_at: None,
+        }
+    }
+
+    fn test_filter_with_statuses(
+        title: &str,
+        action: FilterAction,
+        whole_word: bool,
+        status_ids: &[&str],
+    ) -> StatusFilter {
+        let filter = Filter {
+            id: "filter-1".to_string(),
+            title: title.to_string(),
+            phrase: title.to_string(),
+            context: "home".to_string(),
+            expires_at: None,
+            filter_action: action.as_str().to_string(),
+            irreversible: action == FilterAction::Hide,
+            whole_word,
+        };
+        let keywords = vec![FilterKeyword {
+            id: "keyword-1".to_string(),
+            filter_id: "filter-1".to_string(),
+            keyword: title.to_string(),
+            whole_word,
+        }];
+        let statuses = status_ids
+            .iter()
+            .enumerate()
+            .map(|(index, status_id)| FilterStatus {
+                id: format!("filter-status-{}", index),
+                filter_id: "filter-1".to_string(),
+                status_id: status_id.to_string(),
+            })
+            .collect();
+        StatusFilter {
+            filters: vec![Arc::new(ActiveFilter::new(filter, keywords, statuses))],
+        }
+    }
+
+    fn test_filter(title: &str, action: FilterAction, whole_word: bool) -> StatusFilter {
+        test_filter_with_statuses(title, action, whole_word, &[])
+    }
+
+    #[test]
+    fn whole_word_requires_word_boundaries() {
+        assert!(keyword_matches("cat", true, "a cat sat"));
+        assert!(keyword_matches("cat", true, "Cat!"));
+        assert!(!keyword_matches("cat", true, "concatenate"));
+        assert!(!keyword_matches("cat", true, "cats"));
+        assert!(keyword_matches("cat", false, "concatenate"));
+    }
+
+    #[test]
+    fn whole_word_skips_boundary_for_non_word_edges() {
+        assert!(keyword_matches("#rust", true, "learning#rust today"));
+        assert!(keyword_matches("c++", true, "I like c++."));
+        assert!(!keyword_matches("c++", true, "abc++"));
+    }
+
+    #[test]
+    fn matching_is_case_insensitive_for_unicode() {
+        assert!(keyword_matches("ÉTÉ", true, "un bel été"));
+    }
+
+    #[test]
+    fn matches_content_warning_and_strips_html() {
+        let filter = test_filter("spoiler", FilterAction::Warn, true);
+        let status = test_status("<p>nothing here</p>", Some("Spoiler alert"));
+        let matches = filter.matches(&status);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].keyword_matches, vec!["spoiler".to_string()]);
+        assert!(!matches[0].hides());
+
+        let filter = test_filter("end start", FilterAction::Warn, true);
+        let status = test_status("<p>the end</p><p>start again</p>", None);
+        assert!(filter.matches(&status).is_empty());
+
+        let filter = test_filter("fish & chips", FilterAction::Warn, true);
+        let status = test_status("<p>fish &amp; chips</p>", None);
+        assert_eq!(filter.matches(&status).len(), 1);
+
+        let filter = test_filter("#rust", FilterAction::Warn, true);
+        let status = test_status(
+            r#"<p>I like <a href="https://example.com/tags/rust" class="mention hashtag" rel="tag">#<span>Rust</span></a></p>"#,
+            None,
+        );
+        assert_eq!(filter.matches(&status).len(), 1);
+    }
+
+    #[test]
+    fn hide_action_hides_matching_statuses_only() {
+        let filter = test_filter("secret", FilterAction::Hide, true);
+        assert!(filter.can_hide());
+        assert!(filter.hides(&test_status("<p>a secret</p>", None)));
+        assert!(!filter.hides(&test_status("<p>public</p>", None)));
+        assert!(
+            !test_filter("secret", FilterAction::Warn, true)
+                .hides(&test_status("<p>a secret</p>", None))
+        );
+    }
+
+    #[test]
+    fn blur_action_reports_without_hiding() {
+        let filter = test_filter("secret", FilterAction::Blur, true);
+        assert!(!filter.can_hide());
+        let matches = filter.matches(&test_status("<p>a secret</p>", None));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].filter.action.as_str(), "blur");
+    }
+
+    #[test]
+    fn attached_statuses_match_by_id() {
+        let filter =
+            test_filter_with_statuses("unrelated", FilterAction::Hide, true, &["status-1"]);
+        let matches = filter.matches(&test_status("<p>nothing here</p>", None));
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].keyword_matches.is_empty());
+        assert_eq!(matches[0].status_matches, vec!["status-1".to_string()]);
+        assert!(matches[0].hides());
+    }
+
+    #[test]
+    fn expiry_is_checked_against_now() {
+        let now = Utc::now();
+        assert!(!is_expired(None, now));
+        assert!(is_expired(
+            Some(&(now - chrono::Duration::seconds(1)).to_rfc3339()),
+            now
+        ));
+        assert!(!is_expired(
+            Some(&(now + chrono::Duration::hours(1)).to_rfc3339()),
+            now
+        ));
+    }
+}
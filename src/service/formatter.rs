@@ -0,0 +1,439 @@
+//! Status content formatting
+//!
+//! Renders status text as Mastodon-compatible HTML: paragraphs, line
+//! breaks, linkified URLs, mentions and hashtags. Text is written either as
+//! plain text or as Markdown.
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::data::StatusMention;
+use crate::federation::{parse_mention_at, sanitize_status_html};
+
+/// Number of URL characters shown before the rest is hidden behind an ellipsis
+const URL_DISPLAY_LENGTH: usize = 30;
+
+/// Attributes Mastodon puts on linkified URLs
+const LINK_ATTRIBUTES: &str =
+    r#"target="_blank" rel="nofollow noopener noreferrer" translate="no""#;
+
+/// Authoring format of a local status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentType {
+    #[default]
+    PlainText,
+    Markdown,
+}
+
+impl ContentType {
+    /// Parse a MIME type as accepted by `POST /api/v1/statuses`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text/plain" => Some(Self::PlainText),
+            "text/markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    /// MIME type as stored and exposed through the APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PlainText => "text/plain",
+            Self::Markdown => "text/markdown",
+        }
+    }
+}
+
+/// Formats local status text into HTML
+pub struct ContentFormatter<'a> {
+    base_url: &'a str,
+    local_domain: String,
+    local_username: &'a str,
+    mentions: &'a [StatusMention],
+}
+
+impl<'a> ContentFormatter<'a> {
+    /// Create a formatter for statuses of `local_username` on `base_url`
+    pub fn new(base_url: &'a str, local_username: &'a str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let local_domain = url::Url::parse(base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            .unwrap_or_default();
+        Self {
+            base_url,
+            local_domain,
+            local_username,
+            mentions: &[],
+        }
+    }
+
+    /// Link `@user@domain` mentions of these resolved remote accounts.
+    ///
+    /// Remote mentions that were not resolved are left as plain text.
+    pub fn with_mentions(mut self, mentions: &'a [StatusMention]) -> Self {
+        self.mentions = mentions;
+        self
+    }
+
+    /// Render plain text as HTML.
+    ///
+    /// Blank lines separate paragraphs and single newlines become `<br />`.
+    pub fn format(&self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        let mut paragraphs: Vec<Vec<&str>> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in normalized.split('\n') {
+            if line.trim().is_empty() {
+                if !current.is_empty() {
+                    paragraphs.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(line);
+            }
+        }
+        if !current.is_empty() {
+            paragraphs.push(current);
+        }
+
+        if paragraphs.is_empty() {
+            return "<p></p>".to_string();
+        }
+
+        paragraphs
+            .iter()
+            .map(|lines| {
+                let body = lines
+                    .iter()
+                    .map(|line| self.format_line(line))
+                    .collect::<Vec<_>>()
+                    .join("<br />");
+                format!("<p>{}</p>", body)
+            })
+            .collect()
+    }
+
+    /// Render text written in `content_type` as HTML
+    pub fn render(&self, text: &str, content_type: ContentType) -> String {
+        match content_type {
+            ContentType::PlainText => self.format(text),
+            ContentType::Markdown => self.format_markdown(text),
+        }
+    }
+
+    /// Render Markdown as sanitized HTML.
+    ///
+    /// Mentions, hashtags and bare URLs are linked as in plain text, except
+    /// inside code and links. Single newlines become `<br />` here too.
+    pub fn format_markdown(&self, text: &str) -> String {
+        let mut events: Vec<Event> = Vec::new();
+        let mut pending_text = String::new();
+        let mut verbatim_depth = 0usize;
+
+        for event in Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH) {
+            if let (0, Event::Text(text)) = (verbatim_depth, &event) {
+                pending_text.push_str(text);
+                continue;
+            }
+            if !pending_text.is_empty() {
+                events.push(Event::InlineHtml(self.format_line(&pending_text).into()));
+                pending_text.clear();
+            }
+
+            match &event {
+                Event::Start(Tag::CodeBlock(_) | Tag::Link { .. } | Tag::Image { .. }) => {
+                    verbatim_depth += 1
+                }
+                Event::End(TagEnd::CodeBlock | TagEnd::Link | TagEnd::Image) => {
+                    verbatim_depth = verbatim_depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            events.push(match event {
+                Event::SoftBreak => Event::HardBreak,
+                event => event,
+            });
+        }
+        if !pending_text.is_empty() {
+            events.push(Event::InlineHtml(self.format_line(&pending_text).into()));
+        }
+
+        let mut html = String::with_capacity(text.len() * 2);
+        pulldown_cmark::html::push_html(&mut html, events.into_iter());
+        let sanitized = sanitize_status_html(html.trim_end());
+        if sanitized.is_empty() {
+            return "<p></p>".to_string();
+        }
+        sanitized
+    }
+
+    fn format_line(&self, line: &str) -> String {
+        let chars: Vec<char> = line.chars().collect();
+        let mut html = String::with_capacity(line.len());
+        let mut plain = String::new();
+
+        let mut index = 0;
+        while index < chars.len() {
+            let rendered = match chars[index] {
+                'h' | 'H' => {
+                    parse_url_at(&chars, index).map(|end| (end, render_url(&chars[index..end])))
+                }
+                '@' => parse_mention_at(&chars, index).map(|token| {
+                    (
+                        token.end,
+                        self.render_mention(
+                            &chars[index..token.end],
+                            &token.username,
+                            token.domain.as_deref(),
+                        ),
+                    )
+                }),
+                '#' => parse_hashtag_at(&chars, index).map(|end| {
+                    let tag: String = chars[index + 1..end].iter().collect();
+                    (end, self.render_hashtag(&tag))
+                }),
+                _ => None,
+            };
+
+            match rendered {
+                Some((end, fragment)) => {
+                    html.push_str(&html_escape::encode_text(&plain));
+                    plain.clear();
+                    html.push_str(&fragment);
+                    index = end;
+                }
+                None => {
+                    plain.push(chars[index]);
+                    index += 1;
+                }
+            }
+        }
+        html.push_str(&html_escape::encode_text(&plain));
+
+        html
+    }
+
+    fn render_mention(&self, raw: &[char], username: &str, domain: Option<&str>) -> String {
+        let is_local = domain.is_none_or(|domain| domain == self.local_domain);
+        let href = if is_local {
+            username
+                .eq_ignore_ascii_case(self.local_username)
+                .then(|| format!("{}/users/{}", self.base_url, self.local_username))
+        } else {
+            let address = format!("{}@{}", username, domain.unwrap_or_default());
+            self.mentions
+                .iter()
+                .find(|mention| mention.account_address.eq_ignore_ascii_case(&address))
+                .map(|mention| mention.actor_uri.clone())
+        };
+
+        match href {
+            Some(href) => format!(
+                r#"<span class="h-card" translate="no"><a href="{}" class="u-url mention">@<span>{}</span></a></span>"#,
+                html_escape::encode_double_quoted_attribute(&href),
+                html_escape::encode_text(username)
+            ),
+            None => html_escape::encode_text(&raw.iter().collect::<String>()).into_owned(),
+        }
+    }
+
+    fn render_hashtag(&self, tag: &str) -> String {
+        format!(
+            r#"<a href="{}/tags/{}" class="mention hashtag" rel="tag">#<span>{}</span></a>"#,
+            self.base_url,
+            tag.to_ascii_lowercase(),
+            tag
+        )
+    }
+}
+
+/// Characters allowed in a hashtag, matching `extract_hashtags_from_content`
+fn is_hashtag_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+/// Parse a hashtag starting at `chars[index]`, returning its end index
+fn parse_hashtag_at(chars: &[char], index: usize) -> Option<usize> {
+    let at_boundary = index == 0 || {
+        let previous = chars[index - 1];
+        !is_hashtag_char(previous) && !matches!(previous, '&' | '/')
+    };
+    if !at_boundary {
+        return None;
+    }
+
+    let mut end = index + 1;
+    while end < chars.len() && is_hashtag_char(chars[end]) {
+        end += 1;
+    }
+    (end > index + 1).then_some(end)
+}
+
+/// Parse an `http(s)://` URL starting at `chars[index]`, returning its end index.
+///
+/// Trailing punctuation and unbalanced closing parentheses are left out.
+fn parse_url_at(chars: &[char], index: usize) -> Option<usize> {
+    if index > 0 && (chars[index - 1].is_alphanumeric() || matches!(chars[index - 1], '/' | '@')) {
+        return None;
+    }
+    let head: String = chars[index..]
+        .iter()
+        .take(8)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let scheme_length = if head.starts_with("https://") {
+        8
+    } else if head.starts_with("http://") {
+        7
+    } else {
+        return None;
+    };
+
+    let mut end = index + scheme_length;
+    while end < chars.len() && !chars[end].is_whitespace() && !matches!(chars[end], '<' | '>' | '"')
+    {
+        end += 1;
+    }
+    loop {
+        match chars[end - 1] {
+            '.' | ',' | ':' | ';' | '!' | '?' | '\'' => end -= 1,
+            ')' => {
+                let opened = chars[index..end].iter().filter(|ch| **ch == '(').count();
+                let closed = chars[index..end].iter().filter(|ch| **ch == ')').count();
+                if closed > opened {
+                    end -= 1;
+                } else {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    (end > index + scheme_length).then_some(end)
+}
+
+/// Render a URL with the scheme hidden and long paths shortened
+fn render_url(url: &[char]) -> String {
+    let url: String = url.iter().collect();
+    let lowercase = url.to_ascii_lowercase();
+    let mut prefix_length = if lowercase.starts_with("https://") {
+        8
+    } else {
+        7
+    };
+    if lowercase[prefix_length..].starts_with("www.") {
+        prefix_length += 4;
+    }
+    let (prefix, display) = url.split_at(prefix_length);
+
+    let display_chars: Vec<char> = display.chars().collect();
+    let (visible, hidden, visible_class) = if display_chars.len() > URL_DISPLAY_LENGTH {
+        // Never start the hidden part with `#`, which would read as a hashtag.
+        let mut cut = URL_DISPLAY_LENGTH;
+        while cut < display_chars.len() && display_chars[cut] == '#' {
+            cut += 1;
+        }
+        (
+            display_chars[..cut].iter().collect::<String>(),
+            display_chars[cut..].iter().collect::<String>(),
+            "ellipsis",
+        )
+    } else {
+        (display.to_string(), String::new(), "")
+    };
+
+    format!(
+        r#"<a href="{}" {}><span class="invisible">{}</span><span class="{}">{}</span><span class="invisible">{}</span></a>"#,
+        html_escape::encode_double_quoted_attribute(&url),
+        LINK_ATTRIBUTES,
+        html_escape::encode_text(prefix),
+        visible_class,
+        html_escape::encode_text(&visible),
+        html_escape::encode_text(&hidden)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentFormatter, ContentType};
+    use crate::data::StatusMention;
+
+    fn formatter() -> ContentFormatter<'static> {
+        ContentFormatter::new("https://local.example", "alice")
+    }
+
+    #[test]
+    fn escapes_text_and_builds_paragraphs() {
+        assert_eq!(
+            formatter().format("a <b> & c\nnext line\n\n\nsecond"),
+            "<p>a &lt;b&gt; &amp; c<br />next line</p><p>second</p>"
+        );
+        assert_eq!(formatter().format(""), "<p></p>");
+    }
+
+    #[test]
+    fn linkifies_urls_with_shortened_display() {
+        assert_eq!(
+            formatter().format("see https://example.com/a."),
+            "<p>see <a href=\"https://example.com/a\" target=\"_blank\" rel=\"nofollow noopener noreferrer\" translate=\"no\"><span class=\"invisible\">https://</span><span class=\"\">example.com/a</span><span class=\"invisible\"></span></a>.</p>"
+        );
+        let html =
+            formatter().format("(https://www.example.com/a/very/long/path/that/goes/on?q=1&r=2)");
+        assert!(html.starts_with(
+            "<p>(<a href=\"https://www.example.com/a/very/long/path/that/goes/on?q=1&amp;r=2\""
+        ));
+        assert!(html.contains("<span class=\"invisible\">https://www.</span><span class=\"ellipsis\">example.com/a/very/long/path/t</span><span class=\"invisible\">hat/goes/on?q=1&amp;r=2</span></a>)</p>"));
+    }
+
+    #[test]
+    fn links_hashtags() {
+        let html = formatter().format("#Rust is fun, issue#1 and a/#b are not tags");
+        assert_eq!(
+            html,
+            "<p><a href=\"https://local.example/tags/rust\" class=\"mention hashtag\" rel=\"tag\">#<span>Rust</span></a> is fun, issue#1 and a/#b are not tags</p>"
+        );
+        assert_eq!(
+            crate::data::extract_hashtags_from_content(&html),
+            vec!["rust".to_string()]
+        );
+    }
+
+    #[test]
+    fn links_local_and_resolved_remote_mentions() {
+        let mentions = vec![StatusMention {
+            status_id: "1".to_string(),
+            account_address: "bob@remote.example".to_string(),
+            actor_uri: "https://remote.example/users/bob".to_string(),
+            inbox_uri: None,
+        }];
+        let html = formatter()
+            .with_mentions(&mentions)
+            .format("@alice @Bob@Remote.example @carol@unknown.example bob@remote.example");
+        assert_eq!(
+            html,
+            "<p><span class=\"h-card\" translate=\"no\"><a href=\"https://local.example/users/alice\" class=\"u-url mention\">@<span>alice</span></a></span> \
+             <span class=\"h-card\" translate=\"no\"><a href=\"https://remote.example/users/bob\" class=\"u-url mention\">@<span>Bob</span></a></span> \
+             @carol@unknown.example bob@remote.example</p>"
+        );
+    }
+
+    #[test]
+    fn renders_sanitized_markdown_with_links() {
+        let html = formatter().render(
+            "# Title\n\nSome **bold** #rust,\nsee https://example.com\n\n```\n#not_a_tag <b>\n```\n\n[@alice](https://example.com/x) <script>alert(1)</script>",
+            ContentType::Markdown,
+        );
+        assert!(html.starts_with("<h1>Title</h1>\n<p>Some <strong>bold</strong> <a href=\"https://local.example/tags/rust\" class=\"mention hashtag\" rel=\"noopener noreferrer\">#<span>rust</span></a>,<br>\nsee <a href=\"https://example.com\""));
+        assert!(html.contains("<pre><code>#not_a_tag &lt;b&gt;\n</code></pre>"));
+        assert!(
+            html.contains(
+                "<a href=\"https://example.com/x\" rel=\"noopener noreferrer\">@alice</a>"
+            )
+        );
+        assert!(!html.contains("script"));
+        assert_eq!(
+            crate::data::extract_hashtags_from_content(&html),
+            vec!["rust".to_string()]
+        );
+    }
+}
@@ -4,9 +4,17 @@
 //! Services orchestrate database, cache, and federation operations.
 
 mod account;
+mod filter;
+mod formatter;
 mod status;
+mod streaming;
 mod timeline;
 
 pub use account::AccountService;
+pub use filter::{
+    ActiveFilter, FilterAction, FilterContext, FilterMatch, FilterService, StatusFilter,
+};
+pub use formatter::{ContentFormatter, ContentType};
 pub use status::StatusService;
+pub use streaming::{STREAMING_BUS_CAPACITY, StreamEvent, StreamingBus};
 pub use timeline::TimelineService;
@@ -5,7 +5,11 @@
 
 use std::sync::Arc;
 
-use crate::data::{Database, EntityId, MediaAttachment, PersistedReason, Status, TimelineCache};
+use super::{ContentFormatter, ContentType, StreamEvent, StreamingBus};
+use crate::data::{
+    Database, EntityId, MediaAttachment, PersistedReason, ScheduledStatus, Status, StatusMention,
+    StatusSource, TimelineCache,
+};
 use crate::error::AppError;
 use crate::storage::MediaStorage;
 
@@ -88,6 +92,8 @@ pub struct StatusService {
     cache: Arc<TimelineCache>,
     storage: Arc<MediaStorage>,
     base_url: String,
+    /// Streaming bus notified about status changes
+    streaming: Option<Arc<StreamingBus>>,
 }
 
 impl StatusService {
@@ -103,6 +109,22 @@ impl StatusService {
             cache,
             storage,
             base_url,
+            streaming: None,
+        }
+    }
+
+    /// Set streaming bus
+    ///
+    /// When set, created, edited and deleted statuses are published to
+    /// connected streaming clients.
+    pub fn with_streaming(mut self, streaming: Arc<StreamingBus>) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    fn publish(&self, event: StreamEvent) {
+        if let Some(streaming) = &self.streaming {
+            streaming.publish(event);
         }
     }
 
@@ -113,7 +135,7 @@ impl StatusService {
     /// Create a new status
     ///
     /// # Arguments
-    /// * `content` - HTML content
+    /// * `content` - Plain text content, rendered to HTML
     /// * `content_warning` - Optional CW text
     /// * `visibility` - public, unlisted, private, direct
     /// * `language` - ISO 639-1 language code
@@ -126,6 +148,7 @@ impl StatusService {
     /// # Side Effects
     /// - Inserts into database
     /// - Attaches media
+    /// - Publishes an `update` streaming event
     /// - Triggers federation delivery (via returned status)
     pub async fn create(
         &self,
@@ -165,7 +188,7 @@ impl StatusService {
         let status = Status {
             id: status_id,
             uri,
-            content: format!("<p>{}</p>", html_escape::encode_text(&content)),
+            content: ContentFormatter::new(&self.base_url, &account.username).format(&content),
             content_warning,
             visibility: normalized_visibility,
             language: language.or(Some("en".to_string())),
@@ -180,6 +203,12 @@ impl StatusService {
 
         self.persist_local_status_with_media_and_poll(&status, &media_ids, None)
             .await?;
+        self.save_source(&StatusSource {
+            status_id: status.id.clone(),
+            text: content,
+            content_type: ContentType::PlainText.as_str().to_string(),
+        })
+        .await?;
 
         Ok(status)
     }
@@ -193,7 +222,33 @@ impl StatusService {
     ) -> Result<(), AppError> {
         self.db
             .insert_status_with_media_and_poll(status, media_ids, poll)
-            .await
+            .await?;
+        self.publish(StreamEvent::Update(status.clone()));
+        Ok(())
+    }
+
+    /// Record the remote accounts mentioned by a status, replacing earlier ones
+    pub async fn save_mentions(
+        &self,
+        status_id: &str,
+        mentions: &[StatusMention],
+    ) -> Result<(), AppError> {
+        self.db.replace_status_mentions(status_id, mentions).await
+    }
+
+    /// Store the text a local status was rendered from
+    pub async fn save_source(&self, source: &StatusSource) -> Result<(), AppError> {
+        self.db.upsert_status_source(source).await
+    }
+
+    /// Get the text a local status was rendered from, if recorded
+    pub async fn get_source(&self, status_id: &str) -> Result<Option<StatusSource>, AppError> {
+        self.db.get_status_source(status_id).await
+    }
+
+    /// Get the remote accounts mentioned by a status
+    pub async fn get_mentions(&self, status_id: &str) -> Result<Vec<StatusMention>, AppError> {
+        self.db.get_status_mentions(status_id).await
     }
 
     /// Get status by ID
@@ -247,7 +302,9 @@ impl StatusService {
     ) -> Result<(), AppError> {
         self.db
             .update_status_with_edit_snapshot(previous, updated)
-            .await
+            .await?;
+        self.publish(StreamEvent::StatusUpdate(updated.clone()));
+        Ok(())
     }
 
     /// Persist status update with atomic edit snapshot and optional media replacement.
@@ -259,7 +316,9 @@ impl StatusService {
     ) -> Result<(), AppError> {
         self.db
             .update_status_with_edit_snapshot_and_media(previous, updated, media_ids)
-            .await
+            .await?;
+        self.publish(StreamEvent::StatusUpdate(updated.clone()));
+        Ok(())
     }
 
     /// Get media attachments linked to a status.
@@ -407,6 +466,7 @@ impl StatusService {
         poll_options: Option<&str>,
         poll_expires_in: Option<i64>,
         poll_multiple: bool,
+        content_type: &str,
     ) -> Result<String, AppError> {
         self.db
             .create_scheduled_status(
@@ -419,6 +479,7 @@ impl StatusService {
                 poll_options,
                 poll_expires_in,
                 poll_multiple,
+                content_type,
             )
             .await
     }
@@ -431,6 +492,54 @@ impl StatusService {
         self.db.get_scheduled_status(id).await
     }
 
+    /// Get scheduled statuses that are due for publication.
+    pub async fn get_due_scheduled_statuses(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<ScheduledStatus>, AppError> {
+        self.db
+            .get_due_scheduled_statuses(chrono::Utc::now(), limit)
+            .await
+    }
+
+    /// Publish a scheduled status as a local status.
+    ///
+    /// The scheduled row is consumed in the same transaction that inserts the
+    /// status. Returns `false` when the row was already published or cancelled.
+    pub async fn publish_scheduled_status(
+        &self,
+        scheduled_id: &str,
+        status: &Status,
+        media_ids: &[String],
+        poll: Option<(&[String], i64, bool)>,
+    ) -> Result<bool, AppError> {
+        let published = self
+            .db
+            .publish_scheduled_status(scheduled_id, status, media_ids, poll)
+            .await?;
+        if published {
+            self.publish(StreamEvent::Update(status.clone()));
+        }
+        Ok(published)
+    }
+
+    /// Retry a scheduled status after a failed publication attempt.
+    pub async fn retry_scheduled_status(
+        &self,
+        id: &str,
+        next_attempt_at: chrono::DateTime<chrono::Utc>,
+        error: &str,
+    ) -> Result<(), AppError> {
+        self.db
+            .reschedule_scheduled_status_attempt(id, next_attempt_at, error)
+            .await
+    }
+
+    /// Stop trying to publish a scheduled status; the owner still sees it.
+    pub async fn fail_scheduled_status(&self, id: &str, error: &str) -> Result<(), AppError> {
+        self.db.mark_scheduled_status_failed(id, error).await
+    }
+
     /// Delete status
     ///
     /// Only allowed for own statuses.
@@ -438,6 +547,7 @@ impl StatusService {
     /// # Side Effects
     /// - Deletes from database
     /// - Deletes associated media from R2
+    /// - Publishes a `delete` streaming event
     /// - Should trigger Delete activity (handled by caller)
     pub async fn delete(&self, id: &str) -> Result<(), AppError> {
         let status = self.get(id).await?;
@@ -454,6 +564,7 @@ impl StatusService {
         }
 
         self.db.delete_status(&status.id).await?;
+        self.publish(StreamEvent::Delete(status.id.clone()));
         Ok(())
     }
 
@@ -1059,6 +1170,7 @@ mod tests {
                 attachments: vec![],
                 reply_to_uri: None,
                 boost_of_uri: None,
+                content_warning: None,
             })
             .await;
 
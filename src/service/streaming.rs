@@ -0,0 +1,109 @@
+//! Streaming event bus
+//!
+//! In-process broadcast channel that fans out timeline and notification
+//! events to connected streaming clients. Publishers never block: when no
+//! client is connected events are simply dropped, and slow clients skip
+//! ahead once they fall behind the channel capacity.
+
+use tokio::sync::broadcast;
+
+use crate::data::{Notification, Status};
+
+/// Number of events buffered per subscriber before it starts lagging
+pub const STREAMING_BUS_CAPACITY: usize = 1024;
+
+/// Event published to streaming clients
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// A status appeared on the home timeline (local post or followee post)
+    Update(Status),
+    /// An existing status was edited
+    StatusUpdate(Status),
+    /// A status was deleted; carries the status ID exposed through the API
+    Delete(String),
+    /// A notification was created for the local account
+    Notification(Notification),
+}
+
+/// Broadcast bus shared through `AppState`
+pub struct StreamingBus {
+    sender: broadcast::Sender<StreamEvent>,
+}
+
+impl StreamingBus {
+    /// Create new streaming bus
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Publish an event to every connected subscriber
+    ///
+    /// Returns the number of subscribers the event was delivered to.
+    pub fn publish(&self, event: StreamEvent) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// Subscribe to events published after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<StreamEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Number of currently connected subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for StreamingBus {
+    fn default() -> Self {
+        Self::new(STREAMING_BUS_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publish_without_subscribers_is_dropped() {
+        let bus = StreamingBus::new(4);
+        assert_eq!(bus.publish(StreamEvent::Delete("1".to_string())), 0);
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn subscribers_receive_events_published_after_subscribing() {
+        let bus = StreamingBus::new(4);
+        bus.publish(StreamEvent::Delete("before".to_string()));
+
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+        assert_eq!(bus.publish(StreamEvent::Delete("after".to_string())), 2);
+
+        for receiver in [&mut first, &mut second] {
+            match receiver.recv().await.unwrap() {
+                StreamEvent::Delete(id) => assert_eq!(id, "after"),
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_skips_to_newest_events() {
+        let bus = StreamingBus::new(2);
+        let mut receiver = bus.subscribe();
+        for id in ["1", "2", "3"] {
+            bus.publish(StreamEvent::Delete(id.to_string()));
+        }
+
+        assert!(matches!(
+            receiver.recv().await,
+            Err(broadcast::error::RecvError::Lagged(1))
+        ));
+        match receiver.recv().await.unwrap() {
+            StreamEvent::Delete(id) => assert_eq!(id, "2"),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::{collections::HashSet, future::Future, sync::Arc};
 
+use super::{FilterContext, FilterMatch, FilterService, StatusFilter};
 use crate::data::{Database, ProfileCache, Status, TimelineCache};
 use crate::error::AppError;
 
@@ -12,6 +13,8 @@ pub struct TimelineService {
     db: Arc<Database>,
     timeline_cache: Arc<TimelineCache>,
     profile_cache: Arc<ProfileCache>,
+    /// Whether the local account's keyword filters are applied
+    apply_filters: bool,
 }
 
 const TIMELINE_MUTE_OVERFETCH_MULTIPLIER: usize = 3;
@@ -28,9 +31,28 @@ impl TimelineService {
             db,
             timeline_cache,
             profile_cache,
+            apply_filters: true,
         }
     }
 
+    /// Enable or disable keyword filters
+    ///
+    /// Filters are enabled by default; disable them for anonymous requests
+    /// so the owner's filters don't leak into what visitors see.
+    pub fn with_filters(mut self, apply_filters: bool) -> Self {
+        self.apply_filters = apply_filters;
+        self
+    }
+
+    async fn load_status_filter(&self, context: FilterContext) -> Result<StatusFilter, AppError> {
+        if !self.apply_filters {
+            return Ok(StatusFilter::default());
+        }
+        FilterService::new(self.db.clone())
+            .status_filter(context)
+            .await
+    }
+
     /// Get home timeline
     ///
     /// Returns local statuses for the single-user instance.
@@ -49,21 +71,28 @@ impl TimelineService {
         min_id: Option<&str>,
     ) -> Result<Vec<TimelineItem>, AppError> {
         let min_id = min_id.map(str::to_string);
+        let status_filter = self.load_status_filter(FilterContext::Home).await?;
         let statuses = self
-            .collect_visible_statuses(limit, max_id.map(str::to_string), |fetch_limit, cursor| {
-                let min_id = min_id.clone();
-                async move {
-                    self.db
-                        .get_local_statuses_in_window(
-                            fetch_limit,
-                            cursor.as_deref(),
-                            min_id.as_deref(),
-                        )
-                        .await
-                }
-            })
+            .collect_visible_statuses(
+                &status_filter,
+                limit,
+                max_id.map(str::to_string),
+                |fetch_limit, cursor| {
+                    let min_id = min_id.clone();
+                    async move {
+                        self.db
+                            .get_local_statuses_in_window(
+                                fetch_limit,
+                                cursor.as_deref(),
+                                min_id.as_deref(),
+                            )
+                            .await
+                    }
+                },
+            )
             .await?;
-        self.build_timeline_items_with_interactions(statuses).await
+        self.build_timeline_items_with_interactions(statuses, &status_filter)
+            .await
     }
 
     /// Get public timeline
@@ -83,8 +112,10 @@ impl TimelineService {
         // Single-user instance currently stores local statuses only,
         // so local_only doesn't change query behavior yet.
         let _ = local_only;
+        let status_filter = self.load_status_filter(FilterContext::Public).await?;
         let statuses = self
             .collect_visible_statuses(
+                &status_filter,
                 limit,
                 max_id.map(str::to_string),
                 |fetch_limit, cursor| async move {
@@ -94,7 +125,8 @@ impl TimelineService {
                 },
             )
             .await?;
-        self.build_timeline_items_with_interactions(statuses).await
+        self.build_timeline_items_with_interactions(statuses, &status_filter)
+            .await
     }
 
     /// Get hashtag timeline.
@@ -107,23 +139,30 @@ impl TimelineService {
     ) -> Result<Vec<TimelineItem>, AppError> {
         let hashtag = hashtag.to_string();
         let min_id = min_id.map(str::to_string);
+        let status_filter = self.load_status_filter(FilterContext::Public).await?;
         let statuses = self
-            .collect_visible_statuses(limit, max_id.map(str::to_string), |fetch_limit, cursor| {
-                let hashtag = hashtag.clone();
-                let min_id = min_id.clone();
-                async move {
-                    self.db
-                        .get_statuses_by_hashtag_in_window(
-                            &hashtag,
-                            fetch_limit,
-                            cursor.as_deref(),
-                            min_id.as_deref(),
-                        )
-                        .await
-                }
-            })
+            .collect_visible_statuses(
+                &status_filter,
+                limit,
+                max_id.map(str::to_string),
+                |fetch_limit, cursor| {
+                    let hashtag = hashtag.clone();
+                    let min_id = min_id.clone();
+                    async move {
+                        self.db
+                            .get_statuses_by_hashtag_in_window(
+                                &hashtag,
+                                fetch_limit,
+                                cursor.as_deref(),
+                                min_id.as_deref(),
+                            )
+                            .await
+                    }
+                },
+            )
             .await?;
-        self.build_timeline_items_with_interactions(statuses).await
+        self.build_timeline_items_with_interactions(statuses, &status_filter)
+            .await
     }
 
     /// Get list timeline.
@@ -141,28 +180,35 @@ impl TimelineService {
         let local_account_address = local_account_address.to_string();
         let local_account_id = local_account_id.to_string();
         let min_id = min_id.map(str::to_string);
+        let status_filter = self.load_status_filter(FilterContext::Home).await?;
         let statuses = self
-            .collect_visible_statuses(limit, max_id.map(str::to_string), |fetch_limit, cursor| {
-                let list_id = list_id.clone();
-                let local_account_address = local_account_address.clone();
-                let local_account_id = local_account_id.clone();
-                let min_id = min_id.clone();
-                async move {
-                    self.db
-                        .get_list_timeline_statuses_in_window(
-                            &list_id,
-                            &local_account_address,
-                            &local_account_id,
-                            default_port,
-                            fetch_limit,
-                            cursor.as_deref(),
-                            min_id.as_deref(),
-                        )
-                        .await
-                }
-            })
+            .collect_visible_statuses(
+                &status_filter,
+                limit,
+                max_id.map(str::to_string),
+                |fetch_limit, cursor| {
+                    let list_id = list_id.clone();
+                    let local_account_address = local_account_address.clone();
+                    let local_account_id = local_account_id.clone();
+                    let min_id = min_id.clone();
+                    async move {
+                        self.db
+                            .get_list_timeline_statuses_in_window(
+                                &list_id,
+                                &local_account_address,
+                                &local_account_id,
+                                default_port,
+                                fetch_limit,
+                                cursor.as_deref(),
+                                min_id.as_deref(),
+                            )
+                            .await
+                    }
+                },
+            )
             .await?;
-        self.build_timeline_items_with_interactions(statuses).await
+        self.build_timeline_items_with_interactions(statuses, &status_filter)
+            .await
     }
 
     /// Get account timeline
@@ -198,6 +244,7 @@ impl TimelineService {
     ) -> Result<Vec<TimelineItem>, AppError> {
         let statuses = self
             .collect_visible_statuses(
+                &StatusFilter::default(),
                 limit,
                 max_id.map(str::to_string),
                 |fetch_limit, cursor| async move {
@@ -218,6 +265,7 @@ impl TimelineService {
                 status,
                 favourited: true,
                 reblogged: false,
+                filtered: Vec::new(),
             });
         }
 
@@ -232,6 +280,7 @@ impl TimelineService {
     ) -> Result<Vec<TimelineItem>, AppError> {
         let statuses = self
             .collect_visible_statuses(
+                &StatusFilter::default(),
                 limit,
                 max_id.map(str::to_string),
                 |fetch_limit, cursor| async move {
@@ -252,6 +301,7 @@ impl TimelineService {
                 status,
                 reblogged: false,
                 bookmarked: true,
+                filtered: Vec::new(),
             });
         }
 
@@ -288,6 +338,7 @@ impl TimelineService {
     async fn build_timeline_items_with_interactions(
         &self,
         statuses: Vec<Status>,
+        status_filter: &StatusFilter,
     ) -> Result<Vec<TimelineItem>, AppError> {
         let status_ids: Vec<String> = statuses.iter().map(|status| status.id.clone()).collect();
         let favourited_ids = self.db.get_favourited_status_ids_batch(&status_ids).await?;
@@ -299,6 +350,7 @@ impl TimelineService {
                 account: Self::timeline_account_from_status(&status),
                 favourited: favourited_ids.contains(&status.id),
                 bookmarked: bookmarked_ids.contains(&status.id),
+                filtered: status_filter.matches(&status),
                 status,
                 reblogged: false,
             })
@@ -307,6 +359,7 @@ impl TimelineService {
 
     async fn collect_visible_statuses<F, Fut>(
         &self,
+        status_filter: &StatusFilter,
         limit: usize,
         initial_max_id: Option<String>,
         mut fetch_page: F,
@@ -320,7 +373,7 @@ impl TimelineService {
         }
 
         let muted_thread_uris = self.db.get_muted_thread_uris().await?;
-        if muted_thread_uris.is_empty() {
+        if muted_thread_uris.is_empty() && !status_filter.can_hide() {
             return fetch_page(limit, initial_max_id).await;
         }
 
@@ -344,6 +397,9 @@ impl TimelineService {
                 .filter_muted_threads_with_uris(statuses, &muted_thread_uris)
                 .await?;
             for status in filtered {
+                if status_filter.hides(&status) {
+                    continue;
+                }
                 visible.push(status);
                 if visible.len() >= limit {
                     return Ok(visible);
@@ -397,6 +453,8 @@ pub struct TimelineItem {
     pub reblogged: bool,
     /// Whether user has bookmarked this
     pub bookmarked: bool,
+    /// Warn-action filters matching this status
+    pub filtered: Vec<FilterMatch>,
 }
 
 /// Account info for timeline display
@@ -18,12 +18,17 @@ pub struct TestServer {
 impl TestServer {
     /// Create a new test server instance
     pub async fn new() -> Self {
+        Self::with_config(|_| {}).await
+    }
+
+    /// Create a test server after adjusting the default test configuration
+    pub async fn with_config(configure: impl FnOnce(&mut config::AppConfig)) -> Self {
         // Create temporary directory for test database
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
 
         // Create test configuration
-        let config = config::AppConfig {
+        let mut config = config::AppConfig {
             server: config::ServerConfig {
                 host: "127.0.0.1".to_string(),
                 port: 0, // Let OS assign port
@@ -71,17 +76,21 @@ impl TestServer {
                 display_name: "Test User".to_string(),
                 email: Some("testuser@test.example.com".to_string()),
                 note: Some("Test account".to_string()),
+                also_known_as: Vec::new(),
             },
             cache: config::CacheConfig {
                 timeline_max_items: 2000,
                 profile_ttl: 86400,
             },
+            federation: config::FederationConfig::default(),
             logging: config::LoggingConfig {
                 level: "info".to_string(),
                 format: "pretty".to_string(),
             },
         };
 
+        configure(&mut config);
+
         // Pre-seed the admin account to avoid expensive RSA key generation
         // in AppState::ensure_admin_user for every test server startup.
         {
@@ -103,6 +112,14 @@ impl TestServer {
                 updated_at: now,
             };
             db.upsert_account(&seeded_account).await.unwrap();
+
+            // Same for the instance actor keypair, generated on first use
+            db.set_setting("instance_actor_private_key_pem", TEST_PRIVATE_KEY_PEM)
+                .await
+                .unwrap();
+            db.set_setting("instance_actor_public_key_pem", TEST_PUBLIC_KEY_PEM)
+                .await
+                .unwrap();
         }
 
         // Initialize app state
@@ -127,6 +144,13 @@ impl TestServer {
             axum::serve(listener, app).await.unwrap();
         });
 
+        // Drain queued outbound deliveries and inbound activities and
+        // publish scheduled statuses like the binary does.
+        rustresort::spawn_delivery_queue_worker(state.clone());
+        rustresort::spawn_inbox_queue_worker(state.clone());
+        rustresort::spawn_scheduled_status_task(state.clone());
+        rustresort::spawn_poll_expiry_task(state.clone());
+
         // Poll health endpoint instead of fixed sleep to minimize startup wait.
         let mut healthy = false;
         for _ in 0..200 {
@@ -210,4 +234,18 @@ impl TestServer {
         create_session_token(&session, &self.state.config.auth.session_secret)
             .expect("Failed to create test token")
     }
+
+    /// Post a status as the test account and return the created status
+    pub async fn post_status(&self, token: &str, body: serde_json::Value) -> serde_json::Value {
+        let response = self
+            .client
+            .post(self.url("/api/v1/statuses"))
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        response.json().await.unwrap()
+    }
 }
@@ -200,6 +200,8 @@ async fn test_account_statuses_only_media_pages_until_limit() {
                 blurhash: None,
                 width: Some(64),
                 height: Some(64),
+                focus_x: None,
+                focus_y: None,
                 created_at: now,
             };
             server.state.db.insert_media(&media).await.unwrap();
@@ -966,3 +968,66 @@ async fn test_mute_account_matches_default_https_port_variants() {
             .unwrap()
     );
 }
+
+#[tokio::test]
+async fn test_remote_account_pinned_statuses_serve_stored_pins() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Status};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    // The remote server cannot be reached, so the stored pins are served
+    let pinned = Status {
+        id: EntityId::new().0,
+        uri: "https://remote.invalid/users/bob/statuses/1".to_string(),
+        content: "<p>Pinned by bob</p>".to_string(),
+        content_warning: None,
+        visibility: "public".to_string(),
+        language: None,
+        account_address: "bob@remote.invalid".to_string(),
+        is_local: false,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "featured".to_string(),
+        created_at: Utc::now(),
+        fetched_at: Some(Utc::now()),
+    };
+    server
+        .state
+        .db
+        .insert_remote_status_pin(&pinned)
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/accounts/bob@remote.invalid/statuses?pinned=true"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let statuses: Vec<Value> = response.json().await.unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0]["uri"], pinned.uri);
+    // Pinned by bob, not by the local account
+    assert!(statuses[0]["pinned"].is_null());
+    let pinned_by_bob = server
+        .state
+        .db
+        .get_status_by_uri(&pinned.uri)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        !server
+            .state
+            .db
+            .is_status_pinned(&pinned_by_bob.id)
+            .await
+            .unwrap()
+    );
+    assert_eq!(statuses[0]["account"]["acct"], "bob@remote.invalid");
+}
@@ -28,6 +28,36 @@ async fn test_actor_endpoint() {
     }
 }
 
+#[tokio::test]
+async fn test_actor_publishes_moved_to_after_migration() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+
+    let fetch_actor = || async {
+        server
+            .client
+            .get(server.url("/users/testuser"))
+            .header("Accept", "application/activity+json")
+            .send()
+            .await
+            .unwrap()
+            .json::<Value>()
+            .await
+            .unwrap()
+    };
+    let actor = fetch_actor().await;
+    assert!(actor.get("movedTo").is_none());
+    assert!(actor.get("alsoKnownAs").is_none());
+
+    rustresort::service::AccountService::new(server.state.db.clone(), server.state.storage.clone())
+        .set_moved_to("https://new.example/users/testuser")
+        .await
+        .unwrap();
+
+    let actor = fetch_actor().await;
+    assert_eq!(actor["movedTo"], "https://new.example/users/testuser");
+}
+
 #[tokio::test]
 async fn test_inbox_endpoint_rejects_unsigned_activity() {
     let server = TestServer::new().await;
@@ -140,7 +170,7 @@ async fn test_outbox_excludes_private_and_direct_statuses() {
 
     let response = server
         .client
-        .get(&server.url("/users/testuser/outbox"))
+        .get(&server.url("/users/testuser/outbox?page=true"))
         .header("Accept", "application/activity+json")
         .send()
         .await
@@ -218,6 +248,202 @@ async fn test_following_collection() {
     }
 }
 
+#[tokio::test]
+async fn test_outbox_pages_walk_full_history() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Status};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+
+    for index in 0..25 {
+        let status = Status {
+            id: EntityId::new().0,
+            uri: format!(
+                "https://test.example.com/users/testuser/statuses/history-{}",
+                index
+            ),
+            content: format!("<p>post {}</p>", index),
+            content_warning: None,
+            visibility: "public".to_string(),
+            language: Some("en".to_string()),
+            account_address: "testuser@test.example.com".to_string(),
+            is_local: true,
+            in_reply_to_uri: None,
+            boost_of_uri: None,
+            persisted_reason: "own".to_string(),
+            created_at: Utc::now(),
+            fetched_at: None,
+        };
+        server.state.db.insert_status(&status).await.unwrap();
+        // IDs are only ordered across milliseconds
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+
+    let get = |url: String| {
+        let client = server.client.clone();
+        async move {
+            let response = client
+                .get(url)
+                .header("Accept", "application/activity+json")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 200);
+            response.json::<Value>().await.unwrap()
+        }
+    };
+    let object_ids = |page: &Value| -> Vec<String> {
+        page["orderedItems"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["object"]["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let collection = get(server.url("/users/testuser/outbox")).await;
+    assert_eq!(collection["type"], "OrderedCollection");
+    assert_eq!(collection["totalItems"], 25);
+    assert!(collection.get("orderedItems").is_none());
+
+    // The first page holds the newest posts and has nothing newer
+    let first_url = collection["first"].as_str().unwrap().to_string();
+    let first = get(first_url.replace("https://test.example.com", &server.addr)).await;
+    assert_eq!(first["type"], "OrderedCollectionPage");
+    assert_eq!(
+        first["partOf"],
+        "https://test.example.com/users/testuser/outbox"
+    );
+    let first_ids = object_ids(&first);
+    assert_eq!(first_ids.len(), 20);
+    assert!(first_ids[0].ends_with("/statuses/history-24"));
+    assert!(first.get("prev").is_none());
+
+    // The next page holds the rest and is the last one
+    let next_url = first["next"].as_str().unwrap().to_string();
+    let next = get(next_url.replace("https://test.example.com", &server.addr)).await;
+    let next_ids = object_ids(&next);
+    assert_eq!(next_ids.len(), 5);
+    assert!(next_ids[4].ends_with("/statuses/history-0"));
+    assert!(next.get("next").is_none());
+
+    // Walking back lands on the first page again
+    let prev_url = next["prev"].as_str().unwrap().to_string();
+    let prev = get(prev_url.replace("https://test.example.com", &server.addr)).await;
+    assert_eq!(object_ids(&prev), first_ids);
+}
+
+#[tokio::test]
+async fn test_followers_pages_list_actor_uris() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Follower};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    server
+        .state
+        .db
+        .insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: "https://remote.example/users/bob/inbox".to_string(),
+            uri: "https://remote.example/follows/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let collection: Value = server
+        .client
+        .get(server.url("/users/testuser/followers"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(collection["totalItems"], 1);
+    assert_eq!(
+        collection["first"],
+        "https://test.example.com/users/testuser/followers?page=true"
+    );
+
+    let page: Value = server
+        .client
+        .get(server.url("/users/testuser/followers?page=true"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page["type"], "OrderedCollectionPage");
+    assert_eq!(
+        page["orderedItems"],
+        serde_json::json!(["https://remote.example/users/bob"])
+    );
+    assert!(page.get("next").is_none());
+}
+
+#[tokio::test]
+async fn test_hide_social_graph_publishes_counts_only() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Follow};
+
+    let server = TestServer::with_config(|config| config.federation.hide_social_graph = true).await;
+    server.create_test_account().await;
+    server
+        .state
+        .db
+        .insert_follow(&Follow {
+            id: EntityId::new().0,
+            target_address: "alice@remote.example".to_string(),
+            uri: "https://test.example.com/follows/1".to_string(),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    for path in ["/users/testuser/followers", "/users/testuser/following"] {
+        let collection: Value = server
+            .client
+            .get(server.url(path))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(collection["type"], "OrderedCollection");
+        assert!(collection.get("first").is_none());
+        assert!(collection.get("orderedItems").is_none());
+
+        // Page requests get the bare collection too
+        let page: Value = server
+            .client
+            .get(server.url(&format!("{path}?page=true")))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(page["type"], "OrderedCollection");
+    }
+
+    let following: Value = server
+        .client
+        .get(server.url("/users/testuser/following"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(following["totalItems"], 1);
+}
+
 #[tokio::test]
 async fn test_status_as_activity() {
     let server = TestServer::new().await;
@@ -311,6 +537,75 @@ async fn test_unlisted_status_activity_audience() {
     );
 }
 
+#[tokio::test]
+async fn test_status_with_poll_served_as_question() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Status};
+
+    let status = Status {
+        id: EntityId::new().0,
+        uri: "https://test.example.com/users/testuser/statuses/125".to_string(),
+        content: "<p>Which one?</p>".to_string(),
+        content_warning: Some("poll".to_string()),
+        visibility: "public".to_string(),
+        language: Some("de".to_string()),
+        account_address: String::new(),
+        is_local: true,
+        in_reply_to_uri: None,
+        boost_of_uri: None,
+        persisted_reason: "own".to_string(),
+        created_at: Utc::now(),
+        fetched_at: None,
+    };
+
+    server.state.db.insert_status(&status).await.unwrap();
+    server
+        .state
+        .db
+        .create_poll(&status.id, &["A".to_string(), "B".to_string()], 3600, false)
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .get(&server.url("/users/testuser/statuses/125"))
+        .header("Accept", "application/activity+json")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["type"], "Question");
+    assert_eq!(json["sensitive"], true);
+    assert_eq!(json["summary"], "poll");
+    assert_eq!(json["contentMap"]["de"], "<p>Which one?</p>");
+    assert_eq!(json["oneOf"][0]["name"], "A");
+    assert_eq!(json["oneOf"][1]["replies"]["totalItems"], 0);
+
+    let outbox: Value = server
+        .client
+        .get(&server.url("/users/testuser/outbox?page=true"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        outbox["orderedItems"][0]["object"],
+        json_without_context(json)
+    );
+}
+
+fn json_without_context(mut value: Value) -> Value {
+    value.as_object_mut().unwrap().remove("@context");
+    value
+}
+
 #[tokio::test]
 async fn test_shared_inbox_rejects_unsigned_activity() {
     let server = TestServer::new().await;
@@ -404,3 +699,193 @@ async fn test_actor_content_negotiation() {
         assert!(content_type.to_str().unwrap().contains("application/"));
     }
 }
+
+#[tokio::test]
+async fn test_instance_actor_endpoint() {
+    let server = TestServer::new().await;
+
+    let response = server
+        .client
+        .get(server.url("/actor"))
+        .header("Accept", "application/activity+json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["type"], "Application");
+    assert_eq!(json["id"], "https://test.example.com/actor");
+    assert_eq!(json["inbox"], "https://test.example.com/inbox");
+    assert_eq!(
+        json["publicKey"]["id"],
+        "https://test.example.com/actor#main-key"
+    );
+    assert!(
+        json["publicKey"]["publicKeyPem"]
+            .as_str()
+            .unwrap()
+            .contains("BEGIN PUBLIC KEY")
+    );
+}
+
+#[tokio::test]
+async fn test_authorized_fetch_rejects_unsigned_gets() {
+    let server = TestServer::with_config(|config| config.federation.authorized_fetch = true).await;
+    server.create_test_account().await;
+
+    for path in [
+        "/users/testuser/outbox",
+        "/users/testuser/followers",
+        "/users/testuser/following",
+        "/users/testuser/statuses/1",
+    ] {
+        let response = server
+            .client
+            .get(server.url(path))
+            .header("Accept", "application/activity+json")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            401,
+            "unsigned GET {path} must be rejected"
+        );
+    }
+
+    // The actor stays fetchable for key verification, without profile data
+    let response = server
+        .client
+        .get(server.url("/users/testuser"))
+        .header("Accept", "application/activity+json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let actor: Value = response.json().await.unwrap();
+    assert_eq!(actor["type"], "Person");
+    assert!(actor["publicKey"]["publicKeyPem"].is_string());
+    assert!(actor.get("name").is_none());
+    assert!(actor.get("summary").is_none());
+
+    // So does the instance actor
+    let response = server
+        .client
+        .get(server.url("/actor"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_authorized_fetch_rejects_keys_from_blocked_domains() {
+    let server = TestServer::with_config(|config| config.federation.authorized_fetch = true).await;
+    server.create_test_account().await;
+    server
+        .state
+        .db
+        .insert_domain_block("blocked.example")
+        .await
+        .unwrap();
+
+    for path in ["/users/testuser", "/users/testuser/outbox"] {
+        let response = server
+            .client
+            .get(server.url(path))
+            .header("Accept", "application/activity+json")
+            .header(
+                "Signature",
+                "keyId=\"https://blocked.example/users/mallory#main-key\",algorithm=\"rsa-sha256\",headers=\"(request-target) host date\",signature=\"Zm9v\"",
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            403,
+            "GET {path} signed by a blocked domain must be refused"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_featured_collections_publish_pins_and_tags() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let created: Value = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"status": "pinned #rust post", "visibility": "public"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let status_id = created["id"].as_str().unwrap();
+    let pin_response = server
+        .client
+        .post(server.url(&format!("/api/v1/statuses/{}/pin", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(pin_response.status(), 200);
+    let tag_response = server
+        .client
+        .post(server.url("/api/v1/featured_tags"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"name": "#rust"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(tag_response.status(), 200);
+
+    let actor: Value = server
+        .client
+        .get(server.url("/users/testuser"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let featured_url = actor["featured"].as_str().unwrap();
+    let tags_url = actor["featuredTags"].as_str().unwrap();
+    assert!(featured_url.ends_with("/users/testuser/collections/featured"));
+    assert!(tags_url.ends_with("/users/testuser/collections/tags"));
+
+    let featured: Value = server
+        .client
+        .get(server.url("/users/testuser/collections/featured"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(featured["id"], featured_url);
+    assert_eq!(featured["type"], "OrderedCollection");
+    assert_eq!(featured["totalItems"], 1);
+    assert_eq!(featured["orderedItems"][0]["type"], "Note");
+    assert_eq!(featured["orderedItems"][0]["id"], created["uri"]);
+
+    let tags: Value = server
+        .client
+        .get(server.url("/users/testuser/collections/tags"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(tags["id"], tags_url);
+    assert_eq!(tags["totalItems"], 1);
+    assert_eq!(tags["items"][0]["type"], "Hashtag");
+    assert_eq!(tags["items"][0]["name"], "#rust");
+}
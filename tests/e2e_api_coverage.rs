@@ -526,6 +526,37 @@ async fn test_get_relationships_returns_persisted_mute_notifications_flag() {
     assert_eq!(body[0]["muting_notifications"], false);
 }
 
+#[tokio::test]
+async fn test_get_relationships_returns_blocked_by() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    server
+        .state
+        .db
+        .insert_blocked_by("alice@remote.example", Some(443))
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/accounts/relationships"))
+        .header("Authorization", format!("Bearer {}", token))
+        .query(&[
+            ("id[]", "alice@remote.example:443"),
+            ("id[]", "bob@remote.example"),
+        ])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body[0]["blocked_by"], true);
+    assert_eq!(body[1]["blocked_by"], false);
+}
+
 #[tokio::test]
 async fn test_search_accounts() {
     let server = TestServer::new().await;
@@ -1819,3 +1850,138 @@ async fn test_mark_conversation_read() {
 
     assert!(response.status().is_success() || response.status() == 404);
 }
+
+// ============================================================================
+// Reports Endpoints (7 endpoints)
+// ============================================================================
+
+#[tokio::test]
+async fn test_report_is_queued_for_admin_review() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/reports"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&json!({
+            "account_id": "spammer@remote.example",
+            "comment": "Unsolicited ads",
+            "category": "spam"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let report: serde_json::Value = response.json().await.unwrap();
+    let report_id = report["id"].as_str().unwrap().to_string();
+    assert_eq!(report["category"], "spam");
+    assert_eq!(report["forwarded"], false);
+    assert_eq!(report["target_account"]["acct"], "spammer@remote.example");
+
+    let open_reports: serde_json::Value = server
+        .client
+        .get(server.url("/api/v1/admin/reports?resolved=false"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(open_reports.as_array().unwrap().len(), 1);
+    assert_eq!(open_reports[0]["id"], report_id.as_str());
+    assert_eq!(open_reports[0]["comment"], "Unsolicited ads");
+    assert_eq!(
+        open_reports[0]["target_account"]["domain"],
+        "remote.example"
+    );
+
+    let assigned: serde_json::Value = server
+        .client
+        .post(server.url(&format!(
+            "/api/v1/admin/reports/{}/assign_to_self",
+            report_id
+        )))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(assigned["assigned_account"].is_object());
+
+    let resolved: serde_json::Value = server
+        .client
+        .post(server.url(&format!("/api/v1/admin/reports/{}/resolve", report_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(resolved["action_taken"], true);
+    assert!(resolved["action_taken_at"].is_string());
+
+    let open_reports: serde_json::Value = server
+        .client
+        .get(server.url("/api/v1/admin/reports?resolved=false"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(open_reports.as_array().unwrap().is_empty());
+
+    let reopened: serde_json::Value = server
+        .client
+        .post(server.url(&format!("/api/v1/admin/reports/{}/reopen", report_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reopened["action_taken"], false);
+}
+
+#[tokio::test]
+async fn test_report_rejects_self_report() {
+    let server = TestServer::new().await;
+    let account = server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/reports"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&json!({ "account_id": account.id }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_get_unknown_admin_report() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/admin/reports/missing"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 404);
+}
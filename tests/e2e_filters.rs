@@ -0,0 +1,439 @@
+//! E2E tests for the filters API
+
+mod common;
+
+use common::TestServer;
+use serde_json::{Value, json};
+
+async fn send_json(
+    server: &TestServer,
+    method: reqwest::Method,
+    path: &str,
+    token: &str,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let mut request = server
+        .client
+        .request(method, server.url(path))
+        .header("Authorization", format!("Bearer {}", token));
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    let response = request.send().await.unwrap();
+    let status = response.status().as_u16();
+    let json = response.json().await.unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn home_timeline(server: &TestServer, token: &str) -> Vec<Value> {
+    let (status, json) = send_json(
+        server,
+        reqwest::Method::GET,
+        "/api/v1/timelines/home",
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    json.as_array().unwrap().clone()
+}
+
+fn find_status<'a>(timeline: &'a [Value], id: &str) -> Option<&'a Value> {
+    timeline.iter().find(|status| status["id"] == id)
+}
+
+#[tokio::test]
+async fn test_v2_filter_crud_with_keywords() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let (status, created) = send_json(
+        &server,
+        reqwest::Method::POST,
+        "/api/v2/filters",
+        &token,
+        Some(json!({
+            "title": "Spoilers",
+            "context": ["home", "public"],
+            "filter_action": "blur",
+            "expires_in": 3600,
+            "keywords_attributes": [
+                { "keyword": "finale", "whole_word": true },
+                { "keyword": "plot twist", "whole_word": false }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let filter_id = created["id"].as_str().unwrap().to_string();
+    assert_eq!(created["title"], "Spoilers");
+    assert_eq!(created["filter_action"], "blur");
+    assert_eq!(created["context"], json!(["home", "public"]));
+    assert!(created["expires_at"].is_string());
+    assert_eq!(created["keywords"].as_array().unwrap().len(), 2);
+    assert_eq!(created["statuses"], json!([]));
+
+    let finale_id = created["keywords"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|keyword| keyword["keyword"] == "finale")
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let twist_id = created["keywords"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|keyword| keyword["keyword"] == "plot twist")
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, updated) = send_json(
+        &server,
+        reqwest::Method::PUT,
+        &format!("/api/v2/filters/{}", filter_id),
+        &token,
+        Some(json!({
+            "title": "TV spoilers",
+            "filter_action": "hide",
+            "expires_in": null,
+            "keywords_attributes": [
+                { "id": finale_id, "keyword": "season finale" },
+                { "id": twist_id, "_destroy": true },
+                { "keyword": "cliffhanger" }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(updated["title"], "TV spoilers");
+    assert_eq!(updated["filter_action"], "hide");
+    assert!(updated["expires_at"].is_null());
+    assert_eq!(updated["context"], json!(["home", "public"]));
+    let mut keywords: Vec<String> = updated["keywords"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|keyword| keyword["keyword"].as_str().unwrap().to_string())
+        .collect();
+    keywords.sort();
+    assert_eq!(keywords, vec!["cliffhanger", "season finale"]);
+
+    let (status, listed) = send_json(
+        &server,
+        reqwest::Method::GET,
+        "/api/v2/filters",
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], filter_id.as_str());
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::DELETE,
+        &format!("/api/v2/filters/{}", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/{}", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_v2_filter_rejects_invalid_input() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    for body in [
+        json!({ "title": "", "context": ["home"] }),
+        json!({ "title": "x", "context": [] }),
+        json!({ "title": "x", "context": ["elsewhere"] }),
+        json!({ "title": "x", "context": ["home"], "filter_action": "mute" }),
+        json!({ "title": "x", "context": ["home"], "expires_in": 0 }),
+        json!({ "title": "x", "context": ["home"], "keywords_attributes": [{ "keyword": " " }] }),
+    ] {
+        let (status, _) = send_json(
+            &server,
+            reqwest::Method::POST,
+            "/api/v2/filters",
+            &token,
+            Some(body.clone()),
+        )
+        .await;
+        assert_eq!(status, 400, "expected rejection for {}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_v2_filter_keyword_endpoints_apply_to_timeline() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let (_, filter) = send_json(
+        &server,
+        reqwest::Method::POST,
+        "/api/v2/filters",
+        &token,
+        Some(json!({ "title": "Weather", "context": ["home"] })),
+    )
+    .await;
+    let filter_id = filter["id"].as_str().unwrap().to_string();
+
+    let (status, keyword) = send_json(
+        &server,
+        reqwest::Method::POST,
+        &format!("/api/v2/filters/{}/keywords", filter_id),
+        &token,
+        Some(json!({ "keyword": "rain" })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(keyword["whole_word"], true);
+    let keyword_id = keyword["id"].as_str().unwrap().to_string();
+
+    let rainy = server
+        .post_status(&token, json!({ "status": "More rain today" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let sunny = server
+        .post_status(&token, json!({ "status": "Sunny and snowy" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let timeline = home_timeline(&server, &token).await;
+    let filtered = &find_status(&timeline, &rainy).unwrap()["filtered"];
+    assert_eq!(filtered[0]["filter"]["id"], filter_id.as_str());
+    assert_eq!(filtered[0]["filter"]["filter_action"], "warn");
+    assert_eq!(filtered[0]["keyword_matches"], json!(["rain"]));
+    assert_eq!(
+        find_status(&timeline, &sunny).unwrap()["filtered"],
+        json!([])
+    );
+
+    let (status, keyword) = send_json(
+        &server,
+        reqwest::Method::PUT,
+        &format!("/api/v2/filters/keywords/{}", keyword_id),
+        &token,
+        Some(json!({ "keyword": "snow", "whole_word": false })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(keyword["keyword"], "snow");
+    assert_eq!(keyword["whole_word"], false);
+
+    let timeline = home_timeline(&server, &token).await;
+    assert_eq!(
+        find_status(&timeline, &rainy).unwrap()["filtered"],
+        json!([])
+    );
+    assert_eq!(
+        find_status(&timeline, &sunny).unwrap()["filtered"][0]["keyword_matches"],
+        json!(["snow"])
+    );
+
+    let (status, keywords) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/{}/keywords", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(keywords.as_array().unwrap().len(), 1);
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::DELETE,
+        &format!("/api/v2/filters/keywords/{}", keyword_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/keywords/{}", keyword_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_v2_filter_statuses_hide_attached_statuses() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let hidden = server
+        .post_status(&token, json!({ "status": "Hide this one" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let visible = server
+        .post_status(&token, json!({ "status": "Keep this one" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (_, filter) = send_json(
+        &server,
+        reqwest::Method::POST,
+        "/api/v2/filters",
+        &token,
+        Some(json!({ "title": "Hidden posts", "context": ["home"], "filter_action": "hide" })),
+    )
+    .await;
+    let filter_id = filter["id"].as_str().unwrap().to_string();
+
+    let (status, filter_status) = send_json(
+        &server,
+        reqwest::Method::POST,
+        &format!("/api/v2/filters/{}/statuses", filter_id),
+        &token,
+        Some(json!({ "status_id": hidden })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(filter_status["status_id"], hidden.as_str());
+    let filter_status_id = filter_status["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::POST,
+        &format!("/api/v2/filters/{}/statuses", filter_id),
+        &token,
+        Some(json!({ "status_id": hidden })),
+    )
+    .await;
+    assert_eq!(status, 422);
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::POST,
+        &format!("/api/v2/filters/{}/statuses", filter_id),
+        &token,
+        Some(json!({ "status_id": "missing" })),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let timeline = home_timeline(&server, &token).await;
+    assert!(find_status(&timeline, &hidden).is_none());
+    assert!(find_status(&timeline, &visible).is_some());
+
+    let (status, statuses) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/{}/statuses", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(statuses[0]["id"], filter_status_id.as_str());
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::DELETE,
+        &format!("/api/v2/filters/statuses/{}", filter_status_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let timeline = home_timeline(&server, &token).await;
+    assert!(find_status(&timeline, &hidden).is_some());
+}
+
+#[tokio::test]
+async fn test_v1_filters_are_visible_through_v2() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let (status, v1) = send_json(
+        &server,
+        reqwest::Method::POST,
+        "/api/v1/filters",
+        &token,
+        Some(json!({
+            "phrase": "crypto",
+            "context": ["home"],
+            "irreversible": true,
+            "whole_word": false
+        })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let filter_id = v1["id"].as_str().unwrap().to_string();
+
+    let (status, v2) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/{}", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(v2["title"], "crypto");
+    assert_eq!(v2["filter_action"], "hide");
+    assert_eq!(v2["keywords"][0]["keyword"], "crypto");
+    assert_eq!(v2["keywords"][0]["whole_word"], false);
+
+    let (status, _) = send_json(
+        &server,
+        reqwest::Method::PUT,
+        &format!("/api/v1/filters/{}", filter_id),
+        &token,
+        Some(json!({ "phrase": "nft", "irreversible": false })),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (_, v2) = send_json(
+        &server,
+        reqwest::Method::GET,
+        &format!("/api/v2/filters/{}", filter_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(v2["title"], "nft");
+    assert_eq!(v2["filter_action"], "warn");
+    assert_eq!(v2["keywords"].as_array().unwrap().len(), 1);
+    assert_eq!(v2["keywords"][0]["keyword"], "nft");
+}
@@ -251,3 +251,68 @@ async fn test_status_html_escaping() {
         assert!(!content.contains("<script>"));
     }
 }
+
+#[tokio::test]
+async fn test_featured_tags_crud() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let created: Value = server
+        .client
+        .post(server.url("/api/v1/featured_tags"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"name": "rustlang"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(created["name"], "rustlang");
+    assert_eq!(created["statuses_count"], 0);
+    assert!(created["url"].as_str().unwrap().ends_with("/tags/rustlang"));
+
+    let invalid = server
+        .client
+        .post(server.url("/api/v1/featured_tags"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"name": "not a tag"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), 422);
+
+    let listed: Vec<Value> = server
+        .client
+        .get(server.url("/api/v1/featured_tags"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+
+    let delete_url = server.url(&format!(
+        "/api/v1/featured_tags/{}",
+        created["id"].as_str().unwrap()
+    ));
+    let deleted = server
+        .client
+        .delete(&delete_url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(deleted.status(), 200);
+    let missing = server
+        .client
+        .delete(&delete_url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+}
@@ -379,6 +379,332 @@ async fn test_create_status_with_scheduled_poll_returns_poll_options_array() {
     assert_eq!(json["params"]["poll"]["options"][0], "A");
 }
 
+#[tokio::test]
+async fn test_due_scheduled_status_is_published_and_federated_once() {
+    use axum::{extract::State, http::StatusCode, routing::post};
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Follower};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::net::TcpListener;
+    use tokio::time::{Duration, sleep};
+
+    async fn record_create_delivery(
+        State(counter): State<Arc<AtomicUsize>>,
+        body: String,
+    ) -> StatusCode {
+        if let Ok(activity) = serde_json::from_str::<Value>(&body)
+            && activity["type"] == "Create"
+        {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+        StatusCode::ACCEPTED
+    }
+
+    let create_delivery_count = Arc::new(AtomicUsize::new(0));
+    let remote_router = axum::Router::new()
+        .route("/users/bob/inbox", post(record_create_delivery))
+        .with_state(create_delivery_count.clone());
+    let remote_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let remote_base_url = format!("http://{}", remote_listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(remote_listener, remote_router).await.unwrap();
+    });
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    server
+        .state
+        .db
+        .insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: format!("{}/users/bob/inbox", remote_base_url),
+            uri: format!("{}/users/bob/follows/1", remote_base_url),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "Scheduled <poll>",
+            "scheduled_at": (Utc::now() + chrono::Duration::minutes(10)).to_rfc3339(),
+            "visibility": "public",
+            "poll": {
+                "options": ["A", "B"],
+                "expires_in": 600
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let scheduled: Value = response.json().await.unwrap();
+    let scheduled_id = scheduled["id"].as_str().unwrap();
+
+    // The API only accepts future times, so move the row into the past.
+    server
+        .state
+        .db
+        .update_scheduled_status(
+            scheduled_id,
+            &(Utc::now() - chrono::Duration::seconds(1)).to_rfc3339(),
+        )
+        .await
+        .unwrap();
+
+    // Overlapping passes (including the background task) publish it once.
+    let (first, second) = tokio::join!(
+        rustresort::api::publish_due_scheduled_statuses(&server.state, 20),
+        rustresort::api::publish_due_scheduled_statuses(&server.state, 20),
+    );
+    assert!(first.unwrap() + second.unwrap() <= 1);
+
+    let statuses = server.state.db.get_local_statuses(20, None).await.unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].content, "<p>Scheduled &lt;poll&gt;</p>");
+    assert!(
+        server
+            .state
+            .db
+            .get_poll_by_status_id(&statuses[0].id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    let response = server
+        .client
+        .get(server.url(&format!("/api/v1/scheduled_statuses/{}", scheduled_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    for _ in 0..600 {
+        if create_delivery_count.load(Ordering::SeqCst) > 0 {
+            break;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(create_delivery_count.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_expired_poll_is_closed_notified_and_federated_once() {
+    use axum::{extract::State, http::StatusCode, routing::post};
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Follower};
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+    use tokio::time::{Duration, sleep};
+
+    async fn record_update_delivery(
+        State(updates): State<Arc<Mutex<Vec<Value>>>>,
+        body: String,
+    ) -> StatusCode {
+        if let Ok(activity) = serde_json::from_str::<Value>(&body)
+            && activity["type"] == "Update"
+        {
+            updates.lock().unwrap().push(activity);
+        }
+        StatusCode::ACCEPTED
+    }
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let remote_router = axum::Router::new()
+        .route("/users/bob/inbox", post(record_update_delivery))
+        .with_state(updates.clone());
+    let remote_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let remote_base_url = format!("http://{}", remote_listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(remote_listener, remote_router).await.unwrap();
+    });
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    server
+        .state
+        .db
+        .insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: format!("{}/users/bob/inbox", remote_base_url),
+            uri: format!("{}/users/bob/follows/1", remote_base_url),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "Poll about to close",
+            "visibility": "public"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let created: Value = response.json().await.unwrap();
+    let status_id = created["id"].as_str().unwrap();
+
+    // The API only accepts future closing times, so attach an already
+    // expired poll directly.
+    let poll_id = server
+        .state
+        .db
+        .create_poll(status_id, &["A".to_string(), "B".to_string()], 0, false)
+        .await
+        .unwrap();
+    // Votes arriving after the closing time are rejected.
+    let options = server.state.db.get_poll_options(&poll_id).await.unwrap();
+    server
+        .state
+        .db
+        .add_remote_poll_vote(&poll_id, "bob@remote.example", &options[0].0)
+        .await
+        .unwrap_err();
+
+    // Overlapping passes (including the background task) close it once.
+    let (first, second) = tokio::join!(
+        rustresort::api::close_expired_polls(&server.state, 20),
+        rustresort::api::close_expired_polls(&server.state, 20),
+    );
+    assert!(first.unwrap() + second.unwrap() <= 1);
+
+    let notifications = server
+        .state
+        .db
+        .get_notifications(20, None, false)
+        .await
+        .unwrap();
+    let poll_notifications: Vec<_> = notifications
+        .iter()
+        .filter(|notification| notification.notification_type == "poll")
+        .collect();
+    assert_eq!(poll_notifications.len(), 1);
+    assert!(
+        poll_notifications[0]
+            .status_uri
+            .as_deref()
+            .is_some_and(|uri| uri.ends_with(status_id))
+    );
+
+    for _ in 0..600 {
+        if !updates.lock().unwrap().is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    sleep(Duration::from_millis(100)).await;
+    let updates = updates.lock().unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0]["object"]["type"], "Question");
+    assert!(updates[0]["object"]["closed"].is_string());
+    assert_eq!(updates[0]["object"]["votersCount"], 0);
+}
+
+#[tokio::test]
+async fn test_due_scheduled_status_with_missing_reply_target_is_marked_failed() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let scheduled_id = server
+        .state
+        .db
+        .create_scheduled_status(
+            &(chrono::Utc::now() - chrono::Duration::seconds(1)).to_rfc3339(),
+            "Reply to nothing",
+            "public",
+            None,
+            Some("missing-status"),
+            None,
+            None,
+            None,
+            false,
+            "text/plain",
+        )
+        .await
+        .unwrap();
+
+    let published = rustresort::api::publish_due_scheduled_statuses(&server.state, 20)
+        .await
+        .unwrap();
+    assert_eq!(published, 0);
+    assert!(
+        server
+            .state
+            .db
+            .get_local_statuses(20, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The failed row leaves the pending list, but the owner can still list
+    // it, and it is not retried
+    let response = server
+        .client
+        .get(server.url("/api/v1/scheduled_statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    let pending: Value = response.json().await.unwrap();
+    assert_eq!(pending.as_array().unwrap().len(), 0);
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/scheduled_statuses?failed=true"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    let scheduled: Value = response.json().await.unwrap();
+    assert_eq!(scheduled[0]["id"], scheduled_id.as_str());
+    assert_eq!(scheduled[0]["rustresort"]["failed"], true);
+    assert!(scheduled[0]["rustresort"]["error"].as_str().is_some());
+    assert!(scheduled[0].get("failed").is_none());
+    assert!(
+        server
+            .state
+            .db
+            .get_due_scheduled_statuses(chrono::Utc::now(), 20)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Rescheduling makes it pending again
+    let response = server
+        .client
+        .put(server.url(&format!("/api/v1/scheduled_statuses/{}", scheduled_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "scheduled_at": (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339()
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let rescheduled: Value = response.json().await.unwrap();
+    assert!(rescheduled.get("rustresort").is_none());
+}
+
 #[tokio::test]
 async fn test_create_status_is_idempotent_with_idempotency_key() {
     let server = TestServer::new().await;
@@ -661,6 +987,100 @@ async fn test_boost_status() {
     }
 }
 
+#[tokio::test]
+async fn test_status_counts_and_interaction_lists() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Notification, StatusInteraction};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let post = |body: Value| {
+        server
+            .client
+            .post(server.url("/api/v1/statuses"))
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&body)
+            .send()
+    };
+    let status: Value = post(serde_json::json!({ "status": "Counting" }))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let status_id = status["id"].as_str().unwrap().to_string();
+    let status_uri = status["uri"].as_str().unwrap().to_string();
+    post(serde_json::json!({ "status": "A reply", "in_reply_to_id": status_id }))
+        .await
+        .unwrap();
+
+    for (address, interaction_type) in [
+        ("bob@remote.example", "favourite"),
+        ("carol@remote.example", "reblog"),
+    ] {
+        let notification = Notification {
+            id: EntityId::new().0,
+            notification_type: interaction_type.to_string(),
+            origin_account_address: address.to_string(),
+            status_uri: Some(status_uri.clone()),
+            read: false,
+            created_at: Utc::now(),
+        };
+        let interaction = StatusInteraction {
+            id: EntityId::new().0,
+            activity_uri: None,
+            interaction_type: interaction_type.to_string(),
+            account_address: address.to_string(),
+            status_uri: status_uri.clone(),
+            notification_id: Some(notification.id.clone()),
+            created_at: Utc::now(),
+        };
+        server
+            .state
+            .db
+            .insert_status_interaction(&interaction, &notification)
+            .await
+            .unwrap();
+    }
+    let response = server
+        .client
+        .post(server.url(&format!("/api/v1/statuses/{}/favourite", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let get_json = |path: String| {
+        let request = server
+            .client
+            .get(server.url(&path))
+            .header("Authorization", format!("Bearer {}", token));
+        async move { request.send().await.unwrap().json::<Value>().await.unwrap() }
+    };
+
+    let status = get_json(format!("/api/v1/statuses/{}", status_id)).await;
+    assert_eq!(status["replies_count"], 1);
+    assert_eq!(status["reblogs_count"], 1);
+    assert_eq!(status["favourites_count"], 2);
+
+    let favourited_by = get_json(format!("/api/v1/statuses/{}/favourited_by", status_id)).await;
+    let favourited_by = favourited_by.as_array().unwrap();
+    assert_eq!(favourited_by.len(), 2);
+    assert_eq!(favourited_by[0]["acct"], "testuser");
+    assert_eq!(favourited_by[1]["acct"], "bob@remote.example");
+
+    let reblogged_by = get_json(format!("/api/v1/statuses/{}/reblogged_by", status_id)).await;
+    let reblogged_by = reblogged_by.as_array().unwrap();
+    assert_eq!(reblogged_by.len(), 1);
+    assert_eq!(reblogged_by[0]["acct"], "carol@remote.example");
+
+    let account = get_json("/api/v1/accounts/verify_credentials".to_string()).await;
+    assert_eq!(account["statuses_count"], 2);
+}
+
 #[tokio::test]
 async fn test_status_context() {
     let server = TestServer::new().await;
@@ -941,7 +1361,7 @@ async fn test_status_history_contains_previous_version_after_edit() {
 }
 
 #[tokio::test]
-async fn test_status_history_rejects_remote_status() {
+async fn test_status_history_lists_remote_edits() {
     use chrono::Utc;
     use rustresort::data::{EntityId, Status};
 
@@ -965,6 +1385,16 @@ async fn test_status_history_rejects_remote_status() {
         fetched_at: None,
     };
     server.state.db.insert_status(&remote_status).await.unwrap();
+    let edited_status = Status {
+        content: "<p>Remote status, edited</p>".to_string(),
+        ..remote_status.clone()
+    };
+    server
+        .state
+        .db
+        .update_status_with_edit_snapshot(&remote_status, &edited_status)
+        .await
+        .unwrap();
 
     let history_response = server
         .client
@@ -973,7 +1403,13 @@ async fn test_status_history_rejects_remote_status() {
         .send()
         .await
         .unwrap();
-    assert_eq!(history_response.status(), 403);
+    assert_eq!(history_response.status(), 200);
+    let history: Value = history_response.json().await.unwrap();
+    let items = history.as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["content"], "<p>Remote status, edited</p>");
+    assert_eq!(items[1]["content"], "<p>Remote status</p>");
+    assert_eq!(items[0]["account"]["acct"], "alice@remote.example");
 }
 
 #[tokio::test]
@@ -1246,6 +1682,7 @@ async fn test_create_reply_status_accepts_cache_only_remote_target() {
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1353,6 +1790,7 @@ async fn test_create_reply_status_delivers_to_remote_reply_target_inbox_without_
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1411,6 +1849,7 @@ async fn test_favourite_remote_status_by_uri_persists_from_cache() {
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1483,6 +1922,7 @@ async fn test_favourite_remote_status_by_path_id_uri_fallback_persists_from_cach
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1533,6 +1973,7 @@ async fn test_bookmark_remote_status_by_uri_persists_from_cache() {
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1588,6 +2029,7 @@ async fn test_bookmark_remote_status_by_path_id_uri_fallback_persists_from_cache
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1638,6 +2080,7 @@ async fn test_reblog_remote_status_by_uri_persists_from_cache() {
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1695,6 +2138,7 @@ async fn test_notifications_fallback_to_cached_status() {
             attachments: vec![],
             reply_to_uri: None,
             boost_of_uri: None,
+            content_warning: None,
         })
         .await;
 
@@ -1733,3 +2177,465 @@ async fn test_notifications_fallback_to_cached_status() {
         "<p>Cached notification status</p>"
     );
 }
+
+/// Start a remote server whose `/users/bob/inbox` records delivered activities,
+/// and cache `bob@remote.example` as pointing at it.
+async fn spawn_recording_remote_actor(
+    server: &TestServer,
+) -> (String, std::sync::Arc<tokio::sync::Mutex<Vec<Value>>>) {
+    use axum::{extract::State, http::StatusCode, routing::post};
+    use chrono::Utc;
+    use rustresort::data::CachedProfile;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    async fn record_activity(
+        State(received): State<Arc<Mutex<Vec<Value>>>>,
+        body: String,
+    ) -> StatusCode {
+        received
+            .lock()
+            .await
+            .push(serde_json::from_str(&body).unwrap());
+        StatusCode::ACCEPTED
+    }
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let remote_router = axum::Router::new()
+        .route("/users/bob/inbox", post(record_activity))
+        .with_state(received.clone());
+    let remote_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let remote_base_url = format!("http://{}", remote_listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(remote_listener, remote_router).await.unwrap();
+    });
+
+    let actor_uri = format!("{}/users/bob", remote_base_url);
+    server
+        .state
+        .profile_cache
+        .insert(CachedProfile {
+            address: "bob@remote.example".to_string(),
+            uri: actor_uri.clone(),
+            display_name: Some("Bob".to_string()),
+            note: None,
+            avatar_url: None,
+            header_url: None,
+            public_key_pem: "-----BEGIN PUBLIC KEY-----\nMIIB\n-----END PUBLIC KEY-----"
+                .to_string(),
+            inbox_uri: format!("{}/users/bob/inbox", remote_base_url),
+            outbox_uri: None,
+            followers_count: None,
+            following_count: None,
+            fetched_at: Utc::now(),
+        })
+        .await;
+
+    (actor_uri, received)
+}
+
+async fn wait_for_activity(received: &tokio::sync::Mutex<Vec<Value>>) -> Value {
+    for _ in 0..600 {
+        if let Some(activity) = received.lock().await.first() {
+            return activity.clone();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("expected an activity to be delivered to the mentioned actor");
+}
+
+#[tokio::test]
+async fn test_direct_status_is_delivered_to_mentioned_actor() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "@bob@remote.example psst, and hi @testuser@test.example.com",
+            "visibility": "direct"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+
+    let activity = wait_for_activity(&received).await;
+    assert_eq!(activity["type"], "Create");
+    assert_eq!(activity["to"], serde_json::json!([bob_uri]));
+    assert_eq!(activity["cc"], serde_json::json!([]));
+    assert_eq!(activity["object"]["to"], serde_json::json!([bob_uri]));
+    assert_eq!(
+        activity["object"]["tag"],
+        serde_json::json!([{
+            "type": "Mention",
+            "href": bob_uri,
+            "name": "@bob@remote.example"
+        }])
+    );
+
+    let mentions = server
+        .state
+        .db
+        .get_status_mentions(status_id)
+        .await
+        .unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0].account_address, "bob@remote.example");
+    assert_eq!(mentions[0].actor_uri, bob_uri);
+}
+
+#[tokio::test]
+async fn test_deleting_direct_status_retracts_it_from_mentioned_actor() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "@bob@remote.example this one is a mistake",
+            "visibility": "direct"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+    assert_eq!(wait_for_activity(&received).await["type"], "Create");
+
+    let response = server
+        .client
+        .delete(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let mut delete = None;
+    for _ in 0..600 {
+        delete = received
+            .lock()
+            .await
+            .iter()
+            .find(|activity| activity["type"] == "Delete")
+            .cloned();
+        if delete.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let delete = delete.expect("expected a Delete to be delivered to the mentioned actor");
+    assert_eq!(delete["object"]["id"], status["uri"]);
+    assert_eq!(delete["to"], serde_json::json!([bob_uri]));
+}
+
+#[tokio::test]
+async fn test_editing_out_a_mention_still_sends_update_to_that_actor() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "@bob@remote.example wrong person",
+            "visibility": "direct"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+    assert_eq!(wait_for_activity(&received).await["type"], "Create");
+
+    let response = server
+        .client
+        .put(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"status": "never mind"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let mut update = None;
+    for _ in 0..600 {
+        update = received
+            .lock()
+            .await
+            .iter()
+            .find(|activity| activity["type"] == "Update")
+            .cloned();
+        if update.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let update = update.expect("expected an Update to be delivered to the former mention");
+    assert_eq!(update["object"]["id"], status["uri"]);
+    assert_eq!(update["cc"], serde_json::json!([bob_uri]));
+}
+
+#[tokio::test]
+async fn test_pin_and_unpin_are_federated_to_followers() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Follower};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+    server
+        .state
+        .db
+        .insert_follower(&Follower {
+            id: EntityId::new().0,
+            follower_address: "bob@remote.example".to_string(),
+            inbox_uri: format!("{}/inbox", bob_uri),
+            uri: format!("{}/follows/1", bob_uri),
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({"status": "Pin me", "visibility": "public"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+    assert_eq!(wait_for_activity(&received).await["type"], "Create");
+
+    for (action, activity_type) in [("pin", "Add"), ("unpin", "Remove")] {
+        let response = server
+            .client
+            .post(server.url(&format!("/api/v1/statuses/{}/{}", status_id, action)))
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let mut activity = None;
+        for _ in 0..600 {
+            activity = received
+                .lock()
+                .await
+                .iter()
+                .find(|activity| activity["type"] == activity_type)
+                .cloned();
+            if activity.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let activity = activity.expect("expected the pin change to reach the follower");
+        assert_eq!(activity["object"], status["uri"]);
+        assert!(
+            activity["target"]
+                .as_str()
+                .unwrap()
+                .ends_with("/collections/featured")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_public_status_cc_includes_mentioned_actor() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "status": "Hello @bob@remote.example!",
+            "visibility": "public"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let activity = wait_for_activity(&received).await;
+    assert_eq!(
+        activity["object"]["to"],
+        serde_json::json!(["https://www.w3.org/ns/activitystreams#Public"])
+    );
+    assert_eq!(
+        activity["object"]["cc"],
+        serde_json::json!(["https://test.example.com/users/testuser/followers", bob_uri])
+    );
+    assert_eq!(activity["object"]["tag"][0]["href"], bob_uri.as_str());
+}
+
+#[tokio::test]
+async fn test_status_content_is_formatted_with_mentions_tags_and_links() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, _received) = spawn_recording_remote_actor(&server).await;
+    let text = "Hi @bob@remote.example, #Rust news:\nhttps://example.com/rust\n\nBye <3";
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": text }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+
+    let content = status["content"].as_str().unwrap();
+    assert!(content.starts_with("<p>Hi <span class=\"h-card\" translate=\"no\">"));
+    assert!(content.contains(&format!(
+        "<a href=\"{}\" class=\"u-url mention\">@<span>bob</span></a>",
+        bob_uri
+    )));
+    assert!(content.contains(
+        "<a href=\"https://test.example.com/tags/rust\" class=\"mention hashtag\" rel=\"tag\">#<span>Rust</span></a> news:<br />"
+    ));
+    assert!(content.contains(
+        "<span class=\"invisible\">https://</span><span class=\"\">example.com/rust</span>"
+    ));
+    assert!(content.ends_with("</p><p>Bye &lt;3</p>"));
+
+    assert_eq!(status["mentions"].as_array().unwrap().len(), 1);
+    assert_eq!(status["mentions"][0]["username"], "bob");
+    assert_eq!(status["mentions"][0]["url"], bob_uri.as_str());
+    assert_eq!(
+        status["tags"],
+        serde_json::json!([{ "name": "rust", "url": "https://test.example.com/tags/rust" }])
+    );
+
+    let source: Value = server
+        .client
+        .get(server.url(&format!("/api/v1/statuses/{}/source", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(source["text"], text);
+
+    let response = server
+        .client
+        .put(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": "Just #tags now" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let edited: Value = response.json().await.unwrap();
+    assert_eq!(edited["mentions"], serde_json::json!([]));
+    assert_eq!(edited["tags"][0]["name"], "tags");
+    assert!(
+        server
+            .state
+            .db
+            .get_status_mentions(status_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_markdown_status_is_rendered_and_federated_with_source() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    let (bob_uri, received) = spawn_recording_remote_actor(&server).await;
+    let text = "**Hi** @bob@remote.example\n\n```\nlet x = 1;\n```\n\n<script>alert(1)</script>";
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": text, "content_type": "text/markdown" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    let status_id = status["id"].as_str().unwrap();
+
+    let content = status["content"].as_str().unwrap();
+    assert!(content.starts_with("<p><strong>Hi</strong> <span class=\"h-card\">"));
+    assert!(content.contains("<pre><code>let x = 1;\n</code></pre>"));
+    assert!(!content.contains("script"));
+    assert_eq!(status["mentions"][0]["url"], bob_uri.as_str());
+
+    let source: Value = server
+        .client
+        .get(server.url(&format!("/api/v1/statuses/{}/source", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(source["text"], text);
+    assert_eq!(source["content_type"], "text/markdown");
+
+    let activity = wait_for_activity(&received).await;
+    assert_eq!(activity["object"]["content"], content);
+    assert_eq!(
+        activity["object"]["source"],
+        serde_json::json!({ "content": text, "mediaType": "text/markdown" })
+    );
+
+    let response = server
+        .client
+        .put(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": "_edited_" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let edited: Value = response.json().await.unwrap();
+    assert_eq!(edited["content"], "<p><em>edited</em></p>");
+
+    let response = server
+        .client
+        .post(server.url("/api/v1/statuses"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": "hi", "content_type": "text/html" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
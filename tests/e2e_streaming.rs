@@ -0,0 +1,511 @@
+//! E2E tests for the streaming API (Server-Sent Events)
+
+mod common;
+
+use common::TestServer;
+use serde_json::Value;
+use tokio::time::{Duration, timeout};
+
+/// Incrementally parses `event:`/`data:` frames from an SSE response.
+struct SseReader {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl SseReader {
+    fn new(response: reqwest::Response) -> Self {
+        Self {
+            response,
+            buffer: String::new(),
+        }
+    }
+
+    /// Read the next named event, skipping keep-alive comments.
+    async fn next_event(&mut self) -> Option<(String, String)> {
+        loop {
+            while let Some(frame_end) = self.buffer.find("\n\n") {
+                let frame: String = self.buffer.drain(..frame_end + 2).collect();
+                let mut event_name = None;
+                let mut data = Vec::new();
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        event_name = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push(value.trim_start().to_string());
+                    }
+                }
+                if let Some(event_name) = event_name {
+                    return Some((event_name, data.join("\n")));
+                }
+            }
+
+            let chunk = self.response.chunk().await.ok()??;
+            self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+
+    /// Wait up to `wait` for the next named event.
+    async fn next_event_within(&mut self, wait: Duration) -> Option<(String, String)> {
+        timeout(wait, self.next_event()).await.ok().flatten()
+    }
+}
+
+async fn open_stream(server: &TestServer, token: &str, path: &str) -> SseReader {
+    let response = server
+        .client
+        .get(server.url(path))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    SseReader::new(response)
+}
+
+#[tokio::test]
+async fn test_user_stream_receives_update_and_delete_events() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let mut stream = open_stream(&server, &token, "/api/v1/streaming/user").await;
+
+    let status = server
+        .post_status(
+            &token,
+            serde_json::json!({ "status": "Hello stream", "visibility": "private" }),
+        )
+        .await;
+    let status_id = status["id"].as_str().unwrap().to_string();
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected update event");
+    assert_eq!(event_name, "update");
+    let streamed: Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(streamed["id"], status_id);
+    assert_eq!(streamed["content"], "<p>Hello stream</p>");
+
+    let response = server
+        .client
+        .delete(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected delete event");
+    assert_eq!(event_name, "delete");
+    assert_eq!(payload, status_id);
+}
+
+#[tokio::test]
+async fn test_user_stream_receives_status_update_event_on_edit() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let status = server
+        .post_status(&token, serde_json::json!({ "status": "Before" }))
+        .await;
+    let status_id = status["id"].as_str().unwrap().to_string();
+
+    let mut stream = open_stream(&server, &token, "/api/v1/streaming/user").await;
+
+    let response = server
+        .client
+        .put(server.url(&format!("/api/v1/statuses/{}", status_id)))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "status": "After" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected status.update event");
+    assert_eq!(event_name, "status.update");
+    let streamed: Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(streamed["id"], status_id);
+    assert_eq!(streamed["content"], "<p>After</p>");
+}
+
+#[tokio::test]
+async fn test_public_local_stream_skips_non_public_statuses() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let mut stream = open_stream(&server, &token, "/api/v1/streaming/public/local").await;
+
+    server
+        .post_status(
+            &token,
+            serde_json::json!({ "status": "Followers only", "visibility": "private" }),
+        )
+        .await;
+    let public_status = server
+        .post_status(
+            &token,
+            serde_json::json!({ "status": "Everyone", "visibility": "public" }),
+        )
+        .await;
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected update event");
+    assert_eq!(event_name, "update");
+    let streamed: Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(streamed["id"], public_status["id"]);
+}
+
+#[tokio::test]
+async fn test_hashtag_stream_filters_by_tag() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let mut stream = open_stream(&server, &token, "/api/v1/streaming/hashtag?tag=RustLang").await;
+
+    server
+        .post_status(&token, serde_json::json!({ "status": "No tags here" }))
+        .await;
+    let tagged_status = server
+        .post_status(
+            &token,
+            serde_json::json!({ "status": "Shipping #rustlang today" }),
+        )
+        .await;
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected update event");
+    assert_eq!(event_name, "update");
+    let streamed: Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(streamed["id"], tagged_status["id"]);
+}
+
+#[tokio::test]
+async fn test_hashtag_stream_requires_tag() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/streaming/hashtag"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_list_stream_rejects_unknown_list() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let response = server
+        .client
+        .get(server.url("/api/v1/streaming/list?list=missing"))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_user_stream_applies_keyword_filters() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+    server
+        .state
+        .db
+        .create_filter("hidden", "home", None, true, true)
+        .await
+        .unwrap();
+    server
+        .state
+        .db
+        .create_filter("warned", "home", None, false, true)
+        .await
+        .unwrap();
+
+    let mut stream = open_stream(&server, &token, "/api/v1/streaming/user").await;
+
+    server
+        .post_status(&token, serde_json::json!({ "status": "a hidden post" }))
+        .await;
+    let warned = server
+        .post_status(&token, serde_json::json!({ "status": "a warned post" }))
+        .await;
+
+    let (event_name, payload) = stream
+        .next_event_within(Duration::from_secs(5))
+        .await
+        .expect("expected update event");
+    assert_eq!(event_name, "update");
+    let streamed: Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(streamed["id"], warned["id"]);
+    assert_eq!(streamed["filtered"][0]["filter"]["title"], "warned");
+    assert_eq!(streamed["filtered"][0]["filter"]["filter_action"], "warn");
+}
+
+mod websocket {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use rustresort::data::{EntityId, OAuthApp, OAuthToken};
+    use tokio_tungstenite::tungstenite::{Message, client::IntoClientRequest};
+
+    type Socket = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    fn ws_url(server: &TestServer, path_and_query: &str) -> String {
+        format!(
+            "{}{}",
+            server.addr.replacen("http://", "ws://", 1),
+            path_and_query
+        )
+    }
+
+    async fn connect(server: &TestServer, path_and_query: &str) -> Socket {
+        let (socket, _) = tokio_tungstenite::connect_async(ws_url(server, path_and_query))
+            .await
+            .unwrap();
+        socket
+    }
+
+    /// Read the next JSON text frame, ignoring pings.
+    async fn next_json(socket: &mut Socket) -> Value {
+        timeout(Duration::from_secs(5), async {
+            loop {
+                match socket.next().await.unwrap().unwrap() {
+                    Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                    Message::Close(_) => panic!("socket closed"),
+                    _ => continue,
+                }
+            }
+        })
+        .await
+        .expect("expected a streaming message")
+    }
+
+    async fn send_json(socket: &mut Socket, message: Value) {
+        socket
+            .send(Message::Text(message.to_string()))
+            .await
+            .unwrap();
+    }
+
+    async fn create_oauth_token(server: &TestServer, scopes: &str) -> String {
+        let app = OAuthApp {
+            id: EntityId::new().0,
+            name: "Streaming App".to_string(),
+            website: None,
+            redirect_uri: "https://client.example/callback".to_string(),
+            client_id: EntityId::new().0,
+            client_secret: EntityId::new().0,
+            scopes: scopes.to_string(),
+            created_at: chrono::Utc::now(),
+        };
+        server.state.db.insert_oauth_app(&app).await.unwrap();
+
+        let access_token = EntityId::new().0;
+        let token = OAuthToken {
+            id: EntityId::new().0,
+            app_id: app.id.clone(),
+            access_token: access_token.clone(),
+            grant_type: "authorization_code".to_string(),
+            scopes: scopes.to_string(),
+            created_at: chrono::Utc::now(),
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            revoked: false,
+        };
+        server.state.db.insert_oauth_token(&token).await.unwrap();
+        access_token
+    }
+
+    #[tokio::test]
+    async fn test_websocket_subscribe_receives_events_for_each_stream() {
+        let server = TestServer::new().await;
+        server.create_test_account().await;
+        let token = server.create_test_token().await;
+
+        let mut socket = connect(
+            &server,
+            &format!("/api/v1/streaming?access_token={}", token),
+        )
+        .await;
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "public:local" }),
+        )
+        .await;
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "hashtag", "tag": "Rust" }),
+        )
+        .await;
+        // Subscriptions are applied in order; a round-trip through an invalid
+        // message guarantees both are active before posting.
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "bogus" }),
+        )
+        .await;
+        let error = next_json(&mut socket).await;
+        assert_eq!(error["status"], 400);
+
+        let status = server
+            .post_status(
+                &token,
+                serde_json::json!({ "status": "Hello #rust", "visibility": "public" }),
+            )
+            .await;
+
+        let mut streams = Vec::new();
+        for _ in 0..2 {
+            let message = next_json(&mut socket).await;
+            assert_eq!(message["event"], "update");
+            let payload: Value =
+                serde_json::from_str(message["payload"].as_str().unwrap()).unwrap();
+            assert_eq!(payload["id"], status["id"]);
+            streams.push(message["stream"].clone());
+        }
+        assert!(streams.contains(&serde_json::json!(["public:local"])));
+        assert!(streams.contains(&serde_json::json!(["hashtag", "rust"])));
+
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "unsubscribe", "stream": "hashtag", "tag": "rust" }),
+        )
+        .await;
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "bogus" }),
+        )
+        .await;
+        next_json(&mut socket).await;
+
+        server
+            .post_status(
+                &token,
+                serde_json::json!({ "status": "Again #rust", "visibility": "public" }),
+            )
+            .await;
+        let message = next_json(&mut socket).await;
+        assert_eq!(message["stream"], serde_json::json!(["public:local"]));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_accepts_token_as_subprotocol_with_initial_stream() {
+        let server = TestServer::new().await;
+        server.create_test_account().await;
+        let token = server.create_test_token().await;
+
+        let mut request = ws_url(&server, "/api/v1/streaming?stream=user")
+            .into_client_request()
+            .unwrap();
+        request
+            .headers_mut()
+            .insert("Sec-WebSocket-Protocol", token.parse().unwrap());
+        let (mut socket, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get("Sec-WebSocket-Protocol")
+                .and_then(|value| value.to_str().ok()),
+            Some(token.as_str())
+        );
+
+        let status = server
+            .post_status(
+                &token,
+                serde_json::json!({ "status": "Home only", "visibility": "private" }),
+            )
+            .await;
+        let message = next_json(&mut socket).await;
+        assert_eq!(message["stream"], serde_json::json!(["user"]));
+        assert_eq!(message["event"], "update");
+        let payload: Value = serde_json::from_str(message["payload"].as_str().unwrap()).unwrap();
+        assert_eq!(payload["id"], status["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_requires_access_token() {
+        let server = TestServer::new().await;
+
+        let error = tokio_tungstenite::connect_async(ws_url(&server, "/api/v1/streaming"))
+            .await
+            .unwrap_err();
+        match error {
+            tokio_tungstenite::tungstenite::Error::Http(response) => {
+                assert_eq!(response.status(), 401);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_websocket_enforces_oauth_scopes_per_stream() {
+        let server = TestServer::new().await;
+        server.create_test_account().await;
+        let oauth_token = create_oauth_token(&server, "read:notifications").await;
+
+        let error = tokio_tungstenite::connect_async(ws_url(
+            &server,
+            &format!(
+                "/api/v1/streaming?access_token={}&stream=public",
+                oauth_token
+            ),
+        ))
+        .await
+        .unwrap_err();
+        match error {
+            tokio_tungstenite::tungstenite::Error::Http(response) => {
+                assert_eq!(response.status(), 403);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let mut socket = connect(
+            &server,
+            &format!("/api/v1/streaming?access_token={}", oauth_token),
+        )
+        .await;
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "user" }),
+        )
+        .await;
+        let error = next_json(&mut socket).await;
+        assert_eq!(error["status"], 403);
+
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "direct" }),
+        )
+        .await;
+        send_json(
+            &mut socket,
+            serde_json::json!({ "type": "subscribe", "stream": "bogus" }),
+        )
+        .await;
+        let error = next_json(&mut socket).await;
+        assert_eq!(error["status"], 400);
+    }
+}
@@ -781,3 +781,249 @@ async fn test_public_timeline_backfills_when_newest_statuses_are_muted() {
     assert!(!ids.contains(&muted_root.id));
     assert!(!ids.contains(&mute_target_id));
 }
+
+async fn create_filter(server: &TestServer, token: &str, filter: Value) {
+    let response = server
+        .client
+        .post(server.url("/api/v1/filters"))
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&filter)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+async fn get_timeline(server: &TestServer, path: &str, token: Option<&str>) -> Vec<Value> {
+    let mut request = server.client.get(server.url(path));
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = request.send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let json: Value = response.json().await.unwrap();
+    json.as_array().unwrap().clone()
+}
+
+fn find_status<'a>(timeline: &'a [Value], id: &str) -> Option<&'a Value> {
+    timeline.iter().find(|status| status["id"] == id)
+}
+
+#[tokio::test]
+async fn test_warn_filter_marks_matching_statuses_on_home_timeline() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let spoiler_id = server
+        .post_status(
+            &token,
+            serde_json::json!({ "status": "Huge Spoilers ahead" }),
+        )
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let plain_id = server
+        .post_status(&token, serde_json::json!({ "status": "Nothing to see" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    create_filter(
+        &server,
+        &token,
+        serde_json::json!({ "phrase": "spoilers", "context": ["home"] }),
+    )
+    .await;
+
+    let timeline = get_timeline(&server, "/api/v1/timelines/home", Some(&token)).await;
+    let spoiler = find_status(&timeline, &spoiler_id).unwrap();
+    let filtered = spoiler["filtered"].as_array().unwrap();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0]["filter"]["title"], "spoilers");
+    assert_eq!(filtered[0]["filter"]["filter_action"], "warn");
+    assert_eq!(
+        filtered[0]["filter"]["context"],
+        serde_json::json!(["home"])
+    );
+    assert_eq!(
+        filtered[0]["keyword_matches"],
+        serde_json::json!(["spoilers"])
+    );
+
+    let plain = find_status(&timeline, &plain_id).unwrap();
+    assert_eq!(plain["filtered"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_hide_filter_removes_statuses_only_in_its_contexts() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let hidden_id = server
+        .post_status(&token, serde_json::json!({ "status": "#secret plans" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let visible_id = server
+        .post_status(&token, serde_json::json!({ "status": "secretary meeting" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    create_filter(
+        &server,
+        &token,
+        serde_json::json!({
+            "phrase": "secret",
+            "context": ["public"],
+            "irreversible": true,
+            "whole_word": true
+        }),
+    )
+    .await;
+
+    let public = get_timeline(&server, "/api/v1/timelines/public", Some(&token)).await;
+    assert!(find_status(&public, &hidden_id).is_none());
+    assert!(find_status(&public, &visible_id).is_some());
+
+    let tag = get_timeline(&server, "/api/v1/timelines/tag/secret", Some(&token)).await;
+    assert!(find_status(&tag, &hidden_id).is_none());
+
+    // The filter has no home context.
+    let home = get_timeline(&server, "/api/v1/timelines/home", Some(&token)).await;
+    assert!(find_status(&home, &hidden_id).is_some());
+
+    // Anonymous visitors never see the owner's filters applied.
+    let anonymous = get_timeline(&server, "/api/v1/timelines/public", None).await;
+    let status = find_status(&anonymous, &hidden_id).unwrap();
+    assert_eq!(status["filtered"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_expired_filter_is_ignored() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let status_id = server
+        .post_status(&token, serde_json::json!({ "status": "old news" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let expired_at = (chrono::Utc::now() - chrono::Duration::minutes(1)).to_rfc3339();
+    server
+        .state
+        .db
+        .create_filter("news", "home", Some(&expired_at), true, true)
+        .await
+        .unwrap();
+
+    let home = get_timeline(&server, "/api/v1/timelines/home", Some(&token)).await;
+    let status = find_status(&home, &status_id).unwrap();
+    assert_eq!(status["filtered"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_hide_filter_backfills_home_timeline_page() {
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let older_id = server
+        .post_status(&token, serde_json::json!({ "status": "keep me" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    for index in 0..3 {
+        server
+            .post_status(
+                &token,
+                serde_json::json!({ "status": format!("noise {index}") }),
+            )
+            .await["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+    }
+    create_filter(
+        &server,
+        &token,
+        serde_json::json!({ "phrase": "noise", "context": ["home"], "irreversible": true }),
+    )
+    .await;
+
+    let home = get_timeline(&server, "/api/v1/timelines/home?limit=1", Some(&token)).await;
+    assert_eq!(home.len(), 1);
+    assert_eq!(home[0]["id"], older_id);
+}
+
+#[tokio::test]
+async fn test_notification_filters_hide_and_warn() {
+    use chrono::Utc;
+    use rustresort::data::{EntityId, Notification};
+
+    let server = TestServer::new().await;
+    server.create_test_account().await;
+    let token = server.create_test_token().await;
+
+    let hidden_id = server
+        .post_status(&token, serde_json::json!({ "status": "a hidden word" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let warned_id = server
+        .post_status(&token, serde_json::json!({ "status": "a warned word" }))
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    for status_id in [&hidden_id, &warned_id] {
+        let status = server
+            .state
+            .db
+            .get_status(status_id)
+            .await
+            .unwrap()
+            .unwrap();
+        server
+            .state
+            .db
+            .insert_notification(&Notification {
+                id: EntityId::new().0,
+                notification_type: "favourite".to_string(),
+                origin_account_address: "alice@remote.example".to_string(),
+                status_uri: Some(status.uri),
+                created_at: Utc::now(),
+                read: false,
+            })
+            .await
+            .unwrap();
+    }
+    create_filter(
+        &server,
+        &token,
+        serde_json::json!({ "phrase": "hidden", "context": ["notifications"], "irreversible": true }),
+    )
+    .await;
+    create_filter(
+        &server,
+        &token,
+        serde_json::json!({ "phrase": "warned", "context": ["notifications"] }),
+    )
+    .await;
+
+    let notifications = get_timeline(&server, "/api/v1/notifications", Some(&token)).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["status"]["id"], warned_id);
+    assert_eq!(
+        notifications[0]["status"]["filtered"][0]["filter"]["filter_action"],
+        "warn"
+    );
+}